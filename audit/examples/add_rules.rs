@@ -3,23 +3,19 @@
 //! auditctl -w /etc/passwd -p rwxa -k my_key
 //! auditctl -a always,exit -F arch=b64 -S personality -F key=bypass
 //!
-use std::thread::spawn;
-
-use futures::Future;
-use tokio_core::reactor::Core;
-
 use audit::constants::AUDIT_ARCH_X86_64;
 use audit::new_connection;
 use audit::packet::{RuleAction, RuleField, RuleFieldFlags, RuleFlags, RuleMessage, RuleSyscalls};
 
-fn main() {
+#[tokio::main]
+async fn main() {
     env_logger::init();
 
     // Open the netlink socket
     let (connection, mut handle, _) = new_connection().unwrap();
 
-    // Create the event loop on which that is going to drive our netlink connection
-    spawn(move || Core::new().unwrap().run(connection));
+    // Spawn the netlink connection in the background
+    tokio::spawn(connection);
 
     // create the message for the first rule
     let etc_passwd_rule = RuleMessage {
@@ -50,6 +46,6 @@ fn main() {
     };
 
     // Create the rules
-    handle.add_rule(etc_passwd_rule).wait().unwrap();
-    handle.add_rule(personality_syscall_rule).wait().unwrap();
+    handle.add_rule(etc_passwd_rule).await.unwrap();
+    handle.add_rule(personality_syscall_rule).await.unwrap();
 }
@@ -1,31 +1,32 @@
 //! In this example, we create a netlink connection, and send a request to retrieve the list of
 //! rules. We receive a stream of rule messages that we just prints to the terminal.
-use futures::{Future, Stream};
-use tokio_core::reactor::Core;
+use futures::TryStreamExt;
 
-use audit::new_connection;
+use audit::{new_connection, Error, Handle};
 
-fn main() {
+#[tokio::main]
+async fn main() {
     env_logger::init();
 
-    let mut core = Core::new().unwrap();
-
     // Open the netlink socket
-    let (connection, mut handle, _) = new_connection().unwrap();
+    let (connection, handle, _) = new_connection().unwrap();
 
-    // Spawn the netlink connection Future on the event loop.
-    core.handle().spawn(connection.map_err(|_| ()));
-
-    // Create the request
-    let request = handle.list_rules().for_each(|rule_msg| {
-        println!("{:?}", rule_msg);
-        Ok(())
-    });
+    // Spawn the netlink connection in the background
+    tokio::spawn(connection);
 
     // Run the request
-    if let Err(e) = core.run(request) {
+    if let Err(e) = list_rules(handle).await {
         eprintln!("{}", e);
     } else {
         println!("done");
     }
 }
+
+async fn list_rules(mut handle: Handle) -> Result<(), Error> {
+    // Create the request
+    let mut rules = handle.list_rules();
+    while let Some(rule_msg) = rules.try_next().await? {
+        println!("{:?}", rule_msg);
+    }
+    Ok(())
+}
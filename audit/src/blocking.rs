@@ -0,0 +1,168 @@
+use crate::packet::constants::{
+    AUDIT_STATUS_ENABLED, AUDIT_STATUS_PID, NLM_F_ACK, NLM_F_CREATE, NLM_F_DUMP, NLM_F_EXCL,
+    NLM_F_REQUEST,
+};
+use crate::packet::{
+    AuditMessage, NetlinkFlags, NetlinkMessage, NetlinkPayload, RuleMessage, StatusMessage,
+};
+use failure::ResultExt;
+use netlink_proto::{BlockingConnection, SocketAddr};
+use std::process;
+
+use crate::{Error, ErrorKind};
+
+lazy_static! {
+    static ref KERNEL_UNICAST: SocketAddr = SocketAddr::new(0, 0);
+}
+
+/// A synchronous equivalent of [`Handle`](struct.Handle.html), for programs that do not run an
+/// event loop. The requests are performed on the calling thread, and the audit events are
+/// retrieved with [`next_event`](#method.next_event).
+pub struct BlockingHandle(BlockingConnection);
+
+impl BlockingHandle {
+    pub(crate) fn new(conn: BlockingConnection) -> Self {
+        BlockingHandle(conn)
+    }
+
+    /// Return a mutable reference to the underlying connection, to set a timeout for instance
+    pub fn connection_mut(&mut self) -> &mut BlockingConnection {
+        &mut self.0
+    }
+
+    /// Send a netlink message, and wait for the whole response.
+    pub fn request(&mut self, message: NetlinkMessage) -> Result<Vec<NetlinkMessage>, Error> {
+        Ok(self
+            .0
+            .request(message, *KERNEL_UNICAST)
+            .context(ErrorKind::RequestFailed)?)
+    }
+
+    /// Send a netlink message that expects an acknowledgement, and wait for it. If anything else
+    /// is received, an error is returned.
+    fn acked_request(&mut self, message: NetlinkMessage) -> Result<(), Error> {
+        for nl_msg in self.request(message)? {
+            let (header, payload) = nl_msg.into_parts();
+            match payload {
+                NetlinkPayload::Ack(_) => {}
+                NetlinkPayload::Error(err_msg) => {
+                    return Err(ErrorKind::NetlinkError(err_msg).into())
+                }
+                _ => {
+                    return Err(
+                        ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into(),
+                    )
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the given rule
+    pub fn add_rule(&mut self, rule: RuleMessage) -> Result<(), Error> {
+        let mut req = NetlinkMessage::from(AuditMessage::AddRule(rule));
+        req.header_mut().set_flags(NetlinkFlags::from(
+            NLM_F_REQUEST | NLM_F_ACK | NLM_F_EXCL | NLM_F_CREATE,
+        ));
+        self.acked_request(req)
+    }
+
+    /// List the current rules
+    pub fn list_rules(&mut self) -> Result<Vec<RuleMessage>, Error> {
+        let mut req = NetlinkMessage::from(AuditMessage::ListRules(None));
+        req.header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST | NLM_F_DUMP));
+
+        self.request(req)?
+            .into_iter()
+            .map(|nl_msg| {
+                let (header, payload) = nl_msg.into_parts();
+                match payload {
+                    NetlinkPayload::Audit(AuditMessage::ListRules(Some(rule_msg))) => Ok(rule_msg),
+                    NetlinkPayload::Error(err_msg) => Err(ErrorKind::NetlinkError(err_msg).into()),
+                    _ => Err(
+                        ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into(),
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    /// Enable receiving audit events
+    pub fn enable_events(&mut self) -> Result<(), Error> {
+        let mut status = StatusMessage::new();
+        status.enabled = 1;
+        status.pid = process::id();
+        status.mask = AUDIT_STATUS_ENABLED | AUDIT_STATUS_PID;
+        let mut req = NetlinkMessage::from(AuditMessage::SetStatus(status));
+        req.header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK));
+        self.acked_request(req)
+    }
+
+    /// Wait for the next audit event. Events are only sent once they have been enabled with
+    /// [`enable_events`](#method.enable_events).
+    pub fn next_event(&mut self) -> Result<NetlinkMessage, Error> {
+        Ok(self.0.next_message().context(ErrorKind::RequestFailed)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use failure::Fail;
+
+    use super::*;
+    use crate::new_blocking_connection;
+
+    fn is_timeout(err: &Error) -> bool {
+        let cause = err
+            .cause()
+            .and_then(|cause| cause.downcast_ref::<netlink_proto::Error>());
+        matches!(
+            cause.map(|e| e.kind()),
+            Some(netlink_proto::ErrorKind::Timeout)
+        )
+    }
+
+    fn get_status() -> NetlinkMessage {
+        let mut req = NetlinkMessage::from(AuditMessage::GetStatus(None));
+        req.header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST));
+        req
+    }
+
+    #[test]
+    fn get_status_and_rules() {
+        let mut handle = new_blocking_connection().unwrap();
+        let response = handle.request(get_status()).unwrap();
+        assert_eq!(response.len(), 1);
+        match response[0].payload() {
+            NetlinkPayload::Audit(AuditMessage::GetStatus(Some(_))) => {}
+            payload => panic!("unexpected payload: {:?}", payload),
+        }
+        // the rules are dumped, and reassembled until the end of the dump
+        handle.list_rules().unwrap();
+        assert_eq!(handle.request(get_status()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn skip_stale_replies() {
+        let mut handle = new_blocking_connection().unwrap();
+
+        // time out before the kernel replies
+        handle
+            .connection_mut()
+            .set_timeout(Some(Duration::from_nanos(1)));
+        assert!(is_timeout(&handle.list_rules().unwrap_err()));
+
+        // the late reply is neither part of the next response, nor taken for an event
+        handle.connection_mut().set_timeout(None);
+        handle.list_rules().unwrap();
+        handle
+            .connection_mut()
+            .set_timeout(Some(Duration::from_millis(50)));
+        assert!(is_timeout(&handle.next_event().unwrap_err()));
+    }
+}
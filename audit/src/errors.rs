@@ -12,11 +12,16 @@ pub enum ErrorKind {
     #[fail(display = "Received an unexpected message {:?}", _0)]
     UnexpectedMessage(NetlinkMessage),
 
-    #[fail(display = "Received a netlink error message {:?}", _0)]
+    #[fail(display = "Received a netlink error message: {}", _0)]
     NetlinkError(ErrorMessage),
 
     #[fail(display = "Request failed")]
     RequestFailed,
+
+    #[fail(
+        display = "A dump was interrupted, and its result may be inconsistent: the request should be sent again"
+    )]
+    DumpInterrupted,
 }
 
 impl Fail for Error {
@@ -3,10 +3,12 @@ use crate::packet::constants::{
     NLM_F_REQUEST,
 };
 use crate::packet::{
-    AuditMessage, NetlinkFlags, NetlinkMessage, NetlinkPayload, RuleMessage, StatusMessage,
+    AuditGroup, AuditMessage, NetlinkFlags, NetlinkMessage, NetlinkPayload, RuleMessage,
+    StatusMessage,
 };
-use failure::Fail;
-use futures::{Future, Stream};
+use failure::{Fail, ResultExt};
+use futures::future::{self, Either};
+use futures::{FutureExt, Stream, StreamExt, TryStreamExt};
 use netlink_proto::{ConnectionHandle, SocketAddr};
 use std::process;
 
@@ -25,56 +27,100 @@ impl Handle {
         Handle(conn)
     }
 
-    /// Send a netlink message, and get the reponse as a stream of messages.
+    /// Send a netlink message, and get the response as a stream of messages (see
+    /// [`ConnectionHandle::request`](../netlink_proto/struct.ConnectionHandle.html#method.request)).
     pub fn request(
         &mut self,
         message: NetlinkMessage,
-    ) -> impl Stream<Item = NetlinkMessage, Error = Error> {
-        self.0
+    ) -> Result<impl Stream<Item = Result<NetlinkMessage, Error>>, Error> {
+        let response = self
+            .0
             .request(message, *KERNEL_UNICAST)
-            .map_err(|e| e.context(ErrorKind::RequestFailed).into())
+            .context(ErrorKind::RequestFailed)?;
+        Ok(response.map(|item| item.map_err(response_error)))
+    }
+
+    /// Send the interrupted dumps again, up to `retries` times (see
+    /// [`ConnectionHandle::set_dump_retries`](../netlink_proto/struct.ConnectionHandle.html#method.set_dump_retries)).
+    pub fn set_dump_retries(&mut self, retries: u32) {
+        self.0.set_dump_retries(retries);
+    }
+
+    /// Wait until the connection can take a new request, when the requests channel is bounded
+    /// (see [`ConnectionHandle::ready`](../netlink_proto/struct.ConnectionHandle.html#method.ready)).
+    pub async fn ready(&mut self) -> Result<(), Error> {
+        self.0.ready().await.context(ErrorKind::RequestFailed)?;
+        Ok(())
+    }
+
+    /// Join the given multicast group (for instance `AuditGroup::Readlog` to receive the audit
+    /// events): the messages of that group are received on the stream of unsolicited messages
+    /// returned by `new_connection`. This can be called while the connection is running.
+    pub async fn subscribe(&mut self, group: AuditGroup) -> Result<(), Error> {
+        self.0
+            .subscribe(group)
+            .await
+            .context(ErrorKind::RequestFailed)?;
+        Ok(())
+    }
+
+    /// Leave the given multicast group
+    pub async fn unsubscribe(&mut self, group: AuditGroup) -> Result<(), Error> {
+        self.0
+            .unsubscribe(group)
+            .await
+            .context(ErrorKind::RequestFailed)?;
+        Ok(())
     }
 
     /// Send a netlink message that expects an acknowledgement. The returned future resolved when
     /// that ACK is received. If anything else is received, the future resolves into an error.
-    fn acked_request(&mut self, message: NetlinkMessage) -> impl Future<Item = (), Error = Error> {
-        self.request(message).take(1).for_each(|nl_msg| {
+    async fn acked_request(&mut self, message: NetlinkMessage) -> Result<(), Error> {
+        let mut response = self.request(message)?;
+        if let Some(nl_msg) = response.try_next().await? {
             let (header, payload) = nl_msg.into_parts();
             match payload {
                 NetlinkPayload::Ack(_) => Ok(()),
                 NetlinkPayload::Error(err_msg) => Err(ErrorKind::NetlinkError(err_msg).into()),
                 _ => Err(ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into()),
             }
-        })
+        } else {
+            Ok(())
+        }
     }
 
     /// Add the given rule
-    pub fn add_rule(&mut self, rule: RuleMessage) -> impl Future<Item = (), Error = Error> {
+    pub async fn add_rule(&mut self, rule: RuleMessage) -> Result<(), Error> {
         let mut req = NetlinkMessage::from(AuditMessage::AddRule(rule));
         req.header_mut().set_flags(NetlinkFlags::from(
             NLM_F_REQUEST | NLM_F_ACK | NLM_F_EXCL | NLM_F_CREATE,
         ));
-        self.acked_request(req)
+        self.acked_request(req).await
     }
 
     /// List the current rules
-    pub fn list_rules(&mut self) -> impl Stream<Item = RuleMessage, Error = Error> {
+    pub fn list_rules(&mut self) -> impl Stream<Item = Result<RuleMessage, Error>> {
         let mut req = NetlinkMessage::from(AuditMessage::ListRules(None));
         req.header_mut()
             .set_flags(NetlinkFlags::from(NLM_F_REQUEST | NLM_F_DUMP));
 
-        self.request(req).and_then(|nl_msg| {
-            let (header, payload) = nl_msg.into_parts();
-            match payload {
-                NetlinkPayload::Audit(AuditMessage::ListRules(Some(rule_msg))) => Ok(rule_msg),
-                NetlinkPayload::Error(err_msg) => Err(ErrorKind::NetlinkError(err_msg).into()),
-                _ => Err(ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into()),
-            }
-        })
+        match self.request(req) {
+            Ok(response) => Either::Left(response.map(|nl_msg| {
+                let (header, payload) = nl_msg?.into_parts();
+                match payload {
+                    NetlinkPayload::Audit(AuditMessage::ListRules(Some(rule_msg))) => Ok(rule_msg),
+                    NetlinkPayload::Error(err_msg) => Err(ErrorKind::NetlinkError(err_msg).into()),
+                    _ => Err(
+                        ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into(),
+                    ),
+                }
+            })),
+            Err(e) => Either::Right(future::err::<RuleMessage, Error>(e).into_stream()),
+        }
     }
 
     /// Enable receiving audit events
-    pub fn enable_events(&mut self) -> impl Future<Item = (), Error = Error> {
+    pub async fn enable_events(&mut self) -> Result<(), Error> {
         let mut status = StatusMessage::new();
         status.enabled = 1;
         status.pid = process::id();
@@ -82,6 +128,15 @@ impl Handle {
         let mut req = NetlinkMessage::from(AuditMessage::SetStatus(status));
         req.header_mut()
             .set_flags(NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK));
-        self.acked_request(req)
+        self.acked_request(req).await
     }
 }
+
+fn response_error(e: netlink_proto::Error) -> Error {
+    let kind = if e.is_retryable() {
+        ErrorKind::DumpInterrupted
+    } else {
+        ErrorKind::RequestFailed
+    };
+    e.context(kind).into()
+}
@@ -8,21 +8,83 @@ use failure;
 pub use crate::packet::constants;
 pub use netlink_packet as packet;
 use netlink_proto;
-pub use netlink_proto::{Connection, Protocol};
+pub use netlink_proto::AsyncSocket;
+#[cfg(feature = "async_std_support")]
+pub use netlink_proto::AsyncStdSocket;
+use netlink_proto::NetlinkCodec;
+#[cfg(feature = "smol_support")]
+pub use netlink_proto::SmolSocket;
+#[cfg(feature = "tokio_support")]
+pub use netlink_proto::TokioSocket;
+pub use netlink_proto::{
+    BlockingConnection, ChannelCapacities, Connection, IncomingMessages, NetworkNamespace, Protocol,
+};
 pub use netlink_sys;
 
 mod handle;
 pub use crate::handle::*;
 
+mod blocking;
+pub use crate::blocking::*;
+
 mod errors;
 pub use crate::errors::*;
 
-use crate::packet::NetlinkMessage;
-use futures::sync::mpsc::UnboundedReceiver;
-
 use std::io;
 
-pub fn new_connection() -> io::Result<(Connection, Handle, UnboundedReceiver<NetlinkMessage>)> {
-    let (conn, handle, messages) = netlink_proto::new_connection(Protocol::Audit)?;
+#[cfg(feature = "tokio_support")]
+pub fn new_connection() -> io::Result<(Connection<TokioSocket>, Handle, IncomingMessages)> {
+    new_connection_with_socket()
+}
+
+/// Same as [`new_connection`](fn.new_connection.html), but the connection is opened in the given
+/// network namespace.
+#[cfg(feature = "tokio_support")]
+pub fn new_connection_in_namespace(
+    ns: NetworkNamespace,
+) -> io::Result<(Connection<TokioSocket>, Handle, IncomingMessages)> {
+    new_connection_in_namespace_with_socket(ns)
+}
+
+/// Same as [`new_connection`](fn.new_connection.html), but with the given socket type, so that
+/// the connection can run on another runtime than tokio (`SmolSocket` with the `smol_support`
+/// feature, or `AsyncStdSocket` with the `async_std_support` feature).
+pub fn new_connection_with_socket<S: AsyncSocket>(
+) -> io::Result<(Connection<S>, Handle, IncomingMessages)> {
+    let (conn, handle, messages) = netlink_proto::new_connection_with_socket(Protocol::Audit)?;
     Ok((conn, Handle::new(handle), messages))
 }
+
+/// Same as [`new_connection_in_namespace`](fn.new_connection_in_namespace.html), but with the
+/// given socket type.
+pub fn new_connection_in_namespace_with_socket<S: AsyncSocket>(
+    ns: NetworkNamespace,
+) -> io::Result<(Connection<S>, Handle, IncomingMessages)> {
+    let (conn, handle, messages) =
+        netlink_proto::new_connection_in_namespace_with_socket(Protocol::Audit, ns)?;
+    Ok((conn, Handle::new(handle), messages))
+}
+
+/// Create a connection from an existing `NETLINK_AUDIT` socket, with the given capacities for
+/// the channels between the connection and the handle (see
+/// [`ChannelCapacities`](struct.ChannelCapacities.html)), so that a flood of audit events does
+/// not make memory grow without limit.
+pub fn new_connection_from_socket<S: AsyncSocket>(
+    socket: S,
+    capacities: ChannelCapacities,
+) -> (Connection<S>, Handle, IncomingMessages) {
+    let (conn, handle, messages) = netlink_proto::new_connection_from_socket(
+        socket,
+        NetlinkCodec::for_protocol(Protocol::Audit),
+        capacities,
+    );
+    (conn, Handle::new(handle), messages)
+}
+
+/// Create a synchronous connection, for programs that do not run an event loop (see
+/// [`BlockingHandle`](struct.BlockingHandle.html))
+pub fn new_blocking_connection() -> io::Result<BlockingHandle> {
+    Ok(BlockingHandle::new(BlockingConnection::new(
+        Protocol::Audit,
+    )?))
+}
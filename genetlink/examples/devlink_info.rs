@@ -0,0 +1,24 @@
+use futures::{future, TryStreamExt};
+use genetlink::new_connection;
+
+#[tokio::main]
+async fn main() {
+    let (connection, handle) = new_connection().unwrap();
+    tokio::spawn(connection);
+
+    let devlink = handle.devlink();
+
+    // Equivalent to `devlink dev info`
+    let info = devlink.info().execute().try_for_each(|nlas| {
+        println!("{:#?}", nlas);
+        future::ready(Ok(()))
+    });
+    info.await.unwrap();
+
+    // Equivalent to `devlink health show`
+    let reporters = devlink.health_reporters().execute().try_for_each(|nlas| {
+        println!("{:#?}", nlas);
+        future::ready(Ok(()))
+    });
+    reporters.await.unwrap();
+}
@@ -0,0 +1,25 @@
+use futures::{future, TryStreamExt};
+use genetlink::new_connection;
+
+#[tokio::main]
+async fn main() {
+    // Create a netlink connection, and a handle to send requests via this connection
+    let (connection, handle) = new_connection().unwrap();
+
+    // The connection will run in the background
+    tokio::spawn(connection);
+
+    // Create a request that dumps the link modes of all the devices
+    let request = handle
+        .ethtool()
+        .link_modes()
+        .get()
+        .execute()
+        .try_for_each(|link_modes| {
+            println!("{:#?}", link_modes);
+            future::ready(Ok(()))
+        });
+
+    // Run the request
+    request.await.unwrap();
+}
@@ -0,0 +1,47 @@
+//! This example creates a TCP virtual service with two real servers, and prints the statistics of
+//! the virtual services.
+use std::net::SocketAddr;
+
+use futures::{future, TryStreamExt};
+
+use genetlink::constants::IP_VS_CONN_F_DROUTE;
+use genetlink::{new_connection, IpvsServiceKey};
+
+#[tokio::main]
+async fn main() {
+    let (connection, handle) = new_connection().unwrap();
+    tokio::spawn(connection);
+
+    let ipvs = handle.ipvs();
+    let service = IpvsServiceKey::Tcp("192.168.1.1:80".parse().unwrap());
+
+    // Equivalent to `ipvsadm -A -t 192.168.1.1:80 -s rr`
+    ipvs.service()
+        .add(service)
+        .scheduler("rr")
+        .execute()
+        .await
+        .unwrap();
+
+    // Equivalent to `ipvsadm -a -t 192.168.1.1:80 -r 10.0.0.X:80 -g -w 1`
+    for server in &["10.0.0.2:80", "10.0.0.3:80"] {
+        let addr: SocketAddr = server.parse().unwrap();
+        ipvs.dest()
+            .add(service, addr)
+            .fwd_method(IP_VS_CONN_F_DROUTE)
+            .weight(1)
+            .execute()
+            .await
+            .unwrap();
+    }
+
+    // Equivalent to `ipvsadm -L -n --stats`
+    ipvs.service()
+        .get()
+        .try_for_each(|service| {
+            println!("{:#?}", service);
+            future::ready(Ok(()))
+        })
+        .await
+        .unwrap();
+}
@@ -0,0 +1,45 @@
+//! This example adds a MPTCP endpoint, and prints the MPTCP events that are being received.
+use std::net::{IpAddr, Ipv4Addr};
+
+use futures::{future, TryStreamExt};
+
+use genetlink::constants::{MPTCP_PM_EV_GRP_NAME, MPTCP_PM_NAME};
+use genetlink::{mptcp_events, new_connection, new_connection_with_messages};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    let (connection, handle) = new_connection().unwrap();
+    tokio::spawn(connection);
+
+    // Equivalent to `ip mptcp endpoint add 10.0.0.1 id 1 subflow`
+    handle
+        .mptcp()
+        .endpoint()
+        .add(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)))
+        .id(1)
+        .subflow()
+        .execute()
+        .await
+        .unwrap();
+
+    // The events are sent to a multicast group, which identifier must be resolved
+    let group = handle
+        .resolve_mcast_group(MPTCP_PM_NAME, MPTCP_PM_EV_GRP_NAME)
+        .await
+        .unwrap();
+
+    // Open a second socket, that is a member of the events multicast group
+    let (mut connection, _, messages) = new_connection_with_messages().unwrap();
+    connection.socket_mut().add_membership(group).unwrap();
+    tokio::spawn(connection);
+
+    mptcp_events(messages)
+        .try_for_each(|event| {
+            println!("{:?}", event);
+            future::ready(Ok(()))
+        })
+        .await
+        .unwrap();
+}
@@ -0,0 +1,52 @@
+use std::env;
+
+use genetlink::new_connection;
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 4 {
+        return usage();
+    }
+    let link_name = &args[1];
+    let (rx, tx) = match (args[2].parse(), args[3].parse()) {
+        (Ok(rx), Ok(tx)) => (rx, tx),
+        _ => return usage(),
+    };
+
+    // Create a connection and a handle to use it
+    let (connection, handle) = new_connection().unwrap();
+
+    // Spawn the connection in the background. All we need is the handle.
+    tokio::spawn(connection);
+
+    println!("setting rings of {}: rx {}, tx {}", link_name, rx, tx);
+    match handle
+        .ethtool()
+        .rings()
+        .set(link_name)
+        .rx(rx)
+        .tx(tx)
+        .execute()
+        .await
+    {
+        Ok(()) => println!("done"),
+        Err(e) => eprintln!("error: {}", e),
+    }
+}
+
+fn usage() {
+    eprintln!(
+        "usage:
+    cargo run --example set_rings -- <link name> <rx size> <tx size>
+
+Note that you need to run this program as root. Instead of running cargo as root,
+build the example normally:
+
+    cd genetlink ; cargo build --example set_rings
+
+Then find the binary in the target directory:
+
+    cd ../target/debug/example ; sudo ./set_rings <link_name> <rx size> <tx size>"
+    );
+}
@@ -0,0 +1,65 @@
+//! This example prints the options and the ports of a team device, and then the changes of its
+//! ports and options as they happen.
+//!
+//! Usage: `cargo run --example team_ports -- TEAM_INDEX`
+use std::env;
+
+use futures::{future, TryStreamExt};
+
+use genetlink::constants::{TEAM_GENL_CHANGE_EVENT_MC_GRP_NAME, TEAM_GENL_NAME};
+use genetlink::{new_connection, new_connection_with_messages, team_events, team_ports};
+
+#[tokio::main]
+async fn main() {
+    let team_index: u32 = env::args()
+        .nth(1)
+        .expect("usage: team_ports TEAM_INDEX")
+        .parse()
+        .expect("invalid team index");
+
+    let (connection, handle) = new_connection().unwrap();
+    tokio::spawn(connection);
+
+    // Equivalent to `teamnl DEV options`
+    handle
+        .team()
+        .options(team_index)
+        .try_for_each(|option| {
+            println!("{:?}", option);
+            future::ready(Ok(()))
+        })
+        .await
+        .unwrap();
+
+    // Equivalent to `teamnl DEV ports`
+    handle
+        .team()
+        .ports(team_index)
+        .try_for_each(|port| {
+            println!("{:?}", port);
+            future::ready(Ok(()))
+        })
+        .await
+        .unwrap();
+
+    // The changes are sent to a multicast group, which identifier must be resolved
+    let group = handle
+        .resolve_mcast_group(TEAM_GENL_NAME, TEAM_GENL_CHANGE_EVENT_MC_GRP_NAME)
+        .await
+        .unwrap();
+
+    // Open a second socket, that is a member of the change events multicast group
+    let (mut connection, _, messages) = new_connection_with_messages().unwrap();
+    connection.socket_mut().add_membership(group).unwrap();
+    tokio::spawn(connection);
+
+    team_events(messages)
+        .try_for_each(|event| {
+            for port in team_ports(event) {
+                println!("port changed: {:?}", port);
+            }
+            future::ready(Ok(()))
+        })
+        .await
+        .unwrap();
+}
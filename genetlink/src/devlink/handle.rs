@@ -0,0 +1,68 @@
+use futures::Stream;
+
+use crate::packet::constants::DEVLINK_GENL_NAME;
+use crate::packet::{DevlinkMessage, DevlinkParamData, GenlPayload, NetlinkFlags};
+use crate::{DevlinkGetRequest, DevlinkParamSetRequest, Error, Handle};
+
+#[derive(Clone, Debug)]
+pub struct DevlinkHandle(Handle);
+
+impl DevlinkHandle {
+    pub fn new(handle: Handle) -> Self {
+        DevlinkHandle(handle)
+    }
+
+    /// Send a devlink request, and get the replies as a stream of messages. The `devlink` family
+    /// identifier is resolved first if necessary.
+    pub fn request(
+        &self,
+        message: DevlinkMessage,
+        flags: NetlinkFlags,
+    ) -> impl Stream<Item = Result<DevlinkMessage, Error>> {
+        self.0.family_request(
+            DEVLINK_GENL_NAME,
+            GenlPayload::Devlink(message),
+            flags,
+            |msg| Ok(Some(msg)),
+        )
+    }
+
+    /// Retrieve the devlink devices (equivalent to `devlink dev show`)
+    pub fn devices(&self) -> DevlinkGetRequest {
+        DevlinkGetRequest::new(self.clone(), DevlinkMessage::Get)
+    }
+
+    /// Retrieve the devlink ports (equivalent to `devlink port show`)
+    pub fn ports(&self) -> DevlinkGetRequest {
+        DevlinkGetRequest::new(self.clone(), DevlinkMessage::PortGet)
+    }
+
+    /// Retrieve the driver name, serial number and firmware versions of the devices (equivalent to
+    /// `devlink dev info`)
+    pub fn info(&self) -> DevlinkGetRequest {
+        DevlinkGetRequest::new(self.clone(), DevlinkMessage::InfoGet)
+    }
+
+    /// Retrieve the parameters of the devices (equivalent to `devlink dev param show`)
+    pub fn params(&self) -> DevlinkGetRequest {
+        DevlinkGetRequest::new(self.clone(), DevlinkMessage::ParamGet)
+    }
+
+    /// Change the value of a parameter of a device (equivalent to
+    /// `devlink dev param set BUS/DEV name NAME value VALUE cmode CMODE`)
+    pub fn set_param(
+        &self,
+        bus_name: &str,
+        dev_name: &str,
+        name: &str,
+        cmode: u8,
+        value: DevlinkParamData,
+    ) -> DevlinkParamSetRequest {
+        DevlinkParamSetRequest::new(self.clone(), bus_name, dev_name, name, cmode, value)
+    }
+
+    /// Retrieve the health reporters of the devices (equivalent to `devlink health show`)
+    pub fn health_reporters(&self) -> DevlinkGetRequest {
+        DevlinkGetRequest::new(self.clone(), DevlinkMessage::HealthReporterGet)
+    }
+}
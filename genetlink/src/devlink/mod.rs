@@ -0,0 +1,5 @@
+mod handle;
+pub use self::handle::*;
+
+mod request;
+pub use self::request::*;
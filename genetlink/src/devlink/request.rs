@@ -0,0 +1,110 @@
+use futures::future;
+use futures::{Stream, TryStreamExt};
+
+use crate::packet::constants::{NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST};
+use crate::packet::{DevlinkMessage, DevlinkNla, DevlinkParamData, NetlinkFlags};
+use crate::{DevlinkHandle, Error};
+
+pub struct DevlinkGetRequest {
+    handle: DevlinkHandle,
+    message: fn(Vec<DevlinkNla>) -> DevlinkMessage,
+    nlas: Vec<DevlinkNla>,
+}
+
+impl DevlinkGetRequest {
+    pub(crate) fn new(
+        handle: DevlinkHandle,
+        message: fn(Vec<DevlinkNla>) -> DevlinkMessage,
+    ) -> Self {
+        DevlinkGetRequest {
+            handle,
+            message,
+            nlas: vec![],
+        }
+    }
+
+    /// Only query the device with the given bus name (`pci`, `netdevsim`, etc.) and device name
+    /// (for instance `0000:01:00.0`). By default, all the devices are queried.
+    pub fn device(mut self, bus_name: &str, dev_name: &str) -> Self {
+        self.nlas.push(DevlinkNla::BusName(bus_name.to_string()));
+        self.nlas.push(DevlinkNla::DevName(dev_name.to_string()));
+        self
+    }
+
+    /// Append an attribute to the request, for instance `DevlinkNla::PortIndex` to query a single
+    /// port, or `DevlinkNla::ParamName` to query a single parameter. Attributes other than the
+    /// device are ignored by the kernel unless a device is also given.
+    pub fn append_nla(mut self, nla: DevlinkNla) -> Self {
+        self.nlas.push(nla);
+        self
+    }
+
+    /// Return a mutable reference to the request attributes
+    pub fn nlas_mut(&mut self) -> &mut Vec<DevlinkNla> {
+        &mut self.nlas
+    }
+
+    /// Execute the request. The attributes of each object (device, port, parameter, etc.) are
+    /// returned as one item.
+    pub fn execute(self) -> impl Stream<Item = Result<Vec<DevlinkNla>, Error>> {
+        let DevlinkGetRequest {
+            handle,
+            message,
+            nlas,
+        } = self;
+
+        let dump = !nlas
+            .iter()
+            .any(|nla| matches!(*nla, DevlinkNla::DevName(_)));
+        let flags = if dump {
+            NLM_F_REQUEST | NLM_F_DUMP
+        } else {
+            NLM_F_REQUEST
+        };
+
+        handle
+            .request(message(nlas), NetlinkFlags::from(flags))
+            .map_ok(DevlinkMessage::into_nlas)
+    }
+}
+
+pub struct DevlinkParamSetRequest {
+    handle: DevlinkHandle,
+    nlas: Vec<DevlinkNla>,
+}
+
+impl DevlinkParamSetRequest {
+    pub(crate) fn new(
+        handle: DevlinkHandle,
+        bus_name: &str,
+        dev_name: &str,
+        name: &str,
+        cmode: u8,
+        value: DevlinkParamData,
+    ) -> Self {
+        let mut nlas = vec![
+            DevlinkNla::BusName(bus_name.to_string()),
+            DevlinkNla::DevName(dev_name.to_string()),
+            DevlinkNla::ParamName(name.to_string()),
+            DevlinkNla::ParamType(value.param_type()),
+            DevlinkNla::ParamValueCmode(cmode),
+        ];
+        // a boolean parameter is set to false by omitting the data attribute
+        if let Some(bytes) = value.to_bytes() {
+            nlas.push(DevlinkNla::ParamValueData(bytes));
+        }
+        DevlinkParamSetRequest { handle, nlas }
+    }
+
+    /// Execute the request
+    pub async fn execute(self) -> Result<(), Error> {
+        let DevlinkParamSetRequest { handle, nlas } = self;
+        handle
+            .request(
+                DevlinkMessage::ParamSet(nlas),
+                NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK),
+            )
+            .try_for_each(|_| future::ready(Ok(())))
+            .await
+    }
+}
@@ -0,0 +1,73 @@
+use crate::packet::{ErrorMessage, NetlinkMessage};
+use failure::{Backtrace, Context, Fail};
+use std::fmt::{self, Display};
+
+#[derive(Debug)]
+pub struct Error {
+    inner: Context<ErrorKind>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Fail)]
+pub enum ErrorKind {
+    #[fail(display = "Received an unexpected message {:?}", _0)]
+    UnexpectedMessage(NetlinkMessage),
+
+    #[fail(display = "Received a netlink error message: {}", _0)]
+    NetlinkError(ErrorMessage),
+
+    #[fail(display = "A netlink request failed")]
+    RequestFailed,
+
+    #[fail(
+        display = "A dump was interrupted, and its result may be inconsistent: the request should be sent again"
+    )]
+    DumpInterrupted,
+
+    #[fail(display = "Generic netlink family {:?} is not available", _0)]
+    FamilyNotFound(String),
+
+    #[fail(
+        display = "Generic netlink family {:?} has no multicast group {:?}",
+        _0, _1
+    )]
+    McastGroupNotFound(String, String),
+
+    #[fail(display = "Failed to decode a {} message", _0)]
+    DecodeFailed(String),
+}
+
+impl Fail for Error {
+    fn cause(&self) -> Option<&dyn Fail> {
+        self.inner.cause()
+    }
+
+    fn backtrace(&self) -> Option<&Backtrace> {
+        self.inner.backtrace()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
+
+impl Error {
+    pub fn kind(&self) -> ErrorKind {
+        self.inner.get_context().clone()
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Error {
+        Error {
+            inner: Context::new(kind),
+        }
+    }
+}
+
+impl From<Context<ErrorKind>> for Error {
+    fn from(inner: Context<ErrorKind>) -> Self {
+        Error { inner }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::packet::{EthtoolChannels, EthtoolHeader, EthtoolMessage};
+use crate::{
+    EthtoolAttribute, EthtoolGetRequest, EthtoolHandle, EthtoolSetAttribute, EthtoolSetRequest,
+};
+
+impl EthtoolAttribute for EthtoolChannels {
+    fn header(header: Vec<EthtoolHeader>) -> Self {
+        EthtoolChannels::Header(header)
+    }
+
+    fn get_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::ChannelsGet(nlas)
+    }
+
+    fn get_reply(message: EthtoolMessage) -> Result<Vec<Self>, EthtoolMessage> {
+        match message {
+            EthtoolMessage::ChannelsGetReply(nlas) => Ok(nlas),
+            message => Err(message),
+        }
+    }
+}
+
+impl EthtoolSetAttribute for EthtoolChannels {
+    fn set_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::ChannelsSet(nlas)
+    }
+}
+
+/// A handle for channels (`ETHTOOL_MSG_CHANNELS_*`) requests
+pub struct ChannelsHandle(EthtoolHandle);
+
+impl ChannelsHandle {
+    pub fn new(handle: EthtoolHandle) -> Self {
+        ChannelsHandle(handle)
+    }
+
+    /// Retrieve the channels of the network devices (equivalent to `ethtool -l DEV`)
+    pub fn get(&self) -> EthtoolGetRequest<EthtoolChannels> {
+        EthtoolGetRequest::new(self.0.clone())
+    }
+
+    /// Change the number of channels of the device with the given name
+    pub fn set(&self, name: &str) -> EthtoolSetRequest<EthtoolChannels> {
+        EthtoolSetRequest::new(self.0.clone(), name)
+    }
+}
+
+impl EthtoolSetRequest<EthtoolChannels> {
+    /// Set the number of RX channels (equivalent to `ethtool -L DEV rx COUNT`)
+    pub fn rx(self, count: u32) -> Self {
+        self.append_nla(EthtoolChannels::RxCount(count))
+    }
+
+    /// Set the number of TX channels (equivalent to `ethtool -L DEV tx COUNT`)
+    pub fn tx(self, count: u32) -> Self {
+        self.append_nla(EthtoolChannels::TxCount(count))
+    }
+
+    /// Set the number of other channels (equivalent to `ethtool -L DEV other COUNT`)
+    pub fn other(self, count: u32) -> Self {
+        self.append_nla(EthtoolChannels::OtherCount(count))
+    }
+
+    /// Set the number of combined channels (equivalent to `ethtool -L DEV combined COUNT`)
+    pub fn combined(self, count: u32) -> Self {
+        self.append_nla(EthtoolChannels::CombinedCount(count))
+    }
+}
@@ -0,0 +1,83 @@
+use crate::packet::{EthtoolCoalesce, EthtoolHeader, EthtoolMessage};
+use crate::{
+    EthtoolAttribute, EthtoolGetRequest, EthtoolHandle, EthtoolSetAttribute, EthtoolSetRequest,
+};
+
+impl EthtoolAttribute for EthtoolCoalesce {
+    fn header(header: Vec<EthtoolHeader>) -> Self {
+        EthtoolCoalesce::Header(header)
+    }
+
+    fn get_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::CoalesceGet(nlas)
+    }
+
+    fn get_reply(message: EthtoolMessage) -> Result<Vec<Self>, EthtoolMessage> {
+        match message {
+            EthtoolMessage::CoalesceGetReply(nlas) => Ok(nlas),
+            message => Err(message),
+        }
+    }
+}
+
+impl EthtoolSetAttribute for EthtoolCoalesce {
+    fn set_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::CoalesceSet(nlas)
+    }
+}
+
+/// A handle for interrupt coalescing (`ETHTOOL_MSG_COALESCE_*`) requests
+pub struct CoalesceHandle(EthtoolHandle);
+
+impl CoalesceHandle {
+    pub fn new(handle: EthtoolHandle) -> Self {
+        CoalesceHandle(handle)
+    }
+
+    /// Retrieve the interrupt coalescing settings of the network devices (equivalent to
+    /// `ethtool -c DEV`)
+    pub fn get(&self) -> EthtoolGetRequest<EthtoolCoalesce> {
+        EthtoolGetRequest::new(self.0.clone())
+    }
+
+    /// Change the interrupt coalescing settings of the device with the given name. Settings that
+    /// do not have a dedicated method can be changed with
+    /// [`append_nla()`](struct.EthtoolSetRequest.html#method.append_nla).
+    pub fn set(&self, name: &str) -> EthtoolSetRequest<EthtoolCoalesce> {
+        EthtoolSetRequest::new(self.0.clone(), name)
+    }
+}
+
+impl EthtoolSetRequest<EthtoolCoalesce> {
+    /// Enable or disable adaptive RX coalescing (equivalent to `ethtool -C DEV adaptive-rx on|off`)
+    pub fn adaptive_rx(self, enabled: bool) -> Self {
+        self.append_nla(EthtoolCoalesce::UseAdaptiveRx(enabled))
+    }
+
+    /// Enable or disable adaptive TX coalescing (equivalent to `ethtool -C DEV adaptive-tx on|off`)
+    pub fn adaptive_tx(self, enabled: bool) -> Self {
+        self.append_nla(EthtoolCoalesce::UseAdaptiveTx(enabled))
+    }
+
+    /// Set the RX interrupt delay (equivalent to `ethtool -C DEV rx-usecs USECS`)
+    pub fn rx_usecs(self, usecs: u32) -> Self {
+        self.append_nla(EthtoolCoalesce::RxUsecs(usecs))
+    }
+
+    /// Set the maximum number of frames received before an interrupt (equivalent to
+    /// `ethtool -C DEV rx-frames FRAMES`)
+    pub fn rx_max_frames(self, frames: u32) -> Self {
+        self.append_nla(EthtoolCoalesce::RxMaxFrames(frames))
+    }
+
+    /// Set the TX interrupt delay (equivalent to `ethtool -C DEV tx-usecs USECS`)
+    pub fn tx_usecs(self, usecs: u32) -> Self {
+        self.append_nla(EthtoolCoalesce::TxUsecs(usecs))
+    }
+
+    /// Set the maximum number of frames sent before an interrupt (equivalent to
+    /// `ethtool -C DEV tx-frames FRAMES`)
+    pub fn tx_max_frames(self, frames: u32) -> Self {
+        self.append_nla(EthtoolCoalesce::TxMaxFrames(frames))
+    }
+}
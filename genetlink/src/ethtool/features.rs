@@ -0,0 +1,70 @@
+use crate::packet::{
+    EthtoolBitSet, EthtoolBitSetBit, EthtoolFeatures, EthtoolHeader, EthtoolMessage,
+};
+use crate::{
+    EthtoolAttribute, EthtoolGetRequest, EthtoolHandle, EthtoolSetAttribute, EthtoolSetRequest,
+};
+
+impl EthtoolAttribute for EthtoolFeatures {
+    fn header(header: Vec<EthtoolHeader>) -> Self {
+        EthtoolFeatures::Header(header)
+    }
+
+    fn get_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::FeaturesGet(nlas)
+    }
+
+    fn get_reply(message: EthtoolMessage) -> Result<Vec<Self>, EthtoolMessage> {
+        match message {
+            EthtoolMessage::FeaturesGetReply(nlas) => Ok(nlas),
+            message => Err(message),
+        }
+    }
+}
+
+impl EthtoolSetAttribute for EthtoolFeatures {
+    fn set_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::FeaturesSet(nlas)
+    }
+}
+
+/// A handle for features (`ETHTOOL_MSG_FEATURES_*`) requests
+pub struct FeaturesHandle(EthtoolHandle);
+
+impl FeaturesHandle {
+    pub fn new(handle: EthtoolHandle) -> Self {
+        FeaturesHandle(handle)
+    }
+
+    /// Retrieve the features of the network devices (equivalent to `ethtool -k DEV`)
+    pub fn get(&self) -> EthtoolGetRequest<EthtoolFeatures> {
+        EthtoolGetRequest::new(self.0.clone())
+    }
+
+    /// Enable or disable features of the device with the given name
+    pub fn set(&self, name: &str) -> EthtoolSetRequest<EthtoolFeatures> {
+        EthtoolSetRequest::new(self.0.clone(), name)
+    }
+}
+
+impl EthtoolSetRequest<EthtoolFeatures> {
+    /// Enable or disable the feature with the given name (equivalent to
+    /// `ethtool -K DEV FEATURE on|off`). Feature names are the ones listed by `ethtool -k`, for
+    /// instance `rx-checksum` or `tx-tcp-segmentation`.
+    pub fn feature(mut self, name: &str, enabled: bool) -> Self {
+        let bit = EthtoolBitSetBit::new(name, enabled);
+        for nla in self.nlas_mut().iter_mut() {
+            if let EthtoolFeatures::Wanted(ref mut bitset) = *nla {
+                for attr in bitset.iter_mut() {
+                    if let EthtoolBitSet::Bits(ref mut bits) = *attr {
+                        bits.push(bit);
+                        return self;
+                    }
+                }
+            }
+        }
+        self.append_nla(EthtoolFeatures::Wanted(vec![EthtoolBitSet::Bits(vec![
+            bit,
+        ])]))
+    }
+}
@@ -0,0 +1,93 @@
+use futures::Stream;
+
+use crate::packet::constants::ETHTOOL_GENL_NAME;
+use crate::packet::{EthtoolMessage, GenlPayload, NetlinkFlags};
+use crate::{
+    ChannelsHandle, CoalesceHandle, Error, FeaturesHandle, Handle, LinkInfoHandle, LinkModesHandle,
+    LinkStateHandle, PauseHandle, RingsHandle,
+};
+
+#[derive(Clone, Debug)]
+pub struct EthtoolHandle(Handle);
+
+impl EthtoolHandle {
+    pub fn new(handle: Handle) -> Self {
+        EthtoolHandle(handle)
+    }
+
+    /// Send an ethtool request, and get the replies as a stream of messages. The `ethtool` family
+    /// identifier is resolved first if necessary.
+    pub fn request(
+        &self,
+        message: EthtoolMessage,
+        flags: NetlinkFlags,
+    ) -> impl Stream<Item = Result<EthtoolMessage, Error>> {
+        self.request_with(message, flags, |msg| Ok(Some(msg)))
+    }
+
+    /// Same as [`request`](#method.request), but `reply` extracts what is expected from each
+    /// reply, or hands the reply back if it is not expected, in which case the stream yields an
+    /// `ErrorKind::UnexpectedMessage` error. The replies for which it returns `Ok(None)` are
+    /// skipped.
+    pub(crate) fn request_with<R, F>(
+        &self,
+        message: EthtoolMessage,
+        flags: NetlinkFlags,
+        mut reply: F,
+    ) -> impl Stream<Item = Result<R, Error>>
+    where
+        F: FnMut(EthtoolMessage) -> Result<Option<R>, EthtoolMessage>,
+    {
+        self.0.family_request(
+            ETHTOOL_GENL_NAME,
+            GenlPayload::Ethtool(message),
+            flags,
+            move |msg| reply(msg).map_err(GenlPayload::Ethtool),
+        )
+    }
+
+    /// Create a new handle, specifically for link information requests (port, transceiver, MDI-X)
+    pub fn link_info(&self) -> LinkInfoHandle {
+        LinkInfoHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for link modes requests (speed, duplex, autonegotiation)
+    pub fn link_modes(&self) -> LinkModesHandle {
+        LinkModesHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for link state requests
+    pub fn link_state(&self) -> LinkStateHandle {
+        LinkStateHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for features requests (equivalent to `ethtool -k` and
+    /// `ethtool -K`)
+    pub fn features(&self) -> FeaturesHandle {
+        FeaturesHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for rings requests (equivalent to `ethtool -g` and
+    /// `ethtool -G`)
+    pub fn rings(&self) -> RingsHandle {
+        RingsHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for channels requests (equivalent to `ethtool -l` and
+    /// `ethtool -L`)
+    pub fn channels(&self) -> ChannelsHandle {
+        ChannelsHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for interrupt coalescing requests (equivalent to
+    /// `ethtool -c` and `ethtool -C`)
+    pub fn coalesce(&self) -> CoalesceHandle {
+        CoalesceHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for pause frames requests (equivalent to `ethtool -a`
+    /// and `ethtool -A`)
+    pub fn pause(&self) -> PauseHandle {
+        PauseHandle::new(self.clone())
+    }
+}
@@ -0,0 +1,63 @@
+use crate::packet::{EthtoolHeader, EthtoolLinkInfo, EthtoolMessage, EthtoolPort};
+use crate::{
+    EthtoolAttribute, EthtoolGetRequest, EthtoolHandle, EthtoolSetAttribute, EthtoolSetRequest,
+};
+
+impl EthtoolAttribute for EthtoolLinkInfo {
+    fn header(header: Vec<EthtoolHeader>) -> Self {
+        EthtoolLinkInfo::Header(header)
+    }
+
+    fn get_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::LinkInfoGet(nlas)
+    }
+
+    fn get_reply(message: EthtoolMessage) -> Result<Vec<Self>, EthtoolMessage> {
+        match message {
+            EthtoolMessage::LinkInfoGetReply(nlas) => Ok(nlas),
+            message => Err(message),
+        }
+    }
+}
+
+impl EthtoolSetAttribute for EthtoolLinkInfo {
+    fn set_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::LinkInfoSet(nlas)
+    }
+}
+
+/// A handle for link information (`ETHTOOL_MSG_LINKINFO_*`) requests
+pub struct LinkInfoHandle(EthtoolHandle);
+
+impl LinkInfoHandle {
+    pub fn new(handle: EthtoolHandle) -> Self {
+        LinkInfoHandle(handle)
+    }
+
+    /// Retrieve the link information of the network devices
+    pub fn get(&self) -> EthtoolGetRequest<EthtoolLinkInfo> {
+        EthtoolGetRequest::new(self.0.clone())
+    }
+
+    /// Change the link information of the device with the given name
+    pub fn set(&self, name: &str) -> EthtoolSetRequest<EthtoolLinkInfo> {
+        EthtoolSetRequest::new(self.0.clone(), name)
+    }
+}
+
+impl EthtoolSetRequest<EthtoolLinkInfo> {
+    /// Set the physical connector type (equivalent to `ethtool -s DEV port PORT`)
+    pub fn port(self, port: EthtoolPort) -> Self {
+        self.append_nla(EthtoolLinkInfo::Port(port))
+    }
+
+    /// Set the PHY address (equivalent to `ethtool -s DEV phyad ADDRESS`)
+    pub fn phy_address(self, address: u8) -> Self {
+        self.append_nla(EthtoolLinkInfo::PhyAddr(address))
+    }
+
+    /// Set the MDI(-X) control (`ETH_TP_MDI_*`, equivalent to `ethtool -s DEV mdix on|off|auto`)
+    pub fn mdix(self, mdix: u8) -> Self {
+        self.append_nla(EthtoolLinkInfo::TpMdixCtrl(mdix))
+    }
+}
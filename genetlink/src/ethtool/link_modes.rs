@@ -0,0 +1,68 @@
+use crate::packet::{EthtoolDuplex, EthtoolHeader, EthtoolLinkModes, EthtoolMessage};
+use crate::{
+    EthtoolAttribute, EthtoolGetRequest, EthtoolHandle, EthtoolSetAttribute, EthtoolSetRequest,
+};
+
+impl EthtoolAttribute for EthtoolLinkModes {
+    fn header(header: Vec<EthtoolHeader>) -> Self {
+        EthtoolLinkModes::Header(header)
+    }
+
+    fn get_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::LinkModesGet(nlas)
+    }
+
+    fn get_reply(message: EthtoolMessage) -> Result<Vec<Self>, EthtoolMessage> {
+        match message {
+            EthtoolMessage::LinkModesGetReply(nlas) => Ok(nlas),
+            message => Err(message),
+        }
+    }
+}
+
+impl EthtoolSetAttribute for EthtoolLinkModes {
+    fn set_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::LinkModesSet(nlas)
+    }
+}
+
+/// A handle for link modes (`ETHTOOL_MSG_LINKMODES_*`) requests
+pub struct LinkModesHandle(EthtoolHandle);
+
+impl LinkModesHandle {
+    pub fn new(handle: EthtoolHandle) -> Self {
+        LinkModesHandle(handle)
+    }
+
+    /// Retrieve the link modes of the network devices (equivalent to `ethtool DEV`)
+    pub fn get(&self) -> EthtoolGetRequest<EthtoolLinkModes> {
+        EthtoolGetRequest::new(self.0.clone())
+    }
+
+    /// Change the link modes of the device with the given name
+    pub fn set(&self, name: &str) -> EthtoolSetRequest<EthtoolLinkModes> {
+        EthtoolSetRequest::new(self.0.clone(), name)
+    }
+}
+
+impl EthtoolSetRequest<EthtoolLinkModes> {
+    /// Enable or disable autonegotiation (equivalent to `ethtool -s DEV autoneg on|off`)
+    pub fn autoneg(self, enabled: bool) -> Self {
+        self.append_nla(EthtoolLinkModes::Autoneg(enabled))
+    }
+
+    /// Set the speed in Mb/s (equivalent to `ethtool -s DEV speed SPEED`)
+    pub fn speed(self, speed: u32) -> Self {
+        self.append_nla(EthtoolLinkModes::Speed(speed))
+    }
+
+    /// Set the duplex mode (equivalent to `ethtool -s DEV duplex half|full`)
+    pub fn duplex(self, duplex: EthtoolDuplex) -> Self {
+        self.append_nla(EthtoolLinkModes::Duplex(duplex))
+    }
+
+    /// Set the number of lanes (equivalent to `ethtool -s DEV lanes LANES`)
+    pub fn lanes(self, lanes: u32) -> Self {
+        self.append_nla(EthtoolLinkModes::Lanes(lanes))
+    }
+}
@@ -0,0 +1,34 @@
+use crate::packet::{EthtoolHeader, EthtoolLinkState, EthtoolMessage};
+use crate::{EthtoolAttribute, EthtoolGetRequest, EthtoolHandle};
+
+impl EthtoolAttribute for EthtoolLinkState {
+    fn header(header: Vec<EthtoolHeader>) -> Self {
+        EthtoolLinkState::Header(header)
+    }
+
+    fn get_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::LinkStateGet(nlas)
+    }
+
+    fn get_reply(message: EthtoolMessage) -> Result<Vec<Self>, EthtoolMessage> {
+        match message {
+            EthtoolMessage::LinkStateGetReply(nlas) => Ok(nlas),
+            message => Err(message),
+        }
+    }
+}
+
+/// A handle for link state (`ETHTOOL_MSG_LINKSTATE_*`) requests
+pub struct LinkStateHandle(EthtoolHandle);
+
+impl LinkStateHandle {
+    pub fn new(handle: EthtoolHandle) -> Self {
+        LinkStateHandle(handle)
+    }
+
+    /// Retrieve the link state of the network devices (equivalent to the `Link detected` line of
+    /// `ethtool DEV`)
+    pub fn get(&self) -> EthtoolGetRequest<EthtoolLinkState> {
+        EthtoolGetRequest::new(self.0.clone())
+    }
+}
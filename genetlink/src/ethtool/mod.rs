@@ -0,0 +1,29 @@
+mod handle;
+pub use self::handle::*;
+
+mod request;
+pub use self::request::*;
+
+mod link_info;
+pub use self::link_info::*;
+
+mod link_modes;
+pub use self::link_modes::*;
+
+mod link_state;
+pub use self::link_state::*;
+
+mod features;
+pub use self::features::*;
+
+mod rings;
+pub use self::rings::*;
+
+mod channels;
+pub use self::channels::*;
+
+mod coalesce;
+pub use self::coalesce::*;
+
+mod pause;
+pub use self::pause::*;
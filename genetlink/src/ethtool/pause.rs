@@ -0,0 +1,63 @@
+use crate::packet::{EthtoolHeader, EthtoolMessage, EthtoolPause};
+use crate::{
+    EthtoolAttribute, EthtoolGetRequest, EthtoolHandle, EthtoolSetAttribute, EthtoolSetRequest,
+};
+
+impl EthtoolAttribute for EthtoolPause {
+    fn header(header: Vec<EthtoolHeader>) -> Self {
+        EthtoolPause::Header(header)
+    }
+
+    fn get_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::PauseGet(nlas)
+    }
+
+    fn get_reply(message: EthtoolMessage) -> Result<Vec<Self>, EthtoolMessage> {
+        match message {
+            EthtoolMessage::PauseGetReply(nlas) => Ok(nlas),
+            message => Err(message),
+        }
+    }
+}
+
+impl EthtoolSetAttribute for EthtoolPause {
+    fn set_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::PauseSet(nlas)
+    }
+}
+
+/// A handle for pause frames (`ETHTOOL_MSG_PAUSE_*`) requests
+pub struct PauseHandle(EthtoolHandle);
+
+impl PauseHandle {
+    pub fn new(handle: EthtoolHandle) -> Self {
+        PauseHandle(handle)
+    }
+
+    /// Retrieve the pause frames settings of the network devices (equivalent to `ethtool -a DEV`)
+    pub fn get(&self) -> EthtoolGetRequest<EthtoolPause> {
+        EthtoolGetRequest::new(self.0.clone())
+    }
+
+    /// Change the pause frames settings of the device with the given name
+    pub fn set(&self, name: &str) -> EthtoolSetRequest<EthtoolPause> {
+        EthtoolSetRequest::new(self.0.clone(), name)
+    }
+}
+
+impl EthtoolSetRequest<EthtoolPause> {
+    /// Enable or disable pause autonegotiation (equivalent to `ethtool -A DEV autoneg on|off`)
+    pub fn autoneg(self, enabled: bool) -> Self {
+        self.append_nla(EthtoolPause::Autoneg(enabled))
+    }
+
+    /// Enable or disable RX pause frames (equivalent to `ethtool -A DEV rx on|off`)
+    pub fn rx(self, enabled: bool) -> Self {
+        self.append_nla(EthtoolPause::Rx(enabled))
+    }
+
+    /// Enable or disable TX pause frames (equivalent to `ethtool -A DEV tx on|off`)
+    pub fn tx(self, enabled: bool) -> Self {
+        self.append_nla(EthtoolPause::Tx(enabled))
+    }
+}
@@ -0,0 +1,128 @@
+use futures::future;
+use futures::{Stream, TryStreamExt};
+
+use crate::packet::constants::{ETHTOOL_FLAG_OMIT_REPLY, NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST};
+use crate::packet::{EthtoolHeader, EthtoolMessage, NetlinkFlags};
+use crate::{Error, EthtoolHandle};
+
+/// A group of ethtool attributes that can be retrieved with a `ETHTOOL_MSG_*_GET` request.
+pub trait EthtoolAttribute: Sized {
+    /// Build the header attribute, that identifies the device
+    fn header(header: Vec<EthtoolHeader>) -> Self;
+
+    /// Build the `ETHTOOL_MSG_*_GET` request
+    fn get_request(nlas: Vec<Self>) -> EthtoolMessage;
+
+    /// Extract the attributes from a `ETHTOOL_MSG_*_GET_REPLY` message. The message is returned
+    /// if it is not a reply to `ETHTOOL_MSG_*_GET`.
+    fn get_reply(message: EthtoolMessage) -> Result<Vec<Self>, EthtoolMessage>;
+}
+
+/// A group of ethtool attributes that can be changed with a `ETHTOOL_MSG_*_SET` request.
+pub trait EthtoolSetAttribute: EthtoolAttribute {
+    /// Build the `ETHTOOL_MSG_*_SET` request
+    fn set_request(nlas: Vec<Self>) -> EthtoolMessage;
+}
+
+pub struct EthtoolGetRequest<T> {
+    handle: EthtoolHandle,
+    header: Vec<EthtoolHeader>,
+    nlas: Vec<T>,
+}
+
+impl<T: EthtoolAttribute> EthtoolGetRequest<T> {
+    pub(crate) fn new(handle: EthtoolHandle) -> Self {
+        EthtoolGetRequest {
+            handle,
+            header: vec![],
+            nlas: vec![],
+        }
+    }
+
+    /// Only query the device with the given name. By default, all the devices are queried.
+    pub fn dev_name(mut self, name: &str) -> Self {
+        self.header.push(EthtoolHeader::DevName(name.to_string()));
+        self
+    }
+
+    /// Only query the device with the given index. By default, all the devices are queried.
+    pub fn dev_index(mut self, index: u32) -> Self {
+        self.header.push(EthtoolHeader::DevIndex(index));
+        self
+    }
+
+    /// Set the request flags (`ETHTOOL_FLAG_*`)
+    pub fn flags(mut self, flags: u32) -> Self {
+        self.header.push(EthtoolHeader::Flags(flags));
+        self
+    }
+
+    /// Return a mutable reference to the request attributes, header excepted
+    pub fn nlas_mut(&mut self) -> &mut Vec<T> {
+        &mut self.nlas
+    }
+
+    /// Execute the request. One item is returned per device.
+    pub fn execute(self) -> impl Stream<Item = Result<Vec<T>, Error>> {
+        let EthtoolGetRequest {
+            handle,
+            header,
+            mut nlas,
+        } = self;
+
+        let dump = !header
+            .iter()
+            .any(|nla| matches!(*nla, EthtoolHeader::DevName(_) | EthtoolHeader::DevIndex(_)));
+        let flags = if dump {
+            NLM_F_REQUEST | NLM_F_DUMP
+        } else {
+            NLM_F_REQUEST
+        };
+
+        nlas.insert(0, T::header(header));
+        handle.request_with(T::get_request(nlas), NetlinkFlags::from(flags), |msg| {
+            T::get_reply(msg).map(Some)
+        })
+    }
+}
+
+pub struct EthtoolSetRequest<T> {
+    handle: EthtoolHandle,
+    nlas: Vec<T>,
+}
+
+impl<T: EthtoolSetAttribute> EthtoolSetRequest<T> {
+    pub(crate) fn new(handle: EthtoolHandle, name: &str) -> Self {
+        let header = vec![
+            EthtoolHeader::DevName(name.to_string()),
+            EthtoolHeader::Flags(ETHTOOL_FLAG_OMIT_REPLY),
+        ];
+        EthtoolSetRequest {
+            handle,
+            nlas: vec![T::header(header)],
+        }
+    }
+
+    /// Append an attribute to the request
+    pub fn append_nla(mut self, nla: T) -> Self {
+        self.nlas.push(nla);
+        self
+    }
+
+    /// Return a mutable reference to the request attributes
+    pub fn nlas_mut(&mut self) -> &mut Vec<T> {
+        &mut self.nlas
+    }
+
+    /// Execute the request
+    pub async fn execute(self) -> Result<(), Error> {
+        let EthtoolSetRequest { handle, nlas } = self;
+        handle
+            .request(
+                T::set_request(nlas),
+                NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK),
+            )
+            .try_for_each(|_| future::ready(Ok(())))
+            .await
+    }
+}
@@ -0,0 +1,68 @@
+use crate::packet::{EthtoolHeader, EthtoolMessage, EthtoolRings};
+use crate::{
+    EthtoolAttribute, EthtoolGetRequest, EthtoolHandle, EthtoolSetAttribute, EthtoolSetRequest,
+};
+
+impl EthtoolAttribute for EthtoolRings {
+    fn header(header: Vec<EthtoolHeader>) -> Self {
+        EthtoolRings::Header(header)
+    }
+
+    fn get_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::RingsGet(nlas)
+    }
+
+    fn get_reply(message: EthtoolMessage) -> Result<Vec<Self>, EthtoolMessage> {
+        match message {
+            EthtoolMessage::RingsGetReply(nlas) => Ok(nlas),
+            message => Err(message),
+        }
+    }
+}
+
+impl EthtoolSetAttribute for EthtoolRings {
+    fn set_request(nlas: Vec<Self>) -> EthtoolMessage {
+        EthtoolMessage::RingsSet(nlas)
+    }
+}
+
+/// A handle for rings (`ETHTOOL_MSG_RINGS_*`) requests
+pub struct RingsHandle(EthtoolHandle);
+
+impl RingsHandle {
+    pub fn new(handle: EthtoolHandle) -> Self {
+        RingsHandle(handle)
+    }
+
+    /// Retrieve the rings sizes of the network devices (equivalent to `ethtool -g DEV`)
+    pub fn get(&self) -> EthtoolGetRequest<EthtoolRings> {
+        EthtoolGetRequest::new(self.0.clone())
+    }
+
+    /// Change the rings sizes of the device with the given name
+    pub fn set(&self, name: &str) -> EthtoolSetRequest<EthtoolRings> {
+        EthtoolSetRequest::new(self.0.clone(), name)
+    }
+}
+
+impl EthtoolSetRequest<EthtoolRings> {
+    /// Set the size of the RX ring (equivalent to `ethtool -G DEV rx SIZE`)
+    pub fn rx(self, size: u32) -> Self {
+        self.append_nla(EthtoolRings::Rx(size))
+    }
+
+    /// Set the size of the RX mini ring (equivalent to `ethtool -G DEV rx-mini SIZE`)
+    pub fn rx_mini(self, size: u32) -> Self {
+        self.append_nla(EthtoolRings::RxMini(size))
+    }
+
+    /// Set the size of the RX jumbo ring (equivalent to `ethtool -G DEV rx-jumbo SIZE`)
+    pub fn rx_jumbo(self, size: u32) -> Self {
+        self.append_nla(EthtoolRings::RxJumbo(size))
+    }
+
+    /// Set the size of the TX ring (equivalent to `ethtool -G DEV tx SIZE`)
+    pub fn tx(self, size: u32) -> Self {
+        self.append_nla(EthtoolRings::Tx(size))
+    }
+}
@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use failure::{Fail, ResultExt};
+use futures::future;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use netlink_proto::{ConnectionHandle, SocketAddr};
+
+use crate::packet::constants::NLM_F_REQUEST;
+use crate::packet::{
+    CtrlMessage, CtrlNla, GenlBuffer, GenlMessage, GenlPayload, NetlinkFlags, NetlinkMessage,
+    NetlinkPayload, Parseable,
+};
+use crate::{DevlinkHandle, Error, ErrorKind, EthtoolHandle, IpvsHandle, MptcpHandle, TeamHandle};
+
+lazy_static! {
+    static ref KERNEL_UNICAST: SocketAddr = SocketAddr::new(0, 0);
+}
+
+#[derive(Clone, Debug)]
+pub struct Handle {
+    conn: ConnectionHandle,
+    /// Identifiers of the generic netlink families that have already been resolved
+    families: Arc<Mutex<HashMap<String, u16>>>,
+}
+
+impl Handle {
+    pub(crate) fn new(conn: ConnectionHandle) -> Self {
+        Handle {
+            conn,
+            families: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Send a netlink message, and get the response as a stream of messages (see
+    /// [`ConnectionHandle::request`](../netlink_proto/struct.ConnectionHandle.html#method.request)).
+    pub fn request(
+        &mut self,
+        message: NetlinkMessage,
+    ) -> Result<impl Stream<Item = Result<NetlinkMessage, Error>>, Error> {
+        let response = self
+            .conn
+            .request(message, *KERNEL_UNICAST)
+            .context(ErrorKind::RequestFailed)?;
+        Ok(response.map(|item| item.map_err(response_error)))
+    }
+
+    /// Send the interrupted dumps again, up to `retries` times (see
+    /// [`ConnectionHandle::set_dump_retries`](../netlink_proto/struct.ConnectionHandle.html#method.set_dump_retries)).
+    pub fn set_dump_retries(&mut self, retries: u32) {
+        self.conn.set_dump_retries(retries);
+    }
+
+    /// Wait until the connection can take a new request, when the requests channel is bounded
+    /// (see [`ConnectionHandle::ready`](../netlink_proto/struct.ConnectionHandle.html#method.ready)).
+    pub async fn ready(&mut self) -> Result<(), Error> {
+        self.conn.ready().await.context(ErrorKind::RequestFailed)?;
+        Ok(())
+    }
+
+    pub fn notify(&mut self, msg: NetlinkMessage) -> Result<(), Error> {
+        self.conn
+            .notify(msg, *KERNEL_UNICAST)
+            .context(ErrorKind::RequestFailed)?;
+        Ok(())
+    }
+
+    /// Join the multicast group with the given identifier (see
+    /// [`resolve_mcast_group`](#method.resolve_mcast_group)): the messages sent to that group are
+    /// received on the stream of unsolicited messages of the connection. This can be called while
+    /// the connection is running.
+    pub async fn subscribe(&mut self, group: u32) -> Result<(), Error> {
+        self.conn
+            .subscribe(group)
+            .await
+            .context(ErrorKind::RequestFailed)?;
+        Ok(())
+    }
+
+    /// Leave the multicast group with the given identifier
+    pub async fn unsubscribe(&mut self, group: u32) -> Result<(), Error> {
+        self.conn
+            .unsubscribe(group)
+            .await
+            .context(ErrorKind::RequestFailed)?;
+        Ok(())
+    }
+
+    /// Query the kernel about the generic netlink family with the given name
+    async fn get_family(&self, name: &str) -> Result<CtrlMessage, Error> {
+        let mut req = NetlinkMessage::from(GenlMessage::from(CtrlMessage::GetFamily(vec![
+            CtrlNla::FamilyName(name.to_string()),
+        ])));
+        req.header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST));
+
+        let mut response = self.clone().request(req)?;
+        match response.try_next().await? {
+            Some(msg) => {
+                let (header, payload) = msg.into_parts();
+                match payload {
+                    NetlinkPayload::Genl(GenlMessage {
+                        payload: GenlPayload::Ctrl(msg),
+                        ..
+                    }) => Ok(msg),
+                    NetlinkPayload::Error(err) => Err(ErrorKind::NetlinkError(err).into()),
+                    payload => Err(ErrorKind::UnexpectedMessage(NetlinkMessage::new(
+                        header, payload,
+                    ))
+                    .into()),
+                }
+            }
+            None => Err(ErrorKind::FamilyNotFound(name.to_string()).into()),
+        }
+    }
+
+    /// Resolve the identifier of the generic netlink family with the given name (equivalent to
+    /// `genl ctrl get name NAME`). Identifiers are cached, so the kernel is only queried once per
+    /// family, unless a request to that family fails because the identifier is not valid anymore
+    /// (see [`family_request`](#method.family_request)).
+    pub async fn resolve_family(&self, name: &str) -> Result<u16, Error> {
+        if let Some(id) = self.families.lock().unwrap().get(name) {
+            return Ok(*id);
+        }
+
+        match self.get_family(name).await?.family_id() {
+            Some(id) => {
+                self.families.lock().unwrap().insert(name.to_string(), id);
+                Ok(id)
+            }
+            None => Err(ErrorKind::FamilyNotFound(name.to_string()).into()),
+        }
+    }
+
+    /// Resolve the identifier of a multicast group of a generic netlink family. This identifier
+    /// can then be passed to [`subscribe`](#method.subscribe) to receive the messages sent to this
+    /// group.
+    pub async fn resolve_mcast_group(&self, family: &str, group: &str) -> Result<u32, Error> {
+        match self.get_family(family).await?.mcast_group_id(group) {
+            Some(id) => Ok(id),
+            None => {
+                Err(ErrorKind::McastGroupNotFound(family.to_string(), group.to_string()).into())
+            }
+        }
+    }
+
+    /// Send a request to the generic netlink family with the given name, and get the replies as a
+    /// stream of messages. The family identifier is resolved first if necessary, and the replies
+    /// are decoded as messages of this family. Acks are dropped.
+    ///
+    /// Each decoded reply is passed to `reply`, which extracts what the caller expects from it.
+    /// The replies for which it returns `Ok(None)` are skipped. If it hands the message back as
+    /// an error, the reply is not one the caller expects, and the stream yields an
+    /// `ErrorKind::UnexpectedMessage` error.
+    ///
+    /// If the kernel does not know the family identifier (`ENOENT`) or the command (`EOPNOTSUPP`),
+    /// the family may have been unregistered, or registered again with another identifier (when
+    /// its module is reloaded), so the identifier is dropped from the cache and resolved again by
+    /// the next request.
+    pub(crate) fn family_request<M, R, F>(
+        &self,
+        family: &'static str,
+        payload: GenlPayload,
+        flags: NetlinkFlags,
+        mut reply: F,
+    ) -> impl Stream<Item = Result<R, Error>>
+    where
+        for<'a> GenlBuffer<&'a [u8]>: Parseable<M>,
+        F: FnMut(M) -> Result<Option<R>, GenlPayload>,
+    {
+        let mut handle = self.clone();
+        let families = self.families.clone();
+        let response = async move {
+            let family_id = handle.resolve_family(family).await?;
+            let mut req = NetlinkMessage::from(GenlMessage::new(family_id, payload));
+            req.header_mut().set_flags(flags);
+            handle.request(req)
+        };
+        // the future is boxed so that the stream is `Unpin`, like the other response streams
+        stream::once(Box::pin(response))
+            .try_flatten()
+            .try_filter_map(move |msg| {
+                let (header, payload) = msg.into_parts();
+                future::ready(match payload {
+                    NetlinkPayload::Genl(GenlMessage {
+                        family_id,
+                        payload: GenlPayload::Raw(ref bytes),
+                    }) => match GenlBuffer::new_checked(&bytes[..])
+                        .and_then(|buffer| buffer.parse())
+                    {
+                        Ok(msg) => reply(msg).map_err(|payload| {
+                            let payload =
+                                NetlinkPayload::Genl(GenlMessage::new(family_id, payload));
+                            ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload))
+                                .into()
+                        }),
+                        Err(e) => Err(e.context(ErrorKind::DecodeFailed(family.into())).into()),
+                    },
+                    NetlinkPayload::Ack(_) => Ok(None),
+                    NetlinkPayload::Error(err) => {
+                        if err.code == -libc::ENOENT || err.code == -libc::EOPNOTSUPP {
+                            families.lock().unwrap().remove(family);
+                        }
+                        Err(ErrorKind::NetlinkError(err).into())
+                    }
+                    payload => Err(ErrorKind::UnexpectedMessage(NetlinkMessage::new(
+                        header, payload,
+                    ))
+                    .into()),
+                })
+            })
+    }
+
+    /// Create a new handle, specifically for ethtool requests (equivalent to `ethtool` commands)
+    pub fn ethtool(&self) -> EthtoolHandle {
+        EthtoolHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for devlink requests (equivalent to `devlink` commands)
+    pub fn devlink(&self) -> DevlinkHandle {
+        DevlinkHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for MPTCP path manager requests (equivalent to
+    /// `ip mptcp` commands)
+    pub fn mptcp(&self) -> MptcpHandle {
+        MptcpHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for IPVS requests (equivalent to `ipvsadm` commands)
+    pub fn ipvs(&self) -> IpvsHandle {
+        IpvsHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for team devices requests (equivalent to `teamnl`
+    /// commands)
+    pub fn team(&self) -> TeamHandle {
+        TeamHandle::new(self.clone())
+    }
+}
+
+fn response_error(e: netlink_proto::Error) -> Error {
+    let kind = if e.is_retryable() {
+        ErrorKind::DumpInterrupted
+    } else {
+        ErrorKind::RequestFailed
+    };
+    e.context(kind).into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::new_connection;
+    use crate::packet::constants::{CTRL_CMD_GETFAMILY, GENL_ID_CTRL, NLM_F_DUMP};
+    use crate::packet::DevlinkMessage;
+
+    // Family identifiers are allocated below 1024, so the kernel does not know this one
+    const STALE_FAMILY_ID: u16 = 0xfff0;
+
+    #[tokio::test]
+    async fn resolve_cached_family() {
+        let (connection, handle) = new_connection().unwrap();
+        // the kernel cannot be queried: only the cache can resolve the family
+        drop(connection);
+
+        handle
+            .families
+            .lock()
+            .unwrap()
+            .insert("foo".to_string(), 42);
+        assert_eq!(handle.resolve_family("foo").await.unwrap(), 42);
+        assert!(handle.resolve_family("bar").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn resolve_uncached_family() {
+        let (connection, handle) = new_connection().unwrap();
+        tokio::spawn(connection);
+
+        assert!(handle.families.lock().unwrap().get("nlctrl").is_none());
+        assert_eq!(handle.resolve_family("nlctrl").await.unwrap(), GENL_ID_CTRL);
+        assert_eq!(
+            handle.families.lock().unwrap().get("nlctrl"),
+            Some(&GENL_ID_CTRL)
+        );
+    }
+
+    #[tokio::test]
+    async fn drop_stale_family() {
+        let (connection, handle) = new_connection().unwrap();
+        tokio::spawn(connection);
+
+        handle
+            .families
+            .lock()
+            .unwrap()
+            .insert("nlctrl".to_string(), STALE_FAMILY_ID);
+        let mut response = Box::pin(handle.family_request(
+            "nlctrl",
+            GenlPayload::Raw(vec![CTRL_CMD_GETFAMILY, 1, 0, 0]),
+            NetlinkFlags::from(NLM_F_REQUEST),
+            |msg: DevlinkMessage| Ok(Some(msg)),
+        ));
+        match response.next().await.unwrap().unwrap_err().kind() {
+            ErrorKind::NetlinkError(err) => assert_eq!(err.code, -libc::ENOENT),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+
+        assert!(handle.families.lock().unwrap().get("nlctrl").is_none());
+        assert_eq!(handle.resolve_family("nlctrl").await.unwrap(), GENL_ID_CTRL);
+    }
+
+    #[tokio::test]
+    async fn unexpected_reply() {
+        let (connection, handle) = new_connection().unwrap();
+        tokio::spawn(connection);
+
+        // the families are dumped, but none of them is expected
+        let mut response = Box::pin(handle.family_request::<CtrlMessage, (), _>(
+            "nlctrl",
+            GenlPayload::Raw(vec![CTRL_CMD_GETFAMILY, 1, 0, 0]),
+            NetlinkFlags::from(NLM_F_REQUEST | NLM_F_DUMP),
+            |msg| Err(GenlPayload::Ctrl(msg)),
+        ));
+        match response.next().await.unwrap().unwrap_err().kind() {
+            ErrorKind::UnexpectedMessage(msg) => match msg.payload() {
+                NetlinkPayload::Genl(GenlMessage {
+                    family_id,
+                    payload: GenlPayload::Ctrl(_),
+                }) => assert_eq!(*family_id, GENL_ID_CTRL),
+                payload => panic!("unexpected payload: {:?}", payload),
+            },
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+}
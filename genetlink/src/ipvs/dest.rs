@@ -0,0 +1,148 @@
+use std::net::{IpAddr, SocketAddr};
+
+use futures::Stream;
+
+use crate::packet::constants::{IP_VS_CONN_F_MASQ, NLM_F_DUMP, NLM_F_REQUEST};
+use crate::packet::{IpvsDest, IpvsMessage, IpvsNla, NetlinkFlags};
+use crate::{Error, IpvsHandle, IpvsServiceKey};
+
+fn dest_nlas(addr: SocketAddr) -> Vec<IpvsDest> {
+    let bytes = match addr.ip() {
+        IpAddr::V4(ipv4) => ipv4.octets().to_vec(),
+        IpAddr::V6(ipv6) => ipv6.octets().to_vec(),
+    };
+    vec![IpvsDest::Addr(bytes), IpvsDest::Port(addr.port())]
+}
+
+/// A handle for destinations (real servers) requests
+pub struct IpvsDestHandle(IpvsHandle);
+
+impl IpvsDestHandle {
+    pub fn new(handle: IpvsHandle) -> Self {
+        IpvsDestHandle(handle)
+    }
+
+    /// Add a destination to a virtual service (equivalent to `ipvsadm -a`)
+    pub fn add(&self, service: IpvsServiceKey, addr: SocketAddr) -> IpvsDestRequest {
+        IpvsDestRequest::new(self.0.clone(), service, addr, IpvsMessage::NewDest)
+    }
+
+    /// Edit a destination of a virtual service (equivalent to `ipvsadm -e`). Note that all the
+    /// settings of the destination are replaced, so the ones that are not set on the request are
+    /// reset to their default value.
+    pub fn set(&self, service: IpvsServiceKey, addr: SocketAddr) -> IpvsDestRequest {
+        IpvsDestRequest::new(self.0.clone(), service, addr, IpvsMessage::SetDest)
+    }
+
+    /// Delete a destination from a virtual service (equivalent to `ipvsadm -d`)
+    pub async fn del(&self, service: IpvsServiceKey, addr: SocketAddr) -> Result<(), Error> {
+        self.0
+            .acked_request(IpvsMessage::DelDest(vec![
+                IpvsNla::Service(service.to_nlas()),
+                IpvsNla::Dest(dest_nlas(addr)),
+            ]))
+            .await
+    }
+
+    /// Retrieve the destinations of a virtual service, and their statistics. The attributes of
+    /// each destination are returned as one item.
+    pub fn get(&self, service: IpvsServiceKey) -> impl Stream<Item = Result<Vec<IpvsDest>, Error>> {
+        self.0.request_with(
+            IpvsMessage::GetDest(vec![IpvsNla::Service(service.to_nlas())]),
+            NetlinkFlags::from(NLM_F_REQUEST | NLM_F_DUMP),
+            |msg| {
+                if !msg.nlas().iter().any(|nla| matches!(nla, IpvsNla::Dest(_))) {
+                    return Err(msg);
+                }
+                Ok(msg.into_nlas().into_iter().find_map(|nla| match nla {
+                    IpvsNla::Dest(nlas) => Some(nlas),
+                    _ => None,
+                }))
+            },
+        )
+    }
+}
+
+pub struct IpvsDestRequest {
+    handle: IpvsHandle,
+    service: IpvsServiceKey,
+    addr: SocketAddr,
+    message: fn(Vec<IpvsNla>) -> IpvsMessage,
+    fwd_method: u32,
+    weight: u32,
+    u_thresh: u32,
+    l_thresh: u32,
+}
+
+impl IpvsDestRequest {
+    fn new(
+        handle: IpvsHandle,
+        service: IpvsServiceKey,
+        addr: SocketAddr,
+        message: fn(Vec<IpvsNla>) -> IpvsMessage,
+    ) -> Self {
+        IpvsDestRequest {
+            handle,
+            service,
+            addr,
+            message,
+            fwd_method: IP_VS_CONN_F_MASQ,
+            weight: 1,
+            u_thresh: 0,
+            l_thresh: 0,
+        }
+    }
+
+    /// Set the forwarding method (`IP_VS_CONN_F_MASQ`, `IP_VS_CONN_F_TUNNEL` or
+    /// `IP_VS_CONN_F_DROUTE`). Defaults to masquerading.
+    pub fn fwd_method(mut self, method: u32) -> Self {
+        self.fwd_method = method;
+        self
+    }
+
+    /// Set the weight of the destination (equivalent to `ipvsadm -w WEIGHT`). Defaults to 1.
+    pub fn weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Set the maximum number of connections (equivalent to `ipvsadm -x UTHRESHOLD`). Defaults to
+    /// 0, meaning no limit.
+    pub fn u_thresh(mut self, threshold: u32) -> Self {
+        self.u_thresh = threshold;
+        self
+    }
+
+    /// Set the number of connections below which the destination is used again after reaching
+    /// the upper threshold (equivalent to `ipvsadm -y LTHRESHOLD`)
+    pub fn l_thresh(mut self, threshold: u32) -> Self {
+        self.l_thresh = threshold;
+        self
+    }
+
+    /// Execute the request
+    pub async fn execute(self) -> Result<(), Error> {
+        let IpvsDestRequest {
+            handle,
+            service,
+            addr,
+            message,
+            fwd_method,
+            weight,
+            u_thresh,
+            l_thresh,
+        } = self;
+
+        let mut nlas = dest_nlas(addr);
+        nlas.push(IpvsDest::FwdMethod(fwd_method));
+        nlas.push(IpvsDest::Weight(weight));
+        nlas.push(IpvsDest::UThresh(u_thresh));
+        nlas.push(IpvsDest::LThresh(l_thresh));
+        handle
+            .acked_request(message(vec![
+                IpvsNla::Service(service.to_nlas()),
+                IpvsNla::Dest(nlas),
+            ]))
+            .await
+    }
+}
@@ -0,0 +1,74 @@
+use futures::future;
+use futures::{Stream, TryStreamExt};
+
+use crate::packet::constants::{IPVS_GENL_NAME, NLM_F_ACK, NLM_F_REQUEST};
+use crate::packet::{GenlPayload, IpvsMessage, NetlinkFlags};
+use crate::{Error, Handle, IpvsDestHandle, IpvsServiceHandle};
+
+#[derive(Clone, Debug)]
+pub struct IpvsHandle(Handle);
+
+impl IpvsHandle {
+    pub fn new(handle: Handle) -> Self {
+        IpvsHandle(handle)
+    }
+
+    /// Send an IPVS request, and get the replies as a stream of messages. The `IPVS` family
+    /// identifier is resolved first if necessary.
+    pub fn request(
+        &self,
+        message: IpvsMessage,
+        flags: NetlinkFlags,
+    ) -> impl Stream<Item = Result<IpvsMessage, Error>> {
+        self.request_with(message, flags, |msg| Ok(Some(msg)))
+    }
+
+    /// Same as [`request`](#method.request), but `reply` extracts what is expected from each
+    /// reply, or hands the reply back if it is not expected, in which case the stream yields an
+    /// `ErrorKind::UnexpectedMessage` error. The replies for which it returns `Ok(None)` are
+    /// skipped.
+    pub(crate) fn request_with<R, F>(
+        &self,
+        message: IpvsMessage,
+        flags: NetlinkFlags,
+        mut reply: F,
+    ) -> impl Stream<Item = Result<R, Error>>
+    where
+        F: FnMut(IpvsMessage) -> Result<Option<R>, IpvsMessage>,
+    {
+        self.0.family_request(
+            IPVS_GENL_NAME,
+            GenlPayload::Ipvs(message),
+            flags,
+            move |msg| reply(msg).map_err(GenlPayload::Ipvs),
+        )
+    }
+
+    /// Send an IPVS request that only expects an acknowledgement
+    pub(crate) async fn acked_request(&self, message: IpvsMessage) -> Result<(), Error> {
+        self.request(message, NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK))
+            .try_for_each(|_| future::ready(Ok(())))
+            .await
+    }
+
+    /// Create a new handle, specifically for virtual services requests
+    pub fn service(&self) -> IpvsServiceHandle {
+        IpvsServiceHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for destinations (real servers) requests
+    pub fn dest(&self) -> IpvsDestHandle {
+        IpvsDestHandle::new(self.clone())
+    }
+
+    /// Delete all the virtual services and their destinations (equivalent to `ipvsadm -C`)
+    pub async fn flush(&self) -> Result<(), Error> {
+        self.acked_request(IpvsMessage::Flush(vec![])).await
+    }
+
+    /// Reset the counters of all the virtual services and destinations (equivalent to
+    /// `ipvsadm -Z`)
+    pub async fn zero(&self) -> Result<(), Error> {
+        self.acked_request(IpvsMessage::Zero(vec![])).await
+    }
+}
@@ -0,0 +1,8 @@
+mod handle;
+pub use self::handle::*;
+
+mod service;
+pub use self::service::*;
+
+mod dest;
+pub use self::dest::*;
@@ -0,0 +1,203 @@
+use std::net::{IpAddr, SocketAddr};
+
+use futures::Stream;
+
+use crate::packet::constants::{
+    AF_INET, AF_INET6, IPPROTO_SCTP, IPPROTO_TCP, IPPROTO_UDP, IP_VS_SVC_F_PERSISTENT, NLM_F_DUMP,
+    NLM_F_REQUEST,
+};
+use crate::packet::{IpvsMessage, IpvsNla, IpvsService, IpvsServiceFlags, NetlinkFlags};
+use crate::{Error, IpvsHandle};
+
+/// Identifies a virtual service
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IpvsServiceKey {
+    /// A TCP service listening on the given address (equivalent to `ipvsadm -t`)
+    Tcp(SocketAddr),
+    /// A UDP service listening on the given address (equivalent to `ipvsadm -u`)
+    Udp(SocketAddr),
+    /// A SCTP service listening on the given address (equivalent to `ipvsadm --sctp-service`)
+    Sctp(SocketAddr),
+    /// A service handling the packets with the given firewall mark, for the given address
+    /// family, `AF_INET` or `AF_INET6` (equivalent to `ipvsadm -f`)
+    Fwmark { af: u16, mark: u32 },
+}
+
+impl IpvsServiceKey {
+    fn af(self) -> u16 {
+        match self {
+            IpvsServiceKey::Tcp(addr) | IpvsServiceKey::Udp(addr) | IpvsServiceKey::Sctp(addr) => {
+                if addr.is_ipv4() {
+                    AF_INET
+                } else {
+                    AF_INET6
+                }
+            }
+            IpvsServiceKey::Fwmark { af, .. } => af,
+        }
+    }
+
+    pub(crate) fn to_nlas(self) -> Vec<IpvsService> {
+        let (protocol, addr) = match self {
+            IpvsServiceKey::Tcp(addr) => (IPPROTO_TCP, addr),
+            IpvsServiceKey::Udp(addr) => (IPPROTO_UDP, addr),
+            IpvsServiceKey::Sctp(addr) => (IPPROTO_SCTP, addr),
+            IpvsServiceKey::Fwmark { af, mark } => {
+                return vec![IpvsService::Af(af), IpvsService::Fwmark(mark)];
+            }
+        };
+        let bytes = match addr.ip() {
+            IpAddr::V4(ipv4) => ipv4.octets().to_vec(),
+            IpAddr::V6(ipv6) => ipv6.octets().to_vec(),
+        };
+        vec![
+            IpvsService::Af(self.af()),
+            IpvsService::Protocol(protocol),
+            IpvsService::Addr(bytes),
+            IpvsService::Port(addr.port()),
+        ]
+    }
+}
+
+/// A handle for virtual services requests
+pub struct IpvsServiceHandle(IpvsHandle);
+
+impl IpvsServiceHandle {
+    pub fn new(handle: IpvsHandle) -> Self {
+        IpvsServiceHandle(handle)
+    }
+
+    /// Create a virtual service (equivalent to `ipvsadm -A`)
+    pub fn add(&self, key: IpvsServiceKey) -> IpvsServiceRequest {
+        IpvsServiceRequest::new(self.0.clone(), key, IpvsMessage::NewService)
+    }
+
+    /// Edit a virtual service (equivalent to `ipvsadm -E`). Note that all the settings of the
+    /// service are replaced, so the ones that are not set on the request are reset to their
+    /// default value.
+    pub fn set(&self, key: IpvsServiceKey) -> IpvsServiceRequest {
+        IpvsServiceRequest::new(self.0.clone(), key, IpvsMessage::SetService)
+    }
+
+    /// Delete a virtual service (equivalent to `ipvsadm -D`)
+    pub async fn del(&self, key: IpvsServiceKey) -> Result<(), Error> {
+        self.0
+            .acked_request(IpvsMessage::DelService(vec![IpvsNla::Service(
+                key.to_nlas(),
+            )]))
+            .await
+    }
+
+    /// Retrieve the virtual services, and their statistics (equivalent to
+    /// `ipvsadm -L -n --stats`). The attributes of each service are returned as one item.
+    pub fn get(&self) -> impl Stream<Item = Result<Vec<IpvsService>, Error>> {
+        self.0.request_with(
+            IpvsMessage::GetService(vec![]),
+            NetlinkFlags::from(NLM_F_REQUEST | NLM_F_DUMP),
+            |msg| {
+                if !msg
+                    .nlas()
+                    .iter()
+                    .any(|nla| matches!(nla, IpvsNla::Service(_)))
+                {
+                    return Err(msg);
+                }
+                Ok(msg.into_nlas().into_iter().find_map(|nla| match nla {
+                    IpvsNla::Service(nlas) => Some(nlas),
+                    _ => None,
+                }))
+            },
+        )
+    }
+}
+
+pub struct IpvsServiceRequest {
+    handle: IpvsHandle,
+    key: IpvsServiceKey,
+    message: fn(Vec<IpvsNla>) -> IpvsMessage,
+    scheduler: String,
+    flags: u32,
+    timeout: u32,
+    netmask: Option<u32>,
+    pe_name: Option<String>,
+}
+
+impl IpvsServiceRequest {
+    fn new(
+        handle: IpvsHandle,
+        key: IpvsServiceKey,
+        message: fn(Vec<IpvsNla>) -> IpvsMessage,
+    ) -> Self {
+        IpvsServiceRequest {
+            handle,
+            key,
+            message,
+            scheduler: "wlc".into(),
+            flags: 0,
+            timeout: 0,
+            netmask: None,
+            pe_name: None,
+        }
+    }
+
+    /// Set the scheduler (equivalent to `ipvsadm -s SCHEDULER`). Defaults to `wlc`.
+    pub fn scheduler(mut self, name: &str) -> Self {
+        self.scheduler = name.to_string();
+        self
+    }
+
+    /// Set the service flags (`IP_VS_SVC_F_*`)
+    pub fn flags(mut self, flags: u32) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Make the service persistent, with the given timeout in seconds (equivalent to
+    /// `ipvsadm -p TIMEOUT`)
+    pub fn persistent(mut self, timeout: u32) -> Self {
+        self.flags |= IP_VS_SVC_F_PERSISTENT;
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the netmask used to group clients of persistent services (equivalent to
+    /// `ipvsadm -M NETMASK`). Defaults to `255.255.255.255` for IPv4 services, and to a prefix
+    /// length of 128 for IPv6 services.
+    pub fn netmask(mut self, netmask: u32) -> Self {
+        self.netmask = Some(netmask);
+        self
+    }
+
+    /// Set the persistence engine (equivalent to `ipvsadm --pe NAME`)
+    pub fn pe_name(mut self, name: &str) -> Self {
+        self.pe_name = Some(name.to_string());
+        self
+    }
+
+    /// Execute the request
+    pub async fn execute(self) -> Result<(), Error> {
+        let IpvsServiceRequest {
+            handle,
+            key,
+            message,
+            scheduler,
+            flags,
+            timeout,
+            netmask,
+            pe_name,
+        } = self;
+
+        let netmask = netmask.unwrap_or_else(|| if key.af() == AF_INET6 { 128 } else { !0 });
+        let mut nlas = key.to_nlas();
+        nlas.push(IpvsService::SchedName(scheduler));
+        nlas.push(IpvsService::Flags(IpvsServiceFlags::new(flags, !0)));
+        nlas.push(IpvsService::Timeout(timeout));
+        nlas.push(IpvsService::Netmask(netmask));
+        if let Some(pe_name) = pe_name {
+            nlas.push(IpvsService::PeName(pe_name));
+        }
+        handle
+            .acked_request(message(vec![IpvsNla::Service(nlas)]))
+            .await
+    }
+}
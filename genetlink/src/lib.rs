@@ -0,0 +1,157 @@
+//! This crate provides methods to use generic netlink families via the netlink protocol. Generic
+//! netlink families are identified by name, and the identifiers the kernel allocated for them are
+//! resolved (and cached) transparently.
+//!
+//! At the moment, the following families are supported:
+//!
+//! - `ethtool`, which can be used to query and change the settings of network devices
+//! - `devlink`, which exposes the devices themselves (ports, parameters, firmware versions, health
+//!   reporters, etc.), independently of their network interfaces
+//! - `mptcp_pm`, the Multipath TCP path manager, which manages the endpoints and limits used to
+//!   create additional subflows
+//! - `IPVS`, the kernel layer 4 load balancer, which manages virtual services and their
+//!   destinations (real servers)
+//! - `team`, the team driver, which exposes the options and ports of the team devices, and
+//!   notifies their changes
+//!
+//! # Example: showing the link modes of a device
+//!
+//! ```rust,no_run
+//! use futures::TryStreamExt;
+//! use genetlink::new_connection;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     // Create a netlink connection, and a handle to send requests via this connection
+//!     let (connection, handle) = new_connection().unwrap();
+//!
+//!     // The connection will run in the background
+//!     tokio::spawn(connection);
+//!
+//!     // Create a request (equivalent to `ethtool eth0`)
+//!     let mut link_modes = handle
+//!         .ethtool()
+//!         .link_modes()
+//!         .get()
+//!         .dev_name("eth0")
+//!         .execute();
+//!
+//!     // Wait for the replies
+//!     while let Some(nlas) = link_modes.try_next().await.unwrap() {
+//!         println!("{:#?}", nlas);
+//!     }
+//! }
+//! ```
+//!
+//! # Example: changing the size of the rings of a device
+//!
+//! ```rust,no_run
+//! use genetlink::new_connection;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (connection, handle) = new_connection().unwrap();
+//!     tokio::spawn(connection);
+//!
+//!     // Equivalent to `ethtool -G eth0 rx 4096 tx 4096`
+//!     handle
+//!         .ethtool()
+//!         .rings()
+//!         .set("eth0")
+//!         .rx(4096)
+//!         .tx(4096)
+//!         .execute()
+//!         .await
+//!         .unwrap();
+//! }
+//! ```
+
+#[macro_use]
+extern crate lazy_static;
+
+pub use crate::packet::constants;
+pub use netlink_packet as packet;
+pub use netlink_proto::AsyncSocket;
+#[cfg(feature = "async_std_support")]
+pub use netlink_proto::AsyncStdSocket;
+use netlink_proto::NetlinkCodec;
+#[cfg(feature = "smol_support")]
+pub use netlink_proto::SmolSocket;
+#[cfg(feature = "tokio_support")]
+pub use netlink_proto::TokioSocket;
+pub use netlink_proto::{
+    ChannelCapacities, Connection, IncomingMessages, NetworkNamespace, Protocol,
+};
+
+mod handle;
+pub use crate::handle::*;
+
+mod errors;
+pub use crate::errors::*;
+
+mod ethtool;
+pub use crate::ethtool::*;
+
+mod devlink;
+pub use crate::devlink::*;
+
+mod mptcp;
+pub use crate::mptcp::*;
+
+mod ipvs;
+pub use crate::ipvs::*;
+
+mod team;
+pub use crate::team::*;
+
+use std::io;
+
+#[cfg(feature = "tokio_support")]
+pub fn new_connection() -> io::Result<(Connection<TokioSocket>, Handle)> {
+    let (conn, handle, _) = new_connection_with_socket()?;
+    Ok((conn, handle))
+}
+
+/// Same as [`new_connection`](fn.new_connection.html), but the connection is opened in the given
+/// network namespace, so that the requests made with the handle apply to that namespace.
+#[cfg(feature = "tokio_support")]
+pub fn new_connection_in_namespace(
+    ns: NetworkNamespace,
+) -> io::Result<(Connection<TokioSocket>, Handle)> {
+    let (conn, handle, _) = netlink_proto::new_connection_in_namespace(Protocol::Generic, ns)?;
+    Ok((conn, Handle::new(handle)))
+}
+
+/// Same as [`new_connection`](fn.new_connection.html), but also return the messages that are not
+/// replies to a request, such as the messages sent to the multicast groups the socket is a member
+/// of.
+#[cfg(feature = "tokio_support")]
+pub fn new_connection_with_messages(
+) -> io::Result<(Connection<TokioSocket>, Handle, IncomingMessages)> {
+    new_connection_with_socket()
+}
+
+/// Same as [`new_connection_with_messages`](fn.new_connection_with_messages.html), but with the
+/// given socket type, so that the connection can run on another runtime than tokio
+/// (`SmolSocket` with the `smol_support` feature, or `AsyncStdSocket` with the
+/// `async_std_support` feature).
+pub fn new_connection_with_socket<S: AsyncSocket>(
+) -> io::Result<(Connection<S>, Handle, IncomingMessages)> {
+    let (conn, handle, messages) = netlink_proto::new_connection_with_socket(Protocol::Generic)?;
+    Ok((conn, Handle::new(handle), messages))
+}
+
+/// Create a connection from an existing `NETLINK_GENERIC` socket, with the given capacities for
+/// the channels between the connection and the handle (see
+/// [`ChannelCapacities`](struct.ChannelCapacities.html)).
+pub fn new_connection_from_socket<S: AsyncSocket>(
+    socket: S,
+    capacities: ChannelCapacities,
+) -> (Connection<S>, Handle, IncomingMessages) {
+    let (conn, handle, messages) = netlink_proto::new_connection_from_socket(
+        socket,
+        NetlinkCodec::for_protocol(Protocol::Generic),
+        capacities,
+    );
+    (conn, Handle::new(handle), messages)
+}
@@ -0,0 +1,198 @@
+use std::net::IpAddr;
+
+use futures::future;
+use futures::{Stream, TryStreamExt};
+
+use crate::packet::constants::{
+    AF_INET, AF_INET6, MPTCP_PM_ADDR_FLAG_BACKUP, MPTCP_PM_ADDR_FLAG_SIGNAL,
+    MPTCP_PM_ADDR_FLAG_SUBFLOW, NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST,
+};
+use crate::packet::{MptcpAddress, MptcpPmMessage, MptcpPmNla, NetlinkFlags};
+use crate::{Error, MptcpHandle};
+
+/// A handle for MPTCP endpoints requests
+pub struct MptcpEndpointHandle(MptcpHandle);
+
+impl MptcpEndpointHandle {
+    pub fn new(handle: MptcpHandle) -> Self {
+        MptcpEndpointHandle(handle)
+    }
+
+    /// Add an endpoint with the given address (equivalent to `ip mptcp endpoint add ADDRESS`)
+    pub fn add(&self, address: IpAddr) -> MptcpEndpointAddRequest {
+        MptcpEndpointAddRequest::new(self.0.clone(), address)
+    }
+
+    /// Delete the endpoint with the given identifier (equivalent to
+    /// `ip mptcp endpoint delete id ID`)
+    pub fn del(&self, id: u8) -> MptcpEndpointDelRequest {
+        MptcpEndpointDelRequest::new(self.0.clone(), id)
+    }
+
+    /// Retrieve the endpoints (equivalent to `ip mptcp endpoint show`)
+    pub fn get(&self) -> MptcpEndpointGetRequest {
+        MptcpEndpointGetRequest::new(self.0.clone())
+    }
+
+    /// Delete all the endpoints (equivalent to `ip mptcp endpoint flush`)
+    pub async fn flush(&self) -> Result<(), Error> {
+        self.0
+            .request(
+                MptcpPmMessage::FlushAddrs(vec![]),
+                NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK),
+            )
+            .try_for_each(|_| future::ready(Ok(())))
+            .await
+    }
+}
+
+pub struct MptcpEndpointAddRequest {
+    handle: MptcpHandle,
+    nlas: Vec<MptcpAddress>,
+    flags: u32,
+}
+
+impl MptcpEndpointAddRequest {
+    fn new(handle: MptcpHandle, address: IpAddr) -> Self {
+        let nlas = match address {
+            IpAddr::V4(ipv4) => vec![
+                MptcpAddress::Family(AF_INET),
+                MptcpAddress::Addr4(ipv4.octets().to_vec()),
+            ],
+            IpAddr::V6(ipv6) => vec![
+                MptcpAddress::Family(AF_INET6),
+                MptcpAddress::Addr6(ipv6.octets().to_vec()),
+            ],
+        };
+        MptcpEndpointAddRequest {
+            handle,
+            nlas,
+            flags: 0,
+        }
+    }
+
+    /// Set the endpoint identifier. By default, the kernel allocates one.
+    pub fn id(mut self, id: u8) -> Self {
+        self.nlas.push(MptcpAddress::Id(id));
+        self
+    }
+
+    /// Set the port of the endpoint
+    pub fn port(mut self, port: u16) -> Self {
+        self.nlas.push(MptcpAddress::Port(port));
+        self
+    }
+
+    /// Bind the endpoint to the interface with the given index (equivalent to `dev IFNAME`)
+    pub fn ifindex(mut self, index: u32) -> Self {
+        self.nlas.push(MptcpAddress::IfIndex(index as i32));
+        self
+    }
+
+    /// Announce the address to the peers (`MPTCP_PM_ADDR_FLAG_SIGNAL`)
+    pub fn signal(mut self) -> Self {
+        self.flags |= MPTCP_PM_ADDR_FLAG_SIGNAL;
+        self
+    }
+
+    /// Create subflows from this address (`MPTCP_PM_ADDR_FLAG_SUBFLOW`)
+    pub fn subflow(mut self) -> Self {
+        self.flags |= MPTCP_PM_ADDR_FLAG_SUBFLOW;
+        self
+    }
+
+    /// Only use the subflows from this address as backup (`MPTCP_PM_ADDR_FLAG_BACKUP`)
+    pub fn backup(mut self) -> Self {
+        self.flags |= MPTCP_PM_ADDR_FLAG_BACKUP;
+        self
+    }
+
+    /// Execute the request
+    pub async fn execute(self) -> Result<(), Error> {
+        let MptcpEndpointAddRequest {
+            handle,
+            mut nlas,
+            flags,
+        } = self;
+        if flags != 0 {
+            nlas.push(MptcpAddress::Flags(flags));
+        }
+        handle
+            .request(
+                MptcpPmMessage::AddAddr(vec![MptcpPmNla::Addr(nlas)]),
+                NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK),
+            )
+            .try_for_each(|_| future::ready(Ok(())))
+            .await
+    }
+}
+
+pub struct MptcpEndpointDelRequest {
+    handle: MptcpHandle,
+    id: u8,
+}
+
+impl MptcpEndpointDelRequest {
+    fn new(handle: MptcpHandle, id: u8) -> Self {
+        MptcpEndpointDelRequest { handle, id }
+    }
+
+    /// Execute the request
+    pub async fn execute(self) -> Result<(), Error> {
+        let MptcpEndpointDelRequest { handle, id } = self;
+        handle
+            .request(
+                MptcpPmMessage::DelAddr(vec![MptcpPmNla::Addr(vec![MptcpAddress::Id(id)])]),
+                NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK),
+            )
+            .try_for_each(|_| future::ready(Ok(())))
+            .await
+    }
+}
+
+pub struct MptcpEndpointGetRequest {
+    handle: MptcpHandle,
+    id: Option<u8>,
+}
+
+impl MptcpEndpointGetRequest {
+    fn new(handle: MptcpHandle) -> Self {
+        MptcpEndpointGetRequest { handle, id: None }
+    }
+
+    /// Only retrieve the endpoint with the given identifier. By default, all the endpoints are
+    /// retrieved.
+    pub fn id(mut self, id: u8) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Execute the request. The attributes of each endpoint are returned as one item.
+    pub fn execute(self) -> impl Stream<Item = Result<Vec<MptcpAddress>, Error>> {
+        let MptcpEndpointGetRequest { handle, id } = self;
+        let (nlas, flags) = match id {
+            Some(id) => (
+                vec![MptcpPmNla::Addr(vec![MptcpAddress::Id(id)])],
+                NLM_F_REQUEST,
+            ),
+            None => (vec![], NLM_F_REQUEST | NLM_F_DUMP),
+        };
+        handle.request_with(
+            MptcpPmMessage::GetAddr(nlas),
+            NetlinkFlags::from(flags),
+            |msg| {
+                if !msg
+                    .nlas()
+                    .iter()
+                    .any(|nla| matches!(nla, MptcpPmNla::Addr(_)))
+                {
+                    return Err(msg);
+                }
+                Ok(msg.into_nlas().into_iter().find_map(|nla| match nla {
+                    MptcpPmNla::Addr(nlas) => Some(nlas),
+                    _ => None,
+                }))
+            },
+        )
+    }
+}
@@ -0,0 +1,37 @@
+use failure::Fail;
+use futures::future;
+use futures::{Stream, StreamExt};
+
+use crate::packet::{
+    GenlBuffer, GenlMessage, GenlPayload, MptcpEvent, NetlinkMessage, NetlinkPayload, Parseable,
+};
+use crate::{Error, ErrorKind};
+
+/// Decode the messages received from the `mptcp_pm_events` multicast group. Messages that are not
+/// generic netlink messages are ignored.
+///
+/// See [`new_connection_with_messages`](fn.new_connection_with_messages.html) and
+/// [`Handle::resolve_mcast_group`](struct.Handle.html#method.resolve_mcast_group) to subscribe to
+/// the events.
+pub fn mptcp_events(
+    messages: impl Stream<Item = NetlinkMessage>,
+) -> impl Stream<Item = Result<MptcpEvent, Error>> {
+    messages
+        .filter_map(|msg| {
+            future::ready(match msg.into_parts().1 {
+                NetlinkPayload::Genl(GenlMessage {
+                    payload: GenlPayload::Raw(bytes),
+                    ..
+                }) => Some(bytes),
+                _ => None,
+            })
+        })
+        .map(|bytes| {
+            GenlBuffer::new_checked(&bytes[..])
+                .and_then(|buffer| buffer.parse())
+                .map_err(|e| {
+                    e.context(ErrorKind::DecodeFailed("MPTCP event".into()))
+                        .into()
+                })
+        })
+}
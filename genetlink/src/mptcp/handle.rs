@@ -0,0 +1,57 @@
+use futures::Stream;
+
+use crate::packet::constants::MPTCP_PM_NAME;
+use crate::packet::{GenlPayload, MptcpPmMessage, NetlinkFlags};
+use crate::{Error, Handle, MptcpEndpointHandle, MptcpLimitsHandle};
+
+#[derive(Clone, Debug)]
+pub struct MptcpHandle(Handle);
+
+impl MptcpHandle {
+    pub fn new(handle: Handle) -> Self {
+        MptcpHandle(handle)
+    }
+
+    /// Send a MPTCP path manager request, and get the replies as a stream of messages. The
+    /// `mptcp_pm` family identifier is resolved first if necessary.
+    pub fn request(
+        &self,
+        message: MptcpPmMessage,
+        flags: NetlinkFlags,
+    ) -> impl Stream<Item = Result<MptcpPmMessage, Error>> {
+        self.request_with(message, flags, |msg| Ok(Some(msg)))
+    }
+
+    /// Same as [`request`](#method.request), but `reply` extracts what is expected from each
+    /// reply, or hands the reply back if it is not expected, in which case the stream yields an
+    /// `ErrorKind::UnexpectedMessage` error. The replies for which it returns `Ok(None)` are
+    /// skipped.
+    pub(crate) fn request_with<R, F>(
+        &self,
+        message: MptcpPmMessage,
+        flags: NetlinkFlags,
+        mut reply: F,
+    ) -> impl Stream<Item = Result<R, Error>>
+    where
+        F: FnMut(MptcpPmMessage) -> Result<Option<R>, MptcpPmMessage>,
+    {
+        self.0.family_request(
+            MPTCP_PM_NAME,
+            GenlPayload::MptcpPm(message),
+            flags,
+            move |msg| reply(msg).map_err(GenlPayload::MptcpPm),
+        )
+    }
+
+    /// Create a new handle, specifically for endpoint requests (equivalent to
+    /// `ip mptcp endpoint` commands)
+    pub fn endpoint(&self) -> MptcpEndpointHandle {
+        MptcpEndpointHandle::new(self.clone())
+    }
+
+    /// Create a new handle, specifically for limits requests (equivalent to `ip mptcp limits`
+    /// commands)
+    pub fn limits(&self) -> MptcpLimitsHandle {
+        MptcpLimitsHandle::new(self.clone())
+    }
+}
@@ -0,0 +1,70 @@
+use futures::future;
+use futures::{Stream, TryStreamExt};
+
+use crate::packet::constants::{NLM_F_ACK, NLM_F_REQUEST};
+use crate::packet::{MptcpPmMessage, MptcpPmNla, NetlinkFlags};
+use crate::{Error, MptcpHandle};
+
+/// A handle for MPTCP limits requests
+pub struct MptcpLimitsHandle(MptcpHandle);
+
+impl MptcpLimitsHandle {
+    pub fn new(handle: MptcpHandle) -> Self {
+        MptcpLimitsHandle(handle)
+    }
+
+    /// Retrieve the limits (equivalent to `ip mptcp limits show`)
+    pub fn get(&self) -> impl Stream<Item = Result<Vec<MptcpPmNla>, Error>> {
+        self.0
+            .request(
+                MptcpPmMessage::GetLimits(vec![]),
+                NetlinkFlags::from(NLM_F_REQUEST),
+            )
+            .map_ok(MptcpPmMessage::into_nlas)
+    }
+
+    /// Change the limits (equivalent to `ip mptcp limits set`)
+    pub fn set(&self) -> MptcpLimitsSetRequest {
+        MptcpLimitsSetRequest::new(self.0.clone())
+    }
+}
+
+pub struct MptcpLimitsSetRequest {
+    handle: MptcpHandle,
+    nlas: Vec<MptcpPmNla>,
+}
+
+impl MptcpLimitsSetRequest {
+    fn new(handle: MptcpHandle) -> Self {
+        MptcpLimitsSetRequest {
+            handle,
+            nlas: vec![],
+        }
+    }
+
+    /// Set the maximum number of additional subflows per connection (equivalent to
+    /// `ip mptcp limits set subflow N`)
+    pub fn subflows(mut self, limit: u32) -> Self {
+        self.nlas.push(MptcpPmNla::Subflows(limit));
+        self
+    }
+
+    /// Set the maximum number of addresses accepted from the peers (equivalent to
+    /// `ip mptcp limits set add_addr_accepted N`)
+    pub fn add_addr_accepted(mut self, limit: u32) -> Self {
+        self.nlas.push(MptcpPmNla::RcvAddAddrs(limit));
+        self
+    }
+
+    /// Execute the request
+    pub async fn execute(self) -> Result<(), Error> {
+        let MptcpLimitsSetRequest { handle, nlas } = self;
+        handle
+            .request(
+                MptcpPmMessage::SetLimits(nlas),
+                NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK),
+            )
+            .try_for_each(|_| future::ready(Ok(())))
+            .await
+    }
+}
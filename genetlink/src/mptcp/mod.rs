@@ -0,0 +1,11 @@
+mod handle;
+pub use self::handle::*;
+
+mod endpoint;
+pub use self::endpoint::*;
+
+mod limits;
+pub use self::limits::*;
+
+mod events;
+pub use self::events::*;
@@ -0,0 +1,42 @@
+use failure::Fail;
+use futures::future;
+use futures::{Stream, StreamExt};
+
+use crate::packet::{
+    GenlBuffer, GenlMessage, GenlPayload, NetlinkMessage, NetlinkPayload, Parseable, TeamMessage,
+};
+use crate::{Error, ErrorKind};
+
+/// Decode the messages received from the team `change_event` multicast group. Messages that are
+/// not generic netlink messages are ignored.
+///
+/// Changes of the ports are notified with `TeamMessage::PortListGet` messages, and changes of the
+/// options with `TeamMessage::OptionsGet` messages. Only the items that changed are included, with
+/// their `changed` or `removed` field set. Use [`team_ports`](fn.team_ports.html) and
+/// [`team_options`](fn.team_options.html) to extract them.
+///
+/// See [`new_connection_with_messages`](fn.new_connection_with_messages.html) and
+/// [`Handle::resolve_mcast_group`](struct.Handle.html#method.resolve_mcast_group) to subscribe to
+/// the events.
+pub fn team_events(
+    messages: impl Stream<Item = NetlinkMessage>,
+) -> impl Stream<Item = Result<TeamMessage, Error>> {
+    messages
+        .filter_map(|msg| {
+            future::ready(match msg.into_parts().1 {
+                NetlinkPayload::Genl(GenlMessage {
+                    payload: GenlPayload::Raw(bytes),
+                    ..
+                }) => Some(bytes),
+                _ => None,
+            })
+        })
+        .map(|bytes| {
+            GenlBuffer::new_checked(&bytes[..])
+                .and_then(|buffer| buffer.parse())
+                .map_err(|e| {
+                    e.context(ErrorKind::DecodeFailed("team event".into()))
+                        .into()
+                })
+        })
+}
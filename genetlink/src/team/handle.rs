@@ -0,0 +1,123 @@
+use futures::future;
+use futures::stream::{self, Stream, TryStreamExt};
+
+use crate::packet::constants::{NLM_F_ACK, NLM_F_REQUEST, TEAM_GENL_NAME};
+use crate::packet::{GenlPayload, NetlinkFlags, TeamMessage, TeamNla, TeamOption, TeamPort};
+use crate::{Error, Handle};
+
+#[derive(Clone, Debug)]
+pub struct TeamHandle(Handle);
+
+impl TeamHandle {
+    pub fn new(handle: Handle) -> Self {
+        TeamHandle(handle)
+    }
+
+    /// Send a team request, and get the replies as a stream of messages. The `team` family
+    /// identifier is resolved first if necessary.
+    pub fn request(
+        &self,
+        message: TeamMessage,
+        flags: NetlinkFlags,
+    ) -> impl Stream<Item = Result<TeamMessage, Error>> {
+        self.request_with(message, flags, |msg| Ok(Some(msg)))
+    }
+
+    /// Same as [`request`](#method.request), but `reply` extracts what is expected from each
+    /// reply, or hands the reply back if it is not expected, in which case the stream yields an
+    /// `ErrorKind::UnexpectedMessage` error. The replies for which it returns `Ok(None)` are
+    /// skipped.
+    pub(crate) fn request_with<R, F>(
+        &self,
+        message: TeamMessage,
+        flags: NetlinkFlags,
+        mut reply: F,
+    ) -> impl Stream<Item = Result<R, Error>>
+    where
+        F: FnMut(TeamMessage) -> Result<Option<R>, TeamMessage>,
+    {
+        self.0.family_request(
+            TEAM_GENL_NAME,
+            GenlPayload::Team(message),
+            flags,
+            move |msg| reply(msg).map_err(GenlPayload::Team),
+        )
+    }
+
+    /// Retrieve the options of the team device with the given index, including the options of
+    /// its ports (equivalent to `teamnl DEV options`)
+    pub fn options(&self, team_index: u32) -> impl Stream<Item = Result<TeamOption, Error>> {
+        self.request_with(
+            TeamMessage::OptionsGet(vec![TeamNla::TeamIfIndex(team_index)]),
+            NetlinkFlags::from(NLM_F_REQUEST),
+            |msg| match msg {
+                TeamMessage::OptionsGet(_) => {
+                    Ok(Some(stream::iter(team_options(msg).into_iter().map(Ok))))
+                }
+                msg => Err(msg),
+            },
+        )
+        .try_flatten()
+    }
+
+    /// Set options of the team device with the given index (equivalent to
+    /// `teamnl DEV setoption NAME VALUE`). Options that apply to a port must have their
+    /// `port_ifindex` set.
+    pub async fn set_options(
+        &self,
+        team_index: u32,
+        options: Vec<TeamOption>,
+    ) -> Result<(), Error> {
+        self.request(
+            TeamMessage::OptionsSet(vec![
+                TeamNla::TeamIfIndex(team_index),
+                TeamNla::Options(options),
+            ]),
+            NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK),
+        )
+        .try_for_each(|_| future::ready(Ok(())))
+        .await
+    }
+
+    /// Retrieve the ports of the team device with the given index (equivalent to
+    /// `teamnl DEV ports`)
+    pub fn ports(&self, team_index: u32) -> impl Stream<Item = Result<TeamPort, Error>> {
+        self.request_with(
+            TeamMessage::PortListGet(vec![TeamNla::TeamIfIndex(team_index)]),
+            NetlinkFlags::from(NLM_F_REQUEST),
+            |msg| match msg {
+                TeamMessage::PortListGet(_) => {
+                    Ok(Some(stream::iter(team_ports(msg).into_iter().map(Ok))))
+                }
+                msg => Err(msg),
+            },
+        )
+        .try_flatten()
+    }
+}
+
+/// Return the options carried by a team message
+pub fn team_options(message: TeamMessage) -> Vec<TeamOption> {
+    message
+        .into_nlas()
+        .into_iter()
+        .filter_map(|nla| match nla {
+            TeamNla::Options(options) => Some(options),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// Return the ports carried by a team message
+pub fn team_ports(message: TeamMessage) -> Vec<TeamPort> {
+    message
+        .into_nlas()
+        .into_iter()
+        .filter_map(|nla| match nla {
+            TeamNla::Ports(ports) => Some(ports),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
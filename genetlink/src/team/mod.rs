@@ -0,0 +1,5 @@
+mod handle;
+pub use self::handle::*;
+
+mod events;
+pub use self::events::*;
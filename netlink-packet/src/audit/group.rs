@@ -0,0 +1,29 @@
+use crate::constants::*;
+
+/// Multicast groups of the `NETLINK_AUDIT` protocol (`AUDIT_NLGRP_*`)
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum AuditGroup {
+    /// `AUDIT_NLGRP_READLOG`: audit events, for read only listeners that do not manage the audit
+    /// system
+    Readlog,
+    /// Another group
+    Other(u32),
+}
+
+impl From<u32> for AuditGroup {
+    fn from(value: u32) -> Self {
+        match value {
+            AUDIT_NLGRP_READLOG => AuditGroup::Readlog,
+            _ => AuditGroup::Other(value),
+        }
+    }
+}
+
+impl From<AuditGroup> for u32 {
+    fn from(group: AuditGroup) -> u32 {
+        match group {
+            AuditGroup::Readlog => AUDIT_NLGRP_READLOG,
+            AuditGroup::Other(value) => value,
+        }
+    }
+}
@@ -23,3 +23,6 @@ pub use self::rules::*;
 
 mod message;
 pub use self::message::*;
+
+mod group;
+pub use self::group::*;
@@ -0,0 +1,108 @@
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::constants::GENL_HDRLEN;
+use crate::{DecodeError, Field, Index, NlaBuffer, NlasIterator, Rest};
+
+const CMD: Index = 0;
+const VERSION: Index = 1;
+const RESERVED: Field = 2..4;
+const ATTRIBUTES: Rest = RESERVED.end..;
+
+pub const GENL_HEADER_LEN: usize = ATTRIBUTES.start;
+
+/// A buffer wrapping a generic netlink message payload:
+///
+/// ```no_rust
+/// 0                8                16              24               32
+/// +----------------+----------------+----------------+----------------+
+/// |    command     |    version     |            reserved             |
+/// +----------------+----------------+----------------+----------------+
+/// |                            attributes                             |
+/// |                          (variable length)                        |
+/// +----------------+----------------+----------------+----------------+
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GenlBuffer<T> {
+    buffer: T,
+}
+
+impl<T: AsRef<[u8]>> GenlBuffer<T> {
+    pub fn new(buffer: T) -> GenlBuffer<T> {
+        GenlBuffer { buffer }
+    }
+
+    pub fn new_checked(buffer: T) -> Result<GenlBuffer<T>, DecodeError> {
+        let buf = Self::new(buffer);
+        buf.check_buffer_length()?;
+        Ok(buf)
+    }
+
+    fn check_buffer_length(&self) -> Result<(), DecodeError> {
+        let len = self.buffer.as_ref().len();
+        if len < GENL_HDRLEN {
+            Err(format!(
+                "invalid GenlBuffer: length is {} but generic netlink headers are {} bytes",
+                len, GENL_HDRLEN
+            )
+            .into())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.buffer
+    }
+
+    pub fn cmd(&self) -> u8 {
+        let data = self.buffer.as_ref();
+        data[CMD]
+    }
+
+    pub fn version(&self) -> u8 {
+        let data = self.buffer.as_ref();
+        data[VERSION]
+    }
+
+    pub fn reserved(&self) -> u16 {
+        let data = self.buffer.as_ref();
+        NativeEndian::read_u16(&data[RESERVED])
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> GenlBuffer<&'a T> {
+    /// Return a pointer to the payload.
+    pub fn payload(&self) -> &'a [u8] {
+        let data = self.buffer.as_ref();
+        &data[ATTRIBUTES]
+    }
+
+    pub fn nlas(&self) -> impl Iterator<Item = Result<NlaBuffer<&'a [u8]>, DecodeError>> {
+        NlasIterator::new(self.payload())
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]> + ?Sized> GenlBuffer<&mut T> {
+    /// Return a mutable pointer to the payload.
+    pub fn payload_mut(&mut self) -> &mut [u8] {
+        let data = self.buffer.as_mut();
+        &mut data[ATTRIBUTES]
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> GenlBuffer<T> {
+    pub fn set_cmd(&mut self, value: u8) {
+        let data = self.buffer.as_mut();
+        data[CMD] = value
+    }
+
+    pub fn set_version(&mut self, value: u8) {
+        let data = self.buffer.as_mut();
+        data[VERSION] = value
+    }
+
+    pub fn set_reserved(&mut self, value: u16) {
+        let data = self.buffer.as_mut();
+        NativeEndian::write_u16(&mut data[RESERVED], value)
+    }
+}
@@ -0,0 +1,171 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::{CtrlNla, DecodeError, Emitable, GenlBuffer, GenlHeader, Parseable};
+
+/// Version of the `nlctrl` family
+pub const CTRL_VERSION: u8 = 2;
+
+/// Messages of the generic netlink controller (`nlctrl`), used to query the generic netlink
+/// families registered in the kernel.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CtrlMessage {
+    NewFamily(Vec<CtrlNla>),
+    DelFamily(Vec<CtrlNla>),
+    GetFamily(Vec<CtrlNla>),
+    NewMcastGrp(Vec<CtrlNla>),
+    DelMcastGrp(Vec<CtrlNla>),
+    GetMcastGrp(Vec<CtrlNla>),
+}
+
+impl CtrlMessage {
+    pub fn cmd(&self) -> u8 {
+        use self::CtrlMessage::*;
+        match *self {
+            NewFamily(_) => CTRL_CMD_NEWFAMILY,
+            DelFamily(_) => CTRL_CMD_DELFAMILY,
+            GetFamily(_) => CTRL_CMD_GETFAMILY,
+            NewMcastGrp(_) => CTRL_CMD_NEWMCAST_GRP,
+            DelMcastGrp(_) => CTRL_CMD_DELMCAST_GRP,
+            GetMcastGrp(_) => CTRL_CMD_GETMCAST_GRP,
+        }
+    }
+
+    #[rustfmt::skip]
+    pub fn nlas(&self) -> &[CtrlNla] {
+        use self::CtrlMessage::*;
+        match *self {
+            NewFamily(ref nlas)
+                | DelFamily(ref nlas)
+                | GetFamily(ref nlas)
+                | NewMcastGrp(ref nlas)
+                | DelMcastGrp(ref nlas)
+                | GetMcastGrp(ref nlas) => nlas.as_slice(),
+        }
+    }
+
+    /// Return the family identifier (`CTRL_ATTR_FAMILY_ID`), if any
+    pub fn family_id(&self) -> Option<u16> {
+        self.nlas().iter().find_map(|nla| match *nla {
+            CtrlNla::FamilyId(id) => Some(id),
+            _ => None,
+        })
+    }
+
+    /// Return the family name (`CTRL_ATTR_FAMILY_NAME`), if any
+    pub fn family_name(&self) -> Option<&str> {
+        self.nlas().iter().find_map(|nla| match *nla {
+            CtrlNla::FamilyName(ref name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Return the identifier of the multicast group with the given name (`CTRL_ATTR_MCAST_GROUPS`),
+    /// if any
+    pub fn mcast_group_id(&self, name: &str) -> Option<u32> {
+        self.nlas().iter().find_map(|nla| match *nla {
+            CtrlNla::McastGroups(ref groups) => groups
+                .iter()
+                .find(|group| group.name == name)
+                .map(|group| group.id),
+            _ => None,
+        })
+    }
+}
+
+impl Emitable for CtrlMessage {
+    fn buffer_len(&self) -> usize {
+        GenlHeader::new(self.cmd(), CTRL_VERSION).buffer_len() + self.nlas().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let header = GenlHeader::new(self.cmd(), CTRL_VERSION);
+        header.emit(buffer);
+        self.nlas().emit(&mut buffer[header.buffer_len()..]);
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<CtrlMessage> for GenlBuffer<&T> {
+    fn parse(&self) -> Result<CtrlMessage, DecodeError> {
+        use self::CtrlMessage::*;
+        let mut nlas = vec![];
+        for nla_buf in self.nlas() {
+            nlas.push(nla_buf?.parse().context("invalid nlctrl NLA")?);
+        }
+        Ok(match self.cmd() {
+            CTRL_CMD_NEWFAMILY => NewFamily(nlas),
+            CTRL_CMD_DELFAMILY => DelFamily(nlas),
+            CTRL_CMD_GETFAMILY => GetFamily(nlas),
+            CTRL_CMD_NEWMCAST_GRP => NewMcastGrp(nlas),
+            CTRL_CMD_DELMCAST_GRP => DelMcastGrp(nlas),
+            CTRL_CMD_GETMCAST_GRP => GetMcastGrp(nlas),
+            cmd => return Err(format!("unknown nlctrl command {}", cmd).into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CtrlMcastGroup, CtrlMessage, CtrlNla, Emitable, GenlBuffer, Parseable};
+
+    #[rustfmt::skip]
+    static NEW_FAMILY: [u8; 60] = [
+        0x01, // cmd: CTRL_CMD_NEWFAMILY
+        0x02, // version
+        0x00, 0x00, // reserved
+
+        // CTRL_ATTR_FAMILY_NAME
+        0x0c, 0x00, 0x02, 0x00,
+        0x65, 0x74, 0x68, 0x74, 0x6f, 0x6f, 0x6c, 0x00, // "ethtool\0"
+
+        // CTRL_ATTR_FAMILY_ID
+        0x06, 0x00, 0x01, 0x00,
+        0x14, 0x00, // 20
+        0x00, 0x00, // padding
+
+        // CTRL_ATTR_VERSION
+        0x08, 0x00, 0x03, 0x00,
+        0x01, 0x00, 0x00, 0x00,
+
+        // CTRL_ATTR_MCAST_GROUPS (nested)
+        0x1c, 0x00, 0x07, 0x80,
+            // group 1 (nested)
+            0x18, 0x00, 0x01, 0x80,
+                // CTRL_ATTR_MCAST_GRP_NAME
+                0x0c, 0x00, 0x01, 0x00,
+                0x6d, 0x6f, 0x6e, 0x69, 0x74, 0x6f, 0x72, 0x00, // "monitor\0"
+                // CTRL_ATTR_MCAST_GRP_ID
+                0x08, 0x00, 0x02, 0x00,
+                0x03, 0x00, 0x00, 0x00,
+    ];
+
+    fn new_family() -> CtrlMessage {
+        CtrlMessage::NewFamily(vec![
+            CtrlNla::FamilyName("ethtool".into()),
+            CtrlNla::FamilyId(20),
+            CtrlNla::Version(1),
+            CtrlNla::McastGroups(vec![CtrlMcastGroup {
+                name: "monitor".into(),
+                id: 3,
+            }]),
+        ])
+    }
+
+    #[test]
+    fn parse_new_family() {
+        let buffer = GenlBuffer::new_checked(&NEW_FAMILY[..]).unwrap();
+        let msg: CtrlMessage = buffer.parse().unwrap();
+        assert_eq!(msg, new_family());
+        assert_eq!(msg.family_id(), Some(20));
+        assert_eq!(msg.family_name(), Some("ethtool"));
+    }
+
+    #[test]
+    fn emit_new_family() {
+        let msg = new_family();
+        assert_eq!(msg.buffer_len(), NEW_FAMILY.len());
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &NEW_FAMILY[..]);
+    }
+}
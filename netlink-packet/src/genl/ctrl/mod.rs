@@ -0,0 +1,5 @@
+mod message;
+pub use self::message::*;
+
+mod nlas;
+pub use self::nlas::*;
@@ -0,0 +1,285 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_string, parse_u16, parse_u32};
+use crate::{
+    DecodeError, DefaultNla, Emitable, NestedNla, Nla, NlaBuffer, NlasIterator, Parseable,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CtrlNla {
+    Unspec(Vec<u8>),
+    FamilyId(u16),
+    FamilyName(String),
+    Version(u32),
+    HdrSize(u32),
+    MaxAttr(u32),
+    Ops(Vec<CtrlOp>),
+    McastGroups(Vec<CtrlMcastGroup>),
+    Other(DefaultNla),
+}
+
+/// An operation supported by a generic netlink family (`CTRL_ATTR_OPS` array element)
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CtrlOp {
+    /// Command identifier
+    pub id: u32,
+    /// Capabilities of the command (`GENL_ADMIN_PERM`, `GENL_CMD_CAP_DO`, etc.)
+    pub flags: u32,
+}
+
+/// A multicast group of a generic netlink family (`CTRL_ATTR_MCAST_GROUPS` array element)
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CtrlMcastGroup {
+    pub name: String,
+    /// Group identifier, to be used with `NETLINK_ADD_MEMBERSHIP`
+    pub id: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum CtrlOpNla {
+    Id(u32),
+    Flags(u32),
+}
+
+impl Nla for CtrlOpNla {
+    fn value_len(&self) -> usize {
+        size_of::<u32>()
+    }
+
+    fn kind(&self) -> u16 {
+        match *self {
+            CtrlOpNla::Id(_) => CTRL_ATTR_OP_ID,
+            CtrlOpNla::Flags(_) => CTRL_ATTR_OP_FLAGS,
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match *self {
+            CtrlOpNla::Id(value) | CtrlOpNla::Flags(value) => {
+                NativeEndian::write_u32(buffer, value)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum CtrlMcastGroupNla {
+    Name(String),
+    Id(u32),
+}
+
+impl Nla for CtrlMcastGroupNla {
+    fn value_len(&self) -> usize {
+        match *self {
+            CtrlMcastGroupNla::Name(ref name) => name.len() + 1,
+            CtrlMcastGroupNla::Id(_) => size_of::<u32>(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match *self {
+            CtrlMcastGroupNla::Name(_) => CTRL_ATTR_MCAST_GRP_NAME,
+            CtrlMcastGroupNla::Id(_) => CTRL_ATTR_MCAST_GRP_ID,
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match *self {
+            CtrlMcastGroupNla::Name(ref name) => {
+                buffer[..name.len()].copy_from_slice(name.as_bytes());
+                buffer[name.len()] = 0;
+            }
+            CtrlMcastGroupNla::Id(value) => NativeEndian::write_u32(buffer, value),
+        }
+    }
+}
+
+impl CtrlOp {
+    fn to_nla(&self, index: usize) -> NestedNla<CtrlOpNla> {
+        NestedNla::new(
+            index as u16 + 1,
+            vec![CtrlOpNla::Id(self.id), CtrlOpNla::Flags(self.flags)],
+        )
+    }
+}
+
+impl CtrlMcastGroup {
+    fn to_nla(&self, index: usize) -> NestedNla<CtrlMcastGroupNla> {
+        NestedNla::new(
+            index as u16 + 1,
+            vec![
+                CtrlMcastGroupNla::Name(self.name.clone()),
+                CtrlMcastGroupNla::Id(self.id),
+            ],
+        )
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<CtrlOp> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<CtrlOp, DecodeError> {
+        let mut op = CtrlOp::default();
+        for nla in NlasIterator::new(self.value()) {
+            let nla = nla?;
+            match nla.kind() {
+                CTRL_ATTR_OP_ID => {
+                    op.id = parse_u32(nla.value()).context("invalid CTRL_ATTR_OP_ID value")?
+                }
+                CTRL_ATTR_OP_FLAGS => {
+                    op.flags = parse_u32(nla.value()).context("invalid CTRL_ATTR_OP_FLAGS value")?
+                }
+                _ => {}
+            }
+        }
+        Ok(op)
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<CtrlMcastGroup> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<CtrlMcastGroup, DecodeError> {
+        let mut group = CtrlMcastGroup::default();
+        for nla in NlasIterator::new(self.value()) {
+            let nla = nla?;
+            match nla.kind() {
+                CTRL_ATTR_MCAST_GRP_NAME => {
+                    group.name = parse_string(nla.value())
+                        .context("invalid CTRL_ATTR_MCAST_GRP_NAME value")?
+                }
+                CTRL_ATTR_MCAST_GRP_ID => {
+                    group.id =
+                        parse_u32(nla.value()).context("invalid CTRL_ATTR_MCAST_GRP_ID value")?
+                }
+                _ => {}
+            }
+        }
+        Ok(group)
+    }
+}
+
+impl Nla for CtrlNla {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::CtrlNla::*;
+        match *self {
+            Unspec(ref bytes) => bytes.len(),
+            FamilyId(_) => size_of::<u16>(),
+            FamilyName(ref s) => s.len() + 1,
+            Version(_)
+                | HdrSize(_)
+                | MaxAttr(_) => size_of::<u32>(),
+            Ops(ref ops) => ops
+                .iter()
+                .enumerate()
+                .map(|(i, op)| op.to_nla(i).buffer_len())
+                .sum(),
+            McastGroups(ref groups) => groups
+                .iter()
+                .enumerate()
+                .map(|(i, group)| group.to_nla(i).buffer_len())
+                .sum(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::CtrlNla::*;
+        match *self {
+            Unspec(_) => CTRL_ATTR_UNSPEC,
+            FamilyId(_) => CTRL_ATTR_FAMILY_ID,
+            FamilyName(_) => CTRL_ATTR_FAMILY_NAME,
+            Version(_) => CTRL_ATTR_VERSION,
+            HdrSize(_) => CTRL_ATTR_HDRSIZE,
+            MaxAttr(_) => CTRL_ATTR_MAXATTR,
+            Ops(_) => CTRL_ATTR_OPS,
+            McastGroups(_) => CTRL_ATTR_MCAST_GROUPS,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::CtrlNla::*;
+        match *self {
+            Unspec(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            FamilyId(value) => NativeEndian::write_u16(buffer, value),
+            FamilyName(ref s) => {
+                buffer[..s.len()].copy_from_slice(s.as_bytes());
+                buffer[s.len()] = 0;
+            }
+            Version(value)
+                | HdrSize(value)
+                | MaxAttr(value) => NativeEndian::write_u32(buffer, value),
+            Ops(ref ops) => {
+                let mut offset = 0;
+                for (i, op) in ops.iter().enumerate() {
+                    let nla = op.to_nla(i);
+                    nla.emit(&mut buffer[offset..offset + nla.buffer_len()]);
+                    offset += nla.buffer_len();
+                }
+            }
+            McastGroups(ref groups) => {
+                let mut offset = 0;
+                for (i, group) in groups.iter().enumerate() {
+                    let nla = group.to_nla(i);
+                    nla.emit(&mut buffer[offset..offset + nla.buffer_len()]);
+                    offset += nla.buffer_len();
+                }
+            }
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        use self::CtrlNla::*;
+        matches!(*self, Ops(_) | McastGroups(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<CtrlNla> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<CtrlNla, DecodeError> {
+        use self::CtrlNla::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            CTRL_ATTR_UNSPEC => Unspec(payload.to_vec()),
+            CTRL_ATTR_FAMILY_ID => {
+                FamilyId(parse_u16(payload).context("invalid CTRL_ATTR_FAMILY_ID value")?)
+            }
+            CTRL_ATTR_FAMILY_NAME => {
+                FamilyName(parse_string(payload).context("invalid CTRL_ATTR_FAMILY_NAME value")?)
+            }
+            CTRL_ATTR_VERSION => {
+                Version(parse_u32(payload).context("invalid CTRL_ATTR_VERSION value")?)
+            }
+            CTRL_ATTR_HDRSIZE => {
+                HdrSize(parse_u32(payload).context("invalid CTRL_ATTR_HDRSIZE value")?)
+            }
+            CTRL_ATTR_MAXATTR => {
+                MaxAttr(parse_u32(payload).context("invalid CTRL_ATTR_MAXATTR value")?)
+            }
+            CTRL_ATTR_OPS => {
+                let mut ops = vec![];
+                for nla in NlasIterator::new(payload) {
+                    ops.push(nla?.parse().context("invalid CTRL_ATTR_OPS value")?);
+                }
+                Ops(ops)
+            }
+            CTRL_ATTR_MCAST_GROUPS => {
+                let mut groups = vec![];
+                for nla in NlasIterator::new(payload) {
+                    groups.push(
+                        nla?.parse()
+                            .context("invalid CTRL_ATTR_MCAST_GROUPS value")?,
+                    );
+                }
+                McastGroups(groups)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_string, parse_u64, parse_u8};
+use crate::{DecodeError, GenlAttr, NlaBuffer, NlasIterator, Parseable};
+
+/// A devlink health reporter, as reported by `DEVLINK_CMD_HEALTH_REPORTER_GET`
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct DevlinkHealthReporter {
+    pub name: String,
+    /// `DEVLINK_HEALTH_REPORTER_STATE_HEALTHY` or `DEVLINK_HEALTH_REPORTER_STATE_ERROR`
+    pub state: u8,
+    /// Number of errors reported
+    pub error_count: u64,
+    /// Number of successful recoveries
+    pub recover_count: u64,
+    /// Timestamp of the last dump, in jiffies
+    pub dump_ts: Option<u64>,
+    /// Minimum time between two automatic recoveries, in milliseconds
+    pub graceful_period: Option<u64>,
+    pub auto_recover: Option<bool>,
+}
+
+impl DevlinkHealthReporter {
+    pub fn is_healthy(&self) -> bool {
+        self.state == DEVLINK_HEALTH_REPORTER_STATE_HEALTHY
+    }
+
+    pub(crate) fn to_nlas(&self) -> Vec<GenlAttr> {
+        let mut nlas = vec![
+            GenlAttr::Str(DEVLINK_ATTR_HEALTH_REPORTER_NAME, self.name.clone()),
+            GenlAttr::U8(DEVLINK_ATTR_HEALTH_REPORTER_STATE, self.state),
+            GenlAttr::U64(DEVLINK_ATTR_HEALTH_REPORTER_ERR_COUNT, self.error_count),
+            GenlAttr::U64(
+                DEVLINK_ATTR_HEALTH_REPORTER_RECOVER_COUNT,
+                self.recover_count,
+            ),
+        ];
+        if let Some(ts) = self.dump_ts {
+            nlas.push(GenlAttr::U64(DEVLINK_ATTR_HEALTH_REPORTER_DUMP_TS, ts));
+        }
+        if let Some(period) = self.graceful_period {
+            nlas.push(GenlAttr::U64(
+                DEVLINK_ATTR_HEALTH_REPORTER_GRACEFUL_PERIOD,
+                period,
+            ));
+        }
+        if let Some(auto_recover) = self.auto_recover {
+            nlas.push(GenlAttr::U8(
+                DEVLINK_ATTR_HEALTH_REPORTER_AUTO_RECOVER,
+                auto_recover as u8,
+            ));
+        }
+        nlas
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<DevlinkHealthReporter> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<DevlinkHealthReporter, DecodeError> {
+        let mut reporter = DevlinkHealthReporter::default();
+        for nla in NlasIterator::new(self.value()) {
+            let nla = nla?;
+            let payload = nla.value();
+            match nla.kind() {
+                DEVLINK_ATTR_HEALTH_REPORTER_NAME => {
+                    reporter.name = parse_string(payload)
+                        .context("invalid DEVLINK_ATTR_HEALTH_REPORTER_NAME value")?
+                }
+                DEVLINK_ATTR_HEALTH_REPORTER_STATE => {
+                    reporter.state = parse_u8(payload)
+                        .context("invalid DEVLINK_ATTR_HEALTH_REPORTER_STATE value")?
+                }
+                DEVLINK_ATTR_HEALTH_REPORTER_ERR_COUNT => {
+                    reporter.error_count = parse_u64(payload)
+                        .context("invalid DEVLINK_ATTR_HEALTH_REPORTER_ERR_COUNT value")?
+                }
+                DEVLINK_ATTR_HEALTH_REPORTER_RECOVER_COUNT => {
+                    reporter.recover_count = parse_u64(payload)
+                        .context("invalid DEVLINK_ATTR_HEALTH_REPORTER_RECOVER_COUNT value")?
+                }
+                DEVLINK_ATTR_HEALTH_REPORTER_DUMP_TS => {
+                    reporter.dump_ts = Some(
+                        parse_u64(payload)
+                            .context("invalid DEVLINK_ATTR_HEALTH_REPORTER_DUMP_TS value")?,
+                    )
+                }
+                DEVLINK_ATTR_HEALTH_REPORTER_GRACEFUL_PERIOD => {
+                    reporter.graceful_period =
+                        Some(parse_u64(payload).context(
+                            "invalid DEVLINK_ATTR_HEALTH_REPORTER_GRACEFUL_PERIOD value",
+                        )?)
+                }
+                DEVLINK_ATTR_HEALTH_REPORTER_AUTO_RECOVER => {
+                    reporter.auto_recover = Some(
+                        parse_u8(payload)
+                            .context("invalid DEVLINK_ATTR_HEALTH_REPORTER_AUTO_RECOVER value")?
+                            != 0,
+                    )
+                }
+                _ => {}
+            }
+        }
+        Ok(reporter)
+    }
+}
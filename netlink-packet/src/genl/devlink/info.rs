@@ -0,0 +1,43 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::parse_string;
+use crate::{DecodeError, GenlAttr, NlaBuffer, NlasIterator, Parseable};
+
+/// Version of a device component, as reported by `DEVLINK_CMD_INFO_GET`
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct DevlinkInfoVersion {
+    /// Name of the component, for instance `fw.mgmt` or `board.id`
+    pub name: String,
+    pub value: String,
+}
+
+impl DevlinkInfoVersion {
+    pub(crate) fn to_nlas(&self) -> Vec<GenlAttr> {
+        vec![
+            GenlAttr::Str(DEVLINK_ATTR_INFO_VERSION_NAME, self.name.clone()),
+            GenlAttr::Str(DEVLINK_ATTR_INFO_VERSION_VALUE, self.value.clone()),
+        ]
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<DevlinkInfoVersion> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<DevlinkInfoVersion, DecodeError> {
+        let mut version = DevlinkInfoVersion::default();
+        for nla in NlasIterator::new(self.value()) {
+            let nla = nla?;
+            match nla.kind() {
+                DEVLINK_ATTR_INFO_VERSION_NAME => {
+                    version.name = parse_string(nla.value())
+                        .context("invalid DEVLINK_ATTR_INFO_VERSION_NAME value")?
+                }
+                DEVLINK_ATTR_INFO_VERSION_VALUE => {
+                    version.value = parse_string(nla.value())
+                        .context("invalid DEVLINK_ATTR_INFO_VERSION_VALUE value")?
+                }
+                _ => {}
+            }
+        }
+        Ok(version)
+    }
+}
@@ -0,0 +1,484 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::{DecodeError, DevlinkNla, Emitable, GenlBuffer, GenlHeader, Parseable};
+
+/// Messages of the `devlink` generic netlink family.
+///
+/// Unlike ethtool, devlink uses the same command for a request and for the corresponding replies
+/// and notifications: for instance, `DEVLINK_CMD_NEW` is also the reply to `DEVLINK_CMD_GET`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DevlinkMessage {
+    Get(Vec<DevlinkNla>),
+    Set(Vec<DevlinkNla>),
+    New(Vec<DevlinkNla>),
+    Del(Vec<DevlinkNla>),
+    PortGet(Vec<DevlinkNla>),
+    PortSet(Vec<DevlinkNla>),
+    PortNew(Vec<DevlinkNla>),
+    PortDel(Vec<DevlinkNla>),
+    PortSplit(Vec<DevlinkNla>),
+    PortUnsplit(Vec<DevlinkNla>),
+    SbGet(Vec<DevlinkNla>),
+    SbSet(Vec<DevlinkNla>),
+    SbNew(Vec<DevlinkNla>),
+    SbDel(Vec<DevlinkNla>),
+    SbPoolGet(Vec<DevlinkNla>),
+    SbPoolSet(Vec<DevlinkNla>),
+    SbPoolNew(Vec<DevlinkNla>),
+    SbPoolDel(Vec<DevlinkNla>),
+    EswitchGet(Vec<DevlinkNla>),
+    EswitchSet(Vec<DevlinkNla>),
+    Reload(Vec<DevlinkNla>),
+    ParamGet(Vec<DevlinkNla>),
+    ParamSet(Vec<DevlinkNla>),
+    ParamNew(Vec<DevlinkNla>),
+    ParamDel(Vec<DevlinkNla>),
+    RegionGet(Vec<DevlinkNla>),
+    RegionSet(Vec<DevlinkNla>),
+    RegionNew(Vec<DevlinkNla>),
+    RegionDel(Vec<DevlinkNla>),
+    RegionRead(Vec<DevlinkNla>),
+    InfoGet(Vec<DevlinkNla>),
+    HealthReporterGet(Vec<DevlinkNla>),
+    HealthReporterSet(Vec<DevlinkNla>),
+    HealthReporterRecover(Vec<DevlinkNla>),
+    HealthReporterDiagnose(Vec<DevlinkNla>),
+    HealthReporterDumpGet(Vec<DevlinkNla>),
+    HealthReporterDumpClear(Vec<DevlinkNla>),
+}
+
+impl DevlinkMessage {
+    pub fn cmd(&self) -> u8 {
+        use self::DevlinkMessage::*;
+        match *self {
+            Get(_) => DEVLINK_CMD_GET,
+            Set(_) => DEVLINK_CMD_SET,
+            New(_) => DEVLINK_CMD_NEW,
+            Del(_) => DEVLINK_CMD_DEL,
+            PortGet(_) => DEVLINK_CMD_PORT_GET,
+            PortSet(_) => DEVLINK_CMD_PORT_SET,
+            PortNew(_) => DEVLINK_CMD_PORT_NEW,
+            PortDel(_) => DEVLINK_CMD_PORT_DEL,
+            PortSplit(_) => DEVLINK_CMD_PORT_SPLIT,
+            PortUnsplit(_) => DEVLINK_CMD_PORT_UNSPLIT,
+            SbGet(_) => DEVLINK_CMD_SB_GET,
+            SbSet(_) => DEVLINK_CMD_SB_SET,
+            SbNew(_) => DEVLINK_CMD_SB_NEW,
+            SbDel(_) => DEVLINK_CMD_SB_DEL,
+            SbPoolGet(_) => DEVLINK_CMD_SB_POOL_GET,
+            SbPoolSet(_) => DEVLINK_CMD_SB_POOL_SET,
+            SbPoolNew(_) => DEVLINK_CMD_SB_POOL_NEW,
+            SbPoolDel(_) => DEVLINK_CMD_SB_POOL_DEL,
+            EswitchGet(_) => DEVLINK_CMD_ESWITCH_GET,
+            EswitchSet(_) => DEVLINK_CMD_ESWITCH_SET,
+            Reload(_) => DEVLINK_CMD_RELOAD,
+            ParamGet(_) => DEVLINK_CMD_PARAM_GET,
+            ParamSet(_) => DEVLINK_CMD_PARAM_SET,
+            ParamNew(_) => DEVLINK_CMD_PARAM_NEW,
+            ParamDel(_) => DEVLINK_CMD_PARAM_DEL,
+            RegionGet(_) => DEVLINK_CMD_REGION_GET,
+            RegionSet(_) => DEVLINK_CMD_REGION_SET,
+            RegionNew(_) => DEVLINK_CMD_REGION_NEW,
+            RegionDel(_) => DEVLINK_CMD_REGION_DEL,
+            RegionRead(_) => DEVLINK_CMD_REGION_READ,
+            InfoGet(_) => DEVLINK_CMD_INFO_GET,
+            HealthReporterGet(_) => DEVLINK_CMD_HEALTH_REPORTER_GET,
+            HealthReporterSet(_) => DEVLINK_CMD_HEALTH_REPORTER_SET,
+            HealthReporterRecover(_) => DEVLINK_CMD_HEALTH_REPORTER_RECOVER,
+            HealthReporterDiagnose(_) => DEVLINK_CMD_HEALTH_REPORTER_DIAGNOSE,
+            HealthReporterDumpGet(_) => DEVLINK_CMD_HEALTH_REPORTER_DUMP_GET,
+            HealthReporterDumpClear(_) => DEVLINK_CMD_HEALTH_REPORTER_DUMP_CLEAR,
+        }
+    }
+
+    pub fn nlas(&self) -> &[DevlinkNla] {
+        use self::DevlinkMessage::*;
+        match *self {
+            Get(ref nlas)
+            | Set(ref nlas)
+            | New(ref nlas)
+            | Del(ref nlas)
+            | PortGet(ref nlas)
+            | PortSet(ref nlas)
+            | PortNew(ref nlas)
+            | PortDel(ref nlas)
+            | PortSplit(ref nlas)
+            | PortUnsplit(ref nlas)
+            | SbGet(ref nlas)
+            | SbSet(ref nlas)
+            | SbNew(ref nlas)
+            | SbDel(ref nlas)
+            | SbPoolGet(ref nlas)
+            | SbPoolSet(ref nlas)
+            | SbPoolNew(ref nlas)
+            | SbPoolDel(ref nlas)
+            | EswitchGet(ref nlas)
+            | EswitchSet(ref nlas)
+            | Reload(ref nlas)
+            | ParamGet(ref nlas)
+            | ParamSet(ref nlas)
+            | ParamNew(ref nlas)
+            | ParamDel(ref nlas)
+            | RegionGet(ref nlas)
+            | RegionSet(ref nlas)
+            | RegionNew(ref nlas)
+            | RegionDel(ref nlas)
+            | RegionRead(ref nlas)
+            | InfoGet(ref nlas)
+            | HealthReporterGet(ref nlas)
+            | HealthReporterSet(ref nlas)
+            | HealthReporterRecover(ref nlas)
+            | HealthReporterDiagnose(ref nlas)
+            | HealthReporterDumpGet(ref nlas)
+            | HealthReporterDumpClear(ref nlas) => nlas.as_slice(),
+        }
+    }
+
+    pub fn into_nlas(self) -> Vec<DevlinkNla> {
+        use self::DevlinkMessage::*;
+        match self {
+            Get(nlas)
+            | Set(nlas)
+            | New(nlas)
+            | Del(nlas)
+            | PortGet(nlas)
+            | PortSet(nlas)
+            | PortNew(nlas)
+            | PortDel(nlas)
+            | PortSplit(nlas)
+            | PortUnsplit(nlas)
+            | SbGet(nlas)
+            | SbSet(nlas)
+            | SbNew(nlas)
+            | SbDel(nlas)
+            | SbPoolGet(nlas)
+            | SbPoolSet(nlas)
+            | SbPoolNew(nlas)
+            | SbPoolDel(nlas)
+            | EswitchGet(nlas)
+            | EswitchSet(nlas)
+            | Reload(nlas)
+            | ParamGet(nlas)
+            | ParamSet(nlas)
+            | ParamNew(nlas)
+            | ParamDel(nlas)
+            | RegionGet(nlas)
+            | RegionSet(nlas)
+            | RegionNew(nlas)
+            | RegionDel(nlas)
+            | RegionRead(nlas)
+            | InfoGet(nlas)
+            | HealthReporterGet(nlas)
+            | HealthReporterSet(nlas)
+            | HealthReporterRecover(nlas)
+            | HealthReporterDiagnose(nlas)
+            | HealthReporterDumpGet(nlas)
+            | HealthReporterDumpClear(nlas) => nlas,
+        }
+    }
+}
+
+impl Emitable for DevlinkMessage {
+    fn buffer_len(&self) -> usize {
+        GenlHeader::new(self.cmd(), DEVLINK_GENL_VERSION).buffer_len() + self.nlas().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let header = GenlHeader::new(self.cmd(), DEVLINK_GENL_VERSION);
+        header.emit(buffer);
+        self.nlas().emit(&mut buffer[header.buffer_len()..]);
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<DevlinkMessage> for GenlBuffer<&T> {
+    fn parse(&self) -> Result<DevlinkMessage, DecodeError> {
+        use self::DevlinkMessage::*;
+        let mut nlas = vec![];
+        for nla_buf in self.nlas() {
+            nlas.push(nla_buf?.parse().context("invalid devlink NLA")?);
+        }
+        Ok(match self.cmd() {
+            DEVLINK_CMD_GET => Get(nlas),
+            DEVLINK_CMD_SET => Set(nlas),
+            DEVLINK_CMD_NEW => New(nlas),
+            DEVLINK_CMD_DEL => Del(nlas),
+            DEVLINK_CMD_PORT_GET => PortGet(nlas),
+            DEVLINK_CMD_PORT_SET => PortSet(nlas),
+            DEVLINK_CMD_PORT_NEW => PortNew(nlas),
+            DEVLINK_CMD_PORT_DEL => PortDel(nlas),
+            DEVLINK_CMD_PORT_SPLIT => PortSplit(nlas),
+            DEVLINK_CMD_PORT_UNSPLIT => PortUnsplit(nlas),
+            DEVLINK_CMD_SB_GET => SbGet(nlas),
+            DEVLINK_CMD_SB_SET => SbSet(nlas),
+            DEVLINK_CMD_SB_NEW => SbNew(nlas),
+            DEVLINK_CMD_SB_DEL => SbDel(nlas),
+            DEVLINK_CMD_SB_POOL_GET => SbPoolGet(nlas),
+            DEVLINK_CMD_SB_POOL_SET => SbPoolSet(nlas),
+            DEVLINK_CMD_SB_POOL_NEW => SbPoolNew(nlas),
+            DEVLINK_CMD_SB_POOL_DEL => SbPoolDel(nlas),
+            DEVLINK_CMD_ESWITCH_GET => EswitchGet(nlas),
+            DEVLINK_CMD_ESWITCH_SET => EswitchSet(nlas),
+            DEVLINK_CMD_RELOAD => Reload(nlas),
+            DEVLINK_CMD_PARAM_GET => ParamGet(nlas),
+            DEVLINK_CMD_PARAM_SET => ParamSet(nlas),
+            DEVLINK_CMD_PARAM_NEW => ParamNew(nlas),
+            DEVLINK_CMD_PARAM_DEL => ParamDel(nlas),
+            DEVLINK_CMD_REGION_GET => RegionGet(nlas),
+            DEVLINK_CMD_REGION_SET => RegionSet(nlas),
+            DEVLINK_CMD_REGION_NEW => RegionNew(nlas),
+            DEVLINK_CMD_REGION_DEL => RegionDel(nlas),
+            DEVLINK_CMD_REGION_READ => RegionRead(nlas),
+            DEVLINK_CMD_INFO_GET => InfoGet(nlas),
+            DEVLINK_CMD_HEALTH_REPORTER_GET => HealthReporterGet(nlas),
+            DEVLINK_CMD_HEALTH_REPORTER_SET => HealthReporterSet(nlas),
+            DEVLINK_CMD_HEALTH_REPORTER_RECOVER => HealthReporterRecover(nlas),
+            DEVLINK_CMD_HEALTH_REPORTER_DIAGNOSE => HealthReporterDiagnose(nlas),
+            DEVLINK_CMD_HEALTH_REPORTER_DUMP_GET => HealthReporterDumpGet(nlas),
+            DEVLINK_CMD_HEALTH_REPORTER_DUMP_CLEAR => HealthReporterDumpClear(nlas),
+            cmd => return Err(format!("unknown devlink command {}", cmd).into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::constants::*;
+    use crate::{
+        assert_nested, DevlinkHealthReporter, DevlinkInfoVersion, DevlinkMessage, DevlinkNla,
+        DevlinkParam, DevlinkParamData, DevlinkParamValue, DevlinkRegionChunk,
+        DevlinkRegionSnapshot, Emitable, GenlBuffer, Parseable,
+    };
+
+    #[rustfmt::skip]
+    static INFO_GET_REPLY: [u8; 76] = [
+        0x33, // cmd: DEVLINK_CMD_INFO_GET
+        0x01, // version
+        0x00, 0x00, // reserved
+
+        // DEVLINK_ATTR_BUS_NAME
+        0x08, 0x00, 0x01, 0x00,
+        0x70, 0x63, 0x69, 0x00, // "pci\0"
+
+        // DEVLINK_ATTR_DEV_NAME
+        0x11, 0x00, 0x02, 0x00,
+        // "0000:01:00.0\0"
+        0x30, 0x30, 0x30, 0x30, 0x3a, 0x30, 0x31, 0x3a,
+        0x30, 0x30, 0x2e, 0x30, 0x00,
+        0x00, 0x00, 0x00, // padding
+
+        // DEVLINK_ATTR_INFO_DRIVER_NAME
+        0x0e, 0x00, 0x62, 0x00,
+        // "mlx5_core\0"
+        0x6d, 0x6c, 0x78, 0x35, 0x5f, 0x63, 0x6f, 0x72,
+        0x65, 0x00,
+        0x00, 0x00, // padding
+
+        // DEVLINK_ATTR_INFO_VERSION_RUNNING (nested)
+        0x1c, 0x00, 0x65, 0x80,
+            // DEVLINK_ATTR_INFO_VERSION_NAME
+            0x07, 0x00, 0x67, 0x00,
+            0x66, 0x77, 0x00, // "fw\0"
+            0x00, // padding
+            // DEVLINK_ATTR_INFO_VERSION_VALUE
+            0x0f, 0x00, 0x68, 0x00,
+            // "16.26.1040\0"
+            0x31, 0x36, 0x2e, 0x32, 0x36, 0x2e, 0x31, 0x30,
+            0x34, 0x30, 0x00,
+            0x00, // padding
+    ];
+
+    #[rustfmt::skip]
+    static PARAM_GET_REPLY: [u8; 84] = [
+        0x26, // cmd: DEVLINK_CMD_PARAM_GET
+        0x01, // version
+        0x00, 0x00, // reserved
+
+        // DEVLINK_ATTR_BUS_NAME
+        0x08, 0x00, 0x01, 0x00,
+        0x70, 0x63, 0x69, 0x00, // "pci\0"
+
+        // DEVLINK_ATTR_DEV_NAME
+        0x11, 0x00, 0x02, 0x00,
+        // "0000:01:00.0\0"
+        0x30, 0x30, 0x30, 0x30, 0x3a, 0x30, 0x31, 0x3a,
+        0x30, 0x30, 0x2e, 0x30, 0x00,
+        0x00, 0x00, 0x00, // padding
+
+        // DEVLINK_ATTR_PARAM (nested)
+        0x34, 0x00, 0x50, 0x80,
+            // DEVLINK_ATTR_PARAM_NAME
+            0x10, 0x00, 0x51, 0x00,
+            // "enable_roce\0"
+            0x65, 0x6e, 0x61, 0x62, 0x6c, 0x65, 0x5f, 0x72,
+            0x6f, 0x63, 0x65, 0x00,
+            // DEVLINK_ATTR_PARAM_GENERIC
+            0x04, 0x00, 0x52, 0x00,
+            // DEVLINK_ATTR_PARAM_TYPE
+            0x05, 0x00, 0x53, 0x00,
+            0x06, // DEVLINK_PARAM_TYPE_BOOL
+            0x00, 0x00, 0x00, // padding
+            // DEVLINK_ATTR_PARAM_VALUES_LIST (nested)
+            0x14, 0x00, 0x54, 0x80,
+                // DEVLINK_ATTR_PARAM_VALUE (nested)
+                0x10, 0x00, 0x55, 0x80,
+                    // DEVLINK_ATTR_PARAM_VALUE_CMODE
+                    0x05, 0x00, 0x57, 0x00,
+                    0x01, // DEVLINK_PARAM_CMODE_DRIVERINIT
+                    0x00, 0x00, 0x00, // padding
+                    // DEVLINK_ATTR_PARAM_VALUE_DATA
+                    0x04, 0x00, 0x56, 0x00,
+    ];
+
+    fn param_get_reply() -> DevlinkMessage {
+        DevlinkMessage::ParamGet(vec![
+            DevlinkNla::BusName("pci".into()),
+            DevlinkNla::DevName("0000:01:00.0".into()),
+            DevlinkNla::Param(DevlinkParam {
+                name: "enable_roce".into(),
+                generic: true,
+                param_type: DEVLINK_PARAM_TYPE_BOOL,
+                values: vec![DevlinkParamValue {
+                    cmode: DEVLINK_PARAM_CMODE_DRIVERINIT,
+                    data: DevlinkParamData::Bool(true),
+                }],
+            }),
+        ])
+    }
+
+    #[test]
+    fn parse_info_reply() {
+        let buffer = GenlBuffer::new_checked(&INFO_GET_REPLY[..]).unwrap();
+        let msg: DevlinkMessage = buffer.parse().unwrap();
+        let expected = DevlinkMessage::InfoGet(vec![
+            DevlinkNla::BusName("pci".into()),
+            DevlinkNla::DevName("0000:01:00.0".into()),
+            DevlinkNla::InfoDriverName("mlx5_core".into()),
+            DevlinkNla::InfoVersionRunning(DevlinkInfoVersion {
+                name: "fw".into(),
+                value: "16.26.1040".into(),
+            }),
+        ]);
+        assert_eq!(msg, expected);
+    }
+
+    #[test]
+    fn parse_param_reply() {
+        let buffer = GenlBuffer::new_checked(&PARAM_GET_REPLY[..]).unwrap();
+        let msg: DevlinkMessage = buffer.parse().unwrap();
+        assert_eq!(msg, param_get_reply());
+    }
+
+    #[test]
+    fn emit_param_reply() {
+        let msg = param_get_reply();
+        assert_eq!(msg.buffer_len(), PARAM_GET_REPLY.len());
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &PARAM_GET_REPLY[..]);
+    }
+
+    // Emit the message, check that the attributes at the given paths are flagged as nested, and
+    // parse it back
+    fn round_trip(msg: DevlinkMessage, nested: &[&[u16]]) {
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+        let buffer = GenlBuffer::new_checked(&buf[..]).unwrap();
+        for path in nested {
+            assert_nested(buffer.payload(), path);
+        }
+        assert_eq!(Parseable::<DevlinkMessage>::parse(&buffer).unwrap(), msg);
+    }
+
+    #[test]
+    fn param_values_round_trip() {
+        let values = vec![
+            DevlinkParamData::U8(3),
+            DevlinkParamData::U16(1024),
+            DevlinkParamData::U32(0x0001_0000),
+            DevlinkParamData::String("flow_steering".into()),
+            DevlinkParamData::Bool(true),
+            DevlinkParamData::Bool(false),
+        ];
+        for data in values {
+            let msg = DevlinkMessage::ParamSet(vec![
+                DevlinkNla::BusName("netdevsim".into()),
+                DevlinkNla::DevName("netdevsim10".into()),
+                DevlinkNla::Param(DevlinkParam {
+                    name: "test".into(),
+                    generic: false,
+                    param_type: data.param_type(),
+                    values: vec![
+                        DevlinkParamValue {
+                            cmode: DEVLINK_PARAM_CMODE_RUNTIME,
+                            data: data.clone(),
+                        },
+                        DevlinkParamValue {
+                            cmode: DEVLINK_PARAM_CMODE_DRIVERINIT,
+                            data,
+                        },
+                    ],
+                }),
+            ]);
+            round_trip(
+                msg,
+                &[&[
+                    DEVLINK_ATTR_PARAM,
+                    DEVLINK_ATTR_PARAM_VALUES_LIST,
+                    DEVLINK_ATTR_PARAM_VALUE,
+                ]],
+            );
+        }
+    }
+
+    #[test]
+    fn nested_nlas_round_trip() {
+        round_trip(
+            DevlinkMessage::InfoGet(vec![
+                DevlinkNla::InfoVersionFixed(DevlinkInfoVersion {
+                    name: "board.id".into(),
+                    value: "MT_0000000010".into(),
+                }),
+                DevlinkNla::InfoVersionStored(DevlinkInfoVersion {
+                    name: "fw".into(),
+                    value: "16.26.1040".into(),
+                }),
+            ]),
+            &[
+                &[DEVLINK_ATTR_INFO_VERSION_FIXED],
+                &[DEVLINK_ATTR_INFO_VERSION_STORED],
+            ],
+        );
+        round_trip(
+            DevlinkMessage::RegionGet(vec![
+                DevlinkNla::RegionName("cr-space".into()),
+                DevlinkNla::RegionSnapshots(vec![
+                    DevlinkRegionSnapshot { id: 1 },
+                    DevlinkRegionSnapshot { id: 2 },
+                ]),
+            ]),
+            &[&[DEVLINK_ATTR_REGION_SNAPSHOTS, DEVLINK_ATTR_REGION_SNAPSHOT]],
+        );
+        round_trip(
+            DevlinkMessage::RegionRead(vec![DevlinkNla::RegionChunks(vec![DevlinkRegionChunk {
+                addr: 0x1000,
+                data: vec![0xde, 0xad, 0xbe, 0xef, 0x01],
+            }])]),
+            &[&[DEVLINK_ATTR_REGION_CHUNKS, DEVLINK_ATTR_REGION_CHUNK]],
+        );
+        round_trip(
+            DevlinkMessage::HealthReporterGet(vec![DevlinkNla::HealthReporter(
+                DevlinkHealthReporter {
+                    name: "tx".into(),
+                    state: DEVLINK_HEALTH_REPORTER_STATE_ERROR,
+                    error_count: 2,
+                    recover_count: 1,
+                    dump_ts: Some(4_294_967_296),
+                    graceful_period: Some(500),
+                    auto_recover: Some(true),
+                },
+            )]),
+            &[&[DEVLINK_ATTR_HEALTH_REPORTER]],
+        );
+    }
+}
@@ -0,0 +1,20 @@
+mod port;
+pub use self::port::*;
+
+mod param;
+pub use self::param::*;
+
+mod info;
+pub use self::info::*;
+
+mod health;
+pub use self::health::*;
+
+mod region;
+pub use self::region::*;
+
+mod nlas;
+pub use self::nlas::*;
+
+mod message;
+pub use self::message::*;
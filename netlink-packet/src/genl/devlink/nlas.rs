@@ -0,0 +1,504 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_string, parse_u16, parse_u32, parse_u64, parse_u8};
+use crate::{
+    parse_nested, DecodeError, DefaultNla, DevlinkHealthReporter, DevlinkInfoVersion, DevlinkParam,
+    DevlinkPortFlavour, DevlinkPortType, DevlinkRegionChunk, DevlinkRegionSnapshot, Emitable,
+    GenlAttr, Nla, NlaBuffer, Parseable,
+};
+
+/// Attributes of the devlink messages (`DEVLINK_ATTR_*`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DevlinkNla {
+    /// Name of the bus the device is attached to (`pci`, `netdevsim`, etc.)
+    BusName(String),
+    /// Name of the device on its bus, for instance its PCI address
+    DevName(String),
+    PortIndex(u32),
+    PortType(DevlinkPortType),
+    PortDesiredType(DevlinkPortType),
+    PortNetdevIfindex(u32),
+    PortNetdevName(String),
+    PortIbdevName(String),
+    PortSplitCount(u32),
+    PortSplitGroup(u32),
+    PortFlavour(DevlinkPortFlavour),
+    PortNumber(u32),
+    PortSplitSubportNumber(u32),
+    PortPciPfNumber(u16),
+    PortPciVfNumber(u16),
+    /// Index of a shared buffer
+    SbIndex(u32),
+    SbSize(u32),
+    SbIngressPoolCount(u16),
+    SbEgressPoolCount(u16),
+    SbIngressTcCount(u16),
+    SbEgressTcCount(u16),
+    SbPoolIndex(u16),
+    /// `DEVLINK_SB_POOL_TYPE_INGRESS` or `DEVLINK_SB_POOL_TYPE_EGRESS`
+    SbPoolType(u8),
+    SbPoolSize(u32),
+    SbPoolThresholdType(u8),
+    SbThreshold(u32),
+    SbTcIndex(u16),
+    SbOccCur(u32),
+    SbOccMax(u32),
+    SbPoolCellSize(u32),
+    /// `DEVLINK_ESWITCH_MODE_LEGACY` or `DEVLINK_ESWITCH_MODE_SWITCHDEV`
+    EswitchMode(u16),
+    EswitchInlineMode(u8),
+    EswitchEncapMode(u8),
+    Pad,
+    /// A device parameter, with its current value(s)
+    Param(DevlinkParam),
+    /// Name of the parameter to change, in `DEVLINK_CMD_PARAM_SET` requests
+    ParamName(String),
+    ParamGeneric,
+    /// Type of the parameter to change (`DEVLINK_PARAM_TYPE_*`)
+    ParamType(u8),
+    /// Raw value of the parameter to change. See [`DevlinkParamData::to_bytes`](enum.DevlinkParamData.html#method.to_bytes).
+    ParamValueData(Vec<u8>),
+    /// Configuration mode of the value to change (`DEVLINK_PARAM_CMODE_*`)
+    ParamValueCmode(u8),
+    RegionName(String),
+    RegionSize(u64),
+    RegionSnapshots(Vec<DevlinkRegionSnapshot>),
+    RegionSnapshotId(u32),
+    RegionChunks(Vec<DevlinkRegionChunk>),
+    RegionChunkAddr(u64),
+    RegionChunkLen(u64),
+    InfoDriverName(String),
+    InfoSerialNumber(String),
+    /// Version of a hardware component (board revision, ASIC identifier, etc.)
+    InfoVersionFixed(DevlinkInfoVersion),
+    /// Version of a firmware component currently running
+    InfoVersionRunning(DevlinkInfoVersion),
+    /// Version of a firmware component stored in flash, which will run after the next reset
+    InfoVersionStored(DevlinkInfoVersion),
+    HealthReporter(DevlinkHealthReporter),
+    /// Name of the health reporter a request applies to
+    HealthReporterName(String),
+    HealthReporterGracefulPeriod(u64),
+    HealthReporterAutoRecover(bool),
+    Other(DefaultNla),
+}
+
+impl Nla for DevlinkNla {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::DevlinkNla::*;
+        match *self {
+            BusName(ref s)
+                | DevName(ref s)
+                | PortNetdevName(ref s)
+                | PortIbdevName(ref s)
+                | ParamName(ref s)
+                | RegionName(ref s)
+                | InfoDriverName(ref s)
+                | InfoSerialNumber(ref s)
+                | HealthReporterName(ref s) => s.len() + 1,
+            PortIndex(_)
+                | PortNetdevIfindex(_)
+                | PortSplitCount(_)
+                | PortSplitGroup(_)
+                | PortNumber(_)
+                | PortSplitSubportNumber(_)
+                | SbIndex(_)
+                | SbSize(_)
+                | SbPoolSize(_)
+                | SbThreshold(_)
+                | SbOccCur(_)
+                | SbOccMax(_)
+                | SbPoolCellSize(_)
+                | RegionSnapshotId(_) => size_of::<u32>(),
+            PortType(_)
+                | PortDesiredType(_)
+                | PortFlavour(_)
+                | PortPciPfNumber(_)
+                | PortPciVfNumber(_)
+                | SbIngressPoolCount(_)
+                | SbEgressPoolCount(_)
+                | SbIngressTcCount(_)
+                | SbEgressTcCount(_)
+                | SbPoolIndex(_)
+                | SbTcIndex(_)
+                | EswitchMode(_) => size_of::<u16>(),
+            SbPoolType(_)
+                | SbPoolThresholdType(_)
+                | EswitchInlineMode(_)
+                | EswitchEncapMode(_)
+                | ParamType(_)
+                | ParamValueCmode(_)
+                | HealthReporterAutoRecover(_) => 1,
+            Pad
+                | ParamGeneric => 0,
+            Param(ref value) => value.to_nlas().as_slice().buffer_len(),
+            ParamValueData(ref bytes) => bytes.len(),
+            RegionSize(_)
+                | RegionChunkAddr(_)
+                | RegionChunkLen(_)
+                | HealthReporterGracefulPeriod(_) => size_of::<u64>(),
+            RegionSnapshots(ref values) => values
+                .iter()
+                .map(|value| GenlAttr::Nested(DEVLINK_ATTR_REGION_SNAPSHOT, value.to_nlas()).buffer_len())
+                .sum(),
+            RegionChunks(ref values) => values
+                .iter()
+                .map(|value| GenlAttr::Nested(DEVLINK_ATTR_REGION_CHUNK, value.to_nlas()).buffer_len())
+                .sum(),
+            InfoVersionFixed(ref value)
+                | InfoVersionRunning(ref value)
+                | InfoVersionStored(ref value) => value.to_nlas().as_slice().buffer_len(),
+            HealthReporter(ref value) => value.to_nlas().as_slice().buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::DevlinkNla::*;
+        match *self {
+            BusName(_) => DEVLINK_ATTR_BUS_NAME,
+            DevName(_) => DEVLINK_ATTR_DEV_NAME,
+            PortIndex(_) => DEVLINK_ATTR_PORT_INDEX,
+            PortType(_) => DEVLINK_ATTR_PORT_TYPE,
+            PortDesiredType(_) => DEVLINK_ATTR_PORT_DESIRED_TYPE,
+            PortNetdevIfindex(_) => DEVLINK_ATTR_PORT_NETDEV_IFINDEX,
+            PortNetdevName(_) => DEVLINK_ATTR_PORT_NETDEV_NAME,
+            PortIbdevName(_) => DEVLINK_ATTR_PORT_IBDEV_NAME,
+            PortSplitCount(_) => DEVLINK_ATTR_PORT_SPLIT_COUNT,
+            PortSplitGroup(_) => DEVLINK_ATTR_PORT_SPLIT_GROUP,
+            PortFlavour(_) => DEVLINK_ATTR_PORT_FLAVOUR,
+            PortNumber(_) => DEVLINK_ATTR_PORT_NUMBER,
+            PortSplitSubportNumber(_) => DEVLINK_ATTR_PORT_SPLIT_SUBPORT_NUMBER,
+            PortPciPfNumber(_) => DEVLINK_ATTR_PORT_PCI_PF_NUMBER,
+            PortPciVfNumber(_) => DEVLINK_ATTR_PORT_PCI_VF_NUMBER,
+            SbIndex(_) => DEVLINK_ATTR_SB_INDEX,
+            SbSize(_) => DEVLINK_ATTR_SB_SIZE,
+            SbIngressPoolCount(_) => DEVLINK_ATTR_SB_INGRESS_POOL_COUNT,
+            SbEgressPoolCount(_) => DEVLINK_ATTR_SB_EGRESS_POOL_COUNT,
+            SbIngressTcCount(_) => DEVLINK_ATTR_SB_INGRESS_TC_COUNT,
+            SbEgressTcCount(_) => DEVLINK_ATTR_SB_EGRESS_TC_COUNT,
+            SbPoolIndex(_) => DEVLINK_ATTR_SB_POOL_INDEX,
+            SbPoolType(_) => DEVLINK_ATTR_SB_POOL_TYPE,
+            SbPoolSize(_) => DEVLINK_ATTR_SB_POOL_SIZE,
+            SbPoolThresholdType(_) => DEVLINK_ATTR_SB_POOL_THRESHOLD_TYPE,
+            SbThreshold(_) => DEVLINK_ATTR_SB_THRESHOLD,
+            SbTcIndex(_) => DEVLINK_ATTR_SB_TC_INDEX,
+            SbOccCur(_) => DEVLINK_ATTR_SB_OCC_CUR,
+            SbOccMax(_) => DEVLINK_ATTR_SB_OCC_MAX,
+            SbPoolCellSize(_) => DEVLINK_ATTR_SB_POOL_CELL_SIZE,
+            EswitchMode(_) => DEVLINK_ATTR_ESWITCH_MODE,
+            EswitchInlineMode(_) => DEVLINK_ATTR_ESWITCH_INLINE_MODE,
+            EswitchEncapMode(_) => DEVLINK_ATTR_ESWITCH_ENCAP_MODE,
+            Pad => DEVLINK_ATTR_PAD,
+            Param(_) => DEVLINK_ATTR_PARAM,
+            ParamName(_) => DEVLINK_ATTR_PARAM_NAME,
+            ParamGeneric => DEVLINK_ATTR_PARAM_GENERIC,
+            ParamType(_) => DEVLINK_ATTR_PARAM_TYPE,
+            ParamValueData(_) => DEVLINK_ATTR_PARAM_VALUE_DATA,
+            ParamValueCmode(_) => DEVLINK_ATTR_PARAM_VALUE_CMODE,
+            RegionName(_) => DEVLINK_ATTR_REGION_NAME,
+            RegionSize(_) => DEVLINK_ATTR_REGION_SIZE,
+            RegionSnapshots(_) => DEVLINK_ATTR_REGION_SNAPSHOTS,
+            RegionSnapshotId(_) => DEVLINK_ATTR_REGION_SNAPSHOT_ID,
+            RegionChunks(_) => DEVLINK_ATTR_REGION_CHUNKS,
+            RegionChunkAddr(_) => DEVLINK_ATTR_REGION_CHUNK_ADDR,
+            RegionChunkLen(_) => DEVLINK_ATTR_REGION_CHUNK_LEN,
+            InfoDriverName(_) => DEVLINK_ATTR_INFO_DRIVER_NAME,
+            InfoSerialNumber(_) => DEVLINK_ATTR_INFO_SERIAL_NUMBER,
+            InfoVersionFixed(_) => DEVLINK_ATTR_INFO_VERSION_FIXED,
+            InfoVersionRunning(_) => DEVLINK_ATTR_INFO_VERSION_RUNNING,
+            InfoVersionStored(_) => DEVLINK_ATTR_INFO_VERSION_STORED,
+            HealthReporter(_) => DEVLINK_ATTR_HEALTH_REPORTER,
+            HealthReporterName(_) => DEVLINK_ATTR_HEALTH_REPORTER_NAME,
+            HealthReporterGracefulPeriod(_) => DEVLINK_ATTR_HEALTH_REPORTER_GRACEFUL_PERIOD,
+            HealthReporterAutoRecover(_) => DEVLINK_ATTR_HEALTH_REPORTER_AUTO_RECOVER,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::DevlinkNla::*;
+        match *self {
+            BusName(ref s)
+                | DevName(ref s)
+                | PortNetdevName(ref s)
+                | PortIbdevName(ref s)
+                | ParamName(ref s)
+                | RegionName(ref s)
+                | InfoDriverName(ref s)
+                | InfoSerialNumber(ref s)
+                | HealthReporterName(ref s) => {
+                buffer[..s.len()].copy_from_slice(s.as_bytes());
+                buffer[s.len()] = 0;
+            },
+            PortIndex(value)
+                | PortNetdevIfindex(value)
+                | PortSplitCount(value)
+                | PortSplitGroup(value)
+                | PortNumber(value)
+                | PortSplitSubportNumber(value)
+                | SbIndex(value)
+                | SbSize(value)
+                | SbPoolSize(value)
+                | SbThreshold(value)
+                | SbOccCur(value)
+                | SbOccMax(value)
+                | SbPoolCellSize(value)
+                | RegionSnapshotId(value) => NativeEndian::write_u32(buffer, value),
+            PortType(value)
+                | PortDesiredType(value) => NativeEndian::write_u16(buffer, value.into()),
+            PortFlavour(value) => NativeEndian::write_u16(buffer, value.into()),
+            PortPciPfNumber(value)
+                | PortPciVfNumber(value)
+                | SbIngressPoolCount(value)
+                | SbEgressPoolCount(value)
+                | SbIngressTcCount(value)
+                | SbEgressTcCount(value)
+                | SbPoolIndex(value)
+                | SbTcIndex(value)
+                | EswitchMode(value) => NativeEndian::write_u16(buffer, value),
+            SbPoolType(value)
+                | SbPoolThresholdType(value)
+                | EswitchInlineMode(value)
+                | EswitchEncapMode(value)
+                | ParamType(value)
+                | ParamValueCmode(value) => buffer[0] = value,
+            Pad
+                | ParamGeneric => {},
+            Param(ref value) => value.to_nlas().as_slice().emit(buffer),
+            ParamValueData(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            RegionSize(value)
+                | RegionChunkAddr(value)
+                | RegionChunkLen(value)
+                | HealthReporterGracefulPeriod(value) => NativeEndian::write_u64(buffer, value),
+            RegionSnapshots(ref values) => values
+                .iter()
+                .map(|value| GenlAttr::Nested(DEVLINK_ATTR_REGION_SNAPSHOT, value.to_nlas()))
+                .collect::<Vec<_>>()
+                .as_slice()
+                .emit(buffer),
+            RegionChunks(ref values) => values
+                .iter()
+                .map(|value| GenlAttr::Nested(DEVLINK_ATTR_REGION_CHUNK, value.to_nlas()))
+                .collect::<Vec<_>>()
+                .as_slice()
+                .emit(buffer),
+            InfoVersionFixed(ref value)
+                | InfoVersionRunning(ref value)
+                | InfoVersionStored(ref value) => value.to_nlas().as_slice().emit(buffer),
+            HealthReporter(ref value) => value.to_nlas().as_slice().emit(buffer),
+            HealthReporterAutoRecover(value) => buffer[0] = value as u8,
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        use self::DevlinkNla::*;
+        matches!(
+            *self,
+            Param(_)
+                | RegionSnapshots(_)
+                | RegionChunks(_)
+                | InfoVersionFixed(_)
+                | InfoVersionRunning(_)
+                | InfoVersionStored(_)
+                | HealthReporter(_)
+        )
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<DevlinkNla> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<DevlinkNla, DecodeError> {
+        use self::DevlinkNla::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            DEVLINK_ATTR_BUS_NAME => {
+                BusName(parse_string(payload).context("invalid DEVLINK_ATTR_BUS_NAME value")?)
+            }
+            DEVLINK_ATTR_DEV_NAME => {
+                DevName(parse_string(payload).context("invalid DEVLINK_ATTR_DEV_NAME value")?)
+            }
+            DEVLINK_ATTR_PORT_INDEX => {
+                PortIndex(parse_u32(payload).context("invalid DEVLINK_ATTR_PORT_INDEX value")?)
+            }
+            DEVLINK_ATTR_PORT_TYPE => PortType(
+                parse_u16(payload)
+                    .context("invalid DEVLINK_ATTR_PORT_TYPE value")?
+                    .into(),
+            ),
+            DEVLINK_ATTR_PORT_DESIRED_TYPE => PortDesiredType(
+                parse_u16(payload)
+                    .context("invalid DEVLINK_ATTR_PORT_DESIRED_TYPE value")?
+                    .into(),
+            ),
+            DEVLINK_ATTR_PORT_NETDEV_IFINDEX => PortNetdevIfindex(
+                parse_u32(payload).context("invalid DEVLINK_ATTR_PORT_NETDEV_IFINDEX value")?,
+            ),
+            DEVLINK_ATTR_PORT_NETDEV_NAME => PortNetdevName(
+                parse_string(payload).context("invalid DEVLINK_ATTR_PORT_NETDEV_NAME value")?,
+            ),
+            DEVLINK_ATTR_PORT_IBDEV_NAME => PortIbdevName(
+                parse_string(payload).context("invalid DEVLINK_ATTR_PORT_IBDEV_NAME value")?,
+            ),
+            DEVLINK_ATTR_PORT_SPLIT_COUNT => PortSplitCount(
+                parse_u32(payload).context("invalid DEVLINK_ATTR_PORT_SPLIT_COUNT value")?,
+            ),
+            DEVLINK_ATTR_PORT_SPLIT_GROUP => PortSplitGroup(
+                parse_u32(payload).context("invalid DEVLINK_ATTR_PORT_SPLIT_GROUP value")?,
+            ),
+            DEVLINK_ATTR_PORT_FLAVOUR => PortFlavour(
+                parse_u16(payload)
+                    .context("invalid DEVLINK_ATTR_PORT_FLAVOUR value")?
+                    .into(),
+            ),
+            DEVLINK_ATTR_PORT_NUMBER => {
+                PortNumber(parse_u32(payload).context("invalid DEVLINK_ATTR_PORT_NUMBER value")?)
+            }
+            DEVLINK_ATTR_PORT_SPLIT_SUBPORT_NUMBER => PortSplitSubportNumber(
+                parse_u32(payload)
+                    .context("invalid DEVLINK_ATTR_PORT_SPLIT_SUBPORT_NUMBER value")?,
+            ),
+            DEVLINK_ATTR_PORT_PCI_PF_NUMBER => PortPciPfNumber(
+                parse_u16(payload).context("invalid DEVLINK_ATTR_PORT_PCI_PF_NUMBER value")?,
+            ),
+            DEVLINK_ATTR_PORT_PCI_VF_NUMBER => PortPciVfNumber(
+                parse_u16(payload).context("invalid DEVLINK_ATTR_PORT_PCI_VF_NUMBER value")?,
+            ),
+            DEVLINK_ATTR_SB_INDEX => {
+                SbIndex(parse_u32(payload).context("invalid DEVLINK_ATTR_SB_INDEX value")?)
+            }
+            DEVLINK_ATTR_SB_SIZE => {
+                SbSize(parse_u32(payload).context("invalid DEVLINK_ATTR_SB_SIZE value")?)
+            }
+            DEVLINK_ATTR_SB_INGRESS_POOL_COUNT => SbIngressPoolCount(
+                parse_u16(payload).context("invalid DEVLINK_ATTR_SB_INGRESS_POOL_COUNT value")?,
+            ),
+            DEVLINK_ATTR_SB_EGRESS_POOL_COUNT => SbEgressPoolCount(
+                parse_u16(payload).context("invalid DEVLINK_ATTR_SB_EGRESS_POOL_COUNT value")?,
+            ),
+            DEVLINK_ATTR_SB_INGRESS_TC_COUNT => SbIngressTcCount(
+                parse_u16(payload).context("invalid DEVLINK_ATTR_SB_INGRESS_TC_COUNT value")?,
+            ),
+            DEVLINK_ATTR_SB_EGRESS_TC_COUNT => SbEgressTcCount(
+                parse_u16(payload).context("invalid DEVLINK_ATTR_SB_EGRESS_TC_COUNT value")?,
+            ),
+            DEVLINK_ATTR_SB_POOL_INDEX => {
+                SbPoolIndex(parse_u16(payload).context("invalid DEVLINK_ATTR_SB_POOL_INDEX value")?)
+            }
+            DEVLINK_ATTR_SB_POOL_TYPE => {
+                SbPoolType(parse_u8(payload).context("invalid DEVLINK_ATTR_SB_POOL_TYPE value")?)
+            }
+            DEVLINK_ATTR_SB_POOL_SIZE => {
+                SbPoolSize(parse_u32(payload).context("invalid DEVLINK_ATTR_SB_POOL_SIZE value")?)
+            }
+            DEVLINK_ATTR_SB_POOL_THRESHOLD_TYPE => SbPoolThresholdType(
+                parse_u8(payload).context("invalid DEVLINK_ATTR_SB_POOL_THRESHOLD_TYPE value")?,
+            ),
+            DEVLINK_ATTR_SB_THRESHOLD => {
+                SbThreshold(parse_u32(payload).context("invalid DEVLINK_ATTR_SB_THRESHOLD value")?)
+            }
+            DEVLINK_ATTR_SB_TC_INDEX => {
+                SbTcIndex(parse_u16(payload).context("invalid DEVLINK_ATTR_SB_TC_INDEX value")?)
+            }
+            DEVLINK_ATTR_SB_OCC_CUR => {
+                SbOccCur(parse_u32(payload).context("invalid DEVLINK_ATTR_SB_OCC_CUR value")?)
+            }
+            DEVLINK_ATTR_SB_OCC_MAX => {
+                SbOccMax(parse_u32(payload).context("invalid DEVLINK_ATTR_SB_OCC_MAX value")?)
+            }
+            DEVLINK_ATTR_SB_POOL_CELL_SIZE => SbPoolCellSize(
+                parse_u32(payload).context("invalid DEVLINK_ATTR_SB_POOL_CELL_SIZE value")?,
+            ),
+            DEVLINK_ATTR_ESWITCH_MODE => {
+                EswitchMode(parse_u16(payload).context("invalid DEVLINK_ATTR_ESWITCH_MODE value")?)
+            }
+            DEVLINK_ATTR_ESWITCH_INLINE_MODE => EswitchInlineMode(
+                parse_u8(payload).context("invalid DEVLINK_ATTR_ESWITCH_INLINE_MODE value")?,
+            ),
+            DEVLINK_ATTR_ESWITCH_ENCAP_MODE => EswitchEncapMode(
+                parse_u8(payload).context("invalid DEVLINK_ATTR_ESWITCH_ENCAP_MODE value")?,
+            ),
+            DEVLINK_ATTR_PAD => Pad,
+            DEVLINK_ATTR_PARAM => Param(
+                <Self as Parseable<DevlinkParam>>::parse(self)
+                    .context("invalid DEVLINK_ATTR_PARAM value")?,
+            ),
+            DEVLINK_ATTR_PARAM_NAME => {
+                ParamName(parse_string(payload).context("invalid DEVLINK_ATTR_PARAM_NAME value")?)
+            }
+            DEVLINK_ATTR_PARAM_GENERIC => ParamGeneric,
+            DEVLINK_ATTR_PARAM_TYPE => {
+                ParamType(parse_u8(payload).context("invalid DEVLINK_ATTR_PARAM_TYPE value")?)
+            }
+            DEVLINK_ATTR_PARAM_VALUE_DATA => ParamValueData(payload.to_vec()),
+            DEVLINK_ATTR_PARAM_VALUE_CMODE => ParamValueCmode(
+                parse_u8(payload).context("invalid DEVLINK_ATTR_PARAM_VALUE_CMODE value")?,
+            ),
+            DEVLINK_ATTR_REGION_NAME => {
+                RegionName(parse_string(payload).context("invalid DEVLINK_ATTR_REGION_NAME value")?)
+            }
+            DEVLINK_ATTR_REGION_SIZE => {
+                RegionSize(parse_u64(payload).context("invalid DEVLINK_ATTR_REGION_SIZE value")?)
+            }
+            DEVLINK_ATTR_REGION_SNAPSHOTS => RegionSnapshots(
+                parse_nested(payload).context("invalid DEVLINK_ATTR_REGION_SNAPSHOTS value")?,
+            ),
+            DEVLINK_ATTR_REGION_SNAPSHOT_ID => RegionSnapshotId(
+                parse_u32(payload).context("invalid DEVLINK_ATTR_REGION_SNAPSHOT_ID value")?,
+            ),
+            DEVLINK_ATTR_REGION_CHUNKS => RegionChunks(
+                parse_nested(payload).context("invalid DEVLINK_ATTR_REGION_CHUNKS value")?,
+            ),
+            DEVLINK_ATTR_REGION_CHUNK_ADDR => RegionChunkAddr(
+                parse_u64(payload).context("invalid DEVLINK_ATTR_REGION_CHUNK_ADDR value")?,
+            ),
+            DEVLINK_ATTR_REGION_CHUNK_LEN => RegionChunkLen(
+                parse_u64(payload).context("invalid DEVLINK_ATTR_REGION_CHUNK_LEN value")?,
+            ),
+            DEVLINK_ATTR_INFO_DRIVER_NAME => InfoDriverName(
+                parse_string(payload).context("invalid DEVLINK_ATTR_INFO_DRIVER_NAME value")?,
+            ),
+            DEVLINK_ATTR_INFO_SERIAL_NUMBER => InfoSerialNumber(
+                parse_string(payload).context("invalid DEVLINK_ATTR_INFO_SERIAL_NUMBER value")?,
+            ),
+            DEVLINK_ATTR_INFO_VERSION_FIXED => InfoVersionFixed(
+                <Self as Parseable<DevlinkInfoVersion>>::parse(self)
+                    .context("invalid DEVLINK_ATTR_INFO_VERSION_FIXED value")?,
+            ),
+            DEVLINK_ATTR_INFO_VERSION_RUNNING => InfoVersionRunning(
+                <Self as Parseable<DevlinkInfoVersion>>::parse(self)
+                    .context("invalid DEVLINK_ATTR_INFO_VERSION_RUNNING value")?,
+            ),
+            DEVLINK_ATTR_INFO_VERSION_STORED => InfoVersionStored(
+                <Self as Parseable<DevlinkInfoVersion>>::parse(self)
+                    .context("invalid DEVLINK_ATTR_INFO_VERSION_STORED value")?,
+            ),
+            DEVLINK_ATTR_HEALTH_REPORTER => HealthReporter(
+                <Self as Parseable<DevlinkHealthReporter>>::parse(self)
+                    .context("invalid DEVLINK_ATTR_HEALTH_REPORTER value")?,
+            ),
+            DEVLINK_ATTR_HEALTH_REPORTER_NAME => HealthReporterName(
+                parse_string(payload).context("invalid DEVLINK_ATTR_HEALTH_REPORTER_NAME value")?,
+            ),
+            DEVLINK_ATTR_HEALTH_REPORTER_GRACEFUL_PERIOD => HealthReporterGracefulPeriod(
+                parse_u64(payload)
+                    .context("invalid DEVLINK_ATTR_HEALTH_REPORTER_GRACEFUL_PERIOD value")?,
+            ),
+            DEVLINK_ATTR_HEALTH_REPORTER_AUTO_RECOVER => HealthReporterAutoRecover(
+                parse_u8(payload)
+                    .context("invalid DEVLINK_ATTR_HEALTH_REPORTER_AUTO_RECOVER value")?
+                    != 0,
+            ),
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,205 @@
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_string, parse_u16, parse_u32, parse_u8};
+use crate::{DecodeError, GenlAttr, NlaBuffer, NlasIterator, Parseable};
+
+/// Value of a devlink parameter. Its representation depends on the type of the parameter
+/// (`DEVLINK_PARAM_TYPE_*`).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DevlinkParamData {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    String(String),
+    Bool(bool),
+    /// Value of a parameter which type is unknown: type and raw data
+    Other(u8, Vec<u8>),
+}
+
+impl DevlinkParamData {
+    /// Type of the parameter (`DEVLINK_PARAM_TYPE_*`)
+    pub fn param_type(&self) -> u8 {
+        use self::DevlinkParamData::*;
+        match *self {
+            U8(_) => DEVLINK_PARAM_TYPE_U8,
+            U16(_) => DEVLINK_PARAM_TYPE_U16,
+            U32(_) => DEVLINK_PARAM_TYPE_U32,
+            String(_) => DEVLINK_PARAM_TYPE_STRING,
+            Bool(_) => DEVLINK_PARAM_TYPE_BOOL,
+            Other(kind, _) => kind,
+        }
+    }
+
+    /// Value of the `DEVLINK_ATTR_PARAM_VALUE_DATA` attribute. Boolean parameters are represented
+    /// by the presence of the attribute, so `None` is returned for `Bool(false)`.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        use self::DevlinkParamData::*;
+        match *self {
+            U8(value) => Some(vec![value]),
+            U16(value) => {
+                let mut bytes = vec![0; 2];
+                NativeEndian::write_u16(&mut bytes[..], value);
+                Some(bytes)
+            }
+            U32(value) => {
+                let mut bytes = vec![0; 4];
+                NativeEndian::write_u32(&mut bytes[..], value);
+                Some(bytes)
+            }
+            String(ref s) => {
+                let mut bytes = s.as_bytes().to_vec();
+                bytes.push(0);
+                Some(bytes)
+            }
+            Bool(true) => Some(vec![]),
+            Bool(false) => None,
+            Other(_, ref bytes) => Some(bytes.clone()),
+        }
+    }
+
+    fn parse(kind: u8, data: Option<&[u8]>) -> Result<Self, DecodeError> {
+        use self::DevlinkParamData::*;
+        let payload = data.unwrap_or(&[]);
+        Ok(match kind {
+            DEVLINK_PARAM_TYPE_U8 => U8(parse_u8(payload)?),
+            DEVLINK_PARAM_TYPE_U16 => U16(parse_u16(payload)?),
+            DEVLINK_PARAM_TYPE_U32 => U32(parse_u32(payload)?),
+            DEVLINK_PARAM_TYPE_STRING => String(parse_string(payload)?),
+            DEVLINK_PARAM_TYPE_BOOL => Bool(data.is_some()),
+            _ => Other(kind, payload.to_vec()),
+        })
+    }
+}
+
+/// Value of a devlink parameter in a given configuration mode
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DevlinkParamValue {
+    /// Configuration mode (`DEVLINK_PARAM_CMODE_*`)
+    pub cmode: u8,
+    pub data: DevlinkParamData,
+}
+
+impl DevlinkParamValue {
+    fn to_nlas(&self) -> Vec<GenlAttr> {
+        let mut nlas = vec![GenlAttr::U8(DEVLINK_ATTR_PARAM_VALUE_CMODE, self.cmode)];
+        match self.data.to_bytes() {
+            Some(ref bytes) if bytes.is_empty() => {
+                nlas.push(GenlAttr::Flag(DEVLINK_ATTR_PARAM_VALUE_DATA))
+            }
+            Some(bytes) => nlas.push(GenlAttr::Bytes(DEVLINK_ATTR_PARAM_VALUE_DATA, bytes)),
+            None => {}
+        }
+        nlas
+    }
+
+    fn parse(kind: u8, payload: &[u8]) -> Result<Self, DecodeError> {
+        let mut cmode = None;
+        let mut data = None;
+        for nla in NlasIterator::new(payload) {
+            let nla = nla?;
+            match nla.kind() {
+                DEVLINK_ATTR_PARAM_VALUE_CMODE => {
+                    cmode = Some(
+                        parse_u8(nla.value())
+                            .context("invalid DEVLINK_ATTR_PARAM_VALUE_CMODE value")?,
+                    )
+                }
+                DEVLINK_ATTR_PARAM_VALUE_DATA => data = Some(nla.value().to_vec()),
+                _ => {}
+            }
+        }
+        Ok(DevlinkParamValue {
+            cmode: cmode.ok_or("missing DEVLINK_ATTR_PARAM_VALUE_CMODE")?,
+            data: DevlinkParamData::parse(kind, data.as_ref().map(|data| &data[..]))
+                .context("invalid DEVLINK_ATTR_PARAM_VALUE_DATA value")?,
+        })
+    }
+}
+
+/// A devlink parameter, as reported by `DEVLINK_CMD_PARAM_GET`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DevlinkParam {
+    pub name: String,
+    /// Whether the parameter is a generic one, as opposed to a driver specific one
+    pub generic: bool,
+    /// Type of the parameter (`DEVLINK_PARAM_TYPE_*`)
+    pub param_type: u8,
+    /// Values of the parameter, one per supported configuration mode
+    pub values: Vec<DevlinkParamValue>,
+}
+
+impl DevlinkParam {
+    /// Return the value of the parameter in the given configuration mode (`DEVLINK_PARAM_CMODE_*`)
+    pub fn value(&self, cmode: u8) -> Option<&DevlinkParamData> {
+        self.values
+            .iter()
+            .find(|value| value.cmode == cmode)
+            .map(|value| &value.data)
+    }
+
+    pub(crate) fn to_nlas(&self) -> Vec<GenlAttr> {
+        let mut nlas = vec![GenlAttr::Str(DEVLINK_ATTR_PARAM_NAME, self.name.clone())];
+        if self.generic {
+            nlas.push(GenlAttr::Flag(DEVLINK_ATTR_PARAM_GENERIC));
+        }
+        nlas.push(GenlAttr::U8(DEVLINK_ATTR_PARAM_TYPE, self.param_type));
+        nlas.push(GenlAttr::Nested(
+            DEVLINK_ATTR_PARAM_VALUES_LIST,
+            self.values
+                .iter()
+                .map(|value| GenlAttr::Nested(DEVLINK_ATTR_PARAM_VALUE, value.to_nlas()))
+                .collect(),
+        ));
+        nlas
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<DevlinkParam> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<DevlinkParam, DecodeError> {
+        let mut name = String::new();
+        let mut generic = false;
+        let mut param_type = None;
+        let mut values_list = None;
+        for nla in NlasIterator::new(self.value()) {
+            let nla = nla?;
+            match nla.kind() {
+                DEVLINK_ATTR_PARAM_NAME => {
+                    name = parse_string(nla.value())
+                        .context("invalid DEVLINK_ATTR_PARAM_NAME value")?
+                }
+                DEVLINK_ATTR_PARAM_GENERIC => generic = true,
+                DEVLINK_ATTR_PARAM_TYPE => {
+                    param_type = Some(
+                        parse_u8(nla.value()).context("invalid DEVLINK_ATTR_PARAM_TYPE value")?,
+                    )
+                }
+                DEVLINK_ATTR_PARAM_VALUES_LIST => values_list = Some(nla.value().to_vec()),
+                _ => {}
+            }
+        }
+
+        // the values can only be decoded once the type of the parameter is known
+        let param_type = param_type.ok_or("missing DEVLINK_ATTR_PARAM_TYPE")?;
+        let mut values = vec![];
+        if let Some(payload) = values_list {
+            for nla in NlasIterator::new(&payload[..]) {
+                let nla = nla?;
+                if nla.kind() == DEVLINK_ATTR_PARAM_VALUE {
+                    values.push(
+                        DevlinkParamValue::parse(param_type, nla.value())
+                            .context("invalid DEVLINK_ATTR_PARAM_VALUE value")?,
+                    );
+                }
+            }
+        }
+
+        Ok(DevlinkParam {
+            name,
+            generic,
+            param_type,
+            values,
+        })
+    }
+}
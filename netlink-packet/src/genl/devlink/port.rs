@@ -0,0 +1,81 @@
+use crate::constants::*;
+
+/// Type of a devlink port (`DEVLINK_PORT_TYPE_*`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DevlinkPortType {
+    NotSet,
+    Auto,
+    Eth,
+    Ib,
+    Other(u16),
+}
+
+impl From<u16> for DevlinkPortType {
+    fn from(value: u16) -> Self {
+        use self::DevlinkPortType::*;
+        match value {
+            DEVLINK_PORT_TYPE_NOTSET => NotSet,
+            DEVLINK_PORT_TYPE_AUTO => Auto,
+            DEVLINK_PORT_TYPE_ETH => Eth,
+            DEVLINK_PORT_TYPE_IB => Ib,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<DevlinkPortType> for u16 {
+    fn from(value: DevlinkPortType) -> Self {
+        use self::DevlinkPortType::*;
+        match value {
+            NotSet => DEVLINK_PORT_TYPE_NOTSET,
+            Auto => DEVLINK_PORT_TYPE_AUTO,
+            Eth => DEVLINK_PORT_TYPE_ETH,
+            Ib => DEVLINK_PORT_TYPE_IB,
+            Other(value) => value,
+        }
+    }
+}
+
+/// Flavour of a devlink port (`DEVLINK_PORT_FLAVOUR_*`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DevlinkPortFlavour {
+    /// Port facing the user (front panel)
+    Physical,
+    /// CPU port of a switch
+    Cpu,
+    /// Port interconnecting two switches
+    Dsa,
+    /// Port representing a PCI physical function
+    PciPf,
+    /// Port representing a PCI virtual function
+    PciVf,
+    Other(u16),
+}
+
+impl From<u16> for DevlinkPortFlavour {
+    fn from(value: u16) -> Self {
+        use self::DevlinkPortFlavour::*;
+        match value {
+            DEVLINK_PORT_FLAVOUR_PHYSICAL => Physical,
+            DEVLINK_PORT_FLAVOUR_CPU => Cpu,
+            DEVLINK_PORT_FLAVOUR_DSA => Dsa,
+            DEVLINK_PORT_FLAVOUR_PCI_PF => PciPf,
+            DEVLINK_PORT_FLAVOUR_PCI_VF => PciVf,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<DevlinkPortFlavour> for u16 {
+    fn from(value: DevlinkPortFlavour) -> Self {
+        use self::DevlinkPortFlavour::*;
+        match value {
+            Physical => DEVLINK_PORT_FLAVOUR_PHYSICAL,
+            Cpu => DEVLINK_PORT_FLAVOUR_CPU,
+            Dsa => DEVLINK_PORT_FLAVOUR_DSA,
+            PciPf => DEVLINK_PORT_FLAVOUR_PCI_PF,
+            PciVf => DEVLINK_PORT_FLAVOUR_PCI_VF,
+            Other(value) => value,
+        }
+    }
+}
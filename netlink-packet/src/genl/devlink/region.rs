@@ -0,0 +1,67 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_u32, parse_u64};
+use crate::{DecodeError, GenlAttr, NlaBuffer, NlasIterator, Parseable};
+
+/// A snapshot of a devlink region (`DEVLINK_ATTR_REGION_SNAPSHOTS` array element)
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct DevlinkRegionSnapshot {
+    pub id: u32,
+}
+
+impl DevlinkRegionSnapshot {
+    pub(crate) fn to_nlas(&self) -> Vec<GenlAttr> {
+        vec![GenlAttr::U32(DEVLINK_ATTR_REGION_SNAPSHOT_ID, self.id)]
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<DevlinkRegionSnapshot> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<DevlinkRegionSnapshot, DecodeError> {
+        let mut snapshot = DevlinkRegionSnapshot::default();
+        for nla in NlasIterator::new(self.value()) {
+            let nla = nla?;
+            if nla.kind() == DEVLINK_ATTR_REGION_SNAPSHOT_ID {
+                snapshot.id = parse_u32(nla.value())
+                    .context("invalid DEVLINK_ATTR_REGION_SNAPSHOT_ID value")?;
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+/// A chunk of data read from a devlink region snapshot (`DEVLINK_ATTR_REGION_CHUNKS` array
+/// element)
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct DevlinkRegionChunk {
+    /// Offset of the chunk in the region
+    pub addr: u64,
+    pub data: Vec<u8>,
+}
+
+impl DevlinkRegionChunk {
+    pub(crate) fn to_nlas(&self) -> Vec<GenlAttr> {
+        vec![
+            GenlAttr::Bytes(DEVLINK_ATTR_REGION_CHUNK_DATA, self.data.clone()),
+            GenlAttr::U64(DEVLINK_ATTR_REGION_CHUNK_ADDR, self.addr),
+        ]
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<DevlinkRegionChunk> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<DevlinkRegionChunk, DecodeError> {
+        let mut chunk = DevlinkRegionChunk::default();
+        for nla in NlasIterator::new(self.value()) {
+            let nla = nla?;
+            match nla.kind() {
+                DEVLINK_ATTR_REGION_CHUNK_DATA => chunk.data = nla.value().to_vec(),
+                DEVLINK_ATTR_REGION_CHUNK_ADDR => {
+                    chunk.addr = parse_u64(nla.value())
+                        .context("invalid DEVLINK_ATTR_REGION_CHUNK_ADDR value")?
+                }
+                _ => {}
+            }
+        }
+        Ok(chunk)
+    }
+}
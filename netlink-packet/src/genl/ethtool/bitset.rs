@@ -0,0 +1,199 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_string, parse_u32};
+use crate::{
+    DecodeError, DefaultNla, Emitable, NestedNla, Nla, NlaBuffer, NlasIterator, Parseable,
+};
+
+/// Attributes of an ethtool bit set (`ETHTOOL_A_BITSET_*`), used for link modes and features.
+///
+/// A bit set is either _compact_, in which case the bits are carried by the `Value` and `Mask`
+/// bitmaps, or _verbose_, in which case they are listed in `Bits`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolBitSet {
+    /// The bit set is a list rather than a value/mask pair
+    NoMask,
+    Size(u32),
+    Bits(Vec<EthtoolBitSetBit>),
+    Value(Vec<u8>),
+    Mask(Vec<u8>),
+    Other(DefaultNla),
+}
+
+/// A bit in a verbose bit set. Bits can be identified either by index or by name.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct EthtoolBitSetBit {
+    pub index: Option<u32>,
+    pub name: Option<String>,
+    pub value: bool,
+}
+
+impl EthtoolBitSetBit {
+    pub fn new<S: Into<String>>(name: S, value: bool) -> Self {
+        EthtoolBitSetBit {
+            index: None,
+            name: Some(name.into()),
+            value,
+        }
+    }
+
+    fn to_nla(&self) -> NestedNla<EthtoolBitSetBitNla> {
+        let mut nlas = vec![];
+        if let Some(index) = self.index {
+            nlas.push(EthtoolBitSetBitNla::Index(index));
+        }
+        if let Some(ref name) = self.name {
+            nlas.push(EthtoolBitSetBitNla::Name(name.clone()));
+        }
+        if self.value {
+            nlas.push(EthtoolBitSetBitNla::Value);
+        }
+        NestedNla::new(ETHTOOL_A_BITSET_BITS_BIT, nlas)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum EthtoolBitSetBitNla {
+    Index(u32),
+    Name(String),
+    Value,
+}
+
+impl Nla for EthtoolBitSetBitNla {
+    fn value_len(&self) -> usize {
+        match *self {
+            EthtoolBitSetBitNla::Index(_) => size_of::<u32>(),
+            EthtoolBitSetBitNla::Name(ref s) => s.len() + 1,
+            EthtoolBitSetBitNla::Value => 0,
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match *self {
+            EthtoolBitSetBitNla::Index(_) => ETHTOOL_A_BITSET_BIT_INDEX,
+            EthtoolBitSetBitNla::Name(_) => ETHTOOL_A_BITSET_BIT_NAME,
+            EthtoolBitSetBitNla::Value => ETHTOOL_A_BITSET_BIT_VALUE,
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match *self {
+            EthtoolBitSetBitNla::Index(value) => NativeEndian::write_u32(buffer, value),
+            EthtoolBitSetBitNla::Name(ref s) => {
+                buffer[..s.len()].copy_from_slice(s.as_bytes());
+                buffer[s.len()] = 0;
+            }
+            EthtoolBitSetBitNla::Value => {}
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolBitSetBit> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolBitSetBit, DecodeError> {
+        let mut bit = EthtoolBitSetBit::default();
+        for nla in NlasIterator::new(self.value()) {
+            let nla = nla?;
+            match nla.kind() {
+                ETHTOOL_A_BITSET_BIT_INDEX => {
+                    bit.index = Some(
+                        parse_u32(nla.value())
+                            .context("invalid ETHTOOL_A_BITSET_BIT_INDEX value")?,
+                    )
+                }
+                ETHTOOL_A_BITSET_BIT_NAME => {
+                    bit.name = Some(
+                        parse_string(nla.value())
+                            .context("invalid ETHTOOL_A_BITSET_BIT_NAME value")?,
+                    )
+                }
+                ETHTOOL_A_BITSET_BIT_VALUE => bit.value = true,
+                _ => {}
+            }
+        }
+        Ok(bit)
+    }
+}
+
+impl Nla for EthtoolBitSet {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::EthtoolBitSet::*;
+        match *self {
+            NoMask => 0,
+            Size(_) => size_of::<u32>(),
+            Bits(ref bits) => bits.iter().map(|bit| bit.to_nla().buffer_len()).sum(),
+            Value(ref bytes)
+                | Mask(ref bytes) => bytes.len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolBitSet::*;
+        match *self {
+            NoMask => ETHTOOL_A_BITSET_NOMASK,
+            Size(_) => ETHTOOL_A_BITSET_SIZE,
+            Bits(_) => ETHTOOL_A_BITSET_BITS,
+            Value(_) => ETHTOOL_A_BITSET_VALUE,
+            Mask(_) => ETHTOOL_A_BITSET_MASK,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolBitSet::*;
+        match *self {
+            NoMask => {}
+            Size(value) => NativeEndian::write_u32(buffer, value),
+            Bits(ref bits) => {
+                let mut offset = 0;
+                for bit in bits {
+                    let nla = bit.to_nla();
+                    nla.emit(&mut buffer[offset..offset + nla.buffer_len()]);
+                    offset += nla.buffer_len();
+                }
+            }
+            Value(ref bytes)
+                | Mask(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(*self, EthtoolBitSet::Bits(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolBitSet> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolBitSet, DecodeError> {
+        use self::EthtoolBitSet::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ETHTOOL_A_BITSET_NOMASK => NoMask,
+            ETHTOOL_A_BITSET_SIZE => {
+                Size(parse_u32(payload).context("invalid ETHTOOL_A_BITSET_SIZE value")?)
+            }
+            ETHTOOL_A_BITSET_BITS => {
+                let mut bits = vec![];
+                for nla in NlasIterator::new(payload) {
+                    bits.push(
+                        nla?.parse()
+                            .context("invalid ETHTOOL_A_BITSET_BITS value")?,
+                    );
+                }
+                Bits(bits)
+            }
+            ETHTOOL_A_BITSET_VALUE => Value(payload.to_vec()),
+            ETHTOOL_A_BITSET_MASK => Mask(payload.to_vec()),
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
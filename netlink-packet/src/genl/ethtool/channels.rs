@@ -0,0 +1,129 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::parse_u32;
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, EthtoolHeader, Nla, NlaBuffer, Parseable,
+};
+
+/// Attributes of the `ETHTOOL_MSG_CHANNELS_*` messages
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolChannels {
+    Header(Vec<EthtoolHeader>),
+    /// Maximum number of RX channels
+    RxMax(u32),
+    /// Maximum number of TX channels
+    TxMax(u32),
+    /// Maximum number of other channels
+    OtherMax(u32),
+    /// Maximum number of combined channels
+    CombinedMax(u32),
+    /// Number of RX channels
+    RxCount(u32),
+    /// Number of TX channels
+    TxCount(u32),
+    /// Number of other channels
+    OtherCount(u32),
+    /// Number of combined channels
+    CombinedCount(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for EthtoolChannels {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::EthtoolChannels::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().buffer_len(),
+            RxMax(_)
+                | TxMax(_)
+                | OtherMax(_)
+                | CombinedMax(_)
+                | RxCount(_)
+                | TxCount(_)
+                | OtherCount(_)
+                | CombinedCount(_) => size_of::<u32>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolChannels::*;
+        match *self {
+            Header(_) => ETHTOOL_A_CHANNELS_HEADER,
+            RxMax(_) => ETHTOOL_A_CHANNELS_RX_MAX,
+            TxMax(_) => ETHTOOL_A_CHANNELS_TX_MAX,
+            OtherMax(_) => ETHTOOL_A_CHANNELS_OTHER_MAX,
+            CombinedMax(_) => ETHTOOL_A_CHANNELS_COMBINED_MAX,
+            RxCount(_) => ETHTOOL_A_CHANNELS_RX_COUNT,
+            TxCount(_) => ETHTOOL_A_CHANNELS_TX_COUNT,
+            OtherCount(_) => ETHTOOL_A_CHANNELS_OTHER_COUNT,
+            CombinedCount(_) => ETHTOOL_A_CHANNELS_COMBINED_COUNT,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolChannels::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().emit(buffer),
+            RxMax(value)
+                | TxMax(value)
+                | OtherMax(value)
+                | CombinedMax(value)
+                | RxCount(value)
+                | TxCount(value)
+                | OtherCount(value)
+                | CombinedCount(value) => NativeEndian::write_u32(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(*self, EthtoolChannels::Header(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolChannels> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolChannels, DecodeError> {
+        use self::EthtoolChannels::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ETHTOOL_A_CHANNELS_HEADER => {
+                Header(parse_nested(payload).context("invalid ETHTOOL_A_CHANNELS_HEADER value")?)
+            }
+            ETHTOOL_A_CHANNELS_RX_MAX => {
+                RxMax(parse_u32(payload).context("invalid ETHTOOL_A_CHANNELS_RX_MAX value")?)
+            }
+            ETHTOOL_A_CHANNELS_TX_MAX => {
+                TxMax(parse_u32(payload).context("invalid ETHTOOL_A_CHANNELS_TX_MAX value")?)
+            }
+            ETHTOOL_A_CHANNELS_OTHER_MAX => {
+                OtherMax(parse_u32(payload).context("invalid ETHTOOL_A_CHANNELS_OTHER_MAX value")?)
+            }
+            ETHTOOL_A_CHANNELS_COMBINED_MAX => CombinedMax(
+                parse_u32(payload).context("invalid ETHTOOL_A_CHANNELS_COMBINED_MAX value")?,
+            ),
+            ETHTOOL_A_CHANNELS_RX_COUNT => {
+                RxCount(parse_u32(payload).context("invalid ETHTOOL_A_CHANNELS_RX_COUNT value")?)
+            }
+            ETHTOOL_A_CHANNELS_TX_COUNT => {
+                TxCount(parse_u32(payload).context("invalid ETHTOOL_A_CHANNELS_TX_COUNT value")?)
+            }
+            ETHTOOL_A_CHANNELS_OTHER_COUNT => OtherCount(
+                parse_u32(payload).context("invalid ETHTOOL_A_CHANNELS_OTHER_COUNT value")?,
+            ),
+            ETHTOOL_A_CHANNELS_COMBINED_COUNT => CombinedCount(
+                parse_u32(payload).context("invalid ETHTOOL_A_CHANNELS_COMBINED_COUNT value")?,
+            ),
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
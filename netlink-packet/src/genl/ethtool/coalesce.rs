@@ -0,0 +1,180 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_u32, parse_u8};
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, EthtoolHeader, Nla, NlaBuffer, Parseable,
+};
+
+/// Attributes of the `ETHTOOL_MSG_COALESCE_*` messages
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolCoalesce {
+    Header(Vec<EthtoolHeader>),
+    RxUsecs(u32),
+    RxMaxFrames(u32),
+    RxUsecsIrq(u32),
+    RxMaxFramesIrq(u32),
+    TxUsecs(u32),
+    TxMaxFrames(u32),
+    TxUsecsIrq(u32),
+    TxMaxFramesIrq(u32),
+    StatsBlockUsecs(u32),
+    UseAdaptiveRx(bool),
+    UseAdaptiveTx(bool),
+    PktRateLow(u32),
+    RxUsecsLow(u32),
+    RxMaxFramesLow(u32),
+    TxUsecsLow(u32),
+    TxMaxFramesLow(u32),
+    PktRateHigh(u32),
+    RxUsecsHigh(u32),
+    RxMaxFramesHigh(u32),
+    TxUsecsHigh(u32),
+    TxMaxFramesHigh(u32),
+    RateSampleInterval(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for EthtoolCoalesce {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::EthtoolCoalesce::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().buffer_len(),
+            UseAdaptiveRx(_)
+                | UseAdaptiveTx(_) => 1,
+            RxUsecs(_)
+                | RxMaxFrames(_)
+                | RxUsecsIrq(_)
+                | RxMaxFramesIrq(_)
+                | TxUsecs(_)
+                | TxMaxFrames(_)
+                | TxUsecsIrq(_)
+                | TxMaxFramesIrq(_)
+                | StatsBlockUsecs(_)
+                | PktRateLow(_)
+                | RxUsecsLow(_)
+                | RxMaxFramesLow(_)
+                | TxUsecsLow(_)
+                | TxMaxFramesLow(_)
+                | PktRateHigh(_)
+                | RxUsecsHigh(_)
+                | RxMaxFramesHigh(_)
+                | TxUsecsHigh(_)
+                | TxMaxFramesHigh(_)
+                | RateSampleInterval(_) => size_of::<u32>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolCoalesce::*;
+        match *self {
+            Header(_) => ETHTOOL_A_COALESCE_HEADER,
+            RxUsecs(_) => ETHTOOL_A_COALESCE_RX_USECS,
+            RxMaxFrames(_) => ETHTOOL_A_COALESCE_RX_MAX_FRAMES,
+            RxUsecsIrq(_) => ETHTOOL_A_COALESCE_RX_USECS_IRQ,
+            RxMaxFramesIrq(_) => ETHTOOL_A_COALESCE_RX_MAX_FRAMES_IRQ,
+            TxUsecs(_) => ETHTOOL_A_COALESCE_TX_USECS,
+            TxMaxFrames(_) => ETHTOOL_A_COALESCE_TX_MAX_FRAMES,
+            TxUsecsIrq(_) => ETHTOOL_A_COALESCE_TX_USECS_IRQ,
+            TxMaxFramesIrq(_) => ETHTOOL_A_COALESCE_TX_MAX_FRAMES_IRQ,
+            StatsBlockUsecs(_) => ETHTOOL_A_COALESCE_STATS_BLOCK_USECS,
+            UseAdaptiveRx(_) => ETHTOOL_A_COALESCE_USE_ADAPTIVE_RX,
+            UseAdaptiveTx(_) => ETHTOOL_A_COALESCE_USE_ADAPTIVE_TX,
+            PktRateLow(_) => ETHTOOL_A_COALESCE_PKT_RATE_LOW,
+            RxUsecsLow(_) => ETHTOOL_A_COALESCE_RX_USECS_LOW,
+            RxMaxFramesLow(_) => ETHTOOL_A_COALESCE_RX_MAX_FRAMES_LOW,
+            TxUsecsLow(_) => ETHTOOL_A_COALESCE_TX_USECS_LOW,
+            TxMaxFramesLow(_) => ETHTOOL_A_COALESCE_TX_MAX_FRAMES_LOW,
+            PktRateHigh(_) => ETHTOOL_A_COALESCE_PKT_RATE_HIGH,
+            RxUsecsHigh(_) => ETHTOOL_A_COALESCE_RX_USECS_HIGH,
+            RxMaxFramesHigh(_) => ETHTOOL_A_COALESCE_RX_MAX_FRAMES_HIGH,
+            TxUsecsHigh(_) => ETHTOOL_A_COALESCE_TX_USECS_HIGH,
+            TxMaxFramesHigh(_) => ETHTOOL_A_COALESCE_TX_MAX_FRAMES_HIGH,
+            RateSampleInterval(_) => ETHTOOL_A_COALESCE_RATE_SAMPLE_INTERVAL,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolCoalesce::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().emit(buffer),
+            UseAdaptiveRx(value)
+                | UseAdaptiveTx(value) => buffer[0] = value as u8,
+            RxUsecs(value)
+                | RxMaxFrames(value)
+                | RxUsecsIrq(value)
+                | RxMaxFramesIrq(value)
+                | TxUsecs(value)
+                | TxMaxFrames(value)
+                | TxUsecsIrq(value)
+                | TxMaxFramesIrq(value)
+                | StatsBlockUsecs(value)
+                | PktRateLow(value)
+                | RxUsecsLow(value)
+                | RxMaxFramesLow(value)
+                | TxUsecsLow(value)
+                | TxMaxFramesLow(value)
+                | PktRateHigh(value)
+                | RxUsecsHigh(value)
+                | RxMaxFramesHigh(value)
+                | TxUsecsHigh(value)
+                | TxMaxFramesHigh(value)
+                | RateSampleInterval(value) => NativeEndian::write_u32(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(*self, EthtoolCoalesce::Header(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolCoalesce> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolCoalesce, DecodeError> {
+        use self::EthtoolCoalesce::*;
+        let payload = self.value();
+        let u32_value = || parse_u32(payload).context(format!("invalid NLA {} value", self.kind()));
+        Ok(match self.kind() {
+            ETHTOOL_A_COALESCE_HEADER => {
+                Header(parse_nested(payload).context("invalid ETHTOOL_A_COALESCE_HEADER value")?)
+            }
+            ETHTOOL_A_COALESCE_RX_USECS => RxUsecs(u32_value()?),
+            ETHTOOL_A_COALESCE_RX_MAX_FRAMES => RxMaxFrames(u32_value()?),
+            ETHTOOL_A_COALESCE_RX_USECS_IRQ => RxUsecsIrq(u32_value()?),
+            ETHTOOL_A_COALESCE_RX_MAX_FRAMES_IRQ => RxMaxFramesIrq(u32_value()?),
+            ETHTOOL_A_COALESCE_TX_USECS => TxUsecs(u32_value()?),
+            ETHTOOL_A_COALESCE_TX_MAX_FRAMES => TxMaxFrames(u32_value()?),
+            ETHTOOL_A_COALESCE_TX_USECS_IRQ => TxUsecsIrq(u32_value()?),
+            ETHTOOL_A_COALESCE_TX_MAX_FRAMES_IRQ => TxMaxFramesIrq(u32_value()?),
+            ETHTOOL_A_COALESCE_STATS_BLOCK_USECS => StatsBlockUsecs(u32_value()?),
+            ETHTOOL_A_COALESCE_USE_ADAPTIVE_RX => UseAdaptiveRx(
+                parse_u8(payload).context("invalid ETHTOOL_A_COALESCE_USE_ADAPTIVE_RX value")? != 0,
+            ),
+            ETHTOOL_A_COALESCE_USE_ADAPTIVE_TX => UseAdaptiveTx(
+                parse_u8(payload).context("invalid ETHTOOL_A_COALESCE_USE_ADAPTIVE_TX value")? != 0,
+            ),
+            ETHTOOL_A_COALESCE_PKT_RATE_LOW => PktRateLow(u32_value()?),
+            ETHTOOL_A_COALESCE_RX_USECS_LOW => RxUsecsLow(u32_value()?),
+            ETHTOOL_A_COALESCE_RX_MAX_FRAMES_LOW => RxMaxFramesLow(u32_value()?),
+            ETHTOOL_A_COALESCE_TX_USECS_LOW => TxUsecsLow(u32_value()?),
+            ETHTOOL_A_COALESCE_TX_MAX_FRAMES_LOW => TxMaxFramesLow(u32_value()?),
+            ETHTOOL_A_COALESCE_PKT_RATE_HIGH => PktRateHigh(u32_value()?),
+            ETHTOOL_A_COALESCE_RX_USECS_HIGH => RxUsecsHigh(u32_value()?),
+            ETHTOOL_A_COALESCE_RX_MAX_FRAMES_HIGH => RxMaxFramesHigh(u32_value()?),
+            ETHTOOL_A_COALESCE_TX_USECS_HIGH => TxUsecsHigh(u32_value()?),
+            ETHTOOL_A_COALESCE_TX_MAX_FRAMES_HIGH => TxMaxFramesHigh(u32_value()?),
+            ETHTOOL_A_COALESCE_RATE_SAMPLE_INTERVAL => RateSampleInterval(u32_value()?),
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
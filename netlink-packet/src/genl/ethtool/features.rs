@@ -0,0 +1,94 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, EthtoolBitSet, EthtoolHeader, Nla, NlaBuffer,
+    Parseable,
+};
+
+/// Attributes of the `ETHTOOL_MSG_FEATURES_*` messages
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolFeatures {
+    Header(Vec<EthtoolHeader>),
+    /// Features that can be changed
+    Hw(Vec<EthtoolBitSet>),
+    /// Features requested by the user
+    Wanted(Vec<EthtoolBitSet>),
+    /// Features currently enabled
+    Active(Vec<EthtoolBitSet>),
+    /// Features that cannot be changed
+    NoChange(Vec<EthtoolBitSet>),
+    Other(DefaultNla),
+}
+
+impl Nla for EthtoolFeatures {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::EthtoolFeatures::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().buffer_len(),
+            Hw(ref nlas)
+                | Wanted(ref nlas)
+                | Active(ref nlas)
+                | NoChange(ref nlas) => nlas.as_slice().buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolFeatures::*;
+        match *self {
+            Header(_) => ETHTOOL_A_FEATURES_HEADER,
+            Hw(_) => ETHTOOL_A_FEATURES_HW,
+            Wanted(_) => ETHTOOL_A_FEATURES_WANTED,
+            Active(_) => ETHTOOL_A_FEATURES_ACTIVE,
+            NoChange(_) => ETHTOOL_A_FEATURES_NOCHANGE,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolFeatures::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().emit(buffer),
+            Hw(ref nlas)
+                | Wanted(ref nlas)
+                | Active(ref nlas)
+                | NoChange(ref nlas) => nlas.as_slice().emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        !matches!(*self, EthtoolFeatures::Other(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolFeatures> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolFeatures, DecodeError> {
+        use self::EthtoolFeatures::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ETHTOOL_A_FEATURES_HEADER => {
+                Header(parse_nested(payload).context("invalid ETHTOOL_A_FEATURES_HEADER value")?)
+            }
+            ETHTOOL_A_FEATURES_HW => {
+                Hw(parse_nested(payload).context("invalid ETHTOOL_A_FEATURES_HW value")?)
+            }
+            ETHTOOL_A_FEATURES_WANTED => {
+                Wanted(parse_nested(payload).context("invalid ETHTOOL_A_FEATURES_WANTED value")?)
+            }
+            ETHTOOL_A_FEATURES_ACTIVE => {
+                Active(parse_nested(payload).context("invalid ETHTOOL_A_FEATURES_ACTIVE value")?)
+            }
+            ETHTOOL_A_FEATURES_NOCHANGE => NoChange(
+                parse_nested(payload).context("invalid ETHTOOL_A_FEATURES_NOCHANGE value")?,
+            ),
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,74 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_string, parse_u32};
+use crate::{DecodeError, DefaultNla, Nla, NlaBuffer, Parseable};
+
+/// Attributes of the request/reply header nest (`ETHTOOL_A_*_HEADER`) that identifies the device
+/// a message applies to.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolHeader {
+    DevIndex(u32),
+    DevName(String),
+    /// `ETHTOOL_FLAG_*` flags
+    Flags(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for EthtoolHeader {
+    fn value_len(&self) -> usize {
+        use self::EthtoolHeader::*;
+        match *self {
+            DevIndex(_) | Flags(_) => size_of::<u32>(),
+            DevName(ref s) => s.len() + 1,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolHeader::*;
+        match *self {
+            DevIndex(_) => ETHTOOL_A_HEADER_DEV_INDEX,
+            DevName(_) => ETHTOOL_A_HEADER_DEV_NAME,
+            Flags(_) => ETHTOOL_A_HEADER_FLAGS,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolHeader::*;
+        match *self {
+            DevIndex(value) | Flags(value) => NativeEndian::write_u32(buffer, value),
+            DevName(ref s) => {
+                buffer[..s.len()].copy_from_slice(s.as_bytes());
+                buffer[s.len()] = 0;
+            }
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolHeader> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolHeader, DecodeError> {
+        use self::EthtoolHeader::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ETHTOOL_A_HEADER_DEV_INDEX => {
+                DevIndex(parse_u32(payload).context("invalid ETHTOOL_A_HEADER_DEV_INDEX value")?)
+            }
+            ETHTOOL_A_HEADER_DEV_NAME => {
+                DevName(parse_string(payload).context("invalid ETHTOOL_A_HEADER_DEV_NAME value")?)
+            }
+            ETHTOOL_A_HEADER_FLAGS => {
+                Flags(parse_u32(payload).context("invalid ETHTOOL_A_HEADER_FLAGS value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
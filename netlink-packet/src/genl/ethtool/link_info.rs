@@ -0,0 +1,150 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::parse_u8;
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, EthtoolHeader, Nla, NlaBuffer, Parseable,
+};
+
+/// Physical connector type (`PORT_*`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EthtoolPort {
+    TwistedPair,
+    Aui,
+    Bnc,
+    Mii,
+    Fibre,
+    DirectAttach,
+    None,
+    Other,
+    Unknown(u8),
+}
+
+impl From<u8> for EthtoolPort {
+    fn from(value: u8) -> Self {
+        use self::EthtoolPort::*;
+        match value {
+            PORT_TP => TwistedPair,
+            PORT_AUI => Aui,
+            PORT_BNC => Bnc,
+            PORT_MII => Mii,
+            PORT_FIBRE => Fibre,
+            PORT_DA => DirectAttach,
+            PORT_NONE => None,
+            PORT_OTHER => Other,
+            _ => Unknown(value),
+        }
+    }
+}
+
+impl From<EthtoolPort> for u8 {
+    fn from(value: EthtoolPort) -> Self {
+        use self::EthtoolPort::*;
+        match value {
+            TwistedPair => PORT_TP,
+            Aui => PORT_AUI,
+            Bnc => PORT_BNC,
+            Mii => PORT_MII,
+            Fibre => PORT_FIBRE,
+            DirectAttach => PORT_DA,
+            None => PORT_NONE,
+            Other => PORT_OTHER,
+            Unknown(value) => value,
+        }
+    }
+}
+
+/// Attributes of the `ETHTOOL_MSG_LINKINFO_*` messages
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolLinkInfo {
+    Header(Vec<EthtoolHeader>),
+    Port(EthtoolPort),
+    PhyAddr(u8),
+    /// MDI(-X) status (`ETH_TP_MDI_*`)
+    TpMdix(u8),
+    /// MDI(-X) control (`ETH_TP_MDI_*`)
+    TpMdixCtrl(u8),
+    /// Transceiver type (`XCVR_*`)
+    Transceiver(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for EthtoolLinkInfo {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::EthtoolLinkInfo::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().buffer_len(),
+            Port(_)
+                | PhyAddr(_)
+                | TpMdix(_)
+                | TpMdixCtrl(_)
+                | Transceiver(_) => 1,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolLinkInfo::*;
+        match *self {
+            Header(_) => ETHTOOL_A_LINKINFO_HEADER,
+            Port(_) => ETHTOOL_A_LINKINFO_PORT,
+            PhyAddr(_) => ETHTOOL_A_LINKINFO_PHYADDR,
+            TpMdix(_) => ETHTOOL_A_LINKINFO_TP_MDIX,
+            TpMdixCtrl(_) => ETHTOOL_A_LINKINFO_TP_MDIX_CTRL,
+            Transceiver(_) => ETHTOOL_A_LINKINFO_TRANSCEIVER,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolLinkInfo::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().emit(buffer),
+            Port(value) => buffer[0] = value.into(),
+            PhyAddr(value)
+                | TpMdix(value)
+                | TpMdixCtrl(value)
+                | Transceiver(value) => buffer[0] = value,
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(*self, EthtoolLinkInfo::Header(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolLinkInfo> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolLinkInfo, DecodeError> {
+        use self::EthtoolLinkInfo::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ETHTOOL_A_LINKINFO_HEADER => {
+                Header(parse_nested(payload).context("invalid ETHTOOL_A_LINKINFO_HEADER value")?)
+            }
+            ETHTOOL_A_LINKINFO_PORT => Port(
+                parse_u8(payload)
+                    .context("invalid ETHTOOL_A_LINKINFO_PORT value")?
+                    .into(),
+            ),
+            ETHTOOL_A_LINKINFO_PHYADDR => {
+                PhyAddr(parse_u8(payload).context("invalid ETHTOOL_A_LINKINFO_PHYADDR value")?)
+            }
+            ETHTOOL_A_LINKINFO_TP_MDIX => {
+                TpMdix(parse_u8(payload).context("invalid ETHTOOL_A_LINKINFO_TP_MDIX value")?)
+            }
+            ETHTOOL_A_LINKINFO_TP_MDIX_CTRL => TpMdixCtrl(
+                parse_u8(payload).context("invalid ETHTOOL_A_LINKINFO_TP_MDIX_CTRL value")?,
+            ),
+            ETHTOOL_A_LINKINFO_TRANSCEIVER => Transceiver(
+                parse_u8(payload).context("invalid ETHTOOL_A_LINKINFO_TRANSCEIVER value")?,
+            ),
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
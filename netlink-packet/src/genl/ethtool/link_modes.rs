@@ -0,0 +1,162 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_u32, parse_u8};
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, EthtoolBitSet, EthtoolHeader, Nla, NlaBuffer,
+    Parseable,
+};
+
+/// Duplex mode of a link (`DUPLEX_*`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EthtoolDuplex {
+    Half,
+    Full,
+    Unknown,
+    Other(u8),
+}
+
+impl From<u8> for EthtoolDuplex {
+    fn from(value: u8) -> Self {
+        use self::EthtoolDuplex::*;
+        match value {
+            DUPLEX_HALF => Half,
+            DUPLEX_FULL => Full,
+            DUPLEX_UNKNOWN => Unknown,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<EthtoolDuplex> for u8 {
+    fn from(value: EthtoolDuplex) -> Self {
+        use self::EthtoolDuplex::*;
+        match value {
+            Half => DUPLEX_HALF,
+            Full => DUPLEX_FULL,
+            Unknown => DUPLEX_UNKNOWN,
+            Other(value) => value,
+        }
+    }
+}
+
+/// Attributes of the `ETHTOOL_MSG_LINKMODES_*` messages
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolLinkModes {
+    Header(Vec<EthtoolHeader>),
+    Autoneg(bool),
+    /// Link modes advertised (value) and supported (mask) by the device
+    Ours(Vec<EthtoolBitSet>),
+    /// Link modes advertised by the link partner
+    Peer(Vec<EthtoolBitSet>),
+    /// Speed in Mb/s, or `SPEED_UNKNOWN`
+    Speed(u32),
+    Duplex(EthtoolDuplex),
+    MasterSlaveCfg(u8),
+    MasterSlaveState(u8),
+    Lanes(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for EthtoolLinkModes {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::EthtoolLinkModes::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().buffer_len(),
+            Ours(ref nlas)
+                | Peer(ref nlas) => nlas.as_slice().buffer_len(),
+            Autoneg(_)
+                | Duplex(_)
+                | MasterSlaveCfg(_)
+                | MasterSlaveState(_) => 1,
+            Speed(_)
+                | Lanes(_) => size_of::<u32>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolLinkModes::*;
+        match *self {
+            Header(_) => ETHTOOL_A_LINKMODES_HEADER,
+            Autoneg(_) => ETHTOOL_A_LINKMODES_AUTONEG,
+            Ours(_) => ETHTOOL_A_LINKMODES_OURS,
+            Peer(_) => ETHTOOL_A_LINKMODES_PEER,
+            Speed(_) => ETHTOOL_A_LINKMODES_SPEED,
+            Duplex(_) => ETHTOOL_A_LINKMODES_DUPLEX,
+            MasterSlaveCfg(_) => ETHTOOL_A_LINKMODES_MASTER_SLAVE_CFG,
+            MasterSlaveState(_) => ETHTOOL_A_LINKMODES_MASTER_SLAVE_STATE,
+            Lanes(_) => ETHTOOL_A_LINKMODES_LANES,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolLinkModes::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().emit(buffer),
+            Ours(ref nlas)
+                | Peer(ref nlas) => nlas.as_slice().emit(buffer),
+            Autoneg(value) => buffer[0] = value as u8,
+            Duplex(value) => buffer[0] = value.into(),
+            MasterSlaveCfg(value)
+                | MasterSlaveState(value) => buffer[0] = value,
+            Speed(value)
+                | Lanes(value) => NativeEndian::write_u32(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        use self::EthtoolLinkModes::*;
+        matches!(*self, Header(_) | Ours(_) | Peer(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolLinkModes> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolLinkModes, DecodeError> {
+        use self::EthtoolLinkModes::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ETHTOOL_A_LINKMODES_HEADER => {
+                Header(parse_nested(payload).context("invalid ETHTOOL_A_LINKMODES_HEADER value")?)
+            }
+            ETHTOOL_A_LINKMODES_AUTONEG => Autoneg(
+                parse_u8(payload).context("invalid ETHTOOL_A_LINKMODES_AUTONEG value")? != 0,
+            ),
+            ETHTOOL_A_LINKMODES_OURS => {
+                Ours(parse_nested(payload).context("invalid ETHTOOL_A_LINKMODES_OURS value")?)
+            }
+            ETHTOOL_A_LINKMODES_PEER => {
+                Peer(parse_nested(payload).context("invalid ETHTOOL_A_LINKMODES_PEER value")?)
+            }
+            ETHTOOL_A_LINKMODES_SPEED => {
+                Speed(parse_u32(payload).context("invalid ETHTOOL_A_LINKMODES_SPEED value")?)
+            }
+            ETHTOOL_A_LINKMODES_DUPLEX => Duplex(
+                parse_u8(payload)
+                    .context("invalid ETHTOOL_A_LINKMODES_DUPLEX value")?
+                    .into(),
+            ),
+            ETHTOOL_A_LINKMODES_MASTER_SLAVE_CFG => MasterSlaveCfg(
+                parse_u8(payload).context("invalid ETHTOOL_A_LINKMODES_MASTER_SLAVE_CFG value")?,
+            ),
+            ETHTOOL_A_LINKMODES_MASTER_SLAVE_STATE => MasterSlaveState(
+                parse_u8(payload)
+                    .context("invalid ETHTOOL_A_LINKMODES_MASTER_SLAVE_STATE value")?,
+            ),
+            ETHTOOL_A_LINKMODES_LANES => {
+                Lanes(parse_u32(payload).context("invalid ETHTOOL_A_LINKMODES_LANES value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
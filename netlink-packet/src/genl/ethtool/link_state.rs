@@ -0,0 +1,102 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_u32, parse_u8};
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, EthtoolHeader, Nla, NlaBuffer, Parseable,
+};
+
+/// Attributes of the `ETHTOOL_MSG_LINKSTATE_*` messages
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolLinkState {
+    Header(Vec<EthtoolHeader>),
+    /// Whether the link is up
+    Link(bool),
+    /// Signal quality index
+    Sqi(u32),
+    SqiMax(u32),
+    ExtState(u8),
+    ExtSubstate(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for EthtoolLinkState {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::EthtoolLinkState::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().buffer_len(),
+            Link(_)
+                | ExtState(_)
+                | ExtSubstate(_) => 1,
+            Sqi(_)
+                | SqiMax(_) => size_of::<u32>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolLinkState::*;
+        match *self {
+            Header(_) => ETHTOOL_A_LINKSTATE_HEADER,
+            Link(_) => ETHTOOL_A_LINKSTATE_LINK,
+            Sqi(_) => ETHTOOL_A_LINKSTATE_SQI,
+            SqiMax(_) => ETHTOOL_A_LINKSTATE_SQI_MAX,
+            ExtState(_) => ETHTOOL_A_LINKSTATE_EXT_STATE,
+            ExtSubstate(_) => ETHTOOL_A_LINKSTATE_EXT_SUBSTATE,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolLinkState::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().emit(buffer),
+            Link(value) => buffer[0] = value as u8,
+            ExtState(value)
+                | ExtSubstate(value) => buffer[0] = value,
+            Sqi(value)
+                | SqiMax(value) => NativeEndian::write_u32(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(*self, EthtoolLinkState::Header(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolLinkState> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolLinkState, DecodeError> {
+        use self::EthtoolLinkState::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ETHTOOL_A_LINKSTATE_HEADER => {
+                Header(parse_nested(payload).context("invalid ETHTOOL_A_LINKSTATE_HEADER value")?)
+            }
+            ETHTOOL_A_LINKSTATE_LINK => {
+                Link(parse_u8(payload).context("invalid ETHTOOL_A_LINKSTATE_LINK value")? != 0)
+            }
+            ETHTOOL_A_LINKSTATE_SQI => {
+                Sqi(parse_u32(payload).context("invalid ETHTOOL_A_LINKSTATE_SQI value")?)
+            }
+            ETHTOOL_A_LINKSTATE_SQI_MAX => {
+                SqiMax(parse_u32(payload).context("invalid ETHTOOL_A_LINKSTATE_SQI_MAX value")?)
+            }
+            ETHTOOL_A_LINKSTATE_EXT_STATE => {
+                ExtState(parse_u8(payload).context("invalid ETHTOOL_A_LINKSTATE_EXT_STATE value")?)
+            }
+            ETHTOOL_A_LINKSTATE_EXT_SUBSTATE => ExtSubstate(
+                parse_u8(payload).context("invalid ETHTOOL_A_LINKSTATE_EXT_SUBSTATE value")?,
+            ),
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
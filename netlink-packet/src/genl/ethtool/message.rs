@@ -0,0 +1,306 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::{
+    DecodeError, Emitable, EthtoolChannels, EthtoolCoalesce, EthtoolFeatures, EthtoolLinkInfo,
+    EthtoolLinkModes, EthtoolLinkState, EthtoolPause, EthtoolRings, GenlBuffer, GenlHeader,
+    NlaBuffer, Parseable,
+};
+
+/// Messages of the `ethtool` generic netlink family.
+///
+/// The kernel does not use the same command numbers for requests (`ETHTOOL_MSG_*_GET`,
+/// `ETHTOOL_MSG_*_SET`) and for replies and notifications (`ETHTOOL_MSG_*_GET_REPLY`,
+/// `ETHTOOL_MSG_*_NTF`). Requests are emitted with the former, and parsing always assumes the
+/// message comes from the kernel.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolMessage {
+    LinkInfoGet(Vec<EthtoolLinkInfo>),
+    LinkInfoSet(Vec<EthtoolLinkInfo>),
+    LinkInfoGetReply(Vec<EthtoolLinkInfo>),
+    LinkInfoNtf(Vec<EthtoolLinkInfo>),
+    LinkModesGet(Vec<EthtoolLinkModes>),
+    LinkModesSet(Vec<EthtoolLinkModes>),
+    LinkModesGetReply(Vec<EthtoolLinkModes>),
+    LinkModesNtf(Vec<EthtoolLinkModes>),
+    LinkStateGet(Vec<EthtoolLinkState>),
+    LinkStateGetReply(Vec<EthtoolLinkState>),
+    FeaturesGet(Vec<EthtoolFeatures>),
+    FeaturesSet(Vec<EthtoolFeatures>),
+    FeaturesGetReply(Vec<EthtoolFeatures>),
+    FeaturesSetReply(Vec<EthtoolFeatures>),
+    FeaturesNtf(Vec<EthtoolFeatures>),
+    RingsGet(Vec<EthtoolRings>),
+    RingsSet(Vec<EthtoolRings>),
+    RingsGetReply(Vec<EthtoolRings>),
+    RingsNtf(Vec<EthtoolRings>),
+    ChannelsGet(Vec<EthtoolChannels>),
+    ChannelsSet(Vec<EthtoolChannels>),
+    ChannelsGetReply(Vec<EthtoolChannels>),
+    ChannelsNtf(Vec<EthtoolChannels>),
+    CoalesceGet(Vec<EthtoolCoalesce>),
+    CoalesceSet(Vec<EthtoolCoalesce>),
+    CoalesceGetReply(Vec<EthtoolCoalesce>),
+    CoalesceNtf(Vec<EthtoolCoalesce>),
+    PauseGet(Vec<EthtoolPause>),
+    PauseSet(Vec<EthtoolPause>),
+    PauseGetReply(Vec<EthtoolPause>),
+    PauseNtf(Vec<EthtoolPause>),
+}
+
+impl EthtoolMessage {
+    pub fn cmd(&self) -> u8 {
+        use self::EthtoolMessage::*;
+        match *self {
+            LinkInfoGet(_) => ETHTOOL_MSG_LINKINFO_GET,
+            LinkInfoSet(_) => ETHTOOL_MSG_LINKINFO_SET,
+            LinkInfoGetReply(_) => ETHTOOL_MSG_LINKINFO_GET_REPLY,
+            LinkInfoNtf(_) => ETHTOOL_MSG_LINKINFO_NTF,
+            LinkModesGet(_) => ETHTOOL_MSG_LINKMODES_GET,
+            LinkModesSet(_) => ETHTOOL_MSG_LINKMODES_SET,
+            LinkModesGetReply(_) => ETHTOOL_MSG_LINKMODES_GET_REPLY,
+            LinkModesNtf(_) => ETHTOOL_MSG_LINKMODES_NTF,
+            LinkStateGet(_) => ETHTOOL_MSG_LINKSTATE_GET,
+            LinkStateGetReply(_) => ETHTOOL_MSG_LINKSTATE_GET_REPLY,
+            FeaturesGet(_) => ETHTOOL_MSG_FEATURES_GET,
+            FeaturesSet(_) => ETHTOOL_MSG_FEATURES_SET,
+            FeaturesGetReply(_) => ETHTOOL_MSG_FEATURES_GET_REPLY,
+            FeaturesSetReply(_) => ETHTOOL_MSG_FEATURES_SET_REPLY,
+            FeaturesNtf(_) => ETHTOOL_MSG_FEATURES_NTF,
+            RingsGet(_) => ETHTOOL_MSG_RINGS_GET,
+            RingsSet(_) => ETHTOOL_MSG_RINGS_SET,
+            RingsGetReply(_) => ETHTOOL_MSG_RINGS_GET_REPLY,
+            RingsNtf(_) => ETHTOOL_MSG_RINGS_NTF,
+            ChannelsGet(_) => ETHTOOL_MSG_CHANNELS_GET,
+            ChannelsSet(_) => ETHTOOL_MSG_CHANNELS_SET,
+            ChannelsGetReply(_) => ETHTOOL_MSG_CHANNELS_GET_REPLY,
+            ChannelsNtf(_) => ETHTOOL_MSG_CHANNELS_NTF,
+            CoalesceGet(_) => ETHTOOL_MSG_COALESCE_GET,
+            CoalesceSet(_) => ETHTOOL_MSG_COALESCE_SET,
+            CoalesceGetReply(_) => ETHTOOL_MSG_COALESCE_GET_REPLY,
+            CoalesceNtf(_) => ETHTOOL_MSG_COALESCE_NTF,
+            PauseGet(_) => ETHTOOL_MSG_PAUSE_GET,
+            PauseSet(_) => ETHTOOL_MSG_PAUSE_SET,
+            PauseGetReply(_) => ETHTOOL_MSG_PAUSE_GET_REPLY,
+            PauseNtf(_) => ETHTOOL_MSG_PAUSE_NTF,
+        }
+    }
+}
+
+impl Emitable for EthtoolMessage {
+    #[rustfmt::skip]
+    fn buffer_len(&self) -> usize {
+        use self::EthtoolMessage::*;
+        let nlas_len = match *self {
+            LinkInfoGet(ref nlas)
+                | LinkInfoSet(ref nlas)
+                | LinkInfoGetReply(ref nlas)
+                | LinkInfoNtf(ref nlas) => nlas.as_slice().buffer_len(),
+            LinkModesGet(ref nlas)
+                | LinkModesSet(ref nlas)
+                | LinkModesGetReply(ref nlas)
+                | LinkModesNtf(ref nlas) => nlas.as_slice().buffer_len(),
+            LinkStateGet(ref nlas)
+                | LinkStateGetReply(ref nlas) => nlas.as_slice().buffer_len(),
+            FeaturesGet(ref nlas)
+                | FeaturesSet(ref nlas)
+                | FeaturesGetReply(ref nlas)
+                | FeaturesSetReply(ref nlas)
+                | FeaturesNtf(ref nlas) => nlas.as_slice().buffer_len(),
+            RingsGet(ref nlas)
+                | RingsSet(ref nlas)
+                | RingsGetReply(ref nlas)
+                | RingsNtf(ref nlas) => nlas.as_slice().buffer_len(),
+            ChannelsGet(ref nlas)
+                | ChannelsSet(ref nlas)
+                | ChannelsGetReply(ref nlas)
+                | ChannelsNtf(ref nlas) => nlas.as_slice().buffer_len(),
+            CoalesceGet(ref nlas)
+                | CoalesceSet(ref nlas)
+                | CoalesceGetReply(ref nlas)
+                | CoalesceNtf(ref nlas) => nlas.as_slice().buffer_len(),
+            PauseGet(ref nlas)
+                | PauseSet(ref nlas)
+                | PauseGetReply(ref nlas)
+                | PauseNtf(ref nlas) => nlas.as_slice().buffer_len(),
+        };
+        GenlHeader::new(self.cmd(), ETHTOOL_GENL_VERSION).buffer_len() + nlas_len
+    }
+
+    #[rustfmt::skip]
+    fn emit(&self, buffer: &mut [u8]) {
+        use self::EthtoolMessage::*;
+        let header = GenlHeader::new(self.cmd(), ETHTOOL_GENL_VERSION);
+        header.emit(buffer);
+        let buffer = &mut buffer[header.buffer_len()..];
+        match *self {
+            LinkInfoGet(ref nlas)
+                | LinkInfoSet(ref nlas)
+                | LinkInfoGetReply(ref nlas)
+                | LinkInfoNtf(ref nlas) => nlas.as_slice().emit(buffer),
+            LinkModesGet(ref nlas)
+                | LinkModesSet(ref nlas)
+                | LinkModesGetReply(ref nlas)
+                | LinkModesNtf(ref nlas) => nlas.as_slice().emit(buffer),
+            LinkStateGet(ref nlas)
+                | LinkStateGetReply(ref nlas) => nlas.as_slice().emit(buffer),
+            FeaturesGet(ref nlas)
+                | FeaturesSet(ref nlas)
+                | FeaturesGetReply(ref nlas)
+                | FeaturesSetReply(ref nlas)
+                | FeaturesNtf(ref nlas) => nlas.as_slice().emit(buffer),
+            RingsGet(ref nlas)
+                | RingsSet(ref nlas)
+                | RingsGetReply(ref nlas)
+                | RingsNtf(ref nlas) => nlas.as_slice().emit(buffer),
+            ChannelsGet(ref nlas)
+                | ChannelsSet(ref nlas)
+                | ChannelsGetReply(ref nlas)
+                | ChannelsNtf(ref nlas) => nlas.as_slice().emit(buffer),
+            CoalesceGet(ref nlas)
+                | CoalesceSet(ref nlas)
+                | CoalesceGetReply(ref nlas)
+                | CoalesceNtf(ref nlas) => nlas.as_slice().emit(buffer),
+            PauseGet(ref nlas)
+                | PauseSet(ref nlas)
+                | PauseGetReply(ref nlas)
+                | PauseNtf(ref nlas) => nlas.as_slice().emit(buffer),
+        }
+    }
+}
+
+fn parse_nlas<T, N>(buffer: &GenlBuffer<&T>) -> Result<Vec<N>, DecodeError>
+where
+    T: AsRef<[u8]> + ?Sized,
+    for<'a> NlaBuffer<&'a [u8]>: Parseable<N>,
+{
+    let mut nlas = vec![];
+    for nla_buf in buffer.nlas() {
+        nlas.push(nla_buf?.parse().context("invalid ethtool NLA")?);
+    }
+    Ok(nlas)
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolMessage> for GenlBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolMessage, DecodeError> {
+        use self::EthtoolMessage::*;
+        Ok(match self.cmd() {
+            ETHTOOL_MSG_LINKINFO_GET_REPLY => LinkInfoGetReply(parse_nlas(self)?),
+            ETHTOOL_MSG_LINKINFO_NTF => LinkInfoNtf(parse_nlas(self)?),
+            ETHTOOL_MSG_LINKMODES_GET_REPLY => LinkModesGetReply(parse_nlas(self)?),
+            ETHTOOL_MSG_LINKMODES_NTF => LinkModesNtf(parse_nlas(self)?),
+            ETHTOOL_MSG_LINKSTATE_GET_REPLY => LinkStateGetReply(parse_nlas(self)?),
+            ETHTOOL_MSG_FEATURES_GET_REPLY => FeaturesGetReply(parse_nlas(self)?),
+            ETHTOOL_MSG_FEATURES_SET_REPLY => FeaturesSetReply(parse_nlas(self)?),
+            ETHTOOL_MSG_FEATURES_NTF => FeaturesNtf(parse_nlas(self)?),
+            ETHTOOL_MSG_RINGS_GET_REPLY => RingsGetReply(parse_nlas(self)?),
+            ETHTOOL_MSG_RINGS_NTF => RingsNtf(parse_nlas(self)?),
+            ETHTOOL_MSG_CHANNELS_GET_REPLY => ChannelsGetReply(parse_nlas(self)?),
+            ETHTOOL_MSG_CHANNELS_NTF => ChannelsNtf(parse_nlas(self)?),
+            ETHTOOL_MSG_COALESCE_GET_REPLY => CoalesceGetReply(parse_nlas(self)?),
+            ETHTOOL_MSG_COALESCE_NTF => CoalesceNtf(parse_nlas(self)?),
+            ETHTOOL_MSG_PAUSE_GET_REPLY => PauseGetReply(parse_nlas(self)?),
+            ETHTOOL_MSG_PAUSE_NTF => PauseNtf(parse_nlas(self)?),
+            cmd => return Err(format!("unknown ethtool command {}", cmd).into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::constants::*;
+    use crate::{
+        Emitable, EthtoolBitSet, EthtoolBitSetBit, EthtoolDuplex, EthtoolFeatures, EthtoolHeader,
+        EthtoolLinkModes, EthtoolMessage, GenlBuffer, Parseable,
+    };
+
+    #[rustfmt::skip]
+    static LINKMODES_GET_REPLY: [u8; 52] = [
+        0x04, // cmd: ETHTOOL_MSG_LINKMODES_GET_REPLY
+        0x01, // version
+        0x00, 0x00, // reserved
+
+        // ETHTOOL_A_LINKMODES_HEADER (nested)
+        0x18, 0x00, 0x01, 0x80,
+            // ETHTOOL_A_HEADER_DEV_INDEX
+            0x08, 0x00, 0x01, 0x00,
+            0x02, 0x00, 0x00, 0x00,
+            // ETHTOOL_A_HEADER_DEV_NAME
+            0x09, 0x00, 0x02, 0x00,
+            0x65, 0x74, 0x68, 0x30, 0x00, // "eth0\0"
+            0x00, 0x00, 0x00, // padding
+
+        // ETHTOOL_A_LINKMODES_AUTONEG
+        0x05, 0x00, 0x02, 0x00,
+        0x01,
+        0x00, 0x00, 0x00, // padding
+
+        // ETHTOOL_A_LINKMODES_SPEED
+        0x08, 0x00, 0x05, 0x00,
+        0xe8, 0x03, 0x00, 0x00, // 1000
+
+        // ETHTOOL_A_LINKMODES_DUPLEX
+        0x05, 0x00, 0x06, 0x00,
+        0x01,
+        0x00, 0x00, 0x00, // padding
+    ];
+
+    #[rustfmt::skip]
+    static FEATURES_SET: [u8; 48] = [
+        0x0c, // cmd: ETHTOOL_MSG_FEATURES_SET
+        0x01, // version
+        0x00, 0x00, // reserved
+
+        // ETHTOOL_A_FEATURES_HEADER (nested)
+        0x0c, 0x00, 0x01, 0x80,
+            // ETHTOOL_A_HEADER_DEV_INDEX
+            0x08, 0x00, 0x01, 0x00,
+            0x02, 0x00, 0x00, 0x00,
+
+        // ETHTOOL_A_FEATURES_WANTED (nested)
+        0x20, 0x00, 0x03, 0x80,
+            // ETHTOOL_A_BITSET_BITS (nested)
+            0x1c, 0x00, 0x03, 0x80,
+                // ETHTOOL_A_BITSET_BITS_BIT (nested)
+                0x18, 0x00, 0x01, 0x80,
+                    // ETHTOOL_A_BITSET_BIT_NAME
+                    0x10, 0x00, 0x02, 0x00,
+                    // "rx-checksum\0"
+                    0x72, 0x78, 0x2d, 0x63, 0x68, 0x65, 0x63, 0x6b,
+                    0x73, 0x75, 0x6d, 0x00,
+                    // ETHTOOL_A_BITSET_BIT_VALUE
+                    0x04, 0x00, 0x03, 0x00,
+    ];
+
+    #[test]
+    fn parse_linkmodes_reply() {
+        let buffer = GenlBuffer::new_checked(&LINKMODES_GET_REPLY[..]).unwrap();
+        let msg: EthtoolMessage = buffer.parse().unwrap();
+        let expected = EthtoolMessage::LinkModesGetReply(vec![
+            EthtoolLinkModes::Header(vec![
+                EthtoolHeader::DevIndex(2),
+                EthtoolHeader::DevName("eth0".into()),
+            ]),
+            EthtoolLinkModes::Autoneg(true),
+            EthtoolLinkModes::Speed(1000),
+            EthtoolLinkModes::Duplex(EthtoolDuplex::Full),
+        ]);
+        assert_eq!(msg, expected);
+    }
+
+    #[test]
+    fn emit_features_set() {
+        let msg = EthtoolMessage::FeaturesSet(vec![
+            EthtoolFeatures::Header(vec![EthtoolHeader::DevIndex(2)]),
+            EthtoolFeatures::Wanted(vec![EthtoolBitSet::Bits(vec![EthtoolBitSetBit::new(
+                "rx-checksum",
+                true,
+            )])]),
+        ]);
+        assert_eq!(msg.cmd(), ETHTOOL_MSG_FEATURES_SET);
+        assert_eq!(msg.buffer_len(), FEATURES_SET.len());
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &FEATURES_SET[..]);
+    }
+}
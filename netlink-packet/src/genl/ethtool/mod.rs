@@ -0,0 +1,32 @@
+mod header;
+pub use self::header::*;
+
+mod bitset;
+pub use self::bitset::*;
+
+mod link_info;
+pub use self::link_info::*;
+
+mod link_modes;
+pub use self::link_modes::*;
+
+mod link_state;
+pub use self::link_state::*;
+
+mod features;
+pub use self::features::*;
+
+mod rings;
+pub use self::rings::*;
+
+mod channels;
+pub use self::channels::*;
+
+mod coalesce;
+pub use self::coalesce::*;
+
+mod pause;
+pub use self::pause::*;
+
+mod message;
+pub use self::message::*;
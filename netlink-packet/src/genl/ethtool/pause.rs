@@ -0,0 +1,154 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_u64, parse_u8};
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, EthtoolHeader, Nla, NlaBuffer, Parseable,
+};
+
+/// Attributes of the `ETHTOOL_MSG_PAUSE_*` messages
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolPause {
+    Header(Vec<EthtoolHeader>),
+    Autoneg(bool),
+    Rx(bool),
+    Tx(bool),
+    /// Pause frames statistics, only present if `ETHTOOL_FLAG_STATS` was requested
+    Stats(Vec<EthtoolPauseStat>),
+    Other(DefaultNla),
+}
+
+/// Attributes of the `ETHTOOL_A_PAUSE_STATS` nest
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolPauseStat {
+    Pad(Vec<u8>),
+    TxFrames(u64),
+    RxFrames(u64),
+    Other(DefaultNla),
+}
+
+impl Nla for EthtoolPauseStat {
+    fn value_len(&self) -> usize {
+        use self::EthtoolPauseStat::*;
+        match *self {
+            Pad(ref bytes) => bytes.len(),
+            TxFrames(_) | RxFrames(_) => size_of::<u64>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolPauseStat::*;
+        match *self {
+            Pad(_) => ETHTOOL_A_PAUSE_STAT_PAD,
+            TxFrames(_) => ETHTOOL_A_PAUSE_STAT_TX_FRAMES,
+            RxFrames(_) => ETHTOOL_A_PAUSE_STAT_RX_FRAMES,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolPauseStat::*;
+        match *self {
+            Pad(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            TxFrames(value) | RxFrames(value) => NativeEndian::write_u64(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolPauseStat> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolPauseStat, DecodeError> {
+        use self::EthtoolPauseStat::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ETHTOOL_A_PAUSE_STAT_PAD => Pad(payload.to_vec()),
+            ETHTOOL_A_PAUSE_STAT_TX_FRAMES => TxFrames(
+                parse_u64(payload).context("invalid ETHTOOL_A_PAUSE_STAT_TX_FRAMES value")?,
+            ),
+            ETHTOOL_A_PAUSE_STAT_RX_FRAMES => RxFrames(
+                parse_u64(payload).context("invalid ETHTOOL_A_PAUSE_STAT_RX_FRAMES value")?,
+            ),
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
+
+impl Nla for EthtoolPause {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::EthtoolPause::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().buffer_len(),
+            Autoneg(_)
+                | Rx(_)
+                | Tx(_) => 1,
+            Stats(ref nlas) => nlas.as_slice().buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolPause::*;
+        match *self {
+            Header(_) => ETHTOOL_A_PAUSE_HEADER,
+            Autoneg(_) => ETHTOOL_A_PAUSE_AUTONEG,
+            Rx(_) => ETHTOOL_A_PAUSE_RX,
+            Tx(_) => ETHTOOL_A_PAUSE_TX,
+            Stats(_) => ETHTOOL_A_PAUSE_STATS,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolPause::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().emit(buffer),
+            Autoneg(value)
+                | Rx(value)
+                | Tx(value) => buffer[0] = value as u8,
+            Stats(ref nlas) => nlas.as_slice().emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        use self::EthtoolPause::*;
+        matches!(*self, Header(_) | Stats(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolPause> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolPause, DecodeError> {
+        use self::EthtoolPause::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ETHTOOL_A_PAUSE_HEADER => {
+                Header(parse_nested(payload).context("invalid ETHTOOL_A_PAUSE_HEADER value")?)
+            }
+            ETHTOOL_A_PAUSE_AUTONEG => {
+                Autoneg(parse_u8(payload).context("invalid ETHTOOL_A_PAUSE_AUTONEG value")? != 0)
+            }
+            ETHTOOL_A_PAUSE_RX => {
+                Rx(parse_u8(payload).context("invalid ETHTOOL_A_PAUSE_RX value")? != 0)
+            }
+            ETHTOOL_A_PAUSE_TX => {
+                Tx(parse_u8(payload).context("invalid ETHTOOL_A_PAUSE_TX value")? != 0)
+            }
+            ETHTOOL_A_PAUSE_STATS => {
+                Stats(parse_nested(payload).context("invalid ETHTOOL_A_PAUSE_STATS value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
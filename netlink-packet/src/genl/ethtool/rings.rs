@@ -0,0 +1,129 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::parse_u32;
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, EthtoolHeader, Nla, NlaBuffer, Parseable,
+};
+
+/// Attributes of the `ETHTOOL_MSG_RINGS_*` messages
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EthtoolRings {
+    Header(Vec<EthtoolHeader>),
+    /// Maximum size of the RX ring
+    RxMax(u32),
+    /// Maximum size of the RX mini ring
+    RxMiniMax(u32),
+    /// Maximum size of the RX jumbo ring
+    RxJumboMax(u32),
+    /// Maximum size of the TX ring
+    TxMax(u32),
+    /// Size of the RX ring
+    Rx(u32),
+    /// Size of the RX mini ring
+    RxMini(u32),
+    /// Size of the RX jumbo ring
+    RxJumbo(u32),
+    /// Size of the TX ring
+    Tx(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for EthtoolRings {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::EthtoolRings::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().buffer_len(),
+            RxMax(_)
+                | RxMiniMax(_)
+                | RxJumboMax(_)
+                | TxMax(_)
+                | Rx(_)
+                | RxMini(_)
+                | RxJumbo(_)
+                | Tx(_) => size_of::<u32>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::EthtoolRings::*;
+        match *self {
+            Header(_) => ETHTOOL_A_RINGS_HEADER,
+            RxMax(_) => ETHTOOL_A_RINGS_RX_MAX,
+            RxMiniMax(_) => ETHTOOL_A_RINGS_RX_MINI_MAX,
+            RxJumboMax(_) => ETHTOOL_A_RINGS_RX_JUMBO_MAX,
+            TxMax(_) => ETHTOOL_A_RINGS_TX_MAX,
+            Rx(_) => ETHTOOL_A_RINGS_RX,
+            RxMini(_) => ETHTOOL_A_RINGS_RX_MINI,
+            RxJumbo(_) => ETHTOOL_A_RINGS_RX_JUMBO,
+            Tx(_) => ETHTOOL_A_RINGS_TX,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::EthtoolRings::*;
+        match *self {
+            Header(ref nlas) => nlas.as_slice().emit(buffer),
+            RxMax(value)
+                | RxMiniMax(value)
+                | RxJumboMax(value)
+                | TxMax(value)
+                | Rx(value)
+                | RxMini(value)
+                | RxJumbo(value)
+                | Tx(value) => NativeEndian::write_u32(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(*self, EthtoolRings::Header(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<EthtoolRings> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<EthtoolRings, DecodeError> {
+        use self::EthtoolRings::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ETHTOOL_A_RINGS_HEADER => {
+                Header(parse_nested(payload).context("invalid ETHTOOL_A_RINGS_HEADER value")?)
+            }
+            ETHTOOL_A_RINGS_RX_MAX => {
+                RxMax(parse_u32(payload).context("invalid ETHTOOL_A_RINGS_RX_MAX value")?)
+            }
+            ETHTOOL_A_RINGS_RX_MINI_MAX => {
+                RxMiniMax(parse_u32(payload).context("invalid ETHTOOL_A_RINGS_RX_MINI_MAX value")?)
+            }
+            ETHTOOL_A_RINGS_RX_JUMBO_MAX => RxJumboMax(
+                parse_u32(payload).context("invalid ETHTOOL_A_RINGS_RX_JUMBO_MAX value")?,
+            ),
+            ETHTOOL_A_RINGS_TX_MAX => {
+                TxMax(parse_u32(payload).context("invalid ETHTOOL_A_RINGS_TX_MAX value")?)
+            }
+            ETHTOOL_A_RINGS_RX => {
+                Rx(parse_u32(payload).context("invalid ETHTOOL_A_RINGS_RX value")?)
+            }
+            ETHTOOL_A_RINGS_RX_MINI => {
+                RxMini(parse_u32(payload).context("invalid ETHTOOL_A_RINGS_RX_MINI value")?)
+            }
+            ETHTOOL_A_RINGS_RX_JUMBO => {
+                RxJumbo(parse_u32(payload).context("invalid ETHTOOL_A_RINGS_RX_JUMBO value")?)
+            }
+            ETHTOOL_A_RINGS_TX => {
+                Tx(parse_u32(payload).context("invalid ETHTOOL_A_RINGS_TX value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{DecodeError, Emitable, GenlBuffer, Parseable, GENL_HEADER_LEN};
+
+/// Header shared by all the generic netlink messages (`struct genlmsghdr`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct GenlHeader {
+    pub cmd: u8,
+    pub version: u8,
+}
+
+impl GenlHeader {
+    pub fn new(cmd: u8, version: u8) -> Self {
+        GenlHeader { cmd, version }
+    }
+}
+
+impl Emitable for GenlHeader {
+    fn buffer_len(&self) -> usize {
+        GENL_HEADER_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut packet = GenlBuffer::new(buffer);
+        packet.set_cmd(self.cmd);
+        packet.set_version(self.version);
+        packet.set_reserved(0);
+    }
+}
+
+impl<T: AsRef<[u8]>> Parseable<GenlHeader> for GenlBuffer<T> {
+    fn parse(&self) -> Result<GenlHeader, DecodeError> {
+        Ok(GenlHeader {
+            cmd: self.cmd(),
+            version: self.version(),
+        })
+    }
+}
@@ -0,0 +1,168 @@
+use std::mem::size_of;
+
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_u16, parse_u16_be, parse_u32, parse_u8};
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, IpvsStats, IpvsStats64, Nla, NlaBuffer,
+    Parseable,
+};
+
+/// Attributes of a destination, also called real server (`IPVS_DEST_ATTR_*`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IpvsDest {
+    /// Address of the real server. The kernel always reports 16 bytes, even for IPv4 destinations.
+    Addr(Vec<u8>),
+    Port(u16),
+    /// Forwarding method (`IP_VS_CONN_F_MASQ`, `IP_VS_CONN_F_TUNNEL`, `IP_VS_CONN_F_DROUTE`, etc.)
+    FwdMethod(u32),
+    Weight(u32),
+    /// Upper threshold of connections
+    UThresh(u32),
+    /// Lower threshold of connections
+    LThresh(u32),
+    ActiveConns(u32),
+    InactConns(u32),
+    PersistConns(u32),
+    Stats(Vec<IpvsStats>),
+    /// Address family of the real server, if it differs from the service one
+    AddrFamily(u16),
+    Stats64(Vec<IpvsStats64>),
+    TunType(u8),
+    TunPort(u16),
+    TunFlags(u16),
+    Other(DefaultNla),
+}
+
+impl Nla for IpvsDest {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::IpvsDest::*;
+        match *self {
+            Addr(ref bytes) => bytes.len(),
+            Port(_)
+                | AddrFamily(_)
+                | TunPort(_)
+                | TunFlags(_) => size_of::<u16>(),
+            FwdMethod(_)
+                | Weight(_)
+                | UThresh(_)
+                | LThresh(_)
+                | ActiveConns(_)
+                | InactConns(_)
+                | PersistConns(_) => size_of::<u32>(),
+            Stats(ref nlas) => nlas.as_slice().buffer_len(),
+            Stats64(ref nlas) => nlas.as_slice().buffer_len(),
+            TunType(_) => 1,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::IpvsDest::*;
+        match *self {
+            Addr(_) => IPVS_DEST_ATTR_ADDR,
+            Port(_) => IPVS_DEST_ATTR_PORT,
+            FwdMethod(_) => IPVS_DEST_ATTR_FWD_METHOD,
+            Weight(_) => IPVS_DEST_ATTR_WEIGHT,
+            UThresh(_) => IPVS_DEST_ATTR_U_THRESH,
+            LThresh(_) => IPVS_DEST_ATTR_L_THRESH,
+            ActiveConns(_) => IPVS_DEST_ATTR_ACTIVE_CONNS,
+            InactConns(_) => IPVS_DEST_ATTR_INACT_CONNS,
+            PersistConns(_) => IPVS_DEST_ATTR_PERSIST_CONNS,
+            Stats(_) => IPVS_DEST_ATTR_STATS,
+            AddrFamily(_) => IPVS_DEST_ATTR_ADDR_FAMILY,
+            Stats64(_) => IPVS_DEST_ATTR_STATS64,
+            TunType(_) => IPVS_DEST_ATTR_TUN_TYPE,
+            TunPort(_) => IPVS_DEST_ATTR_TUN_PORT,
+            TunFlags(_) => IPVS_DEST_ATTR_TUN_FLAGS,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::IpvsDest::*;
+        match *self {
+            Addr(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Port(value)
+                | TunPort(value) => BigEndian::write_u16(buffer, value),
+            FwdMethod(value)
+                | Weight(value)
+                | UThresh(value)
+                | LThresh(value)
+                | ActiveConns(value)
+                | InactConns(value)
+                | PersistConns(value) => NativeEndian::write_u32(buffer, value),
+            Stats(ref nlas) => nlas.as_slice().emit(buffer),
+            AddrFamily(value)
+                | TunFlags(value) => NativeEndian::write_u16(buffer, value),
+            Stats64(ref nlas) => nlas.as_slice().emit(buffer),
+            TunType(value) => buffer[0] = value,
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        use self::IpvsDest::*;
+        matches!(*self, Stats(_) | Stats64(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<IpvsDest> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<IpvsDest, DecodeError> {
+        use self::IpvsDest::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            IPVS_DEST_ATTR_ADDR => Addr(payload.to_vec()),
+            IPVS_DEST_ATTR_PORT => {
+                Port(parse_u16_be(payload).context("invalid IPVS_DEST_ATTR_PORT value")?)
+            }
+            IPVS_DEST_ATTR_FWD_METHOD => {
+                FwdMethod(parse_u32(payload).context("invalid IPVS_DEST_ATTR_FWD_METHOD value")?)
+            }
+            IPVS_DEST_ATTR_WEIGHT => {
+                Weight(parse_u32(payload).context("invalid IPVS_DEST_ATTR_WEIGHT value")?)
+            }
+            IPVS_DEST_ATTR_U_THRESH => {
+                UThresh(parse_u32(payload).context("invalid IPVS_DEST_ATTR_U_THRESH value")?)
+            }
+            IPVS_DEST_ATTR_L_THRESH => {
+                LThresh(parse_u32(payload).context("invalid IPVS_DEST_ATTR_L_THRESH value")?)
+            }
+            IPVS_DEST_ATTR_ACTIVE_CONNS => ActiveConns(
+                parse_u32(payload).context("invalid IPVS_DEST_ATTR_ACTIVE_CONNS value")?,
+            ),
+            IPVS_DEST_ATTR_INACT_CONNS => {
+                InactConns(parse_u32(payload).context("invalid IPVS_DEST_ATTR_INACT_CONNS value")?)
+            }
+            IPVS_DEST_ATTR_PERSIST_CONNS => PersistConns(
+                parse_u32(payload).context("invalid IPVS_DEST_ATTR_PERSIST_CONNS value")?,
+            ),
+            IPVS_DEST_ATTR_STATS => {
+                Stats(parse_nested(payload).context("invalid IPVS_DEST_ATTR_STATS value")?)
+            }
+            IPVS_DEST_ATTR_ADDR_FAMILY => {
+                AddrFamily(parse_u16(payload).context("invalid IPVS_DEST_ATTR_ADDR_FAMILY value")?)
+            }
+            IPVS_DEST_ATTR_STATS64 => {
+                Stats64(parse_nested(payload).context("invalid IPVS_DEST_ATTR_STATS64 value")?)
+            }
+            IPVS_DEST_ATTR_TUN_TYPE => {
+                TunType(parse_u8(payload).context("invalid IPVS_DEST_ATTR_TUN_TYPE value")?)
+            }
+            IPVS_DEST_ATTR_TUN_PORT => {
+                TunPort(parse_u16_be(payload).context("invalid IPVS_DEST_ATTR_TUN_PORT value")?)
+            }
+            IPVS_DEST_ATTR_TUN_FLAGS => {
+                TunFlags(parse_u16(payload).context("invalid IPVS_DEST_ATTR_TUN_FLAGS value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{DecodeError, Emitable};
+
+/// Flags of a virtual service (`struct ip_vs_flags`). Only the flags set in `mask` are changed
+/// when the service is edited.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct IpvsServiceFlags {
+    /// `IP_VS_SVC_F_*` flags
+    pub flags: u32,
+    pub mask: u32,
+}
+
+impl IpvsServiceFlags {
+    pub fn new(flags: u32, mask: u32) -> Self {
+        IpvsServiceFlags { flags, mask }
+    }
+
+    pub(crate) fn from_bytes(payload: &[u8]) -> Result<Self, DecodeError> {
+        if payload.len() != 8 {
+            return Err(format!("invalid ip_vs_flags: {:?}", payload).into());
+        }
+        Ok(IpvsServiceFlags {
+            flags: NativeEndian::read_u32(&payload[..4]),
+            mask: NativeEndian::read_u32(&payload[4..]),
+        })
+    }
+}
+
+impl Emitable for IpvsServiceFlags {
+    fn buffer_len(&self) -> usize {
+        8
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        NativeEndian::write_u32(&mut buffer[..4], self.flags);
+        NativeEndian::write_u32(&mut buffer[4..8], self.mask);
+    }
+}
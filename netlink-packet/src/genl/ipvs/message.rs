@@ -0,0 +1,295 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::{DecodeError, Emitable, GenlBuffer, GenlHeader, IpvsNla, Parseable};
+
+/// Messages of the `IPVS` generic netlink family.
+///
+/// The kernel replies to `IPVS_CMD_GET_SERVICE` with `IPVS_CMD_NEW_SERVICE` messages, to
+/// `IPVS_CMD_GET_DEST` with `IPVS_CMD_NEW_DEST` messages, and to `IPVS_CMD_GET_CONFIG` with a
+/// `IPVS_CMD_SET_CONFIG` message.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IpvsMessage {
+    NewService(Vec<IpvsNla>),
+    SetService(Vec<IpvsNla>),
+    DelService(Vec<IpvsNla>),
+    GetService(Vec<IpvsNla>),
+    NewDest(Vec<IpvsNla>),
+    SetDest(Vec<IpvsNla>),
+    DelDest(Vec<IpvsNla>),
+    GetDest(Vec<IpvsNla>),
+    SetConfig(Vec<IpvsNla>),
+    GetConfig(Vec<IpvsNla>),
+    Zero(Vec<IpvsNla>),
+    Flush(Vec<IpvsNla>),
+}
+
+impl IpvsMessage {
+    pub fn cmd(&self) -> u8 {
+        use self::IpvsMessage::*;
+        match *self {
+            NewService(_) => IPVS_CMD_NEW_SERVICE,
+            SetService(_) => IPVS_CMD_SET_SERVICE,
+            DelService(_) => IPVS_CMD_DEL_SERVICE,
+            GetService(_) => IPVS_CMD_GET_SERVICE,
+            NewDest(_) => IPVS_CMD_NEW_DEST,
+            SetDest(_) => IPVS_CMD_SET_DEST,
+            DelDest(_) => IPVS_CMD_DEL_DEST,
+            GetDest(_) => IPVS_CMD_GET_DEST,
+            SetConfig(_) => IPVS_CMD_SET_CONFIG,
+            GetConfig(_) => IPVS_CMD_GET_CONFIG,
+            Zero(_) => IPVS_CMD_ZERO,
+            Flush(_) => IPVS_CMD_FLUSH,
+        }
+    }
+
+    pub fn nlas(&self) -> &[IpvsNla] {
+        use self::IpvsMessage::*;
+        match *self {
+            NewService(ref nlas) | SetService(ref nlas) | DelService(ref nlas)
+            | GetService(ref nlas) | NewDest(ref nlas) | SetDest(ref nlas) | DelDest(ref nlas)
+            | GetDest(ref nlas) | SetConfig(ref nlas) | GetConfig(ref nlas) | Zero(ref nlas)
+            | Flush(ref nlas) => nlas.as_slice(),
+        }
+    }
+
+    pub fn into_nlas(self) -> Vec<IpvsNla> {
+        use self::IpvsMessage::*;
+        match self {
+            NewService(nlas) | SetService(nlas) | DelService(nlas) | GetService(nlas)
+            | NewDest(nlas) | SetDest(nlas) | DelDest(nlas) | GetDest(nlas) | SetConfig(nlas)
+            | GetConfig(nlas) | Zero(nlas) | Flush(nlas) => nlas,
+        }
+    }
+}
+
+impl Emitable for IpvsMessage {
+    fn buffer_len(&self) -> usize {
+        GenlHeader::new(self.cmd(), IPVS_GENL_VERSION).buffer_len() + self.nlas().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let header = GenlHeader::new(self.cmd(), IPVS_GENL_VERSION);
+        header.emit(buffer);
+        self.nlas().emit(&mut buffer[header.buffer_len()..]);
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<IpvsMessage> for GenlBuffer<&T> {
+    fn parse(&self) -> Result<IpvsMessage, DecodeError> {
+        use self::IpvsMessage::*;
+        let mut nlas = vec![];
+        for nla_buf in self.nlas() {
+            nlas.push(nla_buf?.parse().context("invalid IPVS NLA")?);
+        }
+        Ok(match self.cmd() {
+            IPVS_CMD_NEW_SERVICE => NewService(nlas),
+            IPVS_CMD_SET_SERVICE => SetService(nlas),
+            IPVS_CMD_DEL_SERVICE => DelService(nlas),
+            IPVS_CMD_GET_SERVICE => GetService(nlas),
+            IPVS_CMD_NEW_DEST => NewDest(nlas),
+            IPVS_CMD_SET_DEST => SetDest(nlas),
+            IPVS_CMD_DEL_DEST => DelDest(nlas),
+            IPVS_CMD_GET_DEST => GetDest(nlas),
+            IPVS_CMD_SET_CONFIG => SetConfig(nlas),
+            IPVS_CMD_GET_CONFIG => GetConfig(nlas),
+            IPVS_CMD_ZERO => Zero(nlas),
+            IPVS_CMD_FLUSH => Flush(nlas),
+            cmd => return Err(format!("unknown IPVS command {}", cmd).into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::constants::*;
+    use crate::{
+        assert_nested, Emitable, GenlBuffer, IpvsDest, IpvsMessage, IpvsNla, IpvsService,
+        IpvsServiceFlags, IpvsStats, IpvsStats64, Parseable,
+    };
+
+    // The kernel does not set NLA_F_NESTED for IPVS attributes
+    #[rustfmt::skip]
+    static NEW_SERVICE: [u8; 112] = [
+        0x01, // cmd: IPVS_CMD_NEW_SERVICE
+        0x01, // version
+        0x00, 0x00, // reserved
+
+        // IPVS_CMD_ATTR_SERVICE
+        0x6c, 0x00, 0x01, 0x00,
+            // IPVS_SVC_ATTR_AF
+            0x06, 0x00, 0x01, 0x00,
+            0x02, 0x00, // AF_INET
+            0x00, 0x00, // padding
+            // IPVS_SVC_ATTR_PROTOCOL
+            0x06, 0x00, 0x02, 0x00,
+            0x06, 0x00, // IPPROTO_TCP
+            0x00, 0x00, // padding
+            // IPVS_SVC_ATTR_ADDR
+            0x14, 0x00, 0x03, 0x00,
+            0xc0, 0xa8, 0x01, 0x01, // 192.168.1.1
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            // IPVS_SVC_ATTR_PORT
+            0x06, 0x00, 0x04, 0x00,
+            0x00, 0x50, // 80, in network byte order
+            0x00, 0x00, // padding
+            // IPVS_SVC_ATTR_SCHED_NAME
+            0x07, 0x00, 0x06, 0x00,
+            0x72, 0x72, 0x00, // "rr\0"
+            0x00, // padding
+            // IPVS_SVC_ATTR_FLAGS
+            0x0c, 0x00, 0x07, 0x00,
+            0x02, 0x00, 0x00, 0x00, // IP_VS_SVC_F_HASHED
+            0xff, 0xff, 0xff, 0xff, // mask
+            // IPVS_SVC_ATTR_TIMEOUT
+            0x08, 0x00, 0x08, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            // IPVS_SVC_ATTR_NETMASK
+            0x08, 0x00, 0x09, 0x00,
+            0xff, 0xff, 0xff, 0xff,
+            // IPVS_SVC_ATTR_STATS
+            0x18, 0x00, 0x0a, 0x00,
+                // IPVS_STATS_ATTR_CONNS
+                0x08, 0x00, 0x01, 0x00,
+                0x03, 0x00, 0x00, 0x00,
+                // IPVS_STATS_ATTR_INBYTES
+                0x0c, 0x00, 0x04, 0x00,
+                0xdc, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // 1500
+    ];
+
+    #[rustfmt::skip]
+    static NEW_DEST: [u8; 76] = [
+        0x05, // cmd: IPVS_CMD_NEW_DEST
+        0x01, // version
+        0x00, 0x00, // reserved
+
+        // IPVS_CMD_ATTR_SERVICE (nested)
+        0x24, 0x00, 0x01, 0x80,
+            // IPVS_SVC_ATTR_AF
+            0x06, 0x00, 0x01, 0x00,
+            0x02, 0x00, // AF_INET
+            0x00, 0x00, // padding
+            // IPVS_SVC_ATTR_PROTOCOL
+            0x06, 0x00, 0x02, 0x00,
+            0x06, 0x00, // IPPROTO_TCP
+            0x00, 0x00, // padding
+            // IPVS_SVC_ATTR_ADDR
+            0x08, 0x00, 0x03, 0x00,
+            0xc0, 0xa8, 0x01, 0x01, // 192.168.1.1
+            // IPVS_SVC_ATTR_PORT
+            0x06, 0x00, 0x04, 0x00,
+            0x00, 0x50, // 80, in network byte order
+            0x00, 0x00, // padding
+
+        // IPVS_CMD_ATTR_DEST (nested)
+        0x24, 0x00, 0x02, 0x80,
+            // IPVS_DEST_ATTR_ADDR
+            0x08, 0x00, 0x01, 0x00,
+            0x0a, 0x00, 0x00, 0x02, // 10.0.0.2
+            // IPVS_DEST_ATTR_PORT
+            0x06, 0x00, 0x02, 0x00,
+            0x1f, 0x90, // 8080, in network byte order
+            0x00, 0x00, // padding
+            // IPVS_DEST_ATTR_FWD_METHOD
+            0x08, 0x00, 0x03, 0x00,
+            0x03, 0x00, 0x00, 0x00, // IP_VS_CONN_F_DROUTE
+            // IPVS_DEST_ATTR_WEIGHT
+            0x08, 0x00, 0x04, 0x00,
+            0x01, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn parse_new_service() {
+        let buffer = GenlBuffer::new_checked(&NEW_SERVICE[..]).unwrap();
+        let msg: IpvsMessage = buffer.parse().unwrap();
+        let mut addr = vec![0; 16];
+        addr[..4].copy_from_slice(&[192, 168, 1, 1]);
+        let expected = IpvsMessage::NewService(vec![IpvsNla::Service(vec![
+            IpvsService::Af(AF_INET),
+            IpvsService::Protocol(IPPROTO_TCP),
+            IpvsService::Addr(addr),
+            IpvsService::Port(80),
+            IpvsService::SchedName("rr".into()),
+            IpvsService::Flags(IpvsServiceFlags::new(IP_VS_SVC_F_HASHED, 0xffff_ffff)),
+            IpvsService::Timeout(0),
+            IpvsService::Netmask(0xffff_ffff),
+            IpvsService::Stats(vec![IpvsStats::Conns(3), IpvsStats::InBytes(1500)]),
+        ])]);
+        assert_eq!(msg, expected);
+    }
+
+    #[test]
+    fn emit_new_dest() {
+        let msg = IpvsMessage::NewDest(vec![
+            IpvsNla::Service(vec![
+                IpvsService::Af(AF_INET),
+                IpvsService::Protocol(IPPROTO_TCP),
+                IpvsService::Addr(vec![192, 168, 1, 1]),
+                IpvsService::Port(80),
+            ]),
+            IpvsNla::Dest(vec![
+                IpvsDest::Addr(vec![10, 0, 0, 2]),
+                IpvsDest::Port(8080),
+                IpvsDest::FwdMethod(IP_VS_CONN_F_DROUTE),
+                IpvsDest::Weight(1),
+            ]),
+        ]);
+        assert_eq!(msg.buffer_len(), NEW_DEST.len());
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &NEW_DEST[..]);
+    }
+
+    fn stats64() -> Vec<IpvsStats64> {
+        // the kernel pads the 64 bits counters so that they are aligned
+        vec![
+            IpvsStats64::Pad,
+            IpvsStats64::Conns(3),
+            IpvsStats64::InPkts(1 << 32),
+            IpvsStats64::OutPkts(42),
+            IpvsStats64::InBytes(1 << 40),
+            IpvsStats64::OutBytes(1500),
+            IpvsStats64::Cps(1),
+            IpvsStats64::InPps(2),
+            IpvsStats64::OutPps(3),
+            IpvsStats64::InBps(4),
+            IpvsStats64::OutBps(5),
+        ]
+    }
+
+    #[test]
+    fn stats_round_trip() {
+        let msg = IpvsMessage::NewDest(vec![
+            IpvsNla::Service(vec![
+                IpvsService::Af(AF_INET),
+                IpvsService::Fwmark(1),
+                IpvsService::Stats64(stats64()),
+            ]),
+            IpvsNla::Dest(vec![
+                IpvsDest::Addr(vec![10, 0, 0, 2]),
+                IpvsDest::Port(8080),
+                IpvsDest::Stats(vec![IpvsStats::Conns(3), IpvsStats::OutBytes(1500)]),
+                IpvsDest::Stats64(stats64()),
+            ]),
+        ]);
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+
+        let buffer = GenlBuffer::new_checked(&buf[..]).unwrap();
+        assert_nested(
+            buffer.payload(),
+            &[IPVS_CMD_ATTR_SERVICE, IPVS_SVC_ATTR_STATS64],
+        );
+        assert_nested(
+            buffer.payload(),
+            &[IPVS_CMD_ATTR_DEST, IPVS_DEST_ATTR_STATS],
+        );
+        assert_nested(
+            buffer.payload(),
+            &[IPVS_CMD_ATTR_DEST, IPVS_DEST_ATTR_STATS64],
+        );
+        assert_eq!(Parseable::<IpvsMessage>::parse(&buffer).unwrap(), msg);
+    }
+}
@@ -0,0 +1,17 @@
+mod flags;
+pub use self::flags::*;
+
+mod stats;
+pub use self::stats::*;
+
+mod service;
+pub use self::service::*;
+
+mod dest;
+pub use self::dest::*;
+
+mod nlas;
+pub use self::nlas::*;
+
+mod message;
+pub use self::message::*;
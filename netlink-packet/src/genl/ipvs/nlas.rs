@@ -0,0 +1,98 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::parse_u32;
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, IpvsDest, IpvsService, Nla, NlaBuffer,
+    Parseable,
+};
+
+/// Attributes of the IPVS messages (`IPVS_CMD_ATTR_*`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IpvsNla {
+    Service(Vec<IpvsService>),
+    Dest(Vec<IpvsDest>),
+    /// TCP session timeout, in seconds
+    TimeoutTcp(u32),
+    /// TCP session timeout after receiving a FIN, in seconds
+    TimeoutTcpFin(u32),
+    /// UDP session timeout, in seconds
+    TimeoutUdp(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for IpvsNla {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::IpvsNla::*;
+        match *self {
+            Service(ref nlas) => nlas.as_slice().buffer_len(),
+            Dest(ref nlas) => nlas.as_slice().buffer_len(),
+            TimeoutTcp(_)
+                | TimeoutTcpFin(_)
+                | TimeoutUdp(_) => size_of::<u32>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::IpvsNla::*;
+        match *self {
+            Service(_) => IPVS_CMD_ATTR_SERVICE,
+            Dest(_) => IPVS_CMD_ATTR_DEST,
+            TimeoutTcp(_) => IPVS_CMD_ATTR_TIMEOUT_TCP,
+            TimeoutTcpFin(_) => IPVS_CMD_ATTR_TIMEOUT_TCP_FIN,
+            TimeoutUdp(_) => IPVS_CMD_ATTR_TIMEOUT_UDP,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::IpvsNla::*;
+        match *self {
+            Service(ref nlas) => nlas.as_slice().emit(buffer),
+            Dest(ref nlas) => nlas.as_slice().emit(buffer),
+            TimeoutTcp(value)
+                | TimeoutTcpFin(value)
+                | TimeoutUdp(value) => NativeEndian::write_u32(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        use self::IpvsNla::*;
+        matches!(*self, Service(_) | Dest(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<IpvsNla> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<IpvsNla, DecodeError> {
+        use self::IpvsNla::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            IPVS_CMD_ATTR_SERVICE => {
+                Service(parse_nested(payload).context("invalid IPVS_CMD_ATTR_SERVICE value")?)
+            }
+            IPVS_CMD_ATTR_DEST => {
+                Dest(parse_nested(payload).context("invalid IPVS_CMD_ATTR_DEST value")?)
+            }
+            IPVS_CMD_ATTR_TIMEOUT_TCP => {
+                TimeoutTcp(parse_u32(payload).context("invalid IPVS_CMD_ATTR_TIMEOUT_TCP value")?)
+            }
+            IPVS_CMD_ATTR_TIMEOUT_TCP_FIN => TimeoutTcpFin(
+                parse_u32(payload).context("invalid IPVS_CMD_ATTR_TIMEOUT_TCP_FIN value")?,
+            ),
+            IPVS_CMD_ATTR_TIMEOUT_UDP => {
+                TimeoutUdp(parse_u32(payload).context("invalid IPVS_CMD_ATTR_TIMEOUT_UDP value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,152 @@
+use std::mem::size_of;
+
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_string, parse_u16, parse_u16_be, parse_u32, parse_u32_be};
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, IpvsServiceFlags, IpvsStats, IpvsStats64, Nla,
+    NlaBuffer, Parseable,
+};
+
+/// Attributes of a virtual service (`IPVS_SVC_ATTR_*`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IpvsService {
+    /// Address family of the service (`AF_INET` or `AF_INET6`)
+    Af(u16),
+    /// `IPPROTO_TCP`, `IPPROTO_UDP` or `IPPROTO_SCTP`
+    Protocol(u16),
+    /// Virtual address. The kernel always reports 16 bytes, even for IPv4 services.
+    Addr(Vec<u8>),
+    Port(u16),
+    /// Firewall mark of the packets handled by the service, for services that are not identified by address
+    Fwmark(u32),
+    /// Name of the scheduler (`rr`, `wlc`, `sh`, etc.)
+    SchedName(String),
+    Flags(IpvsServiceFlags),
+    /// Persistence timeout, in seconds
+    Timeout(u32),
+    /// Persistence netmask
+    Netmask(u32),
+    Stats(Vec<IpvsStats>),
+    /// Name of the persistence engine
+    PeName(String),
+    Stats64(Vec<IpvsStats64>),
+    Other(DefaultNla),
+}
+
+impl Nla for IpvsService {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::IpvsService::*;
+        match *self {
+            Af(_)
+                | Protocol(_)
+                | Port(_) => size_of::<u16>(),
+            Addr(ref bytes) => bytes.len(),
+            Fwmark(_)
+                | Timeout(_)
+                | Netmask(_) => size_of::<u32>(),
+            SchedName(ref s)
+                | PeName(ref s) => s.len() + 1,
+            Flags(ref value) => value.buffer_len(),
+            Stats(ref nlas) => nlas.as_slice().buffer_len(),
+            Stats64(ref nlas) => nlas.as_slice().buffer_len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::IpvsService::*;
+        match *self {
+            Af(_) => IPVS_SVC_ATTR_AF,
+            Protocol(_) => IPVS_SVC_ATTR_PROTOCOL,
+            Addr(_) => IPVS_SVC_ATTR_ADDR,
+            Port(_) => IPVS_SVC_ATTR_PORT,
+            Fwmark(_) => IPVS_SVC_ATTR_FWMARK,
+            SchedName(_) => IPVS_SVC_ATTR_SCHED_NAME,
+            Flags(_) => IPVS_SVC_ATTR_FLAGS,
+            Timeout(_) => IPVS_SVC_ATTR_TIMEOUT,
+            Netmask(_) => IPVS_SVC_ATTR_NETMASK,
+            Stats(_) => IPVS_SVC_ATTR_STATS,
+            PeName(_) => IPVS_SVC_ATTR_PE_NAME,
+            Stats64(_) => IPVS_SVC_ATTR_STATS64,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::IpvsService::*;
+        match *self {
+            Af(value)
+                | Protocol(value) => NativeEndian::write_u16(buffer, value),
+            Addr(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Port(value) => BigEndian::write_u16(buffer, value),
+            Fwmark(value)
+                | Timeout(value) => NativeEndian::write_u32(buffer, value),
+            SchedName(ref s)
+                | PeName(ref s) => {
+                buffer[..s.len()].copy_from_slice(s.as_bytes());
+                buffer[s.len()] = 0;
+            },
+            Flags(ref value) => value.emit(buffer),
+            Netmask(value) => BigEndian::write_u32(buffer, value),
+            Stats(ref nlas) => nlas.as_slice().emit(buffer),
+            Stats64(ref nlas) => nlas.as_slice().emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        use self::IpvsService::*;
+        matches!(*self, Stats(_) | Stats64(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<IpvsService> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<IpvsService, DecodeError> {
+        use self::IpvsService::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            IPVS_SVC_ATTR_AF => Af(parse_u16(payload).context("invalid IPVS_SVC_ATTR_AF value")?),
+            IPVS_SVC_ATTR_PROTOCOL => {
+                Protocol(parse_u16(payload).context("invalid IPVS_SVC_ATTR_PROTOCOL value")?)
+            }
+            IPVS_SVC_ATTR_ADDR => Addr(payload.to_vec()),
+            IPVS_SVC_ATTR_PORT => {
+                Port(parse_u16_be(payload).context("invalid IPVS_SVC_ATTR_PORT value")?)
+            }
+            IPVS_SVC_ATTR_FWMARK => {
+                Fwmark(parse_u32(payload).context("invalid IPVS_SVC_ATTR_FWMARK value")?)
+            }
+            IPVS_SVC_ATTR_SCHED_NAME => {
+                SchedName(parse_string(payload).context("invalid IPVS_SVC_ATTR_SCHED_NAME value")?)
+            }
+            IPVS_SVC_ATTR_FLAGS => Flags(
+                IpvsServiceFlags::from_bytes(payload)
+                    .context("invalid IPVS_SVC_ATTR_FLAGS value")?,
+            ),
+            IPVS_SVC_ATTR_TIMEOUT => {
+                Timeout(parse_u32(payload).context("invalid IPVS_SVC_ATTR_TIMEOUT value")?)
+            }
+            IPVS_SVC_ATTR_NETMASK => {
+                Netmask(parse_u32_be(payload).context("invalid IPVS_SVC_ATTR_NETMASK value")?)
+            }
+            IPVS_SVC_ATTR_STATS => {
+                Stats(parse_nested(payload).context("invalid IPVS_SVC_ATTR_STATS value")?)
+            }
+            IPVS_SVC_ATTR_PE_NAME => {
+                PeName(parse_string(payload).context("invalid IPVS_SVC_ATTR_PE_NAME value")?)
+            }
+            IPVS_SVC_ATTR_STATS64 => {
+                Stats64(parse_nested(payload).context("invalid IPVS_SVC_ATTR_STATS64 value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,261 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_u32, parse_u64};
+use crate::{DecodeError, DefaultNla, Nla, NlaBuffer, Parseable};
+
+/// Statistics of a virtual service or of a destination (`IPVS_STATS_ATTR_*`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IpvsStats {
+    /// Number of connections scheduled
+    Conns(u32),
+    /// Number of incoming packets
+    InPkts(u32),
+    /// Number of outgoing packets
+    OutPkts(u32),
+    /// Number of incoming bytes
+    InBytes(u64),
+    /// Number of outgoing bytes
+    OutBytes(u64),
+    /// Current connection rate
+    Cps(u32),
+    /// Current incoming packet rate
+    InPps(u32),
+    /// Current outgoing packet rate
+    OutPps(u32),
+    /// Current incoming byte rate
+    InBps(u32),
+    /// Current outgoing byte rate
+    OutBps(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for IpvsStats {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::IpvsStats::*;
+        match *self {
+            Conns(_)
+                | InPkts(_)
+                | OutPkts(_)
+                | Cps(_)
+                | InPps(_)
+                | OutPps(_)
+                | InBps(_)
+                | OutBps(_) => size_of::<u32>(),
+            InBytes(_)
+                | OutBytes(_) => size_of::<u64>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::IpvsStats::*;
+        match *self {
+            Conns(_) => IPVS_STATS_ATTR_CONNS,
+            InPkts(_) => IPVS_STATS_ATTR_INPKTS,
+            OutPkts(_) => IPVS_STATS_ATTR_OUTPKTS,
+            InBytes(_) => IPVS_STATS_ATTR_INBYTES,
+            OutBytes(_) => IPVS_STATS_ATTR_OUTBYTES,
+            Cps(_) => IPVS_STATS_ATTR_CPS,
+            InPps(_) => IPVS_STATS_ATTR_INPPS,
+            OutPps(_) => IPVS_STATS_ATTR_OUTPPS,
+            InBps(_) => IPVS_STATS_ATTR_INBPS,
+            OutBps(_) => IPVS_STATS_ATTR_OUTBPS,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::IpvsStats::*;
+        match *self {
+            Conns(value)
+                | InPkts(value)
+                | OutPkts(value)
+                | Cps(value)
+                | InPps(value)
+                | OutPps(value)
+                | InBps(value)
+                | OutBps(value) => NativeEndian::write_u32(buffer, value),
+            InBytes(value)
+                | OutBytes(value) => NativeEndian::write_u64(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<IpvsStats> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<IpvsStats, DecodeError> {
+        use self::IpvsStats::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            IPVS_STATS_ATTR_CONNS => {
+                Conns(parse_u32(payload).context("invalid IPVS_STATS_ATTR_CONNS value")?)
+            }
+            IPVS_STATS_ATTR_INPKTS => {
+                InPkts(parse_u32(payload).context("invalid IPVS_STATS_ATTR_INPKTS value")?)
+            }
+            IPVS_STATS_ATTR_OUTPKTS => {
+                OutPkts(parse_u32(payload).context("invalid IPVS_STATS_ATTR_OUTPKTS value")?)
+            }
+            IPVS_STATS_ATTR_INBYTES => {
+                InBytes(parse_u64(payload).context("invalid IPVS_STATS_ATTR_INBYTES value")?)
+            }
+            IPVS_STATS_ATTR_OUTBYTES => {
+                OutBytes(parse_u64(payload).context("invalid IPVS_STATS_ATTR_OUTBYTES value")?)
+            }
+            IPVS_STATS_ATTR_CPS => {
+                Cps(parse_u32(payload).context("invalid IPVS_STATS_ATTR_CPS value")?)
+            }
+            IPVS_STATS_ATTR_INPPS => {
+                InPps(parse_u32(payload).context("invalid IPVS_STATS_ATTR_INPPS value")?)
+            }
+            IPVS_STATS_ATTR_OUTPPS => {
+                OutPps(parse_u32(payload).context("invalid IPVS_STATS_ATTR_OUTPPS value")?)
+            }
+            IPVS_STATS_ATTR_INBPS => {
+                InBps(parse_u32(payload).context("invalid IPVS_STATS_ATTR_INBPS value")?)
+            }
+            IPVS_STATS_ATTR_OUTBPS => {
+                OutBps(parse_u32(payload).context("invalid IPVS_STATS_ATTR_OUTBPS value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
+
+/// Same as [`IpvsStats`](enum.IpvsStats.html), but all the counters are 64 bits wide
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IpvsStats64 {
+    /// Number of connections scheduled
+    Conns(u64),
+    /// Number of incoming packets
+    InPkts(u64),
+    /// Number of outgoing packets
+    OutPkts(u64),
+    /// Number of incoming bytes
+    InBytes(u64),
+    /// Number of outgoing bytes
+    OutBytes(u64),
+    /// Current connection rate
+    Cps(u64),
+    /// Current incoming packet rate
+    InPps(u64),
+    /// Current outgoing packet rate
+    OutPps(u64),
+    /// Current incoming byte rate
+    InBps(u64),
+    /// Current outgoing byte rate
+    OutBps(u64),
+    Pad,
+    Other(DefaultNla),
+}
+
+impl Nla for IpvsStats64 {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::IpvsStats64::*;
+        match *self {
+            Conns(_)
+                | InPkts(_)
+                | OutPkts(_)
+                | InBytes(_)
+                | OutBytes(_)
+                | Cps(_)
+                | InPps(_)
+                | OutPps(_)
+                | InBps(_)
+                | OutBps(_) => size_of::<u64>(),
+            Pad => 0,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::IpvsStats64::*;
+        match *self {
+            Conns(_) => IPVS_STATS_ATTR_CONNS,
+            InPkts(_) => IPVS_STATS_ATTR_INPKTS,
+            OutPkts(_) => IPVS_STATS_ATTR_OUTPKTS,
+            InBytes(_) => IPVS_STATS_ATTR_INBYTES,
+            OutBytes(_) => IPVS_STATS_ATTR_OUTBYTES,
+            Cps(_) => IPVS_STATS_ATTR_CPS,
+            InPps(_) => IPVS_STATS_ATTR_INPPS,
+            OutPps(_) => IPVS_STATS_ATTR_OUTPPS,
+            InBps(_) => IPVS_STATS_ATTR_INBPS,
+            OutBps(_) => IPVS_STATS_ATTR_OUTBPS,
+            Pad => IPVS_STATS_ATTR_PAD,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::IpvsStats64::*;
+        match *self {
+            Conns(value)
+                | InPkts(value)
+                | OutPkts(value)
+                | InBytes(value)
+                | OutBytes(value)
+                | Cps(value)
+                | InPps(value)
+                | OutPps(value)
+                | InBps(value)
+                | OutBps(value) => NativeEndian::write_u64(buffer, value),
+            Pad => {},
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<IpvsStats64> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<IpvsStats64, DecodeError> {
+        use self::IpvsStats64::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            IPVS_STATS_ATTR_CONNS => {
+                Conns(parse_u64(payload).context("invalid IPVS_STATS_ATTR_CONNS value")?)
+            }
+            IPVS_STATS_ATTR_INPKTS => {
+                InPkts(parse_u64(payload).context("invalid IPVS_STATS_ATTR_INPKTS value")?)
+            }
+            IPVS_STATS_ATTR_OUTPKTS => {
+                OutPkts(parse_u64(payload).context("invalid IPVS_STATS_ATTR_OUTPKTS value")?)
+            }
+            IPVS_STATS_ATTR_INBYTES => {
+                InBytes(parse_u64(payload).context("invalid IPVS_STATS_ATTR_INBYTES value")?)
+            }
+            IPVS_STATS_ATTR_OUTBYTES => {
+                OutBytes(parse_u64(payload).context("invalid IPVS_STATS_ATTR_OUTBYTES value")?)
+            }
+            IPVS_STATS_ATTR_CPS => {
+                Cps(parse_u64(payload).context("invalid IPVS_STATS_ATTR_CPS value")?)
+            }
+            IPVS_STATS_ATTR_INPPS => {
+                InPps(parse_u64(payload).context("invalid IPVS_STATS_ATTR_INPPS value")?)
+            }
+            IPVS_STATS_ATTR_OUTPPS => {
+                OutPps(parse_u64(payload).context("invalid IPVS_STATS_ATTR_OUTPPS value")?)
+            }
+            IPVS_STATS_ATTR_INBPS => {
+                InBps(parse_u64(payload).context("invalid IPVS_STATS_ATTR_INBPS value")?)
+            }
+            IPVS_STATS_ATTR_OUTBPS => {
+                OutBps(parse_u64(payload).context("invalid IPVS_STATS_ATTR_OUTBPS value")?)
+            }
+            IPVS_STATS_ATTR_PAD => Pad,
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,139 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::{
+    CtrlMessage, DecodeError, DevlinkMessage, Emitable, EthtoolMessage, GenlBuffer, IpvsMessage,
+    MptcpEvent, MptcpPmMessage, Parseable, TeamMessage,
+};
+
+/// A generic netlink message.
+///
+/// Apart from the controller (`nlctrl`), generic netlink families do not have a fixed message
+/// type: the kernel allocates one when the family is registered, and it has to be resolved by
+/// name with a `CTRL_CMD_GETFAMILY` request. As a result, messages for families other than
+/// `nlctrl` are parsed as [`GenlPayload::Raw`](enum.GenlPayload.html#variant.Raw), and can be
+/// decoded once the family is known with [`GenlBuffer`](struct.GenlBuffer.html):
+///
+/// ```rust,ignore
+/// let msg: EthtoolMessage = GenlBuffer::new_checked(&bytes)?.parse()?;
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GenlMessage {
+    /// Family identifier, used as the netlink message type
+    pub family_id: u16,
+    pub payload: GenlPayload,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GenlPayload {
+    Ctrl(CtrlMessage),
+    Ethtool(EthtoolMessage),
+    Devlink(DevlinkMessage),
+    MptcpPm(MptcpPmMessage),
+    MptcpEvent(MptcpEvent),
+    Ipvs(IpvsMessage),
+    Team(TeamMessage),
+    /// Payload of a message (generic netlink header included) that belongs to a family which
+    /// could not be identified when it was parsed.
+    Raw(Vec<u8>),
+}
+
+impl GenlMessage {
+    pub fn new(family_id: u16, payload: GenlPayload) -> Self {
+        GenlMessage { family_id, payload }
+    }
+
+    pub fn message_type(&self) -> u16 {
+        self.family_id
+    }
+
+    pub fn is_ctrl(&self) -> bool {
+        matches!(self.payload, GenlPayload::Ctrl(_))
+    }
+
+    pub fn is_ethtool(&self) -> bool {
+        matches!(self.payload, GenlPayload::Ethtool(_))
+    }
+
+    pub fn is_devlink(&self) -> bool {
+        matches!(self.payload, GenlPayload::Devlink(_))
+    }
+
+    pub fn is_mptcp_pm(&self) -> bool {
+        matches!(self.payload, GenlPayload::MptcpPm(_))
+    }
+
+    pub fn is_mptcp_event(&self) -> bool {
+        matches!(self.payload, GenlPayload::MptcpEvent(_))
+    }
+
+    pub fn is_ipvs(&self) -> bool {
+        matches!(self.payload, GenlPayload::Ipvs(_))
+    }
+
+    pub fn is_team(&self) -> bool {
+        matches!(self.payload, GenlPayload::Team(_))
+    }
+
+    pub fn is_raw(&self) -> bool {
+        matches!(self.payload, GenlPayload::Raw(_))
+    }
+
+    pub(crate) fn parse(message_type: u16, buffer: &[u8]) -> Result<Self, DecodeError> {
+        let payload = match message_type {
+            GENL_ID_CTRL => {
+                let msg: CtrlMessage = GenlBuffer::new_checked(buffer)
+                    .context("invalid nlctrl message")?
+                    .parse()
+                    .context("invalid nlctrl message")?;
+                GenlPayload::Ctrl(msg)
+            }
+            _ => {
+                GenlBuffer::new_checked(buffer).context("invalid generic netlink message")?;
+                GenlPayload::Raw(buffer.to_vec())
+            }
+        };
+        Ok(GenlMessage {
+            family_id: message_type,
+            payload,
+        })
+    }
+}
+
+impl From<CtrlMessage> for GenlMessage {
+    fn from(msg: CtrlMessage) -> Self {
+        GenlMessage::new(GENL_ID_CTRL, GenlPayload::Ctrl(msg))
+    }
+}
+
+impl Emitable for GenlMessage {
+    #[rustfmt::skip]
+    fn buffer_len(&self) -> usize {
+        use self::GenlPayload::*;
+        match self.payload {
+            Ctrl(ref msg) => msg.buffer_len(),
+            Ethtool(ref msg) => msg.buffer_len(),
+            Devlink(ref msg) => msg.buffer_len(),
+            MptcpPm(ref msg) => msg.buffer_len(),
+            MptcpEvent(ref msg) => msg.buffer_len(),
+            Ipvs(ref msg) => msg.buffer_len(),
+            Team(ref msg) => msg.buffer_len(),
+            Raw(ref bytes) => bytes.len(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit(&self, buffer: &mut [u8]) {
+        use self::GenlPayload::*;
+        match self.payload {
+            Ctrl(ref msg) => msg.emit(buffer),
+            Ethtool(ref msg) => msg.emit(buffer),
+            Devlink(ref msg) => msg.emit(buffer),
+            MptcpPm(ref msg) => msg.emit(buffer),
+            MptcpEvent(ref msg) => msg.emit(buffer),
+            Ipvs(ref msg) => msg.emit(buffer),
+            Team(ref msg) => msg.emit(buffer),
+            Raw(ref bytes) => buffer[..bytes.len()].copy_from_slice(bytes),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+mod nested;
+pub(crate) use self::nested::*;
+
+mod buffer;
+pub use self::buffer::*;
+
+mod header;
+pub use self::header::*;
+
+mod message;
+pub use self::message::*;
+
+mod ctrl;
+pub use self::ctrl::*;
+
+mod ethtool;
+pub use self::ethtool::*;
+
+mod devlink;
+pub use self::devlink::*;
+
+mod mptcp;
+pub use self::mptcp::*;
+
+mod ipvs;
+pub use self::ipvs::*;
+
+mod team;
+pub use self::team::*;
@@ -0,0 +1,103 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_i32, parse_u16, parse_u32, parse_u8};
+use crate::{DecodeError, DefaultNla, Nla, NlaBuffer, Parseable};
+
+/// Attributes of an MPTCP endpoint (`MPTCP_PM_ADDR_ATTR_*`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MptcpAddress {
+    /// `AF_INET` or `AF_INET6`
+    Family(u16),
+    /// Identifier of the endpoint
+    Id(u8),
+    /// IPv4 address, in network byte order
+    Addr4(Vec<u8>),
+    /// IPv6 address
+    Addr6(Vec<u8>),
+    Port(u16),
+    /// `MPTCP_PM_ADDR_FLAG_*` flags
+    Flags(u32),
+    /// Index of the interface the endpoint is bound to
+    IfIndex(i32),
+    Other(DefaultNla),
+}
+
+impl Nla for MptcpAddress {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::MptcpAddress::*;
+        match *self {
+            Family(_)
+                | Port(_) => size_of::<u16>(),
+            Id(_) => 1,
+            Addr4(ref bytes)
+                | Addr6(ref bytes) => bytes.len(),
+            Flags(_) => size_of::<u32>(),
+            IfIndex(_) => size_of::<i32>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::MptcpAddress::*;
+        match *self {
+            Family(_) => MPTCP_PM_ADDR_ATTR_FAMILY,
+            Id(_) => MPTCP_PM_ADDR_ATTR_ID,
+            Addr4(_) => MPTCP_PM_ADDR_ATTR_ADDR4,
+            Addr6(_) => MPTCP_PM_ADDR_ATTR_ADDR6,
+            Port(_) => MPTCP_PM_ADDR_ATTR_PORT,
+            Flags(_) => MPTCP_PM_ADDR_ATTR_FLAGS,
+            IfIndex(_) => MPTCP_PM_ADDR_ATTR_IF_IDX,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::MptcpAddress::*;
+        match *self {
+            Family(value)
+                | Port(value) => NativeEndian::write_u16(buffer, value),
+            Id(value) => buffer[0] = value,
+            Addr4(ref bytes)
+                | Addr6(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Flags(value) => NativeEndian::write_u32(buffer, value),
+            IfIndex(value) => NativeEndian::write_i32(buffer, value),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<MptcpAddress> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<MptcpAddress, DecodeError> {
+        use self::MptcpAddress::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            MPTCP_PM_ADDR_ATTR_FAMILY => {
+                Family(parse_u16(payload).context("invalid MPTCP_PM_ADDR_ATTR_FAMILY value")?)
+            }
+            MPTCP_PM_ADDR_ATTR_ID => {
+                Id(parse_u8(payload).context("invalid MPTCP_PM_ADDR_ATTR_ID value")?)
+            }
+            MPTCP_PM_ADDR_ATTR_ADDR4 => Addr4(payload.to_vec()),
+            MPTCP_PM_ADDR_ATTR_ADDR6 => Addr6(payload.to_vec()),
+            MPTCP_PM_ADDR_ATTR_PORT => {
+                Port(parse_u16(payload).context("invalid MPTCP_PM_ADDR_ATTR_PORT value")?)
+            }
+            MPTCP_PM_ADDR_ATTR_FLAGS => {
+                Flags(parse_u32(payload).context("invalid MPTCP_PM_ADDR_ATTR_FLAGS value")?)
+            }
+            MPTCP_PM_ADDR_ATTR_IF_IDX => {
+                IfIndex(parse_i32(payload).context("invalid MPTCP_PM_ADDR_ATTR_IF_IDX value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,148 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::{DecodeError, Emitable, GenlBuffer, GenlHeader, MptcpEventNla, Parseable};
+
+/// Events sent by the kernel to the `mptcp_pm_events` multicast group of the `mptcp_pm` family.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MptcpEvent {
+    Created(Vec<MptcpEventNla>),
+    Established(Vec<MptcpEventNla>),
+    Closed(Vec<MptcpEventNla>),
+    Announced(Vec<MptcpEventNla>),
+    Removed(Vec<MptcpEventNla>),
+    SubEstablished(Vec<MptcpEventNla>),
+    SubClosed(Vec<MptcpEventNla>),
+    SubPriority(Vec<MptcpEventNla>),
+}
+
+impl MptcpEvent {
+    pub fn cmd(&self) -> u8 {
+        use self::MptcpEvent::*;
+        match *self {
+            Created(_) => MPTCP_EVENT_CREATED,
+            Established(_) => MPTCP_EVENT_ESTABLISHED,
+            Closed(_) => MPTCP_EVENT_CLOSED,
+            Announced(_) => MPTCP_EVENT_ANNOUNCED,
+            Removed(_) => MPTCP_EVENT_REMOVED,
+            SubEstablished(_) => MPTCP_EVENT_SUB_ESTABLISHED,
+            SubClosed(_) => MPTCP_EVENT_SUB_CLOSED,
+            SubPriority(_) => MPTCP_EVENT_SUB_PRIORITY,
+        }
+    }
+
+    pub fn nlas(&self) -> &[MptcpEventNla] {
+        use self::MptcpEvent::*;
+        match *self {
+            Created(ref nlas)
+            | Established(ref nlas)
+            | Closed(ref nlas)
+            | Announced(ref nlas)
+            | Removed(ref nlas)
+            | SubEstablished(ref nlas)
+            | SubClosed(ref nlas)
+            | SubPriority(ref nlas) => nlas.as_slice(),
+        }
+    }
+
+    pub fn into_nlas(self) -> Vec<MptcpEventNla> {
+        use self::MptcpEvent::*;
+        match self {
+            Created(nlas) | Established(nlas) | Closed(nlas) | Announced(nlas) | Removed(nlas)
+            | SubEstablished(nlas) | SubClosed(nlas) | SubPriority(nlas) => nlas,
+        }
+    }
+}
+
+impl Emitable for MptcpEvent {
+    fn buffer_len(&self) -> usize {
+        GenlHeader::new(self.cmd(), MPTCP_PM_VER).buffer_len() + self.nlas().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let header = GenlHeader::new(self.cmd(), MPTCP_PM_VER);
+        header.emit(buffer);
+        self.nlas().emit(&mut buffer[header.buffer_len()..]);
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<MptcpEvent> for GenlBuffer<&T> {
+    fn parse(&self) -> Result<MptcpEvent, DecodeError> {
+        use self::MptcpEvent::*;
+        let mut nlas = vec![];
+        for nla_buf in self.nlas() {
+            nlas.push(nla_buf?.parse().context("invalid MPTCP event NLA")?);
+        }
+        Ok(match self.cmd() {
+            MPTCP_EVENT_CREATED => Created(nlas),
+            MPTCP_EVENT_ESTABLISHED => Established(nlas),
+            MPTCP_EVENT_CLOSED => Closed(nlas),
+            MPTCP_EVENT_ANNOUNCED => Announced(nlas),
+            MPTCP_EVENT_REMOVED => Removed(nlas),
+            MPTCP_EVENT_SUB_ESTABLISHED => SubEstablished(nlas),
+            MPTCP_EVENT_SUB_CLOSED => SubClosed(nlas),
+            MPTCP_EVENT_SUB_PRIORITY => SubPriority(nlas),
+            cmd => return Err(format!("unknown MPTCP event command {}", cmd).into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{GenlBuffer, MptcpEvent, MptcpEventNla, Parseable};
+
+    #[rustfmt::skip]
+    static CREATED: [u8; 60] = [
+        0x01, // cmd: MPTCP_EVENT_CREATED
+        0x01, // version
+        0x00, 0x00, // reserved
+
+        // MPTCP_ATTR_TOKEN
+        0x08, 0x00, 0x01, 0x00,
+        0x78, 0x56, 0x34, 0x12,
+
+        // MPTCP_ATTR_FAMILY
+        0x06, 0x00, 0x02, 0x00,
+        0x02, 0x00, // AF_INET
+        0x00, 0x00, // padding
+
+        // MPTCP_ATTR_SADDR4
+        0x08, 0x00, 0x05, 0x00,
+        0x0a, 0x00, 0x00, 0x01, // 10.0.0.1
+
+        // MPTCP_ATTR_DADDR4
+        0x08, 0x00, 0x07, 0x00,
+        0x0a, 0x00, 0x00, 0x02, // 10.0.0.2
+
+        // MPTCP_ATTR_SPORT
+        0x06, 0x00, 0x09, 0x00,
+        0x1f, 0x90, // 8080, in network byte order
+        0x00, 0x00, // padding
+
+        // MPTCP_ATTR_DPORT
+        0x06, 0x00, 0x0a, 0x00,
+        0x01, 0xbb, // 443, in network byte order
+        0x00, 0x00, // padding
+
+        // MPTCP_ATTR_SERVER_SIDE
+        0x05, 0x00, 0x12, 0x00,
+        0x00,
+        0x00, 0x00, 0x00, // padding
+    ];
+
+    #[test]
+    fn parse_created() {
+        let buffer = GenlBuffer::new_checked(&CREATED[..]).unwrap();
+        let msg: MptcpEvent = buffer.parse().unwrap();
+        let expected = MptcpEvent::Created(vec![
+            MptcpEventNla::Token(0x1234_5678),
+            MptcpEventNla::Family(2),
+            MptcpEventNla::Saddr4(vec![10, 0, 0, 1]),
+            MptcpEventNla::Daddr4(vec![10, 0, 0, 2]),
+            MptcpEventNla::Sport(8080),
+            MptcpEventNla::Dport(443),
+            MptcpEventNla::ServerSide(false),
+        ]);
+        assert_eq!(msg, expected);
+    }
+}
@@ -0,0 +1,171 @@
+use std::mem::size_of;
+
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_i32, parse_u16, parse_u16_be, parse_u32, parse_u8};
+use crate::{DecodeError, DefaultNla, Nla, NlaBuffer, Parseable};
+
+/// Attributes of the MPTCP events (`MPTCP_ATTR_*`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MptcpEventNla {
+    /// Token identifying the MPTCP connection
+    Token(u32),
+    Family(u16),
+    LocId(u8),
+    RemId(u8),
+    Saddr4(Vec<u8>),
+    Saddr6(Vec<u8>),
+    Daddr4(Vec<u8>),
+    Daddr6(Vec<u8>),
+    /// Source port
+    Sport(u16),
+    /// Destination port
+    Dport(u16),
+    Backup(u8),
+    Error(u8),
+    Flags(u16),
+    Timeout(u32),
+    IfIndex(i32),
+    ResetReason(u32),
+    ResetFlags(u32),
+    ServerSide(bool),
+    Other(DefaultNla),
+}
+
+impl Nla for MptcpEventNla {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::MptcpEventNla::*;
+        match *self {
+            Token(_)
+                | Timeout(_)
+                | ResetReason(_)
+                | ResetFlags(_) => size_of::<u32>(),
+            Family(_)
+                | Sport(_)
+                | Dport(_)
+                | Flags(_) => size_of::<u16>(),
+            LocId(_)
+                | RemId(_)
+                | Backup(_)
+                | Error(_)
+                | ServerSide(_) => 1,
+            Saddr4(ref bytes)
+                | Saddr6(ref bytes)
+                | Daddr4(ref bytes)
+                | Daddr6(ref bytes) => bytes.len(),
+            IfIndex(_) => size_of::<i32>(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::MptcpEventNla::*;
+        match *self {
+            Token(_) => MPTCP_ATTR_TOKEN,
+            Family(_) => MPTCP_ATTR_FAMILY,
+            LocId(_) => MPTCP_ATTR_LOC_ID,
+            RemId(_) => MPTCP_ATTR_REM_ID,
+            Saddr4(_) => MPTCP_ATTR_SADDR4,
+            Saddr6(_) => MPTCP_ATTR_SADDR6,
+            Daddr4(_) => MPTCP_ATTR_DADDR4,
+            Daddr6(_) => MPTCP_ATTR_DADDR6,
+            Sport(_) => MPTCP_ATTR_SPORT,
+            Dport(_) => MPTCP_ATTR_DPORT,
+            Backup(_) => MPTCP_ATTR_BACKUP,
+            Error(_) => MPTCP_ATTR_ERROR,
+            Flags(_) => MPTCP_ATTR_FLAGS,
+            Timeout(_) => MPTCP_ATTR_TIMEOUT,
+            IfIndex(_) => MPTCP_ATTR_IF_IDX,
+            ResetReason(_) => MPTCP_ATTR_RESET_REASON,
+            ResetFlags(_) => MPTCP_ATTR_RESET_FLAGS,
+            ServerSide(_) => MPTCP_ATTR_SERVER_SIDE,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::MptcpEventNla::*;
+        match *self {
+            Token(value)
+                | Timeout(value)
+                | ResetReason(value)
+                | ResetFlags(value) => NativeEndian::write_u32(buffer, value),
+            Family(value)
+                | Flags(value) => NativeEndian::write_u16(buffer, value),
+            LocId(value)
+                | RemId(value)
+                | Backup(value)
+                | Error(value) => buffer[0] = value,
+            Saddr4(ref bytes)
+                | Saddr6(ref bytes)
+                | Daddr4(ref bytes)
+                | Daddr6(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Sport(value)
+                | Dport(value) => BigEndian::write_u16(buffer, value),
+            IfIndex(value) => NativeEndian::write_i32(buffer, value),
+            ServerSide(value) => buffer[0] = value as u8,
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<MptcpEventNla> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<MptcpEventNla, DecodeError> {
+        use self::MptcpEventNla::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            MPTCP_ATTR_TOKEN => {
+                Token(parse_u32(payload).context("invalid MPTCP_ATTR_TOKEN value")?)
+            }
+            MPTCP_ATTR_FAMILY => {
+                Family(parse_u16(payload).context("invalid MPTCP_ATTR_FAMILY value")?)
+            }
+            MPTCP_ATTR_LOC_ID => {
+                LocId(parse_u8(payload).context("invalid MPTCP_ATTR_LOC_ID value")?)
+            }
+            MPTCP_ATTR_REM_ID => {
+                RemId(parse_u8(payload).context("invalid MPTCP_ATTR_REM_ID value")?)
+            }
+            MPTCP_ATTR_SADDR4 => Saddr4(payload.to_vec()),
+            MPTCP_ATTR_SADDR6 => Saddr6(payload.to_vec()),
+            MPTCP_ATTR_DADDR4 => Daddr4(payload.to_vec()),
+            MPTCP_ATTR_DADDR6 => Daddr6(payload.to_vec()),
+            MPTCP_ATTR_SPORT => {
+                Sport(parse_u16_be(payload).context("invalid MPTCP_ATTR_SPORT value")?)
+            }
+            MPTCP_ATTR_DPORT => {
+                Dport(parse_u16_be(payload).context("invalid MPTCP_ATTR_DPORT value")?)
+            }
+            MPTCP_ATTR_BACKUP => {
+                Backup(parse_u8(payload).context("invalid MPTCP_ATTR_BACKUP value")?)
+            }
+            MPTCP_ATTR_ERROR => Error(parse_u8(payload).context("invalid MPTCP_ATTR_ERROR value")?),
+            MPTCP_ATTR_FLAGS => {
+                Flags(parse_u16(payload).context("invalid MPTCP_ATTR_FLAGS value")?)
+            }
+            MPTCP_ATTR_TIMEOUT => {
+                Timeout(parse_u32(payload).context("invalid MPTCP_ATTR_TIMEOUT value")?)
+            }
+            MPTCP_ATTR_IF_IDX => {
+                IfIndex(parse_i32(payload).context("invalid MPTCP_ATTR_IF_IDX value")?)
+            }
+            MPTCP_ATTR_RESET_REASON => {
+                ResetReason(parse_u32(payload).context("invalid MPTCP_ATTR_RESET_REASON value")?)
+            }
+            MPTCP_ATTR_RESET_FLAGS => {
+                ResetFlags(parse_u32(payload).context("invalid MPTCP_ATTR_RESET_FLAGS value")?)
+            }
+            MPTCP_ATTR_SERVER_SIDE => {
+                ServerSide(parse_u8(payload).context("invalid MPTCP_ATTR_SERVER_SIDE value")? != 0)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,174 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::{DecodeError, Emitable, GenlBuffer, GenlHeader, MptcpPmNla, Parseable};
+
+/// Messages of the `mptcp_pm` generic netlink family: requests, and the corresponding replies.
+///
+/// The events sent to the `mptcp_pm_events` multicast group use command numbers that overlap with
+/// the requests, so they are represented by a distinct type,
+/// [`MptcpEvent`](enum.MptcpEvent.html).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MptcpPmMessage {
+    AddAddr(Vec<MptcpPmNla>),
+    DelAddr(Vec<MptcpPmNla>),
+    GetAddr(Vec<MptcpPmNla>),
+    FlushAddrs(Vec<MptcpPmNla>),
+    SetLimits(Vec<MptcpPmNla>),
+    GetLimits(Vec<MptcpPmNla>),
+    SetFlags(Vec<MptcpPmNla>),
+}
+
+impl MptcpPmMessage {
+    pub fn cmd(&self) -> u8 {
+        use self::MptcpPmMessage::*;
+        match *self {
+            AddAddr(_) => MPTCP_PM_CMD_ADD_ADDR,
+            DelAddr(_) => MPTCP_PM_CMD_DEL_ADDR,
+            GetAddr(_) => MPTCP_PM_CMD_GET_ADDR,
+            FlushAddrs(_) => MPTCP_PM_CMD_FLUSH_ADDRS,
+            SetLimits(_) => MPTCP_PM_CMD_SET_LIMITS,
+            GetLimits(_) => MPTCP_PM_CMD_GET_LIMITS,
+            SetFlags(_) => MPTCP_PM_CMD_SET_FLAGS,
+        }
+    }
+
+    pub fn nlas(&self) -> &[MptcpPmNla] {
+        use self::MptcpPmMessage::*;
+        match *self {
+            AddAddr(ref nlas) | DelAddr(ref nlas) | GetAddr(ref nlas) | FlushAddrs(ref nlas)
+            | SetLimits(ref nlas) | GetLimits(ref nlas) | SetFlags(ref nlas) => nlas.as_slice(),
+        }
+    }
+
+    pub fn into_nlas(self) -> Vec<MptcpPmNla> {
+        use self::MptcpPmMessage::*;
+        match self {
+            AddAddr(nlas) | DelAddr(nlas) | GetAddr(nlas) | FlushAddrs(nlas) | SetLimits(nlas)
+            | GetLimits(nlas) | SetFlags(nlas) => nlas,
+        }
+    }
+}
+
+impl Emitable for MptcpPmMessage {
+    fn buffer_len(&self) -> usize {
+        GenlHeader::new(self.cmd(), MPTCP_PM_VER).buffer_len() + self.nlas().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let header = GenlHeader::new(self.cmd(), MPTCP_PM_VER);
+        header.emit(buffer);
+        self.nlas().emit(&mut buffer[header.buffer_len()..]);
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<MptcpPmMessage> for GenlBuffer<&T> {
+    fn parse(&self) -> Result<MptcpPmMessage, DecodeError> {
+        use self::MptcpPmMessage::*;
+        let mut nlas = vec![];
+        for nla_buf in self.nlas() {
+            nlas.push(nla_buf?.parse().context("invalid mptcp_pm NLA")?);
+        }
+        Ok(match self.cmd() {
+            MPTCP_PM_CMD_ADD_ADDR => AddAddr(nlas),
+            MPTCP_PM_CMD_DEL_ADDR => DelAddr(nlas),
+            MPTCP_PM_CMD_GET_ADDR => GetAddr(nlas),
+            MPTCP_PM_CMD_FLUSH_ADDRS => FlushAddrs(nlas),
+            MPTCP_PM_CMD_SET_LIMITS => SetLimits(nlas),
+            MPTCP_PM_CMD_GET_LIMITS => GetLimits(nlas),
+            MPTCP_PM_CMD_SET_FLAGS => SetFlags(nlas),
+            cmd => return Err(format!("unknown mptcp_pm command {}", cmd).into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::constants::*;
+    use crate::{
+        assert_nested, Emitable, GenlBuffer, MptcpAddress, MptcpPmMessage, MptcpPmNla, Parseable,
+    };
+
+    #[rustfmt::skip]
+    static GET_ADDR_REPLY: [u8; 48] = [
+        0x03, // cmd: MPTCP_PM_CMD_GET_ADDR
+        0x01, // version
+        0x00, 0x00, // reserved
+
+        // MPTCP_PM_ATTR_ADDR (nested)
+        0x2c, 0x00, 0x01, 0x80,
+            // MPTCP_PM_ADDR_ATTR_FAMILY
+            0x06, 0x00, 0x01, 0x00,
+            0x02, 0x00, // AF_INET
+            0x00, 0x00, // padding
+            // MPTCP_PM_ADDR_ATTR_ID
+            0x05, 0x00, 0x02, 0x00,
+            0x01,
+            0x00, 0x00, 0x00, // padding
+            // MPTCP_PM_ADDR_ATTR_ADDR4
+            0x08, 0x00, 0x03, 0x00,
+            0x0a, 0x00, 0x00, 0x01, // 10.0.0.1
+            // MPTCP_PM_ADDR_ATTR_FLAGS
+            0x08, 0x00, 0x06, 0x00,
+            0x01, 0x00, 0x00, 0x00, // MPTCP_PM_ADDR_FLAG_SIGNAL
+            // MPTCP_PM_ADDR_ATTR_IF_IDX
+            0x08, 0x00, 0x07, 0x00,
+            0x02, 0x00, 0x00, 0x00,
+    ];
+
+    fn get_addr_reply() -> MptcpPmMessage {
+        MptcpPmMessage::GetAddr(vec![MptcpPmNla::Addr(vec![
+            MptcpAddress::Family(2),
+            MptcpAddress::Id(1),
+            MptcpAddress::Addr4(vec![10, 0, 0, 1]),
+            MptcpAddress::Flags(MPTCP_PM_ADDR_FLAG_SIGNAL),
+            MptcpAddress::IfIndex(2),
+        ])])
+    }
+
+    #[test]
+    fn parse_get_addr_reply() {
+        let buffer = GenlBuffer::new_checked(&GET_ADDR_REPLY[..]).unwrap();
+        let msg: MptcpPmMessage = buffer.parse().unwrap();
+        assert_eq!(msg, get_addr_reply());
+    }
+
+    #[test]
+    fn emit_get_addr_reply() {
+        let msg = get_addr_reply();
+        assert_eq!(msg.buffer_len(), GET_ADDR_REPLY.len());
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &GET_ADDR_REPLY[..]);
+    }
+
+    #[test]
+    fn addr_round_trip() {
+        let mut addr6 = vec![0; 16];
+        addr6[..2].copy_from_slice(&[0x20, 0x01]);
+        addr6[15] = 1;
+        let msg = MptcpPmMessage::SetFlags(vec![
+            MptcpPmNla::Token(0x1234_5678),
+            MptcpPmNla::Addr(vec![
+                MptcpAddress::Family(AF_INET6),
+                MptcpAddress::Id(2),
+                MptcpAddress::Addr6(addr6),
+                MptcpAddress::Port(8080),
+                MptcpAddress::Flags(MPTCP_PM_ADDR_FLAG_SUBFLOW | MPTCP_PM_ADDR_FLAG_BACKUP),
+                MptcpAddress::IfIndex(-1),
+            ]),
+            MptcpPmNla::AddrRemote(vec![
+                MptcpAddress::Family(AF_INET),
+                MptcpAddress::Addr4(vec![10, 0, 0, 2]),
+                MptcpAddress::Port(443),
+            ]),
+        ]);
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+
+        let buffer = GenlBuffer::new_checked(&buf[..]).unwrap();
+        assert_nested(buffer.payload(), &[MPTCP_PM_ATTR_ADDR]);
+        assert_nested(buffer.payload(), &[MPTCP_PM_ATTR_ADDR_REMOTE]);
+        assert_eq!(Parseable::<MptcpPmMessage>::parse(&buffer).unwrap(), msg);
+    }
+}
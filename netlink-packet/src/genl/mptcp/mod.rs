@@ -0,0 +1,14 @@
+mod address;
+pub use self::address::*;
+
+mod nlas;
+pub use self::nlas::*;
+
+mod message;
+pub use self::message::*;
+
+mod event_nlas;
+pub use self::event_nlas::*;
+
+mod event;
+pub use self::event::*;
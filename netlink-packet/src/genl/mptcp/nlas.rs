@@ -0,0 +1,106 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_u32, parse_u8};
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, MptcpAddress, Nla, NlaBuffer, Parseable,
+};
+
+/// Attributes of the MPTCP path manager messages (`MPTCP_PM_ATTR_*`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MptcpPmNla {
+    /// An endpoint, or the local address of a subflow
+    Addr(Vec<MptcpAddress>),
+    /// Maximum number of addresses accepted from `ADD_ADDR` options
+    RcvAddAddrs(u32),
+    /// Maximum number of additional subflows per connection
+    Subflows(u32),
+    /// Token identifying an MPTCP connection
+    Token(u32),
+    LocId(u8),
+    /// Remote address of a subflow
+    AddrRemote(Vec<MptcpAddress>),
+    Other(DefaultNla),
+}
+
+impl Nla for MptcpPmNla {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::MptcpPmNla::*;
+        match *self {
+            Addr(ref nlas)
+                | AddrRemote(ref nlas) => nlas.as_slice().buffer_len(),
+            RcvAddAddrs(_)
+                | Subflows(_)
+                | Token(_) => size_of::<u32>(),
+            LocId(_) => 1,
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::MptcpPmNla::*;
+        match *self {
+            Addr(_) => MPTCP_PM_ATTR_ADDR,
+            RcvAddAddrs(_) => MPTCP_PM_ATTR_RCV_ADD_ADDRS,
+            Subflows(_) => MPTCP_PM_ATTR_SUBFLOWS,
+            Token(_) => MPTCP_PM_ATTR_TOKEN,
+            LocId(_) => MPTCP_PM_ATTR_LOC_ID,
+            AddrRemote(_) => MPTCP_PM_ATTR_ADDR_REMOTE,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::MptcpPmNla::*;
+        match *self {
+            Addr(ref nlas)
+                | AddrRemote(ref nlas) => nlas.as_slice().emit(buffer),
+            RcvAddAddrs(value)
+                | Subflows(value)
+                | Token(value) => NativeEndian::write_u32(buffer, value),
+            LocId(value) => buffer[0] = value,
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        use self::MptcpPmNla::*;
+        matches!(*self, Addr(_) | AddrRemote(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<MptcpPmNla> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<MptcpPmNla, DecodeError> {
+        use self::MptcpPmNla::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            MPTCP_PM_ATTR_ADDR => {
+                Addr(parse_nested(payload).context("invalid MPTCP_PM_ATTR_ADDR value")?)
+            }
+            MPTCP_PM_ATTR_RCV_ADD_ADDRS => RcvAddAddrs(
+                parse_u32(payload).context("invalid MPTCP_PM_ATTR_RCV_ADD_ADDRS value")?,
+            ),
+            MPTCP_PM_ATTR_SUBFLOWS => {
+                Subflows(parse_u32(payload).context("invalid MPTCP_PM_ATTR_SUBFLOWS value")?)
+            }
+            MPTCP_PM_ATTR_TOKEN => {
+                Token(parse_u32(payload).context("invalid MPTCP_PM_ATTR_TOKEN value")?)
+            }
+            MPTCP_PM_ATTR_LOC_ID => {
+                LocId(parse_u8(payload).context("invalid MPTCP_PM_ATTR_LOC_ID value")?)
+            }
+            MPTCP_PM_ATTR_ADDR_REMOTE => AddrRemote(
+                parse_nested(payload).context("invalid MPTCP_PM_ATTR_ADDR_REMOTE value")?,
+            ),
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,133 @@
+use byteorder::{ByteOrder, NativeEndian};
+
+use crate::{DecodeError, Emitable, Nla, NlaBuffer, NlasIterator, Parseable};
+
+/// An attribute which value is a list of attributes, with the `NLA_F_NESTED` flag set.
+///
+/// Generic netlink families commonly use these to represent arrays, where each element is a
+/// nested attribute which kind is either the element index or a fixed value.
+pub(crate) struct NestedNla<T: Nla> {
+    kind: u16,
+    nlas: Vec<T>,
+}
+
+impl<T: Nla> NestedNla<T> {
+    pub fn new(kind: u16, nlas: Vec<T>) -> Self {
+        NestedNla { kind, nlas }
+    }
+}
+
+impl<T: Nla> Nla for NestedNla<T> {
+    fn value_len(&self) -> usize {
+        self.nlas.as_slice().buffer_len()
+    }
+
+    fn kind(&self) -> u16 {
+        self.kind
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.nlas.as_slice().emit(buffer)
+    }
+
+    fn is_nested(&self) -> bool {
+        true
+    }
+}
+
+/// Parse the value of a nested attribute as a list of attributes
+pub(crate) fn parse_nested<T>(payload: &[u8]) -> Result<Vec<T>, DecodeError>
+where
+    for<'a> NlaBuffer<&'a [u8]>: Parseable<T>,
+{
+    let mut nlas = vec![];
+    for nla_buf in NlasIterator::new(payload) {
+        nlas.push(nla_buf?.parse()?);
+    }
+    Ok(nlas)
+}
+
+/// An attribute built from a plain value. Nested attributes that are exposed as plain structures
+/// are emitted as lists of `GenlAttr`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum GenlAttr {
+    U8(u16, u8),
+    U32(u16, u32),
+    U64(u16, u64),
+    Str(u16, String),
+    Flag(u16),
+    Bytes(u16, Vec<u8>),
+    Nested(u16, Vec<GenlAttr>),
+}
+
+impl Nla for GenlAttr {
+    fn value_len(&self) -> usize {
+        use self::GenlAttr::*;
+        match *self {
+            U8(_, _) => 1,
+            U32(_, _) => 4,
+            U64(_, _) => 8,
+            Str(_, ref s) => s.len() + 1,
+            Flag(_) => 0,
+            Bytes(_, ref bytes) => bytes.len(),
+            Nested(_, ref nlas) => nlas.as_slice().buffer_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::GenlAttr::*;
+        match *self {
+            U8(kind, _)
+            | U32(kind, _)
+            | U64(kind, _)
+            | Str(kind, _)
+            | Flag(kind)
+            | Bytes(kind, _)
+            | Nested(kind, _) => kind,
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::GenlAttr::*;
+        match *self {
+            U8(_, value) => buffer[0] = value,
+            U32(_, value) => NativeEndian::write_u32(buffer, value),
+            U64(_, value) => NativeEndian::write_u64(buffer, value),
+            Str(_, ref s) => {
+                buffer[..s.len()].copy_from_slice(s.as_bytes());
+                buffer[s.len()] = 0;
+            }
+            Flag(_) => {}
+            Bytes(_, ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Nested(_, ref nlas) => nlas.as_slice().emit(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(*self, GenlAttr::Nested(_, _))
+    }
+}
+
+/// Check that the attributes found at the given path of kinds are flagged as nested: strict
+/// generic netlink families reject nested attributes which do not have the `NLA_F_NESTED` flag.
+#[cfg(test)]
+pub(crate) fn assert_nested(payload: &[u8], path: &[u16]) {
+    let (kind, path) = match path.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    let mut found = false;
+    for nla in NlasIterator::new(payload) {
+        let nla = nla.unwrap();
+        if nla.kind() == *kind {
+            assert!(
+                nla.nested_flag(),
+                "attribute {} is not flagged as nested",
+                kind
+            );
+            assert_nested(nla.value(), path);
+            found = true;
+        }
+    }
+    assert!(found, "attribute {} not found", kind);
+}
@@ -0,0 +1,277 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::{DecodeError, Emitable, GenlBuffer, GenlHeader, Parseable, TeamNla};
+
+/// Messages of the `team` generic netlink family.
+///
+/// The notifications sent to the `change_event` multicast group when options or ports change are
+/// `TEAM_CMD_OPTIONS_GET` and `TEAM_CMD_PORT_LIST_GET` messages, that only carry the items that
+/// changed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TeamMessage {
+    Noop(Vec<TeamNla>),
+    OptionsSet(Vec<TeamNla>),
+    OptionsGet(Vec<TeamNla>),
+    PortListGet(Vec<TeamNla>),
+}
+
+impl TeamMessage {
+    pub fn cmd(&self) -> u8 {
+        use self::TeamMessage::*;
+        match *self {
+            Noop(_) => TEAM_CMD_NOOP,
+            OptionsSet(_) => TEAM_CMD_OPTIONS_SET,
+            OptionsGet(_) => TEAM_CMD_OPTIONS_GET,
+            PortListGet(_) => TEAM_CMD_PORT_LIST_GET,
+        }
+    }
+
+    pub fn nlas(&self) -> &[TeamNla] {
+        use self::TeamMessage::*;
+        match *self {
+            Noop(ref nlas)
+            | OptionsSet(ref nlas)
+            | OptionsGet(ref nlas)
+            | PortListGet(ref nlas) => nlas.as_slice(),
+        }
+    }
+
+    pub fn into_nlas(self) -> Vec<TeamNla> {
+        use self::TeamMessage::*;
+        match self {
+            Noop(nlas) | OptionsSet(nlas) | OptionsGet(nlas) | PortListGet(nlas) => nlas,
+        }
+    }
+}
+
+impl Emitable for TeamMessage {
+    fn buffer_len(&self) -> usize {
+        GenlHeader::new(self.cmd(), TEAM_GENL_VERSION).buffer_len() + self.nlas().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let header = GenlHeader::new(self.cmd(), TEAM_GENL_VERSION);
+        header.emit(buffer);
+        self.nlas().emit(&mut buffer[header.buffer_len()..]);
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<TeamMessage> for GenlBuffer<&T> {
+    fn parse(&self) -> Result<TeamMessage, DecodeError> {
+        use self::TeamMessage::*;
+        let mut nlas = vec![];
+        for nla_buf in self.nlas() {
+            nlas.push(nla_buf?.parse().context("invalid team NLA")?);
+        }
+        Ok(match self.cmd() {
+            TEAM_CMD_NOOP => Noop(nlas),
+            TEAM_CMD_OPTIONS_SET => OptionsSet(nlas),
+            TEAM_CMD_OPTIONS_GET => OptionsGet(nlas),
+            TEAM_CMD_PORT_LIST_GET => PortListGet(nlas),
+            cmd => return Err(format!("unknown team command {}", cmd).into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::constants::*;
+    use crate::{
+        assert_nested, Emitable, GenlBuffer, Parseable, TeamMessage, TeamNla, TeamOption,
+        TeamOptionData, TeamPort,
+    };
+
+    #[rustfmt::skip]
+    static OPTIONS_GET_REPLY: [u8; 88] = [
+        0x02, // cmd: TEAM_CMD_OPTIONS_GET
+        0x01, // version
+        0x00, 0x00, // reserved
+
+        // TEAM_ATTR_TEAM_IFINDEX
+        0x08, 0x00, 0x01, 0x00,
+        0x05, 0x00, 0x00, 0x00,
+
+        // TEAM_ATTR_LIST_OPTION (nested)
+        0x4c, 0x00, 0x02, 0x80,
+            // TEAM_ATTR_ITEM_OPTION (nested)
+            0x24, 0x00, 0x01, 0x80,
+                // TEAM_ATTR_OPTION_NAME
+                0x09, 0x00, 0x01, 0x00,
+                0x6d, 0x6f, 0x64, 0x65, 0x00, // "mode"
+                0x00, 0x00, 0x00, // padding
+                // TEAM_ATTR_OPTION_TYPE
+                0x05, 0x00, 0x03, 0x00,
+                0x05, // TEAM_OPTION_TYPE_STRING
+                0x00, 0x00, 0x00, // padding
+                // TEAM_ATTR_OPTION_DATA
+                0x09, 0x00, 0x04, 0x00,
+                0x6c, 0x61, 0x63, 0x70, 0x00, // "lacp"
+                0x00, 0x00, 0x00, // padding
+            // TEAM_ATTR_ITEM_OPTION (nested)
+            0x24, 0x00, 0x01, 0x80,
+                // TEAM_ATTR_OPTION_NAME
+                0x0c, 0x00, 0x01, 0x00,
+                0x65, 0x6e, 0x61, 0x62, 0x6c, 0x65, 0x64, 0x00, // "enabled"
+                // TEAM_ATTR_OPTION_PORT_IFINDEX
+                0x08, 0x00, 0x06, 0x00,
+                0x03, 0x00, 0x00, 0x00,
+                // TEAM_ATTR_OPTION_TYPE
+                0x05, 0x00, 0x03, 0x00,
+                0x06, // TEAM_OPTION_TYPE_BOOL
+                0x00, 0x00, 0x00, // padding
+                // TEAM_ATTR_OPTION_DATA (flag)
+                0x04, 0x00, 0x04, 0x00,
+    ];
+
+    fn options_get_reply() -> TeamMessage {
+        let mut enabled = TeamOption::new("enabled", TeamOptionData::Bool(true));
+        enabled.port_ifindex = Some(3);
+        TeamMessage::OptionsGet(vec![
+            TeamNla::TeamIfIndex(5),
+            TeamNla::Options(vec![
+                TeamOption::new("mode", TeamOptionData::String("lacp".into())),
+                enabled,
+            ]),
+        ])
+    }
+
+    #[test]
+    fn parse_options_get_reply() {
+        let buffer = GenlBuffer::new_checked(&OPTIONS_GET_REPLY[..]).unwrap();
+        let msg: TeamMessage = buffer.parse().unwrap();
+        assert_eq!(msg, options_get_reply());
+    }
+
+    #[test]
+    fn emit_options_get_reply() {
+        let msg = options_get_reply();
+        assert_eq!(msg.buffer_len(), OPTIONS_GET_REPLY.len());
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &OPTIONS_GET_REPLY[..]);
+    }
+
+    #[rustfmt::skip]
+    static PORT_LIST_EVENT: [u8; 52] = [
+        0x03, // cmd: TEAM_CMD_PORT_LIST_GET
+        0x01, // version
+        0x00, 0x00, // reserved
+
+        // TEAM_ATTR_TEAM_IFINDEX
+        0x08, 0x00, 0x01, 0x00,
+        0x05, 0x00, 0x00, 0x00,
+
+        // TEAM_ATTR_LIST_PORT (nested)
+        0x28, 0x00, 0x03, 0x80,
+            // TEAM_ATTR_ITEM_PORT (nested)
+            0x24, 0x00, 0x01, 0x80,
+                // TEAM_ATTR_PORT_IFINDEX
+                0x08, 0x00, 0x01, 0x00,
+                0x03, 0x00, 0x00, 0x00,
+                // TEAM_ATTR_PORT_CHANGED
+                0x04, 0x00, 0x02, 0x00,
+                // TEAM_ATTR_PORT_LINKUP
+                0x04, 0x00, 0x03, 0x00,
+                // TEAM_ATTR_PORT_SPEED
+                0x08, 0x00, 0x04, 0x00,
+                0xe8, 0x03, 0x00, 0x00, // 1000
+                // TEAM_ATTR_PORT_DUPLEX
+                0x05, 0x00, 0x05, 0x00,
+                0x01, // DUPLEX_FULL
+                0x00, 0x00, 0x00, // padding
+    ];
+
+    #[test]
+    fn parse_port_list_event() {
+        let buffer = GenlBuffer::new_checked(&PORT_LIST_EVENT[..]).unwrap();
+        let msg: TeamMessage = buffer.parse().unwrap();
+        assert_eq!(
+            msg,
+            TeamMessage::PortListGet(vec![
+                TeamNla::TeamIfIndex(5),
+                TeamNla::Ports(vec![TeamPort {
+                    ifindex: 3,
+                    changed: true,
+                    removed: false,
+                    linkup: true,
+                    speed: 1000,
+                    duplex: 1,
+                }]),
+            ])
+        );
+    }
+
+    // Emit the message, check that the lists and their items are flagged as nested, and parse it
+    // back
+    fn round_trip(msg: TeamMessage, list: u16, item: u16) {
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+        let buffer = GenlBuffer::new_checked(&buf[..]).unwrap();
+        assert_nested(buffer.payload(), &[list, item]);
+        assert_eq!(Parseable::<TeamMessage>::parse(&buffer).unwrap(), msg);
+    }
+
+    #[test]
+    fn option_data_round_trip() {
+        let mut queue_id = TeamOption::new("queue_id", TeamOptionData::U32(2));
+        queue_id.port_ifindex = Some(3);
+        queue_id.changed = true;
+        let mut bpf_hash_func = TeamOption::new("bpf_hash_func", TeamOptionData::Binary(vec![]));
+        bpf_hash_func.removed = true;
+        let mut lb_tx_hash_to_port_mapping =
+            TeamOption::new("lb_tx_hash_to_port_mapping", TeamOptionData::U32(4));
+        lb_tx_hash_to_port_mapping.array_index = Some(255);
+
+        round_trip(
+            TeamMessage::OptionsSet(vec![
+                TeamNla::TeamIfIndex(5),
+                TeamNla::Options(vec![
+                    TeamOption::new("mode", TeamOptionData::String("activebackup".into())),
+                    TeamOption::new(
+                        "lb_tx_method",
+                        TeamOptionData::Binary(vec![0x06, 0x00, 0x00, 0x00, 0x01]),
+                    ),
+                    TeamOption::new("mcast_rejoin_count", TeamOptionData::U32(0)),
+                    TeamOption::new("user_linkup", TeamOptionData::Bool(true)),
+                    TeamOption::new("user_linkup_enabled", TeamOptionData::Bool(false)),
+                    TeamOption::new("priority", TeamOptionData::S32(-10)),
+                    queue_id,
+                    bpf_hash_func,
+                    lb_tx_hash_to_port_mapping,
+                ]),
+            ]),
+            TEAM_ATTR_LIST_OPTION,
+            TEAM_ATTR_ITEM_OPTION,
+        );
+    }
+
+    #[test]
+    fn ports_round_trip() {
+        round_trip(
+            TeamMessage::PortListGet(vec![
+                TeamNla::TeamIfIndex(5),
+                TeamNla::Ports(vec![
+                    TeamPort {
+                        ifindex: 3,
+                        changed: true,
+                        removed: false,
+                        linkup: true,
+                        speed: 10000,
+                        duplex: 1,
+                    },
+                    TeamPort {
+                        ifindex: 4,
+                        changed: false,
+                        removed: true,
+                        linkup: false,
+                        speed: 0,
+                        duplex: 0,
+                    },
+                ]),
+            ]),
+            TEAM_ATTR_LIST_PORT,
+            TEAM_ATTR_ITEM_PORT,
+        );
+    }
+}
@@ -0,0 +1,11 @@
+mod option;
+pub use self::option::*;
+
+mod port;
+pub use self::port::*;
+
+mod nlas;
+pub use self::nlas::*;
+
+mod message;
+pub use self::message::*;
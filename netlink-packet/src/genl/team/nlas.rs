@@ -0,0 +1,100 @@
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::parse_u32;
+use crate::{
+    parse_nested, DecodeError, DefaultNla, Emitable, GenlAttr, Nla, NlaBuffer, Parseable,
+    TeamOption, TeamPort,
+};
+
+/// Attributes of the team messages (`TEAM_ATTR_*`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TeamNla {
+    /// Index of the team device
+    TeamIfIndex(u32),
+    /// Options of the team device and of its ports
+    Options(Vec<TeamOption>),
+    /// Ports of the team device
+    Ports(Vec<TeamPort>),
+    Other(DefaultNla),
+}
+
+impl Nla for TeamNla {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::TeamNla::*;
+        match *self {
+            TeamIfIndex(_) => size_of::<u32>(),
+            Options(ref values) => values
+                .iter()
+                .map(|value| GenlAttr::Nested(TEAM_ATTR_ITEM_OPTION, value.to_nlas()).buffer_len())
+                .sum(),
+            Ports(ref values) => values
+                .iter()
+                .map(|value| GenlAttr::Nested(TEAM_ATTR_ITEM_PORT, value.to_nlas()).buffer_len())
+                .sum(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::TeamNla::*;
+        match *self {
+            TeamIfIndex(_) => TEAM_ATTR_TEAM_IFINDEX,
+            Options(_) => TEAM_ATTR_LIST_OPTION,
+            Ports(_) => TEAM_ATTR_LIST_PORT,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::TeamNla::*;
+        match *self {
+            TeamIfIndex(value) => NativeEndian::write_u32(buffer, value),
+            Options(ref values) => values
+                .iter()
+                .map(|value| GenlAttr::Nested(TEAM_ATTR_ITEM_OPTION, value.to_nlas()))
+                .collect::<Vec<_>>()
+                .as_slice()
+                .emit(buffer),
+            Ports(ref values) => values
+                .iter()
+                .map(|value| GenlAttr::Nested(TEAM_ATTR_ITEM_PORT, value.to_nlas()))
+                .collect::<Vec<_>>()
+                .as_slice()
+                .emit(buffer),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        use self::TeamNla::*;
+        matches!(*self, Options(_) | Ports(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<TeamNla> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<TeamNla, DecodeError> {
+        use self::TeamNla::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            TEAM_ATTR_TEAM_IFINDEX => {
+                TeamIfIndex(parse_u32(payload).context("invalid TEAM_ATTR_TEAM_IFINDEX value")?)
+            }
+            TEAM_ATTR_LIST_OPTION => {
+                Options(parse_nested(payload).context("invalid TEAM_ATTR_LIST_OPTION value")?)
+            }
+            TEAM_ATTR_LIST_PORT => {
+                Ports(parse_nested(payload).context("invalid TEAM_ATTR_LIST_PORT value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
@@ -0,0 +1,185 @@
+use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_i32, parse_string, parse_u32, parse_u8};
+use crate::{DecodeError, GenlAttr, NlaBuffer, NlasIterator, Parseable};
+
+/// Value of a team option. Its representation depends on the type of the option
+/// (`TEAM_OPTION_TYPE_*`).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TeamOptionData {
+    U32(u32),
+    String(String),
+    Binary(Vec<u8>),
+    Bool(bool),
+    S32(i32),
+    /// Value of an option which type is unknown: type and raw data
+    Other(u8, Vec<u8>),
+}
+
+impl TeamOptionData {
+    /// Type of the option (`TEAM_OPTION_TYPE_*`)
+    pub fn option_type(&self) -> u8 {
+        use self::TeamOptionData::*;
+        match *self {
+            U32(_) => TEAM_OPTION_TYPE_U32,
+            String(_) => TEAM_OPTION_TYPE_STRING,
+            Binary(_) => TEAM_OPTION_TYPE_BINARY,
+            Bool(_) => TEAM_OPTION_TYPE_BOOL,
+            S32(_) => TEAM_OPTION_TYPE_S32,
+            Other(kind, _) => kind,
+        }
+    }
+
+    /// Value of the `TEAM_ATTR_OPTION_DATA` attribute. Boolean options are represented by the
+    /// presence of the attribute, so `None` is returned for `Bool(false)`.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        use self::TeamOptionData::*;
+        match *self {
+            U32(value) => {
+                let mut bytes = vec![0; 4];
+                NativeEndian::write_u32(&mut bytes[..], value);
+                Some(bytes)
+            }
+            String(ref s) => {
+                let mut bytes = s.as_bytes().to_vec();
+                bytes.push(0);
+                Some(bytes)
+            }
+            Binary(ref bytes) | Other(_, ref bytes) => Some(bytes.clone()),
+            Bool(true) => Some(vec![]),
+            Bool(false) => None,
+            S32(value) => {
+                let mut bytes = vec![0; 4];
+                NativeEndian::write_i32(&mut bytes[..], value);
+                Some(bytes)
+            }
+        }
+    }
+
+    fn parse(kind: u8, data: Option<&[u8]>) -> Result<Self, DecodeError> {
+        use self::TeamOptionData::*;
+        let payload = data.unwrap_or(&[]);
+        Ok(match kind {
+            TEAM_OPTION_TYPE_U32 => U32(parse_u32(payload)?),
+            TEAM_OPTION_TYPE_STRING => String(parse_string(payload)?),
+            TEAM_OPTION_TYPE_BINARY => Binary(payload.to_vec()),
+            TEAM_OPTION_TYPE_BOOL => Bool(data.is_some()),
+            TEAM_OPTION_TYPE_S32 => S32(parse_i32(payload)?),
+            _ => Other(kind, payload.to_vec()),
+        })
+    }
+}
+
+/// An option of a team device, or of one of its ports (`TEAM_ATTR_LIST_OPTION` element).
+///
+/// Team options are the knobs of the team driver: the mode (`mode`), the ports state
+/// (`enabled`, `user_linkup`, `queue_id`, etc.), the load balancing settings (`lb_tx_method`,
+/// `lb_hash_stats`, etc.) and so on. Options that apply to a port carry the port index, and array
+/// options carry the index of the element.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TeamOption {
+    pub name: String,
+    /// Whether the option changed since the last notification
+    pub changed: bool,
+    /// Whether the option has been removed, for instance because its port left the team
+    pub removed: bool,
+    /// Index of the port, for per-port options
+    pub port_ifindex: Option<u32>,
+    /// Index of the element, for array options
+    pub array_index: Option<u32>,
+    pub data: TeamOptionData,
+}
+
+impl TeamOption {
+    /// Create a team-wide option with the given value
+    pub fn new(name: &str, data: TeamOptionData) -> Self {
+        TeamOption {
+            name: name.to_string(),
+            changed: false,
+            removed: false,
+            port_ifindex: None,
+            array_index: None,
+            data,
+        }
+    }
+
+    pub(crate) fn to_nlas(&self) -> Vec<GenlAttr> {
+        let mut nlas = vec![GenlAttr::Str(TEAM_ATTR_OPTION_NAME, self.name.clone())];
+        if self.changed {
+            nlas.push(GenlAttr::Flag(TEAM_ATTR_OPTION_CHANGED));
+        }
+        if self.removed {
+            nlas.push(GenlAttr::Flag(TEAM_ATTR_OPTION_REMOVED));
+        }
+        if let Some(ifindex) = self.port_ifindex {
+            nlas.push(GenlAttr::U32(TEAM_ATTR_OPTION_PORT_IFINDEX, ifindex));
+        }
+        if let Some(index) = self.array_index {
+            nlas.push(GenlAttr::U32(TEAM_ATTR_OPTION_ARRAY_INDEX, index));
+        }
+        nlas.push(GenlAttr::U8(TEAM_ATTR_OPTION_TYPE, self.data.option_type()));
+        match self.data.to_bytes() {
+            Some(ref bytes) if bytes.is_empty() => nlas.push(GenlAttr::Flag(TEAM_ATTR_OPTION_DATA)),
+            Some(bytes) => nlas.push(GenlAttr::Bytes(TEAM_ATTR_OPTION_DATA, bytes)),
+            None => {}
+        }
+        nlas
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<TeamOption> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<TeamOption, DecodeError> {
+        let mut name = String::new();
+        let mut changed = false;
+        let mut removed = false;
+        let mut port_ifindex = None;
+        let mut array_index = None;
+        let mut option_type = None;
+        let mut data = None;
+        for nla in NlasIterator::new(self.value()) {
+            let nla = nla?;
+            match nla.kind() {
+                TEAM_ATTR_OPTION_NAME => {
+                    name =
+                        parse_string(nla.value()).context("invalid TEAM_ATTR_OPTION_NAME value")?
+                }
+                TEAM_ATTR_OPTION_CHANGED => changed = true,
+                TEAM_ATTR_OPTION_REMOVED => removed = true,
+                TEAM_ATTR_OPTION_PORT_IFINDEX => {
+                    port_ifindex = Some(
+                        parse_u32(nla.value())
+                            .context("invalid TEAM_ATTR_OPTION_PORT_IFINDEX value")?,
+                    )
+                }
+                TEAM_ATTR_OPTION_ARRAY_INDEX => {
+                    array_index = Some(
+                        parse_u32(nla.value())
+                            .context("invalid TEAM_ATTR_OPTION_ARRAY_INDEX value")?,
+                    )
+                }
+                TEAM_ATTR_OPTION_TYPE => {
+                    option_type =
+                        Some(parse_u8(nla.value()).context("invalid TEAM_ATTR_OPTION_TYPE value")?)
+                }
+                TEAM_ATTR_OPTION_DATA => data = Some(nla.value().to_vec()),
+                _ => {}
+            }
+        }
+
+        // the value can only be decoded once the type of the option is known
+        let option_type = option_type.ok_or("missing TEAM_ATTR_OPTION_TYPE")?;
+        let data = TeamOptionData::parse(option_type, data.as_ref().map(|data| &data[..]))
+            .context("invalid TEAM_ATTR_OPTION_DATA value")?;
+
+        Ok(TeamOption {
+            name,
+            changed,
+            removed,
+            port_ifindex,
+            array_index,
+            data,
+        })
+    }
+}
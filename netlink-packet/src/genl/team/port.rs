@@ -0,0 +1,68 @@
+use failure::ResultExt;
+
+use crate::constants::*;
+use crate::utils::{parse_u32, parse_u8};
+use crate::{DecodeError, GenlAttr, NlaBuffer, NlasIterator, Parseable};
+
+/// A port of a team device (`TEAM_ATTR_LIST_PORT` element)
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct TeamPort {
+    /// Index of the port interface
+    pub ifindex: u32,
+    /// Whether the port changed since the last notification
+    pub changed: bool,
+    /// Whether the port has been removed from the team
+    pub removed: bool,
+    /// Whether the link of the port is up
+    pub linkup: bool,
+    /// Speed of the port, in Mb/s
+    pub speed: u32,
+    /// Duplex mode of the port (`DUPLEX_HALF` or `DUPLEX_FULL`)
+    pub duplex: u8,
+}
+
+impl TeamPort {
+    pub(crate) fn to_nlas(&self) -> Vec<GenlAttr> {
+        let mut nlas = vec![GenlAttr::U32(TEAM_ATTR_PORT_IFINDEX, self.ifindex)];
+        if self.changed {
+            nlas.push(GenlAttr::Flag(TEAM_ATTR_PORT_CHANGED));
+        }
+        if self.removed {
+            nlas.push(GenlAttr::Flag(TEAM_ATTR_PORT_REMOVED));
+        }
+        if self.linkup {
+            nlas.push(GenlAttr::Flag(TEAM_ATTR_PORT_LINKUP));
+        }
+        nlas.push(GenlAttr::U32(TEAM_ATTR_PORT_SPEED, self.speed));
+        nlas.push(GenlAttr::U8(TEAM_ATTR_PORT_DUPLEX, self.duplex));
+        nlas
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<TeamPort> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<TeamPort, DecodeError> {
+        let mut port = TeamPort::default();
+        for nla in NlasIterator::new(self.value()) {
+            let nla = nla?;
+            match nla.kind() {
+                TEAM_ATTR_PORT_IFINDEX => {
+                    port.ifindex =
+                        parse_u32(nla.value()).context("invalid TEAM_ATTR_PORT_IFINDEX value")?
+                }
+                TEAM_ATTR_PORT_CHANGED => port.changed = true,
+                TEAM_ATTR_PORT_REMOVED => port.removed = true,
+                TEAM_ATTR_PORT_LINKUP => port.linkup = true,
+                TEAM_ATTR_PORT_SPEED => {
+                    port.speed =
+                        parse_u32(nla.value()).context("invalid TEAM_ATTR_PORT_SPEED value")?
+                }
+                TEAM_ATTR_PORT_DUPLEX => {
+                    port.duplex =
+                        parse_u8(nla.value()).context("invalid TEAM_ATTR_PORT_DUPLEX value")?
+                }
+                _ => {}
+            }
+        }
+        Ok(port)
+    }
+}
@@ -2,10 +2,16 @@
 //! documentation][libnl] for an introduction to the Netlink protocols.
 //!
 //! This crate provides widely different types based on the features that are enabled. There are
-//! currently two features available, which are mutually exclusive `rtnetlink` and `audit`. With
-//! the `rtnetlink` feature, this crates provides types for the `NETLINK_ROUTE` protocol family
-//! (see `man 7 rtnetlink`). With the `audit` feature, this crate provides types for the
-//! `NETLINK_AUDIT` protocol family.
+//! currently three features available: `rtnetlink`, `audit` and `genetlink`. With the
+//! `rtnetlink` feature, this crates provides types for the `NETLINK_ROUTE` protocol family (see
+//! `man 7 rtnetlink`). With the `audit` feature, this crate provides types for the
+//! `NETLINK_AUDIT` protocol family. With the `genetlink` feature, this crate provides types for
+//! the `NETLINK_GENERIC` protocol family, and for the generic netlink families built on top of it
+//! (`nlctrl`, `ethtool`, `devlink`, `mptcp_pm`, `IPVS` and `team`).
+//!
+//! The features can be enabled together. The payload of a `NetlinkMessage` is then parsed
+//! according to the protocol of the socket it was received from, which is given to
+//! [`NetlinkBuffer::new_with_protocol`](struct.NetlinkBuffer.html#method.new_with_protocol).
 //!
 //! [libnl]: https://www.infradead.org/~tgr/libnl/doc/core.html#core_netlink_fundamentals
 //!
@@ -18,6 +24,7 @@
 //! ```no_rust
 //! cargo doc --open --features audit     # for the audit messages
 //! cargo doc --open --features rtnetlink # for the rtnetlink messages
+//! cargo doc --open --features genetlink # for the generic netlink messages
 //! ```
 //!
 //! # Overview
@@ -116,7 +123,7 @@
 
 #[cfg_attr(feature = "rtnetlink", macro_use)] extern crate log;
 
-pub use netlink_sys::constants;
+pub use netlink_sys::{constants, Protocol};
 
 mod errors;
 pub use self::errors::*;
@@ -127,10 +134,16 @@ pub(crate) type Field = Range<usize>;
 /// Represent a field that starts at a given index in a packet
 pub(crate) type Rest = RangeFrom<usize>;
 
-#[cfg(feature = "rtnetlink")]
+#[cfg(any(feature = "rtnetlink", feature = "genetlink"))]
 /// Represent a field of exactly one byte in a packet
 pub(crate) type Index = usize;
 
+mod nla;
+pub use self::nla::*;
+
+#[cfg_attr(not(feature = "rtnetlink"), allow(dead_code))]
+pub(crate) mod utils;
+
 #[cfg(feature = "rtnetlink")]
 /// rtnetlink types (see `man 7 rtnetlink`)
 mod rtnl;
@@ -142,6 +155,12 @@ mod audit;
 #[cfg(feature = "audit")]
 pub use self::audit::*;
 
+#[cfg(feature = "genetlink")]
+/// generic netlink types (see `man 8 genl`)
+mod genl;
+#[cfg(feature = "genetlink")]
+pub use self::genl::*;
+
 mod netlink;
 pub use self::netlink::*;
 
@@ -1,4 +1,5 @@
 use byteorder::{ByteOrder, NativeEndian};
+use netlink_sys::Protocol;
 
 use crate::{DecodeError, Field, NetlinkFlags, Rest};
 
@@ -96,6 +97,8 @@ pub const NETLINK_HEADER_LEN: usize = PAYLOAD.start;
 /// initialized to 0, so `new_checked()` would return an error.
 pub struct NetlinkBuffer<T> {
     buffer: T,
+    // Protocol of the socket the packet belongs to, which tells how to parse the payload
+    protocol: Option<Protocol>,
 }
 
 impl<T: AsRef<[u8]>> NetlinkBuffer<T> {
@@ -105,7 +108,28 @@ impl<T: AsRef<[u8]>> NetlinkBuffer<T> {
     /// [`new_checked()`](struct.NetlinkBuffer.html#method.new_checked) instead.
     ///
     pub fn new(buffer: T) -> NetlinkBuffer<T> {
-        NetlinkBuffer { buffer }
+        NetlinkBuffer {
+            buffer,
+            protocol: None,
+        }
+    }
+
+    /// Create a new `NetlinkBuffer` for a packet of the given protocol (the protocol of the
+    /// socket it was received from). When several protocol features are enabled, this is how the
+    /// payload of a `NetlinkMessage` is parsed into the right type: a buffer created with
+    /// [`new()`](struct.NetlinkBuffer.html#method.new) is parsed as `rtnetlink`, `audit`, or
+    /// `genetlink`, whichever is enabled first in that order.
+    pub fn new_with_protocol(buffer: T, protocol: Protocol) -> NetlinkBuffer<T> {
+        NetlinkBuffer {
+            buffer,
+            protocol: Some(protocol),
+        }
+    }
+
+    /// Return the protocol given to
+    /// [`new_with_protocol()`](struct.NetlinkBuffer.html#method.new_with_protocol), if any
+    pub fn protocol(&self) -> Option<Protocol> {
+        self.protocol
     }
 
     /// Check the length of the given buffer and make sure it's big enough so that trying to access
@@ -1,18 +1,47 @@
-use crate::{DecodeError, Emitable, Field, Parseable, Rest};
+use crate::constants::*;
+use crate::utils::{parse_string, parse_u32};
+use crate::{
+    DecodeError, DefaultNla, Emitable, Field, NetlinkFlags, Nla, NlaBuffer, NlasIterator,
+    Parseable, Rest,
+};
 use byteorder::{ByteOrder, NativeEndian};
+use failure::ResultExt;
+use std::fmt;
+use std::io;
 use std::mem::size_of;
 
 const CODE: Field = 0..4;
 const PAYLOAD: Rest = 4..;
+// Length of the netlink header, which is all that is echoed when the error message is capped
+const ECHOED_HEADER_LEN: usize = 16;
+
+// The NLMSGERR_ATTR_* constants are C integers, but attribute kinds are u16
+const ATTR_MSG: u16 = NLMSGERR_ATTR_MSG as u16;
+const ATTR_OFFS: u16 = NLMSGERR_ATTR_OFFS as u16;
+const ATTR_COOKIE: u16 = NLMSGERR_ATTR_COOKIE as u16;
+const ATTR_POLICY: u16 = NLMSGERR_ATTR_POLICY as u16;
+const ATTR_MISS_TYPE: u16 = NLMSGERR_ATTR_MISS_TYPE as u16;
+const ATTR_MISS_NEST: u16 = NLMSGERR_ATTR_MISS_NEST as u16;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ErrorBuffer<T> {
     buffer: T,
+    flags: NetlinkFlags,
 }
 
 impl<T: AsRef<[u8]>> ErrorBuffer<T> {
     pub fn new(buffer: T) -> ErrorBuffer<T> {
-        ErrorBuffer { buffer }
+        ErrorBuffer {
+            buffer,
+            flags: NetlinkFlags::new(),
+        }
+    }
+
+    /// Create a buffer for the payload of a `NLMSG_ERROR` message which header has the given
+    /// flags. With extended acks, the kernel sets `NLM_F_CAPPED` when it only echoed the header
+    /// of the original message, and `NLM_F_ACK_TLVS` when it appended attributes after it.
+    pub fn new_with_flags(buffer: T, flags: NetlinkFlags) -> ErrorBuffer<T> {
+        ErrorBuffer { buffer, flags }
     }
 
     /// Consume the packet, returning the underlying buffer.
@@ -25,6 +54,22 @@ impl<T: AsRef<[u8]>> ErrorBuffer<T> {
         let data = self.buffer.as_ref();
         NativeEndian::read_i32(&data[CODE])
     }
+
+    /// Return the length of the echoed original message
+    fn echoed_len(&self) -> usize {
+        let payload_len = self.buffer.as_ref().len() - CODE.end;
+        if !self.flags.has_ack_tvls() {
+            return payload_len;
+        }
+        let len = if self.flags.has_capped() || payload_len < size_of::<u32>() {
+            ECHOED_HEADER_LEN
+        } else {
+            // the length of the original message is the first field of its header
+            let data = &self.buffer.as_ref()[PAYLOAD];
+            (NativeEndian::read_u32(&data[..4]) as usize + 3) & !3
+        };
+        len.min(payload_len)
+    }
 }
 
 impl<'a, T: AsRef<[u8]> + ?Sized> ErrorBuffer<&'a T> {
@@ -33,6 +78,18 @@ impl<'a, T: AsRef<[u8]> + ?Sized> ErrorBuffer<&'a T> {
         let data = self.buffer.as_ref();
         &data[PAYLOAD]
     }
+
+    /// Return a pointer to the original message echoed by the kernel (or only its header if the
+    /// message is capped).
+    pub fn echoed_message(&self) -> &'a [u8] {
+        &self.payload()[..self.echoed_len()]
+    }
+
+    /// Return an iterator over the extended ack attributes (`NLMSGERR_ATTR_*`) that follow the
+    /// echoed message.
+    pub fn nlas(&self) -> impl Iterator<Item = Result<NlaBuffer<&'a [u8]>, DecodeError>> {
+        NlasIterator::new(&self.payload()[self.echoed_len()..])
+    }
 }
 
 impl<'a, T: AsRef<[u8]> + AsMut<[u8]> + ?Sized> ErrorBuffer<&'a mut T> {
@@ -51,26 +108,179 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> ErrorBuffer<T> {
     }
 }
 
+/// Extended acknowledgement attributes (`NLMSGERR_ATTR_*`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ExtAckNla {
+    /// Human readable error message
+    Msg(String),
+    /// Offset of the attribute that caused the error, from the beginning of the original message
+    Offset(u32),
+    /// Subsystem specific data, that can identify the object created by a successful request
+    Cookie(Vec<u8>),
+    /// Policy of the rejected attribute (nested attributes)
+    Policy(Vec<u8>),
+    /// Type of a missing required attribute
+    MissingType(u32),
+    /// Offset of the nested attribute in which a required attribute is missing
+    MissingNest(u32),
+    Other(DefaultNla),
+}
+
+impl Nla for ExtAckNla {
+    #[rustfmt::skip]
+    fn value_len(&self) -> usize {
+        use self::ExtAckNla::*;
+        match *self {
+            Msg(ref s) => s.len() + 1,
+            Offset(_)
+                | MissingType(_)
+                | MissingNest(_) => size_of::<u32>(),
+            Cookie(ref bytes)
+                | Policy(ref bytes) => bytes.len(),
+            Other(ref nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        use self::ExtAckNla::*;
+        match *self {
+            Msg(_) => ATTR_MSG,
+            Offset(_) => ATTR_OFFS,
+            Cookie(_) => ATTR_COOKIE,
+            Policy(_) => ATTR_POLICY,
+            MissingType(_) => ATTR_MISS_TYPE,
+            MissingNest(_) => ATTR_MISS_NEST,
+            Other(ref nla) => nla.kind(),
+        }
+    }
+
+    #[rustfmt::skip]
+    fn emit_value(&self, buffer: &mut [u8]) {
+        use self::ExtAckNla::*;
+        match *self {
+            Msg(ref s) => {
+                buffer[..s.len()].copy_from_slice(s.as_bytes());
+                buffer[s.len()] = 0;
+            }
+            Offset(value)
+                | MissingType(value)
+                | MissingNest(value) => NativeEndian::write_u32(buffer, value),
+            Cookie(ref bytes)
+                | Policy(ref bytes) => buffer.copy_from_slice(bytes.as_slice()),
+            Other(ref nla) => nla.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(*self, ExtAckNla::Policy(_))
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> Parseable<ExtAckNla> for NlaBuffer<&T> {
+    fn parse(&self) -> Result<ExtAckNla, DecodeError> {
+        use self::ExtAckNla::*;
+        let payload = self.value();
+        Ok(match self.kind() {
+            ATTR_MSG => Msg(parse_string(payload).context("invalid NLMSGERR_ATTR_MSG value")?),
+            ATTR_OFFS => Offset(parse_u32(payload).context("invalid NLMSGERR_ATTR_OFFS value")?),
+            ATTR_COOKIE => Cookie(payload.to_vec()),
+            ATTR_POLICY => Policy(payload.to_vec()),
+            ATTR_MISS_TYPE => {
+                MissingType(parse_u32(payload).context("invalid NLMSGERR_ATTR_MISS_TYPE value")?)
+            }
+            ATTR_MISS_NEST => {
+                MissingNest(parse_u32(payload).context("invalid NLMSGERR_ATTR_MISS_NEST value")?)
+            }
+            kind => Other(
+                <Self as Parseable<DefaultNla>>::parse(self)
+                    .context(format!("unknown NLA type {}", kind))?,
+            ),
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ErrorMessage {
     pub code: i32,
+    /// The original message, or only its header if the kernel capped the error message
     pub header: Vec<u8>,
+    /// Extended acknowledgement attributes, sent by the kernel if `NETLINK_EXT_ACK` is set on the
+    /// socket
+    pub nlas: Vec<ExtAckNla>,
 }
 
 pub type AckMessage = ErrorMessage;
 
+impl ErrorMessage {
+    /// Return the error message sent by the kernel, if any (`NLMSGERR_ATTR_MSG`)
+    pub fn ext_msg(&self) -> Option<&str> {
+        self.nlas.iter().find_map(|nla| match *nla {
+            ExtAckNla::Msg(ref s) => Some(s.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Return the offset of the attribute that caused the error, from the beginning of the
+    /// original message, if any (`NLMSGERR_ATTR_OFFS`)
+    pub fn ext_offset(&self) -> Option<u32> {
+        self.nlas.iter().find_map(|nla| match *nla {
+            ExtAckNla::Offset(offset) => Some(offset),
+            _ => None,
+        })
+    }
+
+    /// Return the cookie sent by the kernel, if any (`NLMSGERR_ATTR_COOKIE`)
+    pub fn ext_cookie(&self) -> Option<&[u8]> {
+        self.nlas.iter().find_map(|nla| match *nla {
+            ExtAckNla::Cookie(ref cookie) => Some(cookie.as_slice()),
+            _ => None,
+        })
+    }
+}
+
+impl ErrorMessage {
+    // Set the flags the header of the NLMSG_ERROR message needs for this payload to be parsed
+    // back: NLM_F_ACK_TLVS if there are extended ack attributes, and NLM_F_CAPPED if only the
+    // header of the original message is echoed before them
+    pub(crate) fn set_header_flags(&self, flags: &mut NetlinkFlags) {
+        if self.nlas.is_empty() {
+            return;
+        }
+        flags.set_ack_tvls();
+        if self.header.len() >= size_of::<u32>()
+            && NativeEndian::read_u32(&self.header[..4]) as usize > self.header.len()
+        {
+            flags.set_capped();
+        }
+    }
+}
+
+impl fmt::Display for ErrorMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", io::Error::from_raw_os_error(-self.code))?;
+        if let Some(msg) = self.ext_msg() {
+            write!(f, ": {}", msg)?;
+        }
+        Ok(())
+    }
+}
+
 impl Emitable for ErrorMessage {
     fn buffer_len(&self) -> usize {
-        size_of::<i32>() + self.header.len()
+        size_of::<i32>() + self.header.len() + self.nlas.as_slice().buffer_len()
     }
     fn emit(&self, buffer: &mut [u8]) {
         let mut buffer = ErrorBuffer::new(buffer);
         buffer.set_code(self.code);
-        buffer.payload_mut().copy_from_slice(&self.header)
+        let payload = buffer.payload_mut();
+        payload[..self.header.len()].copy_from_slice(&self.header);
+        self.nlas.as_slice().emit(&mut payload[self.header.len()..]);
     }
 }
 
-impl<'buffer, T: AsRef<[u8]> + 'buffer> Parseable<ErrorMessage> for ErrorBuffer<&'buffer T> {
+impl<'buffer, T: AsRef<[u8]> + ?Sized + 'buffer> Parseable<ErrorMessage>
+    for ErrorBuffer<&'buffer T>
+{
     fn parse(&self) -> Result<ErrorMessage, DecodeError> {
         // FIXME: The payload of an error is basically a truncated packet, which requires custom
         // logic to parse correctly. For now we just return it as a Vec<u8>
@@ -80,9 +290,96 @@ impl<'buffer, T: AsRef<[u8]> + 'buffer> Parseable<ErrorMessage> for ErrorBuffer<
         //         .parse()
         //         .context("failed to parse nelink header")?
         // };
+        let mut nlas = vec![];
+        if self.flags.has_ack_tvls() {
+            for nla_buf in self.nlas() {
+                nlas.push(nla_buf?.parse().context("invalid extended ack attribute")?);
+            }
+        }
         Ok(ErrorMessage {
             code: self.code(),
-            header: self.payload().to_vec(),
+            header: self.echoed_message().to_vec(),
+            nlas,
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{NetlinkBuffer, NetlinkMessage, NetlinkPayload};
+
+    #[rustfmt::skip]
+    static CAPPED_ERROR: [u8; 44] = [
+        0xea, 0xff, 0xff, 0xff, // error code: -EINVAL
+
+        // echoed header of the original message
+        0x28, 0x00, 0x00, 0x00, // length
+        0x10, 0x00, // message type: RTM_NEWLINK
+        0x05, 0x06, // flags
+        0x01, 0x00, 0x00, 0x00, // sequence number
+        0x00, 0x00, 0x00, 0x00, // port number
+
+        // NLMSGERR_ATTR_MSG
+        0x0d, 0x00, 0x01, 0x00,
+        0x62, 0x61, 0x64, 0x20, 0x61, 0x74, 0x74, 0x72, 0x00, // "bad attr"
+        0x00, 0x00, 0x00, // padding
+
+        // NLMSGERR_ATTR_OFFS
+        0x08, 0x00, 0x02, 0x00,
+        0x20, 0x00, 0x00, 0x00,
+    ];
+
+    fn capped_error() -> ErrorMessage {
+        ErrorMessage {
+            code: -22,
+            header: CAPPED_ERROR[4..20].to_vec(),
+            nlas: vec![ExtAckNla::Msg("bad attr".into()), ExtAckNla::Offset(32)],
+        }
+    }
+
+    #[test]
+    fn parse_capped_error_with_ext_ack() {
+        let mut flags = NetlinkFlags::new();
+        flags.set_capped().set_ack_tvls();
+        let msg: ErrorMessage = ErrorBuffer::new_with_flags(&CAPPED_ERROR[..], flags)
+            .parse()
+            .unwrap();
+        assert_eq!(msg, capped_error());
+        assert_eq!(msg.ext_msg(), Some("bad attr"));
+        assert_eq!(msg.ext_offset(), Some(32));
+        assert_eq!(msg.ext_cookie(), None);
+    }
+
+    #[test]
+    fn parse_error_without_ext_ack() {
+        // without NLM_F_ACK_TLVS, everything after the error code is the echoed message
+        let msg: ErrorMessage = ErrorBuffer::new(&CAPPED_ERROR[..]).parse().unwrap();
+        assert_eq!(msg.header, &CAPPED_ERROR[4..]);
+        assert!(msg.nlas.is_empty());
+    }
+
+    #[test]
+    fn emit_capped_error_with_ext_ack() {
+        let msg = capped_error();
+        assert_eq!(msg.buffer_len(), CAPPED_ERROR.len());
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf[..]);
+        assert_eq!(&buf[..], &CAPPED_ERROR[..]);
+    }
+
+    #[test]
+    fn error_with_ext_ack_round_trip() {
+        let mut message = NetlinkMessage::from(NetlinkPayload::Error(capped_error()));
+        message.finalize();
+        let flags = message.header().flags();
+        assert!(flags.has_ack_tvls());
+        assert!(flags.has_capped());
+
+        let mut buf = vec![0; message.buffer_len()];
+        message.emit(&mut buf[..]);
+        let buffer = &buf[..];
+        let parsed: NetlinkMessage = NetlinkBuffer::new(&buffer).parse().unwrap();
+        assert_eq!(parsed, message);
+    }
+}
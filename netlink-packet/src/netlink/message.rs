@@ -1,5 +1,7 @@
 use crate::constants::*;
 use failure::ResultExt;
+#[cfg(any(feature = "rtnetlink", feature = "audit", feature = "genetlink"))]
+use netlink_sys::Protocol;
 
 use crate::{
     AckMessage, DecodeError, Emitable, ErrorBuffer, ErrorMessage, NetlinkBuffer, NetlinkHeader,
@@ -12,6 +14,9 @@ use crate::RtnlMessage;
 #[cfg(feature = "audit")]
 use crate::AuditMessage;
 
+#[cfg(feature = "genetlink")]
+use crate::GenlMessage;
+
 /// Represent a netlink message.
 ///
 /// A netlink message is made of a header (represented by
@@ -52,7 +57,9 @@ pub enum NetlinkPayload {
     Rtnl(RtnlMessage),
     #[cfg(feature = "audit")]
     Audit(AuditMessage),
-    #[cfg(not(any(feature = "rtnetlink", feature = "audit")))]
+    #[cfg(feature = "genetlink")]
+    Genl(GenlMessage),
+    #[cfg(not(any(feature = "rtnetlink", feature = "audit", feature = "genetlink")))]
     #[doc(hidden)]
     __Default,
 }
@@ -70,7 +77,9 @@ impl NetlinkPayload {
             Rtnl(ref msg) => msg.message_type(),
             #[cfg(feature = "audit")]
             Audit(ref msg) => msg.message_type(),
-            #[cfg(not(any(feature = "rtnetlink", feature = "audit")))]
+            #[cfg(feature = "genetlink")]
+            Genl(ref msg) => msg.message_type(),
+            #[cfg(not(any(feature = "rtnetlink", feature = "audit", feature = "genetlink")))]
             _ => 0,
         }
     }
@@ -93,6 +102,11 @@ impl NetlinkPayload {
         }
     }
 
+    #[cfg(feature = "genetlink")]
+    pub fn is_genl(&self) -> bool {
+        matches!(*self, NetlinkPayload::Genl(_))
+    }
+
     pub fn is_done(&self) -> bool {
         *self == NetlinkPayload::Done
     }
@@ -149,6 +163,13 @@ impl From<AuditMessage> for NetlinkMessage {
     }
 }
 
+#[cfg(feature = "genetlink")]
+impl From<GenlMessage> for NetlinkMessage {
+    fn from(msg: GenlMessage) -> Self {
+        NetlinkMessage::from(NetlinkPayload::Genl(msg))
+    }
+}
+
 impl NetlinkMessage {
     pub fn new(header: NetlinkHeader, payload: NetlinkPayload) -> Self {
         NetlinkMessage { header, payload }
@@ -204,6 +225,13 @@ impl NetlinkMessage {
         self.payload().is_ack()
     }
 
+    /// Check if the `NLM_F_DUMP_INTR` flag is set. The kernel sets it on the messages of a dump
+    /// when its tables changed while the dump was in progress, in which case the dump may be
+    /// inconsistent and should be retried.
+    pub fn is_dump_interrupted(&self) -> bool {
+        self.header.flags().has_dump_intr()
+    }
+
     #[cfg(feature = "rtnetlink")]
     pub fn is_rtnl(&self) -> bool {
         self.payload().is_rtnl()
@@ -214,10 +242,17 @@ impl NetlinkMessage {
         self.payload().is_audit()
     }
 
+    #[cfg(feature = "genetlink")]
+    pub fn is_genl(&self) -> bool {
+        self.payload().is_genl()
+    }
+
     /// Ensure the header (`NetlinkHeader`) is consistent with the payload (`NetlinkPayload`):
     ///
     /// - compute the payload length and set the header's length field
     /// - check the payload type and set the header's message type field accordingly
+    /// - for error messages with extended ack attributes, set the `NLM_F_ACK_TLVS` flag (and
+    ///   `NLM_F_CAPPED` if only the header of the original message is echoed)
     ///
     /// If you are not 100% sure the header is correct, this method should be called before calling
     /// [`Emitable::emit()`](trait.Emitable.html#tymethod.emit), as it could panic if the header is
@@ -225,9 +260,24 @@ impl NetlinkMessage {
     pub fn finalize(&mut self) {
         *self.header.length_mut() = self.buffer_len() as u32;
         *self.header.message_type_mut() = self.payload.message_type();
+        if let NetlinkPayload::Error(ref msg) | NetlinkPayload::Ack(ref msg) = self.payload {
+            msg.set_header_flags(self.header.flags_mut());
+        }
     }
 }
 
+// Protocol of the packets which buffer was created without one (see
+// `NetlinkBuffer::new_with_protocol`)
+#[cfg(feature = "rtnetlink")]
+const DEFAULT_PROTOCOL: Protocol = Protocol::Route;
+#[cfg(all(feature = "audit", not(feature = "rtnetlink")))]
+const DEFAULT_PROTOCOL: Protocol = Protocol::Audit;
+#[cfg(all(
+    feature = "genetlink",
+    not(any(feature = "rtnetlink", feature = "audit"))
+))]
+const DEFAULT_PROTOCOL: Protocol = Protocol::Generic;
+
 impl<'buffer, T: AsRef<[u8]> + 'buffer> Parseable<NetlinkMessage> for NetlinkBuffer<&'buffer T> {
     fn parse(&self) -> Result<NetlinkMessage, DecodeError> {
         use self::NetlinkPayload::*;
@@ -236,9 +286,10 @@ impl<'buffer, T: AsRef<[u8]> + 'buffer> Parseable<NetlinkMessage> for NetlinkBuf
 
         let payload = match header.message_type() {
             NLMSG_ERROR => {
-                let msg: ErrorMessage = ErrorBuffer::new(&self.payload())
-                    .parse()
-                    .context("failed to parse NLMSG_ERROR")?;
+                let msg: ErrorMessage =
+                    ErrorBuffer::new_with_flags(&self.payload(), header.flags())
+                        .parse()
+                        .context("failed to parse NLMSG_ERROR")?;
                 if msg.code >= 0 {
                     Ack(msg as AckMessage)
                 } else {
@@ -248,17 +299,33 @@ impl<'buffer, T: AsRef<[u8]> + 'buffer> Parseable<NetlinkMessage> for NetlinkBuf
             NLMSG_NOOP => Noop,
             NLMSG_DONE => Done,
 
-            #[cfg(feature = "rtnetlink")]
-            message_type => {
-                NetlinkPayload::Rtnl(RtnlMessage::parse(message_type, &self.payload())?)
-            }
+            #[cfg(any(feature = "rtnetlink", feature = "audit", feature = "genetlink"))]
+            message_type => match self.protocol().unwrap_or(DEFAULT_PROTOCOL) {
+                #[cfg(feature = "rtnetlink")]
+                Protocol::Route => {
+                    NetlinkPayload::Rtnl(RtnlMessage::parse(message_type, self.payload())?)
+                }
 
-            #[cfg(feature = "audit")]
-            message_type => {
-                NetlinkPayload::Audit(AuditMessage::parse(message_type, &self.payload())?)
-            }
+                #[cfg(feature = "audit")]
+                Protocol::Audit => {
+                    NetlinkPayload::Audit(AuditMessage::parse(message_type, self.payload())?)
+                }
 
-            #[cfg(not(any(feature = "rtnetlink", feature = "audit")))]
+                #[cfg(feature = "genetlink")]
+                Protocol::Generic => {
+                    NetlinkPayload::Genl(GenlMessage::parse(message_type, self.payload())?)
+                }
+
+                protocol => {
+                    return Err(format!(
+                        "cannot parse the payload of {:?} messages (is the feature enabled?)",
+                        protocol
+                    )
+                    .into())
+                }
+            },
+
+            #[cfg(not(any(feature = "rtnetlink", feature = "audit", feature = "genetlink")))]
             _ => __Default,
         };
         Ok(NetlinkMessage { header, payload })
@@ -280,7 +347,10 @@ impl Emitable for NetlinkMessage {
             #[cfg(feature = "audit")]
             Audit(ref msg) => msg.buffer_len(),
 
-            #[cfg(not(any(feature = "rtnetlink", feature = "audit")))]
+            #[cfg(feature = "genetlink")]
+            Genl(ref msg) => msg.buffer_len(),
+
+            #[cfg(not(any(feature = "rtnetlink", feature = "audit", feature = "genetlink")))]
             __Default => 0,
         };
 
@@ -305,8 +375,107 @@ impl Emitable for NetlinkMessage {
             #[cfg(feature = "audit")]
             Audit(ref msg) => msg.emit(buffer),
 
-            #[cfg(not(any(feature = "rtnetlink", feature = "audit")))]
+            #[cfg(feature = "genetlink")]
+            Genl(ref msg) => msg.emit(buffer),
+
+            #[cfg(not(any(feature = "rtnetlink", feature = "audit", feature = "genetlink")))]
             __Default => {}
         }
     }
 }
+
+#[cfg(all(test, feature = "rtnetlink", feature = "audit", feature = "genetlink"))]
+mod tests {
+    use super::*;
+    use crate::{CtrlMessage, GenlPayload};
+
+    // an AUDIT_GET request, which payload is empty
+    #[rustfmt::skip]
+    static AUDIT_GET_PKT: [u8; 16] = [
+        0x10, 0x00, 0x00, 0x00, // length = 16
+        0xe8, 0x03, // message type = 1000 (AUDIT_GET)
+        0x05, 0x00, // flags = Request + Ack
+        0x01, 0x00, 0x00, 0x00, // sequence number = 1
+        0x00, 0x00, 0x00, 0x00, // port id = 0
+    ];
+
+    // a CTRL_CMD_GETFAMILY request without attributes, sent to the nlctrl family
+    #[rustfmt::skip]
+    static CTRL_GETFAMILY_PKT: [u8; 20] = [
+        0x14, 0x00, 0x00, 0x00, // length = 20
+        0x10, 0x00, // message type = 16 (GENL_ID_CTRL, but also RTM_NEWLINK)
+        0x01, 0x00, // flags = Request
+        0x01, 0x00, 0x00, 0x00, // sequence number = 1
+        0x00, 0x00, 0x00, 0x00, // port id = 0
+        // generic netlink header
+        0x03, // command = CTRL_CMD_GETFAMILY
+        0x01, // version
+        0x00, 0x00, // reserved
+    ];
+
+    fn parse(buffer: NetlinkBuffer<&&[u8]>) -> Result<NetlinkMessage, DecodeError> {
+        buffer.parse()
+    }
+
+    #[test]
+    fn parse_audit_message() {
+        let buffer = &AUDIT_GET_PKT[..];
+        let message = parse(NetlinkBuffer::new_with_protocol(&buffer, Protocol::Audit)).unwrap();
+        assert_eq!(
+            message.payload(),
+            &NetlinkPayload::Audit(AuditMessage::GetStatus(None))
+        );
+        // without a protocol, the payload is parsed as rtnetlink, which does not know this type
+        assert!(parse(NetlinkBuffer::new(&buffer)).is_err());
+    }
+
+    #[test]
+    fn parse_genl_message() {
+        let buffer = &CTRL_GETFAMILY_PKT[..];
+        let message = parse(NetlinkBuffer::new_with_protocol(&buffer, Protocol::Generic)).unwrap();
+        assert_eq!(
+            message.payload(),
+            &NetlinkPayload::Genl(GenlMessage::new(
+                GENL_ID_CTRL,
+                GenlPayload::Ctrl(CtrlMessage::GetFamily(vec![]))
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_unsupported_protocol() {
+        let buffer = &AUDIT_GET_PKT[..];
+        assert!(parse(NetlinkBuffer::new_with_protocol(
+            &buffer,
+            Protocol::Netfilter
+        ))
+        .is_err());
+    }
+}
+
+// Control messages, which payload does not depend on the protocol
+#[cfg(test)]
+mod control_tests {
+    use super::*;
+
+    // end of a dump during which the kernel tables changed
+    #[rustfmt::skip]
+    static INTERRUPTED_DONE_PKT: [u8; 20] = [
+        0x14, 0x00, 0x00, 0x00, // length = 20
+        0x03, 0x00, // message type = 3 (NLMSG_DONE)
+        0x12, 0x00, // flags = Multipart + Dump Interrupted
+        0x34, 0x0e, 0xf9, 0x5a, // sequence number = 1526271540
+        0x00, 0x00, 0x00, 0x00, // port id = 0
+        // payload
+        0x00, 0x00, 0x00, 0x00];
+
+    #[test]
+    fn parse_dump_interrupted() {
+        let bytes = &INTERRUPTED_DONE_PKT[..];
+        let message: NetlinkMessage = NetlinkBuffer::new_checked(&bytes).unwrap().parse().unwrap();
+        assert!(message.is_done());
+        assert!(message.is_dump_interrupted());
+        assert!(message.header().flags().has_multipart());
+        assert!(message.header().flags().has_dump_intr());
+    }
+}
@@ -174,6 +174,12 @@ pub trait Nla {
     fn kind(&self) -> u16;
 
     fn emit_value(&self, buffer: &mut [u8]);
+
+    /// Whether the `NLA_F_NESTED` flag should be set when emitting this attribute. Some protocols
+    /// (generic netlink families such as `ethtool` in particular) strictly validate this flag.
+    fn is_nested(&self) -> bool {
+        false
+    }
 }
 
 impl<T: Nla> Emitable for T {
@@ -185,6 +191,9 @@ impl<T: Nla> Emitable for T {
     fn emit(&self, buffer: &mut [u8]) {
         let mut buffer = NlaBuffer::new(buffer);
         buffer.set_kind(self.kind());
+        if self.is_nested() {
+            buffer.set_nested_flag();
+        }
         // do not include the padding here, but do include the header
         buffer.set_length(self.value_len() as u16 + 4);
         self.emit_value(buffer.value_mut());
@@ -0,0 +1,164 @@
+use crate::constants::*;
+
+/// Multicast groups of the `NETLINK_ROUTE` protocol (`RTNLGRP_*`). Sockets that subscribe to a
+/// group receive a notification each time an object of that group is created, updated or deleted.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum RtnlGroup {
+    /// `RTNLGRP_LINK`: links
+    Link,
+    /// `RTNLGRP_NOTIFY`
+    Notify,
+    /// `RTNLGRP_NEIGH`: neighbours
+    Neigh,
+    /// `RTNLGRP_TC`: traffic control
+    Tc,
+    /// `RTNLGRP_IPV4_IFADDR`: IPv4 addresses
+    Ipv4Ifaddr,
+    /// `RTNLGRP_IPV4_MROUTE`: IPv4 multicast routes
+    Ipv4Mroute,
+    /// `RTNLGRP_IPV4_ROUTE`: IPv4 routes
+    Ipv4Route,
+    /// `RTNLGRP_IPV4_RULE`: IPv4 routing rules
+    Ipv4Rule,
+    /// `RTNLGRP_IPV6_IFADDR`: IPv6 addresses
+    Ipv6Ifaddr,
+    /// `RTNLGRP_IPV6_MROUTE`: IPv6 multicast routes
+    Ipv6Mroute,
+    /// `RTNLGRP_IPV6_ROUTE`: IPv6 routes
+    Ipv6Route,
+    /// `RTNLGRP_IPV6_IFINFO`: IPv6 settings of the links
+    Ipv6Ifinfo,
+    /// `RTNLGRP_DECNET_IFADDR`
+    DecnetIfaddr,
+    /// `RTNLGRP_DECNET_ROUTE`
+    DecnetRoute,
+    /// `RTNLGRP_DECNET_RULE`
+    DecnetRule,
+    /// `RTNLGRP_IPV6_PREFIX`: IPv6 prefixes
+    Ipv6Prefix,
+    /// `RTNLGRP_IPV6_RULE`: IPv6 routing rules
+    Ipv6Rule,
+    /// `RTNLGRP_ND_USEROPT`: neighbour discovery user options
+    NdUseropt,
+    /// `RTNLGRP_PHONET_IFADDR`
+    PhonetIfaddr,
+    /// `RTNLGRP_PHONET_ROUTE`
+    PhonetRoute,
+    /// `RTNLGRP_DCB`: data center bridging
+    Dcb,
+    /// `RTNLGRP_IPV4_NETCONF`: IPv4 settings of the links
+    Ipv4Netconf,
+    /// `RTNLGRP_IPV6_NETCONF`: IPv6 settings of the links
+    Ipv6Netconf,
+    /// `RTNLGRP_MDB`: multicast database of the bridges
+    Mdb,
+    /// `RTNLGRP_MPLS_ROUTE`: MPLS routes
+    MplsRoute,
+    /// `RTNLGRP_NSID`: network namespace identifiers
+    Nsid,
+    /// `RTNLGRP_MPLS_NETCONF`: MPLS settings of the links
+    MplsNetconf,
+    /// `RTNLGRP_IPV4_MROUTE_R`
+    Ipv4MrouteR,
+    /// `RTNLGRP_IPV6_MROUTE_R`
+    Ipv6MrouteR,
+    /// Another group
+    Other(u32),
+}
+
+impl From<u32> for RtnlGroup {
+    fn from(value: u32) -> Self {
+        use self::RtnlGroup::*;
+        match value as i32 {
+            RTNLGRP_LINK => Link,
+            RTNLGRP_NOTIFY => Notify,
+            RTNLGRP_NEIGH => Neigh,
+            RTNLGRP_TC => Tc,
+            RTNLGRP_IPV4_IFADDR => Ipv4Ifaddr,
+            RTNLGRP_IPV4_MROUTE => Ipv4Mroute,
+            RTNLGRP_IPV4_ROUTE => Ipv4Route,
+            RTNLGRP_IPV4_RULE => Ipv4Rule,
+            RTNLGRP_IPV6_IFADDR => Ipv6Ifaddr,
+            RTNLGRP_IPV6_MROUTE => Ipv6Mroute,
+            RTNLGRP_IPV6_ROUTE => Ipv6Route,
+            RTNLGRP_IPV6_IFINFO => Ipv6Ifinfo,
+            RTNLGRP_DECNET_IFADDR => DecnetIfaddr,
+            RTNLGRP_DECNET_ROUTE => DecnetRoute,
+            RTNLGRP_DECNET_RULE => DecnetRule,
+            RTNLGRP_IPV6_PREFIX => Ipv6Prefix,
+            RTNLGRP_IPV6_RULE => Ipv6Rule,
+            RTNLGRP_ND_USEROPT => NdUseropt,
+            RTNLGRP_PHONET_IFADDR => PhonetIfaddr,
+            RTNLGRP_PHONET_ROUTE => PhonetRoute,
+            RTNLGRP_DCB => Dcb,
+            RTNLGRP_IPV4_NETCONF => Ipv4Netconf,
+            RTNLGRP_IPV6_NETCONF => Ipv6Netconf,
+            RTNLGRP_MDB => Mdb,
+            RTNLGRP_MPLS_ROUTE => MplsRoute,
+            RTNLGRP_NSID => Nsid,
+            RTNLGRP_MPLS_NETCONF => MplsNetconf,
+            RTNLGRP_IPV4_MROUTE_R => Ipv4MrouteR,
+            RTNLGRP_IPV6_MROUTE_R => Ipv6MrouteR,
+            _ => Other(value),
+        }
+    }
+}
+
+impl From<RtnlGroup> for u32 {
+    fn from(group: RtnlGroup) -> u32 {
+        use self::RtnlGroup::*;
+        let value = match group {
+            Link => RTNLGRP_LINK,
+            Notify => RTNLGRP_NOTIFY,
+            Neigh => RTNLGRP_NEIGH,
+            Tc => RTNLGRP_TC,
+            Ipv4Ifaddr => RTNLGRP_IPV4_IFADDR,
+            Ipv4Mroute => RTNLGRP_IPV4_MROUTE,
+            Ipv4Route => RTNLGRP_IPV4_ROUTE,
+            Ipv4Rule => RTNLGRP_IPV4_RULE,
+            Ipv6Ifaddr => RTNLGRP_IPV6_IFADDR,
+            Ipv6Mroute => RTNLGRP_IPV6_MROUTE,
+            Ipv6Route => RTNLGRP_IPV6_ROUTE,
+            Ipv6Ifinfo => RTNLGRP_IPV6_IFINFO,
+            DecnetIfaddr => RTNLGRP_DECNET_IFADDR,
+            DecnetRoute => RTNLGRP_DECNET_ROUTE,
+            DecnetRule => RTNLGRP_DECNET_RULE,
+            Ipv6Prefix => RTNLGRP_IPV6_PREFIX,
+            Ipv6Rule => RTNLGRP_IPV6_RULE,
+            NdUseropt => RTNLGRP_ND_USEROPT,
+            PhonetIfaddr => RTNLGRP_PHONET_IFADDR,
+            PhonetRoute => RTNLGRP_PHONET_ROUTE,
+            Dcb => RTNLGRP_DCB,
+            Ipv4Netconf => RTNLGRP_IPV4_NETCONF,
+            Ipv6Netconf => RTNLGRP_IPV6_NETCONF,
+            Mdb => RTNLGRP_MDB,
+            MplsRoute => RTNLGRP_MPLS_ROUTE,
+            Nsid => RTNLGRP_NSID,
+            MplsNetconf => RTNLGRP_MPLS_NETCONF,
+            Ipv4MrouteR => RTNLGRP_IPV4_MROUTE_R,
+            Ipv6MrouteR => RTNLGRP_IPV6_MROUTE_R,
+            Other(value) => return value,
+        };
+        value as u32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn group_conversions() {
+        assert_eq!(RtnlGroup::from(1), RtnlGroup::Link);
+        assert_eq!(
+            RtnlGroup::from(RTNLGRP_IPV6_IFADDR as u32),
+            RtnlGroup::Ipv6Ifaddr
+        );
+        assert_eq!(u32::from(RtnlGroup::Ipv4Ifaddr), RTNLGRP_IPV4_IFADDR as u32);
+        assert_eq!(RtnlGroup::from(33), RtnlGroup::Other(33));
+        assert_eq!(u32::from(RtnlGroup::Other(33)), 33);
+        for value in 1..64 {
+            assert_eq!(u32::from(RtnlGroup::from(value)), value);
+        }
+    }
+}
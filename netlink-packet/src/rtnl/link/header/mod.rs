@@ -179,6 +179,8 @@ impl Emitable for LinkHeader {
     fn emit(&self, buffer: &mut [u8]) {
         let mut packet = LinkBuffer::new(buffer);
         packet.set_address_family(self.address_family);
+        // the buffer may not be zeroed, and the kernel rejects requests with garbage in there
+        packet.set_reserved_1(0);
         packet.set_link_index(self.index);
         packet.set_change_mask(self.change_mask);
         packet.set_link_layer_type(self.link_layer_type);
@@ -15,6 +15,7 @@ const VLAN: &str = "vlan";
 const VETH: &str = "veth";
 const VXLAN: &str = "vxlan";
 const BOND: &str = "bond";
+const TEAM: &str = "team";
 const IPVLAN: &str = "ipvlan";
 const MACVLAN: &str = "macvlan";
 const MACVTAP: &str = "macvtap";
@@ -150,6 +151,7 @@ impl<'buffer, T: AsRef<[u8]> + ?Sized> Parseable<Vec<LinkInfo>> for NlaBuffer<&'
                             }
                             LinkInfoKind::Vxlan => LinkInfoData::Vxlan(payload.to_vec()),
                             LinkInfoKind::Bond => LinkInfoData::Bond(payload.to_vec()),
+                            LinkInfoKind::Team => LinkInfoData::Team(payload.to_vec()),
                             LinkInfoKind::IpVlan => LinkInfoData::IpVlan(payload.to_vec()),
                             LinkInfoKind::MacVlan => LinkInfoData::MacVlan(payload.to_vec()),
                             LinkInfoKind::MacVtap => LinkInfoData::MacVtap(payload.to_vec()),
@@ -188,6 +190,7 @@ pub enum LinkInfoData {
     Veth(LinkMessage),
     Vxlan(Vec<u8>),
     Bond(Vec<u8>),
+    Team(Vec<u8>),
     IpVlan(Vec<u8>),
     MacVlan(Vec<u8>),
     MacVtap(Vec<u8>),
@@ -217,6 +220,7 @@ impl Nla for LinkInfoData {
                 | Ifb(ref bytes)
                 | Vxlan(ref bytes)
                 | Bond(ref bytes)
+                | Team(ref bytes)
                 | IpVlan(ref bytes)
                 | MacVlan(ref bytes)
                 | MacVtap(ref bytes)
@@ -247,6 +251,7 @@ impl Nla for LinkInfoData {
                 | Ifb(ref bytes)
                 | Vxlan(ref bytes)
                 | Bond(ref bytes)
+                | Team(ref bytes)
                 | IpVlan(ref bytes)
                 | MacVlan(ref bytes)
                 | MacVtap(ref bytes)
@@ -280,6 +285,7 @@ pub enum LinkInfoKind {
     Veth,
     Vxlan,
     Bond,
+    Team,
     IpVlan,
     MacVlan,
     MacVtap,
@@ -308,6 +314,7 @@ impl Nla for LinkInfoKind {
             Veth => VETH.len(),
             Vxlan => VXLAN.len(),
             Bond => BOND.len(),
+            Team => TEAM.len(),
             IpVlan => IPVLAN.len(),
             MacVlan => MACVLAN.len(),
             MacVtap => MACVTAP.len(),
@@ -337,6 +344,7 @@ impl Nla for LinkInfoKind {
             Veth => VETH,
             Vxlan => VXLAN,
             Bond => BOND,
+            Team => TEAM,
             IpVlan => IPVLAN,
             MacVlan => MACVLAN,
             MacVtap => MACVTAP,
@@ -381,6 +389,7 @@ impl<'buffer, T: AsRef<[u8]> + ?Sized> Parseable<LinkInfoKind> for NlaBuffer<&'b
             VETH => Veth,
             VXLAN => Vxlan,
             BOND => Bond,
+            TEAM => Team,
             IPVLAN => IpVlan,
             MACVLAN => MacVlan,
             MACVTAP => MacVtap,
@@ -1171,4 +1180,28 @@ mod tests {
         nlas.as_slice().emit(&mut vec);
         assert_eq!(&vec[..], &BRIDGE[..]);
     }
+
+    #[rustfmt::skip]
+    static TEAM_INFO: [u8; 12] = [
+        0x09, 0x00, // L = 9
+        0x01, 0x00, // T = 1 (IFLA_INFO_KIND)
+        0x74, 0x65, 0x61, 0x6d, 0x00, // V = "team"
+        0x00, 0x00, 0x00, // padding
+    ];
+
+    #[test]
+    fn parse_info_kind_team() {
+        let nla = NlaBuffer::new_checked(&TEAM_INFO[..]).unwrap();
+        let parsed = <NlaBuffer<_> as Parseable<Vec<LinkInfo>>>::parse(&nla).unwrap();
+        assert_eq!(parsed, vec![LinkInfo::Kind(LinkInfoKind::Team)]);
+    }
+
+    #[test]
+    fn emit_info_kind_team() {
+        let nlas = vec![LinkInfo::Kind(LinkInfoKind::Team)];
+        assert_eq!(nlas.as_slice().buffer_len(), TEAM_INFO.len());
+        let mut vec = vec![0xff; TEAM_INFO.len()];
+        nlas.as_slice().emit(&mut vec);
+        assert_eq!(&vec[..], &TEAM_INFO[..]);
+    }
 }
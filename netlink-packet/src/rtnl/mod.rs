@@ -13,7 +13,5 @@ pub use self::route::*;
 mod message;
 pub use self::message::*;
 
-mod nla;
-pub use self::nla::*;
-
-pub(crate) mod utils;
+mod group;
+pub use self::group::*;
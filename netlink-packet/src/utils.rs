@@ -63,6 +63,22 @@ pub fn parse_u16(payload: &[u8]) -> Result<u16, DecodeError> {
     Ok(NativeEndian::read_u16(payload))
 }
 
+#[cfg(feature = "genetlink")]
+pub fn parse_u16_be(payload: &[u8]) -> Result<u16, DecodeError> {
+    if payload.len() != size_of::<u16>() {
+        return Err(format!("invalid u16: {:?}", payload).into());
+    }
+    Ok(byteorder::BigEndian::read_u16(payload))
+}
+
+#[cfg(feature = "genetlink")]
+pub fn parse_u32_be(payload: &[u8]) -> Result<u32, DecodeError> {
+    if payload.len() != size_of::<u32>() {
+        return Err(format!("invalid u32: {:?}", payload).into());
+    }
+    Ok(byteorder::BigEndian::read_u32(payload))
+}
+
 pub fn parse_i32(payload: &[u8]) -> Result<i32, DecodeError> {
     if payload.len() != 4 {
         return Err(format!("invalid u32: {:?}", payload).into());
@@ -0,0 +1,33 @@
+use futures::TryStreamExt;
+
+use netlink_packet::{
+    LinkHeader, LinkLayerType, LinkMessage, NetlinkFlags, NetlinkMessage, RtnlMessage,
+};
+use netlink_proto::{new_connection_with_socket, AsyncStdSocket, Protocol, SocketAddr};
+use netlink_sys::constants::{NLM_F_DUMP, NLM_F_REQUEST};
+
+fn main() {
+    env_logger::init();
+
+    // Create the connection, which socket is registered with the async-std reactor
+    let (connection, mut handle, _) =
+        new_connection_with_socket::<AsyncStdSocket>(Protocol::Route).unwrap();
+
+    async_std::task::block_on(async move {
+        // Run the connection in the background
+        async_std::task::spawn(connection);
+
+        let mut header = LinkHeader::new();
+        header.set_link_layer_type(LinkLayerType::Netrom);
+        let mut request: NetlinkMessage =
+            RtnlMessage::GetLink(LinkMessage::from_parts(header, vec![])).into();
+        request
+            .header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_DUMP | NLM_F_REQUEST));
+
+        let mut response = handle.request(request, SocketAddr::new(0, 0)).unwrap();
+        while let Some(message) = response.try_next().await.unwrap() {
+            println!("<<< {:?}", message);
+        }
+    });
+}
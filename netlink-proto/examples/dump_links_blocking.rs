@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use netlink_packet::{
+    LinkHeader, LinkLayerType, LinkMessage, NetlinkFlags, NetlinkMessage, RtnlMessage,
+};
+use netlink_proto::BlockingConnection;
+use netlink_sys::constants::{NLM_F_DUMP, NLM_F_REQUEST};
+use netlink_sys::{Protocol, SocketAddr};
+
+fn main() {
+    env_logger::init();
+    let mut conn = BlockingConnection::new(Protocol::Route).unwrap();
+    conn.set_timeout(Some(Duration::from_secs(5)));
+
+    // dump requests must have a zeroed header, since strict checking is enabled
+    let mut header = LinkHeader::new();
+    header.set_link_layer_type(LinkLayerType::Netrom);
+    let mut packet: NetlinkMessage =
+        RtnlMessage::GetLink(LinkMessage::from_parts(header, vec![])).into();
+    packet
+        .header_mut()
+        .set_flags(NetlinkFlags::from(NLM_F_DUMP | NLM_F_REQUEST));
+
+    println!(">>> {:?}", packet);
+    for packet in conn.request(packet, SocketAddr::new(0, 0)).unwrap() {
+        println!("<<< {:?}", packet);
+    }
+}
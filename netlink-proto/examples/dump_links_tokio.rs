@@ -1,6 +1,6 @@
 use env_logger;
 
-use futures::{Future, Sink, Stream};
+use futures::{SinkExt, StreamExt};
 
 use netlink_packet::{
     Emitable, LinkHeader, LinkMessage, NetlinkFlags, NetlinkMessage, RtnlMessage,
@@ -9,12 +9,13 @@ use netlink_proto::{NetlinkCodec, NetlinkFramed};
 use netlink_sys::constants::{NLM_F_DUMP, NLM_F_REQUEST};
 use netlink_sys::{Protocol, SocketAddr, TokioSocket};
 
-fn main() {
+#[tokio::main]
+async fn main() {
     env_logger::init();
     let mut socket = TokioSocket::new(Protocol::Route).unwrap();
     let _port_number = socket.bind_auto().unwrap().port_number();
     socket.connect(&SocketAddr::new(0, 0)).unwrap();
-    let stream = NetlinkFramed::new(socket, NetlinkCodec::<NetlinkMessage>::new());
+    let mut stream = NetlinkFramed::new(socket, NetlinkCodec::<NetlinkMessage>::new());
 
     let mut packet: NetlinkMessage =
         RtnlMessage::GetLink(LinkMessage::from_parts(LinkHeader::new(), vec![])).into();
@@ -27,13 +28,12 @@ fn main() {
     packet.emit(&mut buf[..packet.buffer_len()]);
 
     println!(">>> {:?}", packet);
-    let stream = stream.send((packet, SocketAddr::new(0, 0))).wait().unwrap();
+    stream.send((packet, SocketAddr::new(0, 0))).await.unwrap();
 
-    stream
-        .for_each(|(packet, _addr)| {
-            println!("<<< {:?}", packet);
-            Ok(())
-        })
-        .wait()
-        .unwrap();
+    while let Some(Ok((packet, _addr, _meta))) = stream.next().await {
+        println!("<<< {:?}", packet);
+        if packet.is_done() {
+            break;
+        }
+    }
 }
@@ -0,0 +1,503 @@
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::time::{Duration, Instant};
+
+use bytes::BytesMut;
+use netlink_packet::NetlinkMessage;
+use netlink_sys::{NetworkNamespace, Protocol, Socket, SocketAddr};
+
+use crate::codecs::{Decoder, Encoder, NetlinkCodec};
+use crate::errors::{Error, ErrorKind};
+use crate::Message;
+
+const INITIAL_READER_CAPACITY: usize = 64 * 1024;
+const INITIAL_WRITER_CAPACITY: usize = 8 * 1024;
+
+// How long the late replies to a request that timed out are expected. The reply to a request may
+// never come (if it was dropped because the socket receive buffer was full for instance), so the
+// requests that timed out cannot be remembered until their reply arrives.
+const STALE_REQUEST_LIFETIME: Duration = Duration::from_secs(60);
+
+/// A synchronous connection to a netlink socket, for programs that do not run an event loop.
+///
+/// Unlike [`Connection`](struct.Connection.html), it does not run in the background: requests
+/// are sent and their responses are read from the calling thread, one request at a time. The
+/// messages that are received while waiting for a response but are not part of it (multicast
+/// messages for instance) are buffered, and can be retrieved with
+/// [`next_message`](#method.next_message). The late replies to the requests that timed out are
+/// dropped if they arrive within a minute, as well as the rest of the responses that are cut short
+/// by an error.
+pub struct BlockingConnection<T = NetlinkMessage> {
+    socket: Socket,
+    codec: NetlinkCodec<T>,
+    sequence_id: u32,
+    timeout: Option<Duration>,
+    writer: BytesMut,
+    // Messages decoded from the last datagram, that have not been processed yet
+    received_messages: VecDeque<(T, SocketAddr)>,
+    // Messages that do not belong to any request
+    incoming_messages: VecDeque<T>,
+    // Requests that timed out, which replies are dropped if they arrive later
+    stale_requests: HashMap<(SocketAddr, u32), StaleRequest>,
+    stale_request_lifetime: Duration,
+}
+
+struct StaleRequest {
+    dump: bool,
+    // When the late replies to the request are not expected anymore
+    expiry: Instant,
+}
+
+impl<T: Message> BlockingConnection<T> {
+    pub fn new(protocol: Protocol) -> io::Result<Self> {
+        Ok(BlockingConnection::from_socket(
+            Socket::new(protocol)?,
+            NetlinkCodec::for_protocol(protocol),
+        ))
+    }
+
+    /// Same as [`new`](#method.new), but the netlink socket is created in the given network
+    /// namespace.
+    pub fn new_in_namespace(protocol: Protocol, ns: NetworkNamespace) -> io::Result<Self> {
+        Ok(BlockingConnection::from_socket(
+            Socket::new_in_namespace(protocol, ns)?,
+            NetlinkCodec::for_protocol(protocol),
+        ))
+    }
+
+    fn from_socket(mut socket: Socket, codec: NetlinkCodec<T>) -> Self {
+        // Same as the asynchronous connection: these options are only supported by recent
+        // kernels, so failing to set them is not an error.
+        if let Err(e) = socket.set_ext_ack(true) {
+            debug!("failed to enable extended acks: {}", e);
+        }
+        if let Err(e) = socket.set_strict_check(true) {
+            debug!("failed to enable strict checking: {}", e);
+        }
+        BlockingConnection {
+            socket,
+            codec,
+            sequence_id: 0,
+            timeout: None,
+            writer: BytesMut::with_capacity(INITIAL_WRITER_CAPACITY),
+            received_messages: VecDeque::new(),
+            incoming_messages: VecDeque::new(),
+            stale_requests: HashMap::new(),
+            stale_request_lifetime: STALE_REQUEST_LIFETIME,
+        }
+    }
+
+    pub fn socket_mut(&mut self) -> &mut Socket {
+        &mut self.socket
+    }
+
+    /// Set how long [`request`](#method.request) and [`next_message`](#method.next_message)
+    /// wait for messages before failing with `ErrorKind::Timeout`. `None` (the default) means
+    /// they wait indefinitely.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Send a request and wait for the whole response. Like with
+    /// [`ConnectionHandle::request`](struct.ConnectionHandle.html#method.request), the messages of
+    /// a dump are reassembled until the "end of dump" message, which is not part of the response,
+    /// and the response ends with the first error or acknowledgement message, which is. If the
+    /// kernel tables changed during a dump, `ErrorKind::DumpInterrupted` is returned.
+    ///
+    /// Since the kernel runs one dump at a time per socket, a dump request is only sent once the
+    /// dumps that timed out are over.
+    pub fn request(&mut self, message: T, destination: SocketAddr) -> Result<Vec<T>, Error<T>> {
+        self.purge_stale_requests();
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let dump = message.header().flags().has_dump();
+        if dump {
+            self.wait_stale_dumps(destination, deadline)?;
+        }
+        let sequence_id = self.send(message, destination)?;
+
+        let mut response = vec![];
+        let mut interrupted = false;
+        loop {
+            let (message, source) = match self.next_received_message(deadline) {
+                Ok(item) => item,
+                Err(e) => {
+                    // the rest of the response may still arrive
+                    self.mark_stale(destination, sequence_id, dump);
+                    return Err(e);
+                }
+            };
+            if source != destination || message.header().sequence_number() != sequence_id {
+                if !self.is_stale(&message, source) {
+                    trace!("message is not part of the response, buffering it");
+                    self.incoming_messages.push_back(message);
+                }
+                continue;
+            }
+
+            interrupted |= message.is_dump_interrupted();
+            let done = is_last(&message);
+
+            if message.is_done() {
+                trace!("received end of dump message");
+            } else if message.is_noop() {
+                trace!("ignoring NOOP");
+            } else if message.is_overrun() {
+                if !done {
+                    self.mark_stale(destination, sequence_id, dump);
+                }
+                return Err(ErrorKind::SocketIo(io::Error::other(
+                    "overrun: receive buffer is full",
+                ))
+                .into());
+            } else {
+                response.push(message);
+            }
+
+            if done && interrupted {
+                return Err(ErrorKind::DumpInterrupted.into());
+            } else if done {
+                return Ok(response);
+            }
+        }
+    }
+
+    /// Send a message without waiting for a response.
+    pub fn notify(&mut self, message: T, destination: SocketAddr) -> Result<(), Error<T>> {
+        self.send(message, destination)?;
+        Ok(())
+    }
+
+    /// Return the next message that is not part of a response to a request (multicast messages
+    /// for instance), waiting for it if necessary.
+    pub fn next_message(&mut self) -> Result<T, Error<T>> {
+        if let Some(message) = self.incoming_messages.pop_front() {
+            return Ok(message);
+        }
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            let (message, source) = self.next_received_message(deadline)?;
+            if !self.is_stale(&message, source) {
+                return Ok(message);
+            }
+        }
+    }
+
+    // Read the late replies to the dumps sent to the given destination that timed out, until
+    // these dumps are over
+    fn wait_stale_dumps(
+        &mut self,
+        destination: SocketAddr,
+        deadline: Option<Instant>,
+    ) -> Result<(), Error<T>> {
+        while self
+            .stale_requests
+            .iter()
+            .any(|(&(addr, _), request)| request.dump && addr == destination)
+        {
+            let (message, source) = self.next_received_message(deadline)?;
+            if !self.is_stale(&message, source) {
+                self.incoming_messages.push_back(message);
+            }
+        }
+        Ok(())
+    }
+
+    fn mark_stale(&mut self, destination: SocketAddr, sequence_id: u32, dump: bool) {
+        let request = StaleRequest {
+            dump,
+            expiry: Instant::now() + self.stale_request_lifetime,
+        };
+        self.stale_requests
+            .insert((destination, sequence_id), request);
+    }
+
+    // Forget the requests that timed out long ago: their late replies are not dropped anymore
+    fn purge_stale_requests(&mut self) {
+        let now = Instant::now();
+        self.stale_requests
+            .retain(|_, request| request.expiry > now);
+    }
+
+    // Check whether the message is a late reply to a request that timed out, and forget about
+    // that request once its reply is complete
+    fn is_stale(&mut self, message: &T, source: SocketAddr) -> bool {
+        let request = (source, message.header().sequence_number());
+        if !self.stale_requests.contains_key(&request) {
+            return false;
+        }
+        trace!("dropping a late reply to a request that timed out");
+        if is_last(message) {
+            self.stale_requests.remove(&request);
+        }
+        true
+    }
+
+    fn send(&mut self, mut message: T, destination: SocketAddr) -> Result<u32, Error<T>> {
+        self.sequence_id += 1;
+        message.header_mut().set_sequence_number(self.sequence_id);
+        message.finalize();
+        trace!("sending message: {:?} to {:?}", message, destination);
+
+        self.writer.clear();
+        self.codec
+            .encode(message, &mut self.writer)
+            .map_err(|e| Error::from(ErrorKind::SocketIo(e)))?;
+        let n = self
+            .socket
+            .send_to(&self.writer, &destination, 0)
+            .map_err(|e| Error::from(ErrorKind::SocketIo(e)))?;
+        if n != self.writer.len() {
+            return Err(ErrorKind::SocketIo(io::Error::other(
+                "failed to write entire datagram to socket",
+            ))
+            .into());
+        }
+        Ok(self.sequence_id)
+    }
+
+    fn next_received_message(
+        &mut self,
+        deadline: Option<Instant>,
+    ) -> Result<(T, SocketAddr), Error<T>> {
+        loop {
+            if let Some(item) = self.received_messages.pop_front() {
+                return Ok(item);
+            }
+            self.recv(deadline)?;
+        }
+    }
+
+    // Receive a datagram, and decode the messages it contains
+    fn recv(&mut self, deadline: Option<Instant>) -> Result<(), Error<T>> {
+        let timeout = match deadline {
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(ErrorKind::Timeout.into());
+                }
+                Some(deadline - now)
+            }
+            None => None,
+        };
+        self.socket
+            .set_read_timeout(timeout)
+            .map_err(|e| Error::from(ErrorKind::SocketIo(e)))?;
+
+        let len = self.socket.peek_len(0).map_err(recv_error)?;
+        let mut buf = vec![0; len.max(INITIAL_READER_CAPACITY)];
+        let (n, source) = self.socket.recv_from(&mut buf[..], 0).map_err(recv_error)?;
+
+        let mut reader = BytesMut::from(&buf[..n]);
+        while !reader.is_empty() {
+            if let Some(message) = self
+                .codec
+                .decode(&mut reader)
+                .map_err(|e| Error::from(ErrorKind::SocketIo(e)))?
+            {
+                trace!("message received: {:?}", message);
+                self.received_messages.push_back((message, source));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Whether the message is the last one of a response
+fn is_last<T: Message>(message: &T) -> bool {
+    !message.header().flags().has_multipart() || message.is_done() || message.is_error()
+}
+
+fn recv_error<T>(e: io::Error) -> Error<T> {
+    match e.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => ErrorKind::Timeout.into(),
+        _ => ErrorKind::SocketIo(e).into(),
+    }
+}
+
+#[cfg(all(test, feature = "rtnetlink"))]
+mod test {
+    use super::*;
+    use std::fmt;
+
+    use netlink_packet::constants::{NLM_F_DUMP, NLM_F_MULTIPART, NLM_F_REQUEST};
+    use netlink_packet::{
+        AckMessage, Emitable, LinkMessage, NetlinkFlags, NetlinkPayload, RtnlMessage,
+    };
+
+    // A connection, and a socket that plays the kernel: it sends the replies to the requests of
+    // the connection. Sequence numbers start at 1, so the replies can be sent in advance.
+    struct Peer {
+        socket: Socket,
+        connection_addr: SocketAddr,
+    }
+
+    impl Peer {
+        fn send(&self, sequence_number: u32, flags: u16, payload: NetlinkPayload) {
+            let mut message = NetlinkMessage::from(payload);
+            message
+                .header_mut()
+                .set_sequence_number(sequence_number)
+                .set_flags(NetlinkFlags::from(flags));
+            message.finalize();
+            let mut buf = vec![0; message.buffer_len()];
+            message.emit(&mut buf[..]);
+            self.socket
+                .send_to(&buf[..], &self.connection_addr, 0)
+                .unwrap();
+        }
+
+        fn send_link(&self, sequence_number: u32, flags: u16) {
+            let payload = NetlinkPayload::Rtnl(RtnlMessage::NewLink(LinkMessage::new()));
+            self.send(sequence_number, flags, payload);
+        }
+
+        fn send_ack(&self, sequence_number: u32) {
+            let ack = AckMessage {
+                code: 0,
+                header: vec![],
+                nlas: vec![],
+            };
+            self.send(sequence_number, 0, NetlinkPayload::Ack(ack));
+        }
+    }
+
+    fn connection() -> (BlockingConnection, Peer, SocketAddr) {
+        let mut socket = Socket::new(Protocol::UserSock).unwrap();
+        let connection_addr = socket.bind_auto().unwrap();
+        let connection =
+            BlockingConnection::from_socket(socket, NetlinkCodec::for_protocol(Protocol::Route));
+
+        let mut socket = Socket::new(Protocol::UserSock).unwrap();
+        let peer_addr = socket.bind_auto().unwrap();
+        let peer = Peer {
+            socket,
+            connection_addr,
+        };
+        (connection, peer, peer_addr)
+    }
+
+    fn get_link() -> NetlinkMessage {
+        let mut message = NetlinkMessage::from(RtnlMessage::GetLink(LinkMessage::new()));
+        message
+            .header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST));
+        message
+    }
+
+    fn dump_links() -> NetlinkMessage {
+        let mut message = get_link();
+        message
+            .header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST | NLM_F_DUMP));
+        message
+    }
+
+    fn assert_timeout<T: fmt::Debug>(result: Result<T, Error>) {
+        match result.unwrap_err().kind() {
+            ErrorKind::Timeout => {}
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn match_sequence_numbers() {
+        let (mut connection, peer, peer_addr) = connection();
+        peer.send_link(0, 0);
+        peer.send_link(42, 0);
+        peer.send_ack(1);
+
+        let response = connection.request(get_link(), peer_addr).unwrap();
+        assert_eq!(response.len(), 1);
+        assert!(response[0].is_ack());
+
+        // the other messages are not part of the response
+        assert_eq!(
+            connection
+                .next_message()
+                .unwrap()
+                .header()
+                .sequence_number(),
+            0
+        );
+        assert_eq!(
+            connection
+                .next_message()
+                .unwrap()
+                .header()
+                .sequence_number(),
+            42
+        );
+    }
+
+    #[test]
+    fn reassemble_multipart_response() {
+        let (mut connection, peer, peer_addr) = connection();
+        peer.send_link(1, NLM_F_MULTIPART);
+        peer.send_link(1, NLM_F_MULTIPART);
+        peer.send(1, NLM_F_MULTIPART, NetlinkPayload::Done);
+
+        let response = connection.request(get_link(), peer_addr).unwrap();
+        assert_eq!(response.len(), 2);
+        for message in response {
+            assert_eq!(
+                message.payload(),
+                &NetlinkPayload::Rtnl(RtnlMessage::NewLink(LinkMessage::new()))
+            );
+        }
+    }
+
+    #[test]
+    fn request_timeout() {
+        let (mut connection, _peer, peer_addr) = connection();
+        connection.set_timeout(Some(Duration::from_millis(50)));
+        assert_timeout(connection.request(get_link(), peer_addr));
+        assert_timeout(connection.next_message());
+    }
+
+    #[test]
+    fn purge_stale_requests() {
+        let (mut connection, peer, peer_addr) = connection();
+        connection.set_timeout(Some(Duration::from_millis(50)));
+        connection.stale_request_lifetime = Duration::from_millis(100);
+        // the reply never comes
+        assert_timeout(connection.request(get_link(), peer_addr));
+        assert!(connection.stale_requests.contains_key(&(peer_addr, 1)));
+
+        // the request is forgotten once its lifetime is over
+        std::thread::sleep(Duration::from_millis(100));
+        peer.send_ack(2);
+        connection.request(get_link(), peer_addr).unwrap();
+        assert!(connection.stale_requests.is_empty());
+    }
+
+    #[test]
+    fn drop_stale_replies() {
+        let (mut connection, peer, peer_addr) = connection();
+        connection.set_timeout(Some(Duration::from_millis(50)));
+        assert_timeout(connection.request(dump_links(), peer_addr));
+
+        // the reply to the first dump arrives late, and the second one is only sent after it
+        peer.send_link(1, NLM_F_MULTIPART);
+        peer.send(1, NLM_F_MULTIPART, NetlinkPayload::Done);
+        peer.send_ack(2);
+        // replies to that request are not expected anymore
+        peer.send_link(1, 0);
+
+        let response = connection.request(dump_links(), peer_addr).unwrap();
+        assert_eq!(response.len(), 1);
+        assert!(response[0].is_ack());
+        assert_eq!(
+            connection
+                .next_message()
+                .unwrap()
+                .header()
+                .sequence_number(),
+            1
+        );
+        assert_timeout(connection.next_message());
+    }
+}
@@ -0,0 +1,190 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::channel::mpsc::{self, SendError, TrySendError};
+use futures::{Stream, StreamExt};
+
+/// Capacities of the channels between a [`Connection`](struct.Connection.html) and its handles.
+/// `None` means the channel is unbounded, which is the default for all of them.
+///
+/// When a channel that carries messages received from the socket is full, the connection stops
+/// reading from the socket until the consumer catches up. The kernel then waits before sending
+/// the next messages of a dump, but drops the multicast messages if the socket receive buffer
+/// fills up (this is reported with a `NetlinkPayload::Overrun` message on the stream of
+/// unsolicited messages).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelCapacities {
+    /// Number of requests that can wait for the connection to send them. When the channel is
+    /// full, requests fail with `ErrorKind::ChannelFull` (see
+    /// [`ConnectionHandle::ready`](struct.ConnectionHandle.html#method.ready)).
+    pub requests: Option<usize>,
+    /// Number of messages of a response that can wait for the response stream to read them
+    pub responses: Option<usize>,
+    /// Number of unsolicited messages that can wait for the stream returned along with the
+    /// connection to read them
+    pub incoming_messages: Option<usize>,
+}
+
+// Sending side of a channel, that is bounded or not depending on its capacity
+#[derive(Debug)]
+pub(crate) enum Sender<T> {
+    Bounded(mpsc::Sender<T>),
+    Unbounded(mpsc::UnboundedSender<T>),
+}
+
+// Receiving side of a channel, that is bounded or not depending on its capacity
+#[derive(Debug)]
+pub(crate) enum Receiver<T> {
+    Bounded(mpsc::Receiver<T>),
+    Unbounded(mpsc::UnboundedReceiver<T>),
+}
+
+pub(crate) fn channel<T>(capacity: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    match capacity {
+        Some(capacity) => {
+            let (tx, rx) = mpsc::channel(capacity);
+            (Sender::Bounded(tx), Receiver::Bounded(rx))
+        }
+        None => {
+            let (tx, rx) = mpsc::unbounded();
+            (Sender::Unbounded(tx), Receiver::Unbounded(rx))
+        }
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Sender::Bounded(tx) => Sender::Bounded(tx.clone()),
+            Sender::Unbounded(tx) => Sender::Unbounded(tx.clone()),
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    // Check whether the channel has room for a message. If it does not, the current task is
+    // woken up when it does. An error is returned if the receiver is gone.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
+        match self {
+            Sender::Bounded(tx) => tx.poll_ready(cx),
+            Sender::Unbounded(tx) => tx.poll_ready(cx),
+        }
+    }
+
+    // Send a message. This only fails if the receiver is gone, or if the channel is full, which
+    // cannot happen right after `poll_ready` returned `Ready`.
+    pub fn try_send(&mut self, message: T) -> Result<(), TrySendError<T>> {
+        match self {
+            Sender::Bounded(tx) => tx.try_send(message),
+            Sender::Unbounded(tx) => tx.unbounded_send(message),
+        }
+    }
+
+    // Send a message even if the channel is full, so that the errors that end a response are
+    // never lost. Each sender of a bounded channel has a slot of its own, so a new sender can
+    // always send a message.
+    pub fn force_send(&self, message: T) -> Result<(), TrySendError<T>> {
+        match self {
+            Sender::Bounded(tx) => tx.clone().try_send(message),
+            Sender::Unbounded(tx) => tx.unbounded_send(message),
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        match self {
+            Sender::Bounded(tx) => tx.is_closed(),
+            Sender::Unbounded(tx) => tx.is_closed(),
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    pub fn close(&mut self) {
+        match self {
+            Receiver::Bounded(rx) => rx.close(),
+            Receiver::Unbounded(rx) => rx.close(),
+        }
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        match self.get_mut() {
+            Receiver::Bounded(rx) => rx.poll_next_unpin(cx),
+            Receiver::Unbounded(rx) => rx.poll_next_unpin(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use futures::executor::block_on;
+    use futures::task::{self, ArcWake};
+
+    use super::*;
+
+    // Waker that records whether it was woken up
+    #[derive(Default)]
+    struct Flag(AtomicBool);
+
+    impl ArcWake for Flag {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn bounded_channel_full() {
+        let (mut tx, mut rx) = channel::<u32>(Some(1));
+        // each sender has a slot of its own, in addition to the capacity of the channel
+        assert!(tx.try_send(1).is_ok());
+        assert!(tx.try_send(2).is_ok());
+        assert!(tx.try_send(3).unwrap_err().is_full());
+
+        let flag = Arc::new(Flag::default());
+        let waker = task::waker(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+        assert!(tx.poll_ready(&mut cx).is_pending());
+
+        assert_eq!(block_on(rx.next()), Some(1));
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert!(matches!(tx.poll_ready(&mut cx), Poll::Ready(Ok(()))));
+        assert!(tx.try_send(3).is_ok());
+    }
+
+    #[test]
+    fn force_send_full_channel() {
+        let (mut tx, mut rx) = channel::<u32>(Some(0));
+        assert!(tx.try_send(1).is_ok());
+        assert!(tx.try_send(2).unwrap_err().is_full());
+        assert!(tx.force_send(2).is_ok());
+
+        assert_eq!(block_on(rx.next()), Some(1));
+        assert_eq!(block_on(rx.next()), Some(2));
+    }
+
+    #[test]
+    fn unbounded_channel() {
+        let (mut tx, _rx) = channel::<u32>(None);
+        let mut cx = Context::from_waker(task::noop_waker_ref());
+        for i in 0..1000 {
+            assert!(tx.try_send(i).is_ok());
+        }
+        assert!(matches!(tx.poll_ready(&mut cx), Poll::Ready(Ok(()))));
+    }
+
+    #[test]
+    fn closed_channel() {
+        let (mut tx, rx) = channel::<u32>(Some(1));
+        assert!(!tx.is_closed());
+        drop(rx);
+        assert!(tx.is_closed());
+        assert!(tx.try_send(1).unwrap_err().is_disconnected());
+        assert!(tx.force_send(1).is_err());
+    }
+}
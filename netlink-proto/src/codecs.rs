@@ -6,10 +6,43 @@ use std::marker::PhantomData;
 // These traits need to be in scope for some methods to be called.
 
 use bytes::{BufMut, BytesMut};
-use netlink_packet::{Emitable, NetlinkBuffer, NetlinkMessage, Parseable};
-use tokio_io::codec::{Decoder, Encoder};
+use netlink_packet::NetlinkBuffer;
+use netlink_sys::Protocol;
 
+use crate::Message;
+
+/// Decoding of the datagrams received by a [`NetlinkFramed`](struct.NetlinkFramed.html).
+///
+/// This mirrors the `Decoder` trait of `tokio-util`.
+pub trait Decoder {
+    type Item;
+    type Error: From<io::Error>;
+
+    /// Decode the next message from the buffer. `Ok(None)` is returned when the buffer does not
+    /// contain any more message.
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error>;
+}
+
+/// Encoding of the messages sent by a [`NetlinkFramed`](struct.NetlinkFramed.html).
+///
+/// This mirrors the `Encoder` trait of `tokio-util`.
+pub trait Encoder {
+    type Item;
+    type Error: From<io::Error>;
+
+    /// Encode the message and append it to the buffer.
+    fn encode(&mut self, item: Self::Item, dst: &mut BytesMut) -> Result<(), Self::Error>;
+}
+
+/// Codec for the messages of type `T` (see [`Message`](trait.Message.html)).
+///
+/// The quirks of the netlink protocols are handled by the codec instance: the codec returned by
+/// [`for_protocol`](#method.for_protocol) works around the malformed packets of the protocols
+/// that send some, so that connections to different protocols can live in the same program.
 pub struct NetlinkCodec<T> {
+    // Protocol of the messages, which tells how to parse their payload, and whether the length
+    // in their header may be too short (see `decode`)
+    protocol: Option<Protocol>,
     phantom: PhantomData<T>,
 }
 
@@ -22,15 +55,30 @@ impl<T> Default for NetlinkCodec<T> {
 impl<T> NetlinkCodec<T> {
     pub fn new() -> Self {
         NetlinkCodec {
+            protocol: None,
             phantom: PhantomData,
         }
     }
+
+    /// Codec for the messages of the given protocol. The received messages are parsed according
+    /// to that protocol (see `NetlinkBuffer::new_with_protocol`), and the workarounds it needs
+    /// are applied. For now, only the audit messages need one: the length in their header is
+    /// sometimes shorter than the message.
+    pub fn for_protocol(protocol: Protocol) -> Self {
+        NetlinkCodec {
+            protocol: Some(protocol),
+            phantom: PhantomData,
+        }
+    }
+
+    // Whether the length in the header of the received messages may be too short
+    fn fix_truncated_length(&self) -> bool {
+        self.protocol == Some(Protocol::Audit)
+    }
 }
 
-// FIXME: it seems that for audit, we're receiving malformed packets.
-// See https://github.com/mozilla/libaudit-go/issues/24
-impl Decoder for NetlinkCodec<NetlinkMessage> {
-    type Item = NetlinkMessage;
+impl<T: Message> Decoder for NetlinkCodec<T> {
+    type Item = T;
     type Error = io::Error;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
@@ -45,10 +93,7 @@ impl Decoder for NetlinkCodec<NetlinkMessage> {
         // This is a bit hacky because we don't want to keep `src` borrowed, since we need to
         // mutate it later.
         let len = match NetlinkBuffer::new_checked(src.as_ref()) {
-            #[cfg(not(feature = "audit"))]
-            Ok(buf) => buf.length() as usize,
-            #[cfg(feature = "audit")]
-            Ok(buf) => {
+            Ok(buf) if self.fix_truncated_length() => {
                 if (src.as_ref().len() as isize - buf.length() as isize) <= 16 {
                     // The audit messages are sometimes truncated, because the length specified in
                     // the header, does not take the header itself into account. To workaround
@@ -66,6 +111,7 @@ impl Decoder for NetlinkCodec<NetlinkMessage> {
                     buf.length() as usize
                 }
             }
+            Ok(buf) => buf.length() as usize,
             Err(e) => {
                 // We either received a truncated packet, or the packet if malformed (invalid
                 // length field). If the packet is truncated, there's not point in waiting for
@@ -85,39 +131,36 @@ impl Decoder for NetlinkCodec<NetlinkMessage> {
             }
         };
 
-        #[cfg(feature = "audit")]
-        let bytes = {
-            let mut bytes = src.split_to(len);
-            {
-                let mut buf = NetlinkBuffer::new(bytes.as_mut());
-                // If the buffer contains more bytes than what the header says the length is, it
-                // means we ran into a malformed packet (see comment above), and we just set the
-                // "right" length ourself, so that parsing does not fail.
-                //
-                // How do we know that's the right length? Due to an implementation detail and to
-                // the fact that netlink is a datagram protocol.
-                //
-                // - our implementation of Stream always calls the codec with at most 1 message in
-                //   the buffer, so we know the extra bytes do not belong to another message.
-                // - because netlink is a datagram protocol, we receive entire messages, so we know
-                //   that if those extra bytes do not belong to another message, they belong to
-                //   this one.
-                if len != buf.length() as usize {
-                    warn!(
-                        "setting packet length to {} instead of {}",
-                        len,
-                        buf.length()
-                    );
-                    buf.set_length(len as u32);
-                }
+        let mut bytes = src.split_to(len);
+        if self.fix_truncated_length() {
+            let mut buf = NetlinkBuffer::new(bytes.as_mut());
+            // If the buffer contains more bytes than what the header says the length is, it
+            // means we ran into a malformed packet (see comment above), and we just set the
+            // "right" length ourself, so that parsing does not fail.
+            //
+            // How do we know that's the right length? Due to an implementation detail and to
+            // the fact that netlink is a datagram protocol.
+            //
+            // - our implementation of Stream always calls the codec with at most 1 message in
+            //   the buffer, so we know the extra bytes do not belong to another message.
+            // - because netlink is a datagram protocol, we receive entire messages, so we know
+            //   that if those extra bytes do not belong to another message, they belong to
+            //   this one.
+            if len != buf.length() as usize {
+                warn!(
+                    "setting packet length to {} instead of {}",
+                    len,
+                    buf.length()
+                );
+                buf.set_length(len as u32);
             }
-            bytes
-        };
+        }
 
-        #[cfg(not(feature = "audit"))]
-        let bytes = src.split_to(len);
-        let parsed =
-            <NetlinkBuffer<_> as Parseable<NetlinkMessage>>::parse(&NetlinkBuffer::new(&bytes));
+        let buffer = match self.protocol {
+            Some(protocol) => NetlinkBuffer::new_with_protocol(&bytes, protocol),
+            None => NetlinkBuffer::new(&bytes),
+        };
+        let parsed = T::parse_buffer(&buffer);
 
         match parsed {
             Ok(packet) => Ok(Some(packet)),
@@ -133,18 +176,17 @@ impl Decoder for NetlinkCodec<NetlinkMessage> {
     }
 }
 
-impl Encoder for NetlinkCodec<NetlinkMessage> {
-    type Item = NetlinkMessage;
+impl<T: Message> Encoder for NetlinkCodec<T> {
+    type Item = T;
     type Error = io::Error;
 
     fn encode(&mut self, msg: Self::Item, buf: &mut BytesMut) -> Result<(), Self::Error> {
-        let msg_len = msg.buffer_len();
-        // FIXME: we should have a max length for the buffer
-        while buf.remaining_mut() < msg_len {
-            let new_len = buf.len() + 2048;
-            buf.resize(new_len, 0);
-        }
         let size = msg.buffer_len();
+        // FIXME: we should have a max length for the buffer
+        //
+        // Several messages can be encoded in the same buffer (see `ConnectionHandle::batch`), so
+        // the buffer is grown without touching the messages already encoded.
+        buf.reserve(size);
         // bytes_mut() and advance_mut() are unsafe.
         // TODO/FIXME: comment on why what we do here is safe.
         unsafe {
@@ -1,18 +1,25 @@
 use std::collections::HashMap;
+use std::future::Future;
 use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
-use futures::sync::mpsc::{UnboundedReceiver, UnboundedSender};
-use futures::{Async, AsyncSink, Future, Poll, Sink, Stream};
+use futures::{Sink, Stream, StreamExt};
+use futures_timer::Delay;
 
 use netlink_packet::NetlinkMessage;
-use netlink_sys::{Protocol, SocketAddr, TokioSocket};
+use netlink_sys::{AsyncSocket, RecvMeta, SockFilter, SocketAddr};
 use std::collections::VecDeque;
 
 use crate::errors::{Error, ErrorKind};
+use crate::Message;
 
+use super::channel::{channel, Receiver};
 use super::codecs::NetlinkCodec;
 use super::framed::NetlinkFramed;
-use super::request::Request;
+use super::listener::Listener;
+use super::request::{Command, Request, ResponseSender};
 
 lazy_static! {
     static ref KERNEL_UNICAST: SocketAddr = SocketAddr::new(0, 0);
@@ -22,53 +29,168 @@ lazy_static! {
 ///
 /// [`ConnectionHandle`](struct.ConnectionHandle.html) are used to pass new requests to the
 /// `Connection`, that in turn, sends them through the netlink socket.
-pub struct Connection {
-    socket: NetlinkFramed<NetlinkCodec<NetlinkMessage>>,
+///
+/// When the socket receive buffer overflows, the kernel drops messages (it reports this with an
+/// `ENOBUFS` error, or an `NLMSG_OVERRUN` message). The connection keeps running, but since the
+/// responses to the dumps in flight may be incomplete, these dumps fail with
+/// `ErrorKind::DumpInterrupted` and can be retried. A message with a `NetlinkPayload::Overrun`
+/// payload is sent on the stream of unsolicited messages to tell the listeners that they missed
+/// messages, and the callback set with [`set_resync_callback`](#method.set_resync_callback) is
+/// called.
+///
+/// Requests are cancelled when the stream of their response is dropped, and they can be
+/// given a timeout (see [`set_default_timeout`](#method.set_default_timeout) and
+/// [`ConnectionHandle::request_with_timeout`](struct.ConnectionHandle.html#method.request_with_timeout)),
+/// so that the connection does not wait forever for responses that will never come.
+///
+/// When the consumer of a response or of the unsolicited messages does not keep up, and its
+/// channel is full (see [`ChannelCapacities`](struct.ChannelCapacities.html)), the connection
+/// stops reading from the socket until it catches up.
+///
+/// The connection is a `Future` that can be spawned on any runtime, as long as the socket is
+/// registered with the reactor of that runtime (see
+/// [`AsyncSocket`](trait.AsyncSocket.html)).
+///
+/// The messages are `NetlinkMessage` by default, but any type implementing
+/// [`Message`](trait.Message.html) can be used instead, along with a codec that handles the quirks
+/// of its protocol (see [`NetlinkCodec::for_protocol`](struct.NetlinkCodec.html#method.for_protocol)).
+pub struct Connection<S, T = NetlinkMessage> {
+    socket: NetlinkFramed<NetlinkCodec<T>, S>,
 
     // Counter that is incremented for each message sent
     sequence_id: u32,
 
     // Requests for which we're waiting for a response
-    pending_requests: HashMap<(SocketAddr, u32), UnboundedSender<NetlinkMessage>>,
+    pending_requests: HashMap<(SocketAddr, u32), PendingRequest<T>>,
 
     // Requests to be sent out
-    requests_buffer: VecDeque<Request>,
+    requests_buffer: VecDeque<Request<T>>,
 
     // Channel used by the user to pass requests to the connection. These requests are either sent
     // out as soon as they are received, or put in self.requests_buffer for and processed later.
-    requests_rx: UnboundedReceiver<Request>,
+    requests_rx: Receiver<Command<T>>,
+
+    // Listeners which get a copy of the unsollicited messages received from the socket (multicast
+    // messages for instance) that match their filter: the stream returned along with the
+    // connection, and the ones registered with `ConnectionHandle::listen`.
+    listeners: Vec<Listener<T>>,
 
-    // Channel used to transmit to the ConnectionHandle the unsollicited messages received from the
-    // socket (multicast messages for instance).
-    incoming_messages_tx: UnboundedSender<NetlinkMessage>,
+    // Message received from the socket, that waits for its consumers to have room for it
+    undelivered: Option<(T, SocketAddr, RecvMeta)>,
 
     // Indicate whether this connection is shutting down.
     shutting_down: bool,
+
+    // Called when messages have been lost
+    resync_callback: Option<Box<dyn FnMut() + Send>>,
+
+    // Timeout of the requests that do not have their own
+    default_timeout: Option<Duration>,
+
+    // Fires when the earliest deadline of the pending requests expires
+    timer: Option<Delay>,
+}
+
+struct PendingRequest<T> {
+    chan: ResponseSender<T>,
+    // Whether the request is a dump, which response may be incomplete if messages are lost
+    dump: bool,
+    // How long to wait for the next message of the response
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    // Whether the kernel flagged the dump with `NLM_F_DUMP_INTR`
+    interrupted: bool,
+    // Whether the dump already failed, because messages were lost while it was in flight or
+    // because it timed out: the rest of its response is discarded
+    discard: bool,
 }
 
-impl Connection {
+impl<S: AsyncSocket, T: Message> Connection<S, T> {
     pub(crate) fn new(
-        requests_rx: UnboundedReceiver<Request>,
-        incoming_messages_tx: UnboundedSender<NetlinkMessage>,
-        protocol: Protocol,
-    ) -> io::Result<Self> {
-        let socket = TokioSocket::new(protocol)?;
-        Ok(Connection {
-            socket: NetlinkFramed::new(socket, NetlinkCodec::<NetlinkMessage>::new()),
+        requests_rx: Receiver<Command<T>>,
+        incoming_messages: Listener<T>,
+        mut socket: S,
+        codec: NetlinkCodec<T>,
+    ) -> Self {
+        // Ask for extended acks (so that errors carry a message from the kernel), and for strict
+        // checking of the dump requests (so that dumps can be filtered by the kernel). Older
+        // kernels do not support these options, in which case we keep going without them.
+        if let Err(e) = socket.socket_mut().set_ext_ack(true) {
+            debug!("failed to enable NETLINK_EXT_ACK: {}", e);
+        }
+        if let Err(e) = socket.socket_mut().set_strict_check(true) {
+            debug!("failed to enable NETLINK_GET_STRICT_CHK: {}", e);
+        }
+        // Ask for the multicast group of the received messages, which is passed to the listeners
+        if let Err(e) = socket.socket_mut().set_pktinfo(true) {
+            debug!("failed to enable NETLINK_PKTINFO: {}", e);
+        }
+        Connection {
+            socket: NetlinkFramed::new(socket, codec),
             sequence_id: 0,
             pending_requests: HashMap::new(),
             requests_buffer: VecDeque::with_capacity(1024),
             requests_rx,
             shutting_down: false,
-            incoming_messages_tx,
-        })
+            listeners: vec![incoming_messages],
+            undelivered: None,
+            resync_callback: None,
+            default_timeout: None,
+            timer: None,
+        }
     }
 
-    pub fn socket_mut(&mut self) -> &mut TokioSocket {
+    pub fn socket_mut(&mut self) -> &mut S {
         self.socket.get_mut()
     }
 
-    fn prepare_message(&mut self, message: &mut NetlinkMessage) {
+    /// Attach a BPF program to the underlying socket, to drop the unwanted messages in the kernel
+    /// (see [`MessageTypeFilter`](struct.MessageTypeFilter.html)). This is useful for high rate
+    /// listeners, that would otherwise waste time decoding messages they ignore.
+    pub fn attach_filter(&mut self, filter: &[SockFilter]) -> io::Result<()> {
+        self.socket.get_mut().socket_mut().attach_filter(filter)
+    }
+
+    /// Remove the BPF program attached to the underlying socket
+    pub fn detach_filter(&mut self) -> io::Result<()> {
+        self.socket.get_mut().socket_mut().detach_filter()
+    }
+
+    /// Set a callback that is called when messages have been lost because the socket receive
+    /// buffer overflowed. Listeners that maintain a view of the kernel state from notifications
+    /// can use it to trigger a new dump, and resynchronize this view.
+    pub fn set_resync_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.resync_callback = Some(Box::new(callback));
+    }
+
+    /// Set the timeout of the requests that are not sent with
+    /// [`ConnectionHandle::request_with_timeout`](struct.ConnectionHandle.html#method.request_with_timeout).
+    /// When no message of a response is received for that long, the response stream ends with an
+    /// `ErrorKind::Timeout` error. `None` (the default) means requests wait indefinitely.
+    pub fn set_default_timeout(&mut self, timeout: Option<Duration>) {
+        self.default_timeout = timeout;
+    }
+
+    pub fn default_timeout(&self) -> Option<Duration> {
+        self.default_timeout
+    }
+
+    // The socket is bound first if needed: the kernel sends many notifications with a port number
+    // of 0, and does not deliver them to the sockets which port number is also 0.
+    fn add_membership(&mut self, group: u32) -> io::Result<()> {
+        let socket = self.socket.get_mut().socket_mut();
+        let mut address = SocketAddr::new(0, 0);
+        socket.get_address(&mut address)?;
+        if address.port_number() == 0 {
+            socket.bind_auto()?;
+        }
+        socket.add_membership(group)
+    }
+
+    fn prepare_message(&mut self, message: &mut T) {
         self.sequence_id += 1;
         message.header_mut().set_sequence_number(self.sequence_id);
         message.finalize();
@@ -76,60 +198,107 @@ impl Connection {
 
     // FIXME: this should return an error when the sink is full and we don't have any more
     // space to buffer the message
-    fn send(&mut self, request: Request) -> AsyncSink<Request> {
-        if !self.requests_buffer.is_empty() {
-            trace!("there are already requests waiting for being sent");
-            return AsyncSink::NotReady(request);
+    //
+    // If the message cannot be sent now, the request is given back.
+    #[allow(clippy::result_large_err)]
+    fn send(&mut self, cx: &mut Context<'_>, request: Request<T>) -> Result<(), Request<T>> {
+        // NetlinkFramed can only buffer one frame. poll_ready() tries to flush it, and returns
+        // Pending if it is still busy.
+        if Pin::new(&mut self.socket).poll_ready(cx).is_pending() {
+            trace!("the sink is full, cannot send the message now");
+            return Err(request);
         }
 
-        let (tx, message, destination) = request.into();
-        trace!("sending message: {:?} to {:?}", message, destination);
-        match self.socket.start_send((message, destination)).unwrap() {
-            AsyncSink::NotReady((message, destination)) => {
-                // The sink is full atm. There is no need to try to call poll_send() because
-                // internally start_send should call it:
-                //
-                //     https://docs.rs/tokio/0.1.7/tokio/prelude/trait.Sink.html#tymethod.start_send
-                //
-                //     The method returns AsyncSink::NotReady if the sink was unable to begin
-                //     sending, usually due to being full. The sink must have attempted to complete
-                //     processing any outstanding requests (equivalent to poll_complete) before
-                //     yielding this result.
-                trace!("the sink is full, cannot send the message now");
-                AsyncSink::NotReady((tx, message, destination).into())
-            }
-            AsyncSink::Ready => {
-                trace!("message sent!");
-                // NetlinkFramed can only buffer one frame, so to avoid clogging the sink, we need
-                // to flush it as soon as we call start_send(). We don't care about the result
-                // however.
-                let _ = self.socket.poll_complete().unwrap();
-                // Return Ready, because the message is being sent
-                AsyncSink::Ready
+        let Request {
+            messages,
+            destination,
+            timeout,
+        } = request;
+        let timeout = timeout.or(self.default_timeout);
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        // All the messages go in the same datagram
+        let mut batch = Vec::with_capacity(messages.len());
+        let mut pending = Vec::with_capacity(messages.len());
+        for (message, chan) in messages {
+            let seq = message.header().sequence_number();
+            let dump = message.header().flags().has_dump();
+            trace!("sending message: {:?} to {:?}", message, destination);
+            batch.push(message);
+            pending.push((
+                seq,
+                PendingRequest {
+                    chan,
+                    dump,
+                    timeout,
+                    deadline,
+                    interrupted: false,
+                    discard: false,
+                },
+            ));
+        }
+        if let Err(e) = self.socket.start_send_batch(batch, destination) {
+            error!("failed to encode the request: {}", e);
+            for (_, request) in pending {
+                let e = io::Error::new(e.kind(), e.to_string());
+                let _ = request.chan.force_send(Err(ErrorKind::SocketIo(e).into()));
             }
+            return Ok(());
         }
+        for (seq, request) in pending {
+            self.pending_requests.insert((destination, seq), request);
+        }
+        trace!("message sent!");
+        // To avoid clogging the sink, we need to flush it as soon as we call start_send(). We
+        // don't care about the result however.
+        let _ = Pin::new(&mut self.socket).poll_flush(cx);
+        Ok(())
     }
 
-    fn process_buffered_requests(&mut self) {
+    fn process_buffered_requests(&mut self, cx: &mut Context<'_>) {
         while let Some(request) = self.requests_buffer.pop_front() {
-            match self.send(request) {
-                AsyncSink::Ready => {}
-                AsyncSink::NotReady(request) => {
-                    self.requests_buffer.push_front(request);
-                    return;
-                }
+            if let Err(request) = self.send(cx, request) {
+                self.requests_buffer.push_front(request);
+                return;
             }
         }
         trace!("all the buffered requests have been sent");
     }
 
-    fn handle_message(&mut self, message: NetlinkMessage, source: SocketAddr) {
+    fn handle_message(&mut self, message: T, source: SocketAddr, meta: RecvMeta) {
         let seq = message.header().sequence_number();
         let mut close_chan = false;
 
         debug!("handling message {}", seq);
 
-        if let Some(tx) = self.pending_requests.get_mut(&(source, seq)) {
+        if message.is_overrun() {
+            self.handle_overrun();
+            return;
+        }
+
+        if let Some(request) = self.pending_requests.get_mut(&(source, seq)) {
+            // The kernel is still answering: push the deadline back
+            if let Some(timeout) = request.timeout {
+                request.deadline = Some(Instant::now() + timeout);
+            }
+
+            if request.discard {
+                trace!("discarding a message of a dump that already failed");
+                if !message.header().flags().has_multipart()
+                    || message.is_done()
+                    || message.is_error()
+                {
+                    debug!("removing {} from the pending requests", seq);
+                    let _ = self.pending_requests.remove(&(source, seq));
+                }
+                return;
+            }
+
+            if message.is_dump_interrupted() {
+                request.interrupted = true;
+            }
+
+            let tx = &mut request.chan;
             if !message.header().flags().has_multipart() {
                 trace!("not a multipart message");
                 close_chan = true;
@@ -144,7 +313,7 @@ impl Connection {
                 trace!("forwarding error message and closing channel with handle");
                 // If send returns an Err, its because the other side has been dropped, so it
                 // does not really matter.
-                let _ = tx.unbounded_send(message);
+                let _ = tx.try_send(Ok(message));
                 close_chan = true;
             } else if message.is_ack() {
                 trace!("got ack for message {}", message.header().sequence_number());
@@ -153,17 +322,18 @@ impl Connection {
                 // request. But I'm not sure whether that's the case.
                 //
                 // close_chan = true;
-                let _ = tx.unbounded_send(message);
-            } else if message.is_overrun() {
-                // FIXME: we should obviously NOT panic here but I'm not sure what we
-                // should do. Can we increase the buffer size now? Should we leave that the
-                // users?
-                panic!("overrun: receive buffer is full");
+                let _ = tx.try_send(Ok(message));
             } else {
-                let _ = tx.unbounded_send(message);
+                let _ = tx.try_send(Ok(message));
+            }
+
+            // The whole response has been forwarded, but it may be inconsistent
+            if close_chan && request.interrupted {
+                debug!("the kernel tables changed during dump {}", seq);
+                let _ = tx.force_send(Err(ErrorKind::DumpInterrupted.into()));
             }
         } else {
-            let _ = self.incoming_messages_tx.unbounded_send(message);
+            self.forward_incoming_message(message, meta);
         }
 
         if close_chan {
@@ -172,53 +342,213 @@ impl Connection {
         }
     }
 
-    fn read_all(&mut self) -> Result<(), Error> {
+    // Send an unsolicited message to the listeners, and forget the ones that went away
+    fn forward_incoming_message(&mut self, message: T, meta: RecvMeta) {
+        self.listeners
+            .retain(|listener| listener.forward(&message, meta));
+    }
+
+    // Handle the last message received from the socket, if its consumers have room for it.
+    // Otherwise, the current task is woken up when they do.
+    fn poll_deliver(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let (message, source, meta) = match self.undelivered.take() {
+            Some(undelivered) => undelivered,
+            None => return Poll::Ready(()),
+        };
+        if self
+            .poll_consumers(cx, &message, source, &meta)
+            .is_pending()
+        {
+            trace!("the consumers are lagging behind: not reading from the socket for now");
+            self.undelivered = Some((message, source, meta));
+            return Poll::Pending;
+        }
+        self.handle_message(message, source, meta);
+        Poll::Ready(())
+    }
+
+    // Check whether the consumers of a message have room for it
+    fn poll_consumers(
+        &mut self,
+        cx: &mut Context<'_>,
+        message: &T,
+        source: SocketAddr,
+        meta: &RecvMeta,
+    ) -> Poll<()> {
+        if message.is_overrun() {
+            return Poll::Ready(());
+        }
+        let seq = message.header().sequence_number();
+        if let Some(request) = self.pending_requests.get_mut(&(source, seq)) {
+            if request.discard {
+                return Poll::Ready(());
+            }
+            // If the response stream was dropped, the message is discarded
+            return request.chan.poll_ready(cx).map(|_| ());
+        }
+        let mut ready = true;
+        for listener in self.listeners.iter() {
+            if listener.poll_ready(cx, message, meta).is_pending() {
+                ready = false;
+            }
+        }
+        if ready {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    // The kernel dropped messages because the socket receive buffer was full.
+    fn handle_overrun(&mut self) {
+        warn!("the socket receive buffer overflowed: messages have been lost");
+
+        // The responses of the dumps in flight may have lost some parts: fail them, so that they
+        // can be retried. They are kept until they are over, so that the rest of their response
+        // is discarded instead of being taken for unsolicited messages, and their response stream
+        // only ends then: the kernel does not start another dump on the socket before that.
+        // Other requests are left alone, their response is a single message that may still be
+        // received.
+        for request in self.pending_requests.values_mut() {
+            if request.dump && !request.discard {
+                let _ = request
+                    .chan
+                    .force_send(Err(ErrorKind::DumpInterrupted.into()));
+                request.discard = true;
+            }
+        }
+
+        // Tell the listeners that they missed messages
+        self.forward_incoming_message(T::overrun(), RecvMeta::default());
+
+        if let Some(ref mut callback) = self.resync_callback {
+            callback();
+        }
+    }
+
+    // Forget the requests which response stream has been dropped, fail the ones that timed out,
+    // and arm the timer so that the connection is woken up when the next deadline expires.
+    fn check_pending_requests(&mut self, cx: &mut Context<'_>) {
+        loop {
+            let now = Instant::now();
+            let mut next_deadline: Option<Instant> = None;
+
+            self.pending_requests.retain(|_, request| {
+                // The kernel keeps sending the response to a dump even if nobody reads it, so
+                // dumps are only forgotten once they are over: until then, their messages are
+                // discarded, instead of being taken for unsolicited messages.
+                if request.chan.is_closed() && !request.dump {
+                    trace!("the response stream was dropped: cancelling the request");
+                    return false;
+                }
+                match request.deadline {
+                    Some(deadline) if deadline <= now && request.dump => {
+                        debug!("dump timed out: discarding the rest of its response");
+                        let _ = request.chan.force_send(Err(ErrorKind::Timeout.into()));
+                        // End the response stream right away, but keep the dump until it is over
+                        let (closed, _) = channel(Some(0));
+                        request.chan = closed;
+                        request.timeout = None;
+                        request.deadline = None;
+                        request.discard = true;
+                        true
+                    }
+                    Some(deadline) if deadline <= now => {
+                        debug!("request timed out");
+                        let _ = request.chan.force_send(Err(ErrorKind::Timeout.into()));
+                        false
+                    }
+                    Some(deadline) => {
+                        next_deadline = Some(match next_deadline {
+                            Some(next_deadline) if next_deadline < deadline => next_deadline,
+                            _ => deadline,
+                        });
+                        true
+                    }
+                    None => true,
+                }
+            });
+
+            let next_deadline = match next_deadline {
+                Some(next_deadline) => next_deadline,
+                None => {
+                    self.timer = None;
+                    return;
+                }
+            };
+
+            let delay = next_deadline - now;
+            match self.timer {
+                Some(ref mut timer) => timer.reset(delay),
+                None => self.timer = Some(Delay::new(delay)),
+            }
+            // Polling the timer registers the current task, to be woken up when it fires. If the
+            // deadline already passed, the expired requests have to be checked again.
+            if Pin::new(self.timer.as_mut().unwrap()).poll(cx).is_pending() {
+                return;
+            }
+        }
+    }
+
+    fn read_all(&mut self, cx: &mut Context<'_>) -> Result<(), Error<T>> {
         trace!("reading from socket");
         loop {
-            match self
-                .socket
-                .poll()
-                .map_err(|e| Error::from(ErrorKind::SocketIo(e)))?
-            {
-                Async::Ready(Some((message, source))) => {
-                    trace!("message received: {:?}", message);
-                    self.handle_message(message, source);
+            if self.poll_deliver(cx).is_pending() {
+                return Ok(());
+            }
+            match Pin::new(&mut self.socket).poll_next(cx) {
+                Poll::Ready(Some(Ok((message, source, meta)))) => {
+                    trace!("message received: {:?} ({:?})", message, meta);
+                    self.undelivered = Some((message, source, meta));
                 }
-                Async::Ready(None) => {
+                Poll::Ready(Some(Err(ref e))) if e.raw_os_error() == Some(libc::ENOBUFS) => {
+                    self.handle_overrun();
+                }
+                Poll::Ready(Some(Err(e))) => return Err(ErrorKind::SocketIo(e).into()),
+                Poll::Ready(None) => {
                     trace!("socket closed");
                     return Err(ErrorKind::ConnectionClosed.into());
                 }
-                Async::NotReady => return Ok(()),
+                Poll::Pending => return Ok(()),
             }
         }
     }
 
-    fn process_requests(&mut self) {
+    fn process_requests(&mut self, cx: &mut Context<'_>) {
         trace!("polling the requests channel");
-        // FIXME: this fails if the request channel is closed, which can be the case, for instance
-        // for read-only connections, where there's no need for requests.
-        while let Async::Ready(item) = self.requests_rx.poll().unwrap() {
-            if let Some(mut request) = item {
-                trace!("request received, sending it through the netlink socket");
-                self.prepare_message(&mut request.message);
-                let destination = request.destination;
-                let response_chan = request.chan.clone();
-                // NOTE: one send returns NotReady, it will keep returning NotReady for the
-                // rest of the requests, so they will all be buffered.
-                match self.send(request) {
-                    AsyncSink::Ready => {
-                        self.pending_requests
-                            .insert((destination, self.sequence_id), response_chan);
+        while let Poll::Ready(item) = self.requests_rx.poll_next_unpin(cx) {
+            match item {
+                Some(Command::Request(mut request)) => {
+                    trace!("request received, sending it through the netlink socket");
+                    for (message, _) in request.messages.iter_mut() {
+                        self.prepare_message(message);
                     }
-                    AsyncSink::NotReady(request) => {
+                    // NOTE: once send returns an error, the requests must be buffered to preserve
+                    // their order.
+                    if !self.requests_buffer.is_empty() {
+                        self.requests_buffer.push_back(request);
+                    } else if let Err(request) = self.send(cx, request) {
                         trace!("buffering the request");
                         self.requests_buffer.push_back(request);
                     }
                 }
-            } else {
-                trace!("requests channel is closed");
-                self.shutdown();
-                break;
+                Some(Command::AddMembership(group, tx)) => {
+                    debug!("joining multicast group {}", group);
+                    let _ = tx.send(self.add_membership(group));
+                }
+                Some(Command::DropMembership(group, tx)) => {
+                    debug!("leaving multicast group {}", group);
+                    let _ = tx.send(self.socket.get_mut().socket_mut().drop_membership(group));
+                }
+                Some(Command::Listen(listener)) => {
+                    trace!("new listener for the unsolicited messages");
+                    self.listeners.push(listener);
+                }
+                None => {
+                    trace!("requests channel is closed");
+                    self.shutdown();
+                    break;
+                }
             }
         }
     }
@@ -228,49 +558,232 @@ impl Connection {
         self.requests_rx.close();
         self.shutting_down = true;
     }
-}
-
-impl Future for Connection {
-    type Item = ();
-    type Error = Error;
 
-    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+    fn poll_connection(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error<T>>> {
         debug!("polling connection");
-        if let Err(e) = self.read_all() {
+        if let Err(e) = self.read_all(cx) {
             match e.kind() {
-                ErrorKind::ConnectionClosed => return Ok(Async::Ready(())),
-                _ => return Err(e),
+                ErrorKind::ConnectionClosed => return Poll::Ready(Ok(())),
+                _ => return Poll::Ready(Err(e)),
             }
         }
 
         trace!("flushing socket");
-        if let Async::NotReady = self.socket.poll_complete().unwrap() {
+        if Pin::new(&mut self.socket)
+            .poll_flush(cx)
+            .map(Result::unwrap)
+            .is_pending()
+        {
             // We do not poll the requests channel if the sink is full to create backpressure. It's
             // ok not to poll because as soon as the sink makes progress, this future will be
             // called.
             trace!("socket is still busy sending messages: the connection will be polled again when progress has been made");
-            return Ok(Async::NotReady);
+            return Poll::Pending;
         }
 
-        self.process_buffered_requests();
+        self.process_buffered_requests(cx);
         if !self.requests_buffer.is_empty() {
             trace!("there are requests waiting to be sent. Not processing any new request for now");
-            return Ok(Async::NotReady);
+            return Poll::Pending;
         }
 
         if self.shutting_down {
             // If we're shutting down, we don't accept any more request
             trace!("the connection is shutting down: not trying to get new requests");
-            return Ok(Async::NotReady);
+            return Poll::Pending;
         }
 
-        self.process_requests();
+        self.process_requests(cx);
 
         // After sending the requests, flush the sink. We don't care about the outcome here
         trace!("flushing outgoing messages");
-        let _ = self.socket.poll_complete().unwrap();
+        let _ = Pin::new(&mut self.socket).poll_flush(cx);
 
         trace!("re-registering interest in readiness events for the connection");
-        Ok(Async::NotReady)
+        Poll::Pending
+    }
+}
+
+// None of the fields is pinned
+impl<S, T> Unpin for Connection<S, T> {}
+
+impl<S: AsyncSocket, T: Message> Future for Connection<S, T> {
+    type Output = Result<(), Error<T>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let poll = this.poll_connection(cx);
+        if poll.is_pending() {
+            // This is done last, so that the requests sent while polling the connection are
+            // taken into account.
+            this.check_pending_requests(cx);
+        }
+        poll
+    }
+}
+
+#[cfg(all(test, feature = "rtnetlink", feature = "tokio_support"))]
+mod test {
+    use futures::task::noop_waker_ref;
+    use futures::FutureExt;
+    use netlink_packet::constants::{NLM_F_DUMP, NLM_F_MULTIPART, NLM_F_REQUEST};
+    use netlink_packet::{LinkMessage, NetlinkFlags, NetlinkPayload, RtnlMessage};
+    use netlink_sys::{Protocol, TokioSocket};
+
+    use super::*;
+    use crate::testing::fake_connection;
+    use crate::{new_connection, ChannelCapacities, IncomingMessages};
+
+    fn message(sequence_number: u32, payload: NetlinkPayload) -> NetlinkMessage {
+        let mut message = NetlinkMessage::from(payload);
+        message
+            .header_mut()
+            .set_sequence_number(sequence_number)
+            .set_flags(NetlinkFlags::from(NLM_F_MULTIPART));
+        message
+    }
+
+    fn link(sequence_number: u32) -> NetlinkMessage {
+        let payload = NetlinkPayload::Rtnl(RtnlMessage::NewLink(LinkMessage::new()));
+        message(sequence_number, payload)
+    }
+
+    fn get_link(flags: u16) -> NetlinkMessage {
+        let mut message = NetlinkMessage::from(RtnlMessage::GetLink(LinkMessage::new()));
+        message
+            .header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST | flags));
+        message
+    }
+
+    // A connection with a pending request, which sequence number is 1
+    fn pending_request(
+        dump: bool,
+    ) -> (
+        Connection<TokioSocket>,
+        IncomingMessages,
+        Receiver<Result<NetlinkMessage, Error>>,
+    ) {
+        let (mut connection, _, messages) = new_connection(Protocol::Route).unwrap();
+        let (chan, response) = channel(None);
+        connection.pending_requests.insert(
+            (*KERNEL_UNICAST, 1),
+            PendingRequest {
+                chan,
+                dump,
+                timeout: None,
+                deadline: None,
+                interrupted: false,
+                discard: false,
+            },
+        );
+        (connection, messages, response)
+    }
+
+    #[tokio::test]
+    async fn discard_dump_after_overrun() {
+        let (mut connection, mut messages, response) = pending_request(true);
+        let meta = RecvMeta::default();
+        connection.handle_message(link(1), *KERNEL_UNICAST, meta);
+        connection.handle_overrun();
+        connection.handle_message(link(1), *KERNEL_UNICAST, meta);
+        assert!(connection
+            .pending_requests
+            .contains_key(&(*KERNEL_UNICAST, 1)));
+        connection.handle_message(message(1, NetlinkPayload::Done), *KERNEL_UNICAST, meta);
+        assert!(connection.pending_requests.is_empty());
+
+        // the response ends once the dump is over
+        let response: Vec<_> = response.collect().await;
+        assert_eq!(response.len(), 2);
+        assert_eq!(response[0].as_ref().unwrap(), &link(1));
+        assert!(response[1].as_ref().unwrap_err().is_retryable());
+
+        // the rest of the dump is not taken for unsolicited messages
+        assert!(messages.next().await.unwrap().is_overrun());
+        assert!(messages.next().now_or_never().is_none());
+    }
+
+    #[tokio::test]
+    async fn request_timeout() {
+        // the fake kernel never answers
+        let (connection, mut handle, _, kernel) =
+            fake_connection(ChannelCapacities::default(), |_| vec![]);
+        tokio::spawn(connection);
+
+        let timeout = Duration::from_millis(50);
+        let start = Instant::now();
+        let mut response = handle
+            .request_with_timeout(get_link(NLM_F_DUMP), kernel, timeout)
+            .unwrap();
+        match response.next().await.unwrap().unwrap_err().kind() {
+            ErrorKind::Timeout => {}
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        assert!(start.elapsed() >= timeout);
+        assert!(response.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn discard_dump_after_timeout() {
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let meta = RecvMeta::default();
+
+        let (mut connection, mut messages, mut response) = pending_request(true);
+        connection
+            .pending_requests
+            .get_mut(&(*KERNEL_UNICAST, 1))
+            .unwrap()
+            .deadline = Some(Instant::now());
+        connection.check_pending_requests(&mut cx);
+        match response.next().await.unwrap().unwrap_err().kind() {
+            ErrorKind::Timeout => {}
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        assert!(response.next().await.is_none());
+        assert!(connection
+            .pending_requests
+            .contains_key(&(*KERNEL_UNICAST, 1)));
+
+        // the rest of the dump is not taken for unsolicited messages
+        connection.handle_message(link(1), *KERNEL_UNICAST, meta);
+        connection.handle_message(link(1), *KERNEL_UNICAST, meta);
+        connection.handle_message(message(1, NetlinkPayload::Done), *KERNEL_UNICAST, meta);
+        assert!(connection.pending_requests.is_empty());
+        assert!(messages.next().now_or_never().is_none());
+    }
+
+    #[tokio::test]
+    async fn cancel_request() {
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        // the request is forgotten as soon as its response stream is dropped
+        let (mut connection, _, response) = pending_request(false);
+        drop(response);
+        connection.check_pending_requests(&mut cx);
+        assert!(connection.pending_requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn cancel_dump() {
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let meta = RecvMeta::default();
+
+        let (mut connection, mut messages, response) = pending_request(true);
+        drop(response);
+        connection.check_pending_requests(&mut cx);
+        assert!(connection
+            .pending_requests
+            .contains_key(&(*KERNEL_UNICAST, 1)));
+
+        // the rest of the dump is discarded until the end of the dump
+        connection.handle_message(link(1), *KERNEL_UNICAST, meta);
+        assert!(messages.next().now_or_never().is_none());
+        connection.handle_message(message(1, NetlinkPayload::Done), *KERNEL_UNICAST, meta);
+        assert!(connection.pending_requests.is_empty());
+
+        // later messages with the same sequence number are unsolicited
+        connection.handle_message(link(1), *KERNEL_UNICAST, meta);
+        assert_eq!(messages.next().await.unwrap(), link(1));
     }
 }
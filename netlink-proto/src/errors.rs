@@ -5,56 +5,78 @@ use std::io;
 use netlink_packet::NetlinkMessage;
 
 #[derive(Debug)]
-pub struct Error {
-    kind: ErrorKind,
+pub struct Error<T = NetlinkMessage> {
+    kind: ErrorKind<T>,
 }
 
-impl Error {
-    pub fn kind(&self) -> &ErrorKind {
+impl<T> Error<T> {
+    /// Whether sending the request again may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.kind, ErrorKind::DumpInterrupted)
+    }
+
+    pub fn kind(&self) -> &ErrorKind<T> {
         &self.kind
     }
 
-    pub fn into_inner(self) -> ErrorKind {
+    pub fn into_inner(self) -> ErrorKind<T> {
         self.kind
     }
 }
 
 #[derive(Debug)]
-pub enum ErrorKind {
+pub enum ErrorKind<T = NetlinkMessage> {
     /// The netlink connection is closed
     ConnectionClosed,
 
     /// Received an error message as a response
-    NetlinkError(NetlinkMessage),
+    NetlinkError(T),
 
     /// Error while reading from or writing to the netlink socket
     SocketIo(io::Error),
+
+    /// No response was received before the timeout expired
+    Timeout,
+
+    /// The response to a dump may be inconsistent, because messages were lost while it was being
+    /// received (the socket receive buffer overflowed), or because the kernel tables changed
+    /// during the dump (the kernel set `NLM_F_DUMP_INTR`). The request should be sent again.
+    DumpInterrupted,
+
+    /// The channel on which requests are passed to the connection is full (see
+    /// [`ConnectionHandle::ready`](struct.ConnectionHandle.html#method.ready))
+    ChannelFull,
 }
 
-impl From<ErrorKind> for Error {
-    fn from(kind: ErrorKind) -> Error {
+impl<T> From<ErrorKind<T>> for Error<T> {
+    fn from(kind: ErrorKind<T>) -> Error<T> {
         Error { kind }
     }
 }
 
-impl fmt::Display for Error {
+impl<T: fmt::Debug> fmt::Display for Error<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use crate::ErrorKind::*;
         match self.kind() {
             SocketIo(ref e) => write!(f, "{}: {}", self.description(), e),
-            ConnectionClosed => write!(f, "{}", self.description()),
+            ConnectionClosed | Timeout | DumpInterrupted | ChannelFull => {
+                write!(f, "{}", self.description())
+            }
             NetlinkError(ref message) => write!(f, "{}: {:?}", self.description(), message),
         }
     }
 }
 
-impl StdError for Error {
+impl<T: fmt::Debug> StdError for Error<T> {
     fn description(&self) -> &str {
         use crate::ErrorKind::*;
         match self.kind() {
             SocketIo(_) => "Error while reading from or writing to the netlink socket",
             ConnectionClosed => "The netlink connection is closed",
             NetlinkError(_) => "Received an error message as a response",
+            Timeout => "Timed out while waiting for a response",
+            DumpInterrupted => "The dump was interrupted, and its result may be inconsistent",
+            ChannelFull => "Too many requests are waiting to be sent",
         }
     }
 
@@ -1,143 +1,199 @@
 use std::io;
-use tokio_io::codec::{Decoder, Encoder};
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 use bytes::{BufMut, BytesMut};
-use futures::{Async, AsyncSink, Poll, Sink, StartSend, Stream};
-use netlink_sys::{SocketAddr, TokioSocket};
-
-pub struct NetlinkFramed<C> {
-    socket: TokioSocket,
+use futures::{ready, Sink, Stream};
+use netlink_sys::{AsyncSocket, RecvMeta, SocketAddr};
+
+use crate::codecs::{Decoder, Encoder};
+
+/// A `Stream` and `Sink` of netlink messages, built on top of a netlink socket and a codec.
+///
+/// The messages are sent along with their destination address, and received along with their
+/// source address and the metadata of the datagram that carried them (the multicast group it was
+/// sent to, and the network namespace it comes from, if the socket is configured to report them
+/// with `NETLINK_PKTINFO` and `NETLINK_LISTEN_ALL_NSID`).
+///
+/// The socket can be any [`AsyncSocket`](trait.AsyncSocket.html), so that the runtime is chosen
+/// by the user (`TokioSocket`, `SmolSocket` or `AsyncStdSocket`).
+pub struct NetlinkFramed<C, S> {
+    socket: S,
     codec: C,
     reader: BytesMut,
     writer: BytesMut,
     out_addr: SocketAddr,
     in_addr: SocketAddr,
+    in_meta: RecvMeta,
     flushed: bool,
 }
 
-impl<C: Decoder> Stream for NetlinkFramed<C> {
-    type Item = (C::Item, SocketAddr);
-    type Error = C::Error;
+// None of the fields is pinned
+impl<C, S> Unpin for NetlinkFramed<C, S> {}
 
-    fn poll(&mut self) -> Poll<Option<(Self::Item)>, Self::Error> {
-        if let Some(item) = self.codec.decode(&mut self.reader)? {
-            return Ok(Async::Ready(Some((item, self.in_addr))));
-        }
+impl<C: Decoder, S: AsyncSocket> Stream for NetlinkFramed<C, S> {
+    type Item = Result<(C::Item, SocketAddr, RecvMeta), C::Error>;
 
-        // There should not be byte left in the buffer. NetlinkMessage oriented protocols guarantee that
-        // complete datagrams are being delivered.
-        if !self.reader.is_empty() {
-            error!(
-                "{} bytes left in the buffer that could not be decoded",
-                self.reader.len()
-            );
-        }
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match this.codec.decode(&mut this.reader) {
+                Ok(Some(item)) => return Poll::Ready(Some(Ok((item, this.in_addr, this.in_meta)))),
+                Ok(None) => {}
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
 
-        self.reader.clear();
-        self.reader.reserve(INITIAL_READER_CAPACITY);
+            // The codec returns None if there's nothing left in the reader, or if it failed to
+            // decode a message. There should not be byte left in the buffer: netlink message
+            // oriented protocols guarantee that complete datagrams are being delivered.
+            if !this.reader.is_empty() {
+                error!(
+                    "{} bytes left in the buffer that could not be decoded",
+                    this.reader.len()
+                );
+            }
 
-        self.in_addr = unsafe {
-            // Read into the buffer without having to initialize the memory.
-            // Note: this can return NotReady
-            let (n, addr) = try_ready!(self.socket.poll_recv_from(self.reader.bytes_mut()));
-            self.reader.advance_mut(n);
-            addr
-        };
+            // It is important to reset the reader's cursor here, otherwise, the next message will
+            // lead to a non-recoverable decoding error.
+            this.reader.clear();
+            this.reader.reserve(INITIAL_READER_CAPACITY);
+
+            // Datagrams larger than the buffer would be silently truncated, so look at the length
+            // of the next one first, and grow the buffer if necessary. Large dumps (links with
+            // many VFs for instance) can exceed the default capacity.
+            let len = match ready!(this.socket.poll_peek_len(cx)) {
+                Ok(len) => len,
+                Err(e) => return Poll::Ready(Some(Err(e.into()))),
+            };
+            if len > this.reader.capacity() {
+                debug!(
+                    "growing the reader buffer to receive a {} bytes datagram",
+                    len
+                );
+                this.reader.reserve(len);
+            }
 
-        if let Some(item) = self.codec.decode(&mut self.reader)? {
-            Ok(Async::Ready(Some((item, self.in_addr))))
-        } else {
-            // The codec returns None if there's nothing left in the reader. Previously, we were
-            // returning Async::NotReady here. But under some conditions (specifically, when the
-            // Codec returned None upon failing to decode a message, and recursively calling
-            // codec.decode()), this would lead to hangs, because we MUST poll the socket until it
-            // returns NotReady before returning NotReady ourself.
-            //
-            // It is also important to reset the reader's cursor here, otherwise, the next message
-            // will lead to a non-recoverable decoding error.
-            self.reader.clear();
-            self.reader.reserve(INITIAL_READER_CAPACITY);
-            self.poll()
+            // Read into the buffer without having to initialize the memory. We MUST poll the
+            // socket until it returns Pending before returning Pending ourself, which is why we
+            // loop until either a message is decoded or the socket is not ready.
+            let res = unsafe { ready!(this.socket.poll_recv_msg(cx, this.reader.bytes_mut())) };
+            match res {
+                Ok((n, addr, meta)) => {
+                    unsafe { this.reader.advance_mut(n) };
+                    this.in_addr = addr;
+                    this.in_meta = meta;
+                }
+                Err(e) => return Poll::Ready(Some(Err(e.into()))),
+            }
         }
     }
 }
 
-impl<C: Encoder> Sink for NetlinkFramed<C> {
-    type SinkItem = (C::Item, SocketAddr);
-    type SinkError = C::Error;
-
-    fn start_send(&mut self, item: Self::SinkItem) -> StartSend<Self::SinkItem, Self::SinkError> {
-        trace!("sending frame");
+impl<C: Encoder, S: AsyncSocket> Sink<(C::Item, SocketAddr)> for NetlinkFramed<C, S> {
+    type Error = C::Error;
 
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         if !self.flushed {
             trace!("flushing the sink, before sending the frame");
-            match self.poll_complete()? {
-                Async::Ready(()) => trace!("sink flushed"),
-                Async::NotReady => {
+            match self.poll_flush(cx)? {
+                Poll::Ready(()) => trace!("sink flushed"),
+                Poll::Pending => {
                     trace!("could not flush the sink entirely");
-                    return Ok(AsyncSink::NotReady(item));
+                    return Poll::Pending;
                 }
             }
         }
+        Poll::Ready(Ok(()))
+    }
 
+    fn start_send(self: Pin<&mut Self>, item: (C::Item, SocketAddr)) -> Result<(), Self::Error> {
+        trace!("sending frame");
+        let this = self.get_mut();
         let (frame, out_addr) = item;
-        self.codec.encode(frame, &mut self.writer)?;
-        self.out_addr = out_addr;
-        self.flushed = false;
-        trace!("frame encoded; length={}", self.writer.len());
-
-        Ok(AsyncSink::Ready)
+        this.codec.encode(frame, &mut this.writer)?;
+        this.out_addr = out_addr;
+        this.flushed = false;
+        trace!("frame encoded; length={}", this.writer.len());
+        Ok(())
     }
 
-    fn poll_complete(&mut self) -> Poll<(), C::Error> {
-        if self.flushed {
-            return Ok(Async::Ready(()));
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        if this.flushed {
+            return Poll::Ready(Ok(()));
         }
 
-        trace!("flushing frame; length={}", self.writer.len());
-        let n = try_ready!(self.socket.poll_send_to(&self.writer, &self.out_addr));
+        trace!("flushing frame; length={}", this.writer.len());
+        let n = ready!(this.socket.poll_send_to(cx, &this.writer, &this.out_addr))?;
         trace!("written {}", n);
 
-        let wrote_all = n == self.writer.len();
-        self.writer.clear();
-        self.flushed = true;
+        let wrote_all = n == this.writer.len();
+        this.writer.clear();
+        this.flushed = true;
 
         if wrote_all {
-            Ok(Async::Ready(()))
+            Poll::Ready(Ok(()))
         } else {
-            Err(io::Error::new(
-                io::ErrorKind::Other,
+            Poll::Ready(Err(io::Error::other(
                 "failed to write entire datagram to socket",
             )
-            .into())
+            .into()))
         }
     }
 
-    fn close(&mut self) -> Poll<(), C::Error> {
-        try_ready!(self.poll_complete());
-        Ok(().into())
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.poll_flush(cx))?;
+        Poll::Ready(Ok(()))
     }
 }
 
 const INITIAL_READER_CAPACITY: usize = 64 * 1024;
 const INITIAL_WRITER_CAPACITY: usize = 8 * 1024;
 
-impl<C> NetlinkFramed<C> {
+impl<C, S> NetlinkFramed<C, S> {
     /// Create a new `NetlinkFramed` backed by the given socket and codec.
     ///
     /// See struct level documentation for more details.
-    pub fn new(socket: TokioSocket, codec: C) -> NetlinkFramed<C> {
+    pub fn new(socket: S, codec: C) -> NetlinkFramed<C, S> {
         NetlinkFramed {
             socket,
             codec,
             out_addr: SocketAddr::new(0, 0),
             in_addr: SocketAddr::new(0, 0),
+            in_meta: RecvMeta::default(),
             reader: BytesMut::with_capacity(INITIAL_READER_CAPACITY),
             writer: BytesMut::with_capacity(INITIAL_WRITER_CAPACITY),
             flushed: true,
         }
     }
 
+    /// Same as `Sink::start_send`, but several messages are sent in a single datagram. The kernel
+    /// processes them one after the other, as if they had been sent separately.
+    ///
+    /// Like `start_send`, this must be preceded by a successful call to `Sink::poll_ready`.
+    pub fn start_send_batch(
+        &mut self,
+        frames: Vec<C::Item>,
+        out_addr: SocketAddr,
+    ) -> Result<(), C::Error>
+    where
+        C: Encoder,
+    {
+        trace!("sending {} frames", frames.len());
+        // If a frame cannot be encoded, none of them is sent
+        let len = self.writer.len();
+        for frame in frames {
+            if let Err(e) = self.codec.encode(frame, &mut self.writer) {
+                self.writer.truncate(len);
+                return Err(e);
+            }
+        }
+        self.out_addr = out_addr;
+        self.flushed = false;
+        trace!("frames encoded; length={}", self.writer.len());
+        Ok(())
+    }
+
     /// Returns a reference to the underlying I/O stream wrapped by `Framed`.
     ///
     /// # Note
@@ -145,7 +201,7 @@ impl<C> NetlinkFramed<C> {
     /// Care should be taken to not tamper with the underlying stream of data
     /// coming in as it may corrupt the stream of frames otherwise being worked
     /// with.
-    pub fn get_ref(&self) -> &TokioSocket {
+    pub fn get_ref(&self) -> &S {
         &self.socket
     }
 
@@ -157,12 +213,12 @@ impl<C> NetlinkFramed<C> {
     /// Care should be taken to not tamper with the underlying stream of data
     /// coming in as it may corrupt the stream of frames otherwise being worked
     /// with.
-    pub fn get_mut(&mut self) -> &mut TokioSocket {
+    pub fn get_mut(&mut self) -> &mut S {
         &mut self.socket
     }
 
     /// Consumes the `Framed`, returning its underlying I/O stream.
-    pub fn into_inner(self) -> TokioSocket {
+    pub fn into_inner(self) -> S {
         self.socket
     }
 }
@@ -1,20 +1,68 @@
-use futures::sync::mpsc::{unbounded, UnboundedSender};
-use futures::Stream;
+use std::time::Duration;
+
+use futures::channel::mpsc::TrySendError;
+use futures::channel::oneshot;
+use futures::future::{self, Either, Future};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
 use netlink_packet::NetlinkMessage;
 
 use crate::errors::{Error, ErrorKind};
-use crate::Request;
+use crate::{
+    channel, Command, DropPolicy, IncomingMessages, Listener, Message, MessageFilter, Request,
+    ResponseReceiver, Sender,
+};
 use netlink_sys::SocketAddr;
 
+/// Maximum size of the datagrams sent by [`ConnectionHandle::batch`](struct.ConnectionHandle.html#method.batch).
+/// It is well below the default size of the socket send buffer.
+pub const MAX_BATCH_SIZE: usize = 32 * 1024;
+
+/// Maximum number of messages in the datagrams sent by
+/// [`ConnectionHandle::batch`](struct.ConnectionHandle.html#method.batch). The kernel queues a
+/// response for each of them (at least an acknowledgement) on the socket, so this bounds how much
+/// of the socket receive buffer the responses take.
+pub const MAX_BATCH_MESSAGES: usize = 32;
+
 /// A handle to pass requests to a [`Connection`](struct.Connection.html).
 #[derive(Clone, Debug)]
-pub struct ConnectionHandle {
-    requests_tx: UnboundedSender<Request>,
+pub struct ConnectionHandle<T = NetlinkMessage> {
+    requests_tx: Sender<Command<T>>,
+    // Capacity of the response channels
+    responses_capacity: Option<usize>,
+    dump_retries: u32,
 }
 
-impl ConnectionHandle {
-    pub(crate) fn new(requests_tx: UnboundedSender<Request>) -> Self {
-        ConnectionHandle { requests_tx }
+impl<T: Message> ConnectionHandle<T> {
+    pub(crate) fn new(requests_tx: Sender<Command<T>>, responses_capacity: Option<usize>) -> Self {
+        ConnectionHandle {
+            requests_tx,
+            responses_capacity,
+            dump_retries: 0,
+        }
+    }
+
+    /// Wait until the connection can take a new request. This is only useful when the requests
+    /// channel is bounded (see [`ChannelCapacities`](struct.ChannelCapacities.html)): requests
+    /// sent while it is full fail with `ErrorKind::ChannelFull`.
+    pub async fn ready(&mut self) -> Result<(), Error<T>> {
+        future::poll_fn(|cx| self.requests_tx.poll_ready(cx))
+            .await
+            .map_err(|_| ErrorKind::ConnectionClosed.into())
+    }
+
+    /// Send the dumps that fail with `ErrorKind::DumpInterrupted` again, up to `retries` times.
+    /// When retries are enabled, the messages of a dump are only yielded once the whole dump has
+    /// been received without interruption, so that the response is a consistent snapshot of the
+    /// kernel tables. By default, dumps are not retried.
+    ///
+    /// This applies to the requests sent with this handle, and with the handles cloned from it
+    /// afterwards.
+    pub fn set_dump_retries(&mut self, retries: u32) {
+        self.dump_retries = retries;
+    }
+
+    pub fn dump_retries(&self) -> u32 {
+        self.dump_retries
     }
 
     /// Send a new request and get the response as a stream of messages. Note that some messages
@@ -23,34 +71,460 @@ impl ConnectionHandle {
     /// - **acknowledgements**: when an acknowledgement is received, the stream is closed
     /// - **end of dump messages**: similarly, upon receiving an "end of dump" message, the stream is
     /// closed
+    ///
+    /// An error is returned if the connection is closed. If the response to a dump may be
+    /// inconsistent (messages were lost, or the kernel tables changed during the dump), the stream
+    /// yields an `ErrorKind::DumpInterrupted` error, and ends once the kernel is done with the
+    /// dump: the request can then be sent again. If the connection has a default timeout (see
+    /// [`Connection::set_default_timeout`](struct.Connection.html#method.set_default_timeout)),
+    /// the stream ends with an `ErrorKind::Timeout` error when the kernel stops answering.
+    ///
+    /// Dropping the stream cancels the request: the response is discarded.
+    ///
+    /// Interrupted dumps can be retried automatically, see
+    /// [`set_dump_retries`](#method.set_dump_retries).
     pub fn request(
         &mut self,
-        message: NetlinkMessage,
+        message: T,
+        destination: SocketAddr,
+    ) -> Result<impl Stream<Item = Result<T, Error<T>>>, Error<T>> {
+        self.send_request(message, destination, None)
+    }
+
+    /// Same as [`request`](#method.request), but the stream ends with an `ErrorKind::Timeout`
+    /// error if no message of the response is received for `timeout`, whatever the default
+    /// timeout of the connection is.
+    pub fn request_with_timeout(
+        &mut self,
+        message: T,
         destination: SocketAddr,
-    ) -> impl Stream<Item = NetlinkMessage, Error = Error> {
-        let (tx, rx) = unbounded::<NetlinkMessage>();
-        let request = Request::from((tx, message, destination));
+        timeout: Duration,
+    ) -> Result<impl Stream<Item = Result<T, Error<T>>>, Error<T>> {
+        self.send_request(message, destination, Some(timeout))
+    }
+
+    fn send_request(
+        &mut self,
+        message: T,
+        destination: SocketAddr,
+        timeout: Option<Duration>,
+    ) -> Result<impl Stream<Item = Result<T, Error<T>>>, Error<T>> {
+        if self.dump_retries == 0 || !message.header().flags().has_dump() {
+            return Ok(Either::Left(self.forward_request(
+                message,
+                destination,
+                timeout,
+            )?));
+        }
+
+        let response = self.forward_request(message.clone(), destination, timeout)?;
+        let handle = self.clone();
+        let response = handle.collect_dump(response, message, destination, timeout);
+        // the future is boxed so that the stream is `Unpin`, like the channel
+        Ok(Either::Right(
+            stream::once(Box::pin(response))
+                .map_ok(|messages| stream::iter(messages.into_iter().map(Ok)))
+                .try_flatten(),
+        ))
+    }
+
+    // Receive the whole response to a dump, and send the dump again each time it is interrupted
+    async fn collect_dump(
+        mut self,
+        mut response: ResponseReceiver<T>,
+        message: T,
+        destination: SocketAddr,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<T>, Error<T>> {
+        let mut retries = 0;
+        loop {
+            let mut messages = vec![];
+            let mut interrupted = false;
+            // The response stream ends when the kernel is done with the dump, which must happen
+            // before the dump is sent again: the kernel does not run two dumps at the same time
+            // on a socket (it fails the second one with `EBUSY`).
+            while let Some(item) = response.next().await {
+                match item {
+                    Ok(message) => messages.push(message),
+                    Err(e) if e.is_retryable() => interrupted = true,
+                    Err(e) => return Err(e),
+                }
+            }
+            if !interrupted {
+                return Ok(messages);
+            }
+            if retries == self.dump_retries {
+                return Err(ErrorKind::DumpInterrupted.into());
+            }
+            retries += 1;
+            debug!(
+                "handle: dump interrupted, sending it again ({}/{})",
+                retries, self.dump_retries
+            );
+            self.ready().await?;
+            response = self.forward_request(message.clone(), destination, timeout)?;
+        }
+    }
+
+    fn forward_request(
+        &mut self,
+        message: T,
+        destination: SocketAddr,
+        timeout: Option<Duration>,
+    ) -> Result<ResponseReceiver<T>, Error<T>> {
+        let (tx, rx) = channel::<Result<T, Error<T>>>(self.responses_capacity);
+        let request = Request {
+            messages: vec![(message, tx)],
+            destination,
+            timeout,
+        };
         debug!("handle: forwarding new request to connection");
-        // We don't handle the error here, because we would have to return a Result, which makes
-        // the signature of this method pretty ugly. If this fails, we know that the receiver has
-        // been dropped, so the request (and the tx channed it contains) will be dropped when this
-        // function returns. Then rx.poll() will return the error we want.
-        let _ = UnboundedSender::unbounded_send(&self.requests_tx, request);
-        rx.map_err(|()| {
-            error!("could not forward new request to connection: the connection is closed");
-            ErrorKind::ConnectionClosed.into()
+        self.send_command(Command::Request(request))?;
+        Ok(rx)
+    }
+
+    pub fn notify(&mut self, message: T, destination: SocketAddr) -> Result<(), Error<T>> {
+        // The receiver is dropped right away, so the connection discards the response
+        let (tx, _rx) = channel::<Result<T, Error<T>>>(None);
+        let request = Request {
+            messages: vec![(message, tx)],
+            destination,
+            timeout: None,
+        };
+        debug!("handle: forwarding new request to connection");
+        self.send_command(Command::Request(request))
+    }
+
+    /// Send several messages, packing them in datagrams of up to
+    /// [`MAX_BATCH_MESSAGES`](constant.MAX_BATCH_MESSAGES.html) messages and
+    /// [`MAX_BATCH_SIZE`](constant.MAX_BATCH_SIZE.html) bytes, so that creating many objects does
+    /// not take one system call per object. Each message gets its own sequence number, and its
+    /// own response. The responses to the messages of a datagram are received before the next
+    /// datagram is sent, so that they do not overflow the socket receive buffer.
+    ///
+    /// The returned future resolves into the results of the messages, in the same order as the
+    /// messages. The result of a message is its response, minus the acknowledgement. If the
+    /// kernel answers with an error message instead, the result is an `ErrorKind::NetlinkError`
+    /// error.
+    ///
+    /// The messages should have the `NLM_F_ACK` flag set: otherwise, the kernel does not answer to
+    /// the messages that succeed, and their results are only known when the request times out (see
+    /// [`Connection::set_default_timeout`](struct.Connection.html#method.set_default_timeout)).
+    #[allow(clippy::type_complexity)]
+    pub fn batch(
+        &mut self,
+        messages: Vec<T>,
+        destination: SocketAddr,
+    ) -> Result<impl Future<Output = Vec<Result<Vec<T>, Error<T>>>>, Error<T>> {
+        let mut batches = split_batch(messages).into_iter();
+        let mut responses = match batches.next() {
+            Some(batch) => self.forward_batch(batch, destination)?,
+            None => vec![],
+        };
+
+        let mut handle = self.clone();
+        Ok(async move {
+            let mut results = vec![];
+            loop {
+                results.extend(future::join_all(responses.into_iter().map(batch_response)).await);
+
+                let batch = match batches.next() {
+                    Some(batch) => batch,
+                    None => return results,
+                };
+                let len = batch.len();
+                let sent = match handle.ready().await {
+                    Ok(()) => handle.forward_batch(batch, destination),
+                    Err(e) => Err(e),
+                };
+                responses = match sent {
+                    Ok(responses) => responses,
+                    Err(_) => {
+                        let len = len + batches.map(|batch| batch.len()).sum::<usize>();
+                        results.extend((0..len).map(|_| Err(ErrorKind::ConnectionClosed.into())));
+                        return results;
+                    }
+                };
+            }
         })
     }
 
-    pub fn notify(
+    // Send the given messages in a single datagram
+    fn forward_batch(
         &mut self,
-        message: NetlinkMessage,
+        messages: Vec<T>,
         destination: SocketAddr,
-    ) -> Result<(), Error> {
-        let (tx, _rx) = unbounded::<NetlinkMessage>();
-        let request = Request::from((tx, message, destination));
-        debug!("handle: forwarding new request to connection");
-        UnboundedSender::unbounded_send(&self.requests_tx, request)
-            .map_err(|_| ErrorKind::ConnectionClosed.into())
+    ) -> Result<Vec<ResponseReceiver<T>>, Error<T>> {
+        let mut responses = Vec::with_capacity(messages.len());
+        let messages = messages
+            .into_iter()
+            .map(|message| {
+                let (tx, rx) = channel::<Result<T, Error<T>>>(self.responses_capacity);
+                responses.push(rx);
+                (message, tx)
+            })
+            .collect();
+        let request = Request {
+            messages,
+            destination,
+            timeout: None,
+        };
+        debug!("handle: forwarding new batch to connection");
+        self.send_command(Command::Request(request))?;
+        Ok(responses)
+    }
+
+    /// Join the given multicast group (for instance `RtnlGroup::Link`, or one of the `RTNLGRP_*`
+    /// constants), so that the messages the kernel broadcasts to that group are received on the
+    /// stream of unsolicited messages. Unlike `Socket::add_membership`, this can be called while
+    /// the connection is running.
+    pub async fn subscribe<G: Into<u32>>(&mut self, group: G) -> Result<(), Error<T>> {
+        let (tx, rx) = oneshot::channel();
+        self.send_command(Command::AddMembership(group.into(), tx))?;
+        membership_result(rx).await
+    }
+
+    /// Leave the given multicast group
+    pub async fn unsubscribe<G: Into<u32>>(&mut self, group: G) -> Result<(), Error<T>> {
+        let (tx, rx) = oneshot::channel();
+        self.send_command(Command::DropMembership(group.into(), tx))?;
+        membership_result(rx).await
+    }
+
+    /// Get a new stream of all the unsolicited messages (the multicast messages of the groups the
+    /// connection is subscribed to for instance). This is the same as
+    /// [`listen`](#method.listen) with `MessageFilter::All` and an unbounded buffer.
+    pub fn incoming_messages(&mut self) -> Result<IncomingMessages<T>, Error<T>> {
+        self.listen(MessageFilter::All, usize::MAX, DropPolicy::DropNewest)
+    }
+
+    /// Get a new stream of the unsolicited messages that match the given filter, so that several
+    /// consumers can each receive the messages they are interested in. Each stream gets a copy of
+    /// the matching messages received after it was created, in addition to the stream returned
+    /// along with the connection. The `NetlinkPayload::Overrun` messages, which tell that
+    /// messages were lost, are received by all the streams.
+    ///
+    /// The connection buffers up to `capacity` messages for the stream. When the buffer is full,
+    /// the connection does not wait for the stream to be read: messages are discarded as per
+    /// `policy` (see [`IncomingMessages::dropped`](struct.IncomingMessages.html#method.dropped)).
+    pub fn listen(
+        &mut self,
+        filter: MessageFilter<T>,
+        capacity: usize,
+        policy: DropPolicy,
+    ) -> Result<IncomingMessages<T>, Error<T>> {
+        let (listener, messages) = Listener::new(filter, capacity, policy);
+        self.send_command(Command::Listen(listener))?;
+        Ok(messages)
+    }
+
+    fn send_command(&mut self, command: Command<T>) -> Result<(), Error<T>> {
+        self.requests_tx
+            .try_send(command)
+            .map_err(|e: TrySendError<Command<T>>| {
+                if e.is_full() {
+                    debug!("could not forward command to connection: the channel is full");
+                    ErrorKind::ChannelFull.into()
+                } else {
+                    error!("could not forward command to connection: the connection is closed");
+                    ErrorKind::ConnectionClosed.into()
+                }
+            })
+    }
+}
+
+// Wait for the connection to join or leave a multicast group
+async fn membership_result<T>(rx: oneshot::Receiver<std::io::Result<()>>) -> Result<(), Error<T>> {
+    match rx.await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => Err(ErrorKind::SocketIo(e).into()),
+        Err(_) => Err(ErrorKind::ConnectionClosed.into()),
+    }
+}
+
+// Split the messages of a batch into datagrams
+fn split_batch<T: Message>(messages: Vec<T>) -> Vec<Vec<T>> {
+    let mut batches = vec![];
+    let mut batch = vec![];
+    let mut batch_len = 0;
+    for message in messages {
+        let len = message.buffer_len();
+        if !batch.is_empty()
+            && (batch.len() == MAX_BATCH_MESSAGES || batch_len + len > MAX_BATCH_SIZE)
+        {
+            batches.push(batch);
+            batch = vec![];
+            batch_len = 0;
+        }
+        batch.push(message);
+        batch_len += len;
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    batches
+}
+
+// Collect the response to a message of a batch
+async fn batch_response<T: Message>(mut response: ResponseReceiver<T>) -> Result<Vec<T>, Error<T>> {
+    let mut messages = vec![];
+    while let Some(message) = response.next().await {
+        let message = message?;
+        if message.is_error() {
+            return Err(ErrorKind::NetlinkError(message).into());
+        } else if !message.is_ack() {
+            messages.push(message);
+        }
+    }
+    Ok(messages)
+}
+
+#[cfg(all(test, feature = "rtnetlink", feature = "tokio_support"))]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::Context;
+
+    use netlink_packet::constants::{NLM_F_DUMP, NLM_F_DUMP_INTR, NLM_F_MULTIPART, NLM_F_REQUEST};
+    use netlink_packet::{Emitable, LinkMessage, NetlinkFlags, NetlinkPayload, RtnlMessage};
+
+    use futures::task::noop_waker_ref;
+
+    use super::*;
+    use crate::testing::{fake_connection, reply};
+    use crate::ChannelCapacities;
+
+    #[tokio::test]
+    async fn requests_channel_full() {
+        let (tx, mut rx) = channel::<Command<NetlinkMessage>>(Some(0));
+        let mut handle = ConnectionHandle::new(tx, None);
+        let destination = SocketAddr::new(0, 0);
+        // the sender of the handle has a slot of its own
+        handle.notify(dump_links(), destination).unwrap();
+        match handle.notify(dump_links(), destination) {
+            Err(e) => assert!(matches!(e.kind(), ErrorKind::ChannelFull)),
+            Ok(()) => panic!("the requests channel should be full"),
+        }
+
+        let mut ready = Box::pin(handle.ready());
+        let mut cx = Context::from_waker(noop_waker_ref());
+        assert!(ready.as_mut().poll(&mut cx).is_pending());
+        assert!(rx.next().await.is_some());
+        ready.await.unwrap();
+        handle.notify(dump_links(), destination).unwrap();
+    }
+
+    fn dump_links() -> NetlinkMessage {
+        let mut message = NetlinkMessage::from(RtnlMessage::GetLink(LinkMessage::new()));
+        message
+            .header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST | NLM_F_DUMP));
+        message
+    }
+
+    fn link() -> NetlinkPayload {
+        NetlinkPayload::Rtnl(RtnlMessage::NewLink(LinkMessage::new()))
+    }
+
+    // A handle to a fake kernel, which dumps are interrupted the first `interrupted` times they
+    // are sent. Also return how many times a dump was sent.
+    fn handle(interrupted: usize) -> (ConnectionHandle, SocketAddr, Arc<AtomicUsize>) {
+        let dumps = Arc::new(AtomicUsize::new(0));
+        let counter = dumps.clone();
+        let (connection, handle, _, kernel) =
+            fake_connection(ChannelCapacities::default(), move |request| {
+                let mut flags = NLM_F_MULTIPART;
+                if counter.fetch_add(1, Ordering::SeqCst) < interrupted {
+                    flags |= NLM_F_DUMP_INTR;
+                }
+                vec![
+                    reply(request, flags, link()),
+                    reply(request, flags, link()),
+                    reply(request, flags, NetlinkPayload::Done),
+                ]
+            });
+        tokio::spawn(connection);
+        (handle, kernel, dumps)
+    }
+
+    #[tokio::test]
+    async fn retry_interrupted_dump() {
+        let (mut handle, kernel, dumps) = handle(2);
+        handle.set_dump_retries(2);
+        let response: Vec<_> = handle
+            .request(dump_links(), kernel)
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        // only the messages of the last dump are yielded
+        assert_eq!(response.len(), 2);
+        assert!(!response[0].is_dump_interrupted());
+        assert_eq!(dumps.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn dump_retries_limit() {
+        let (mut handle, kernel, dumps) = handle(usize::MAX);
+        handle.set_dump_retries(2);
+        let response: Vec<_> = handle
+            .request(dump_links(), kernel)
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(response.len(), 1);
+        assert!(response[0].as_ref().unwrap_err().is_retryable());
+        assert_eq!(dumps.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn no_dump_retries() {
+        let (mut handle, kernel, dumps) = handle(usize::MAX);
+        handle.set_dump_retries(0);
+        let response: Vec<_> = handle
+            .request(dump_links(), kernel)
+            .unwrap()
+            .collect()
+            .await;
+        // the messages are yielded as they are received, and the error comes last
+        assert_eq!(response.len(), 3);
+        assert!(response[0].as_ref().unwrap().is_dump_interrupted());
+        assert!(response[1].as_ref().unwrap().is_dump_interrupted());
+        assert!(response[2].as_ref().unwrap_err().is_retryable());
+        assert_eq!(dumps.load(Ordering::SeqCst), 1);
+    }
+
+    fn messages(len: usize, count: usize) -> Vec<NetlinkMessage> {
+        // the header takes 16 bytes
+        let message = NetlinkMessage::from(NetlinkPayload::Overrun(vec![0; len - 16]));
+        assert_eq!(message.buffer_len(), len);
+        vec![message; count]
+    }
+
+    fn batch_lens(messages: Vec<NetlinkMessage>) -> Vec<usize> {
+        split_batch(messages).iter().map(Vec::len).collect()
+    }
+
+    #[test]
+    fn split_batch_messages() {
+        assert_eq!(batch_lens(messages(16, MAX_BATCH_MESSAGES)), vec![32]);
+        assert_eq!(
+            batch_lens(messages(16, MAX_BATCH_MESSAGES + 1)),
+            vec![32, 1]
+        );
+        assert!(batch_lens(vec![]).is_empty());
+    }
+
+    #[test]
+    fn split_batch_size() {
+        let half = MAX_BATCH_SIZE / 2;
+        assert_eq!(batch_lens(messages(half, 2)), vec![2]);
+        assert_eq!(batch_lens(messages(half, 3)), vec![2, 1]);
+
+        // a message that does not fit in a batch is sent on its own
+        let mut batch = messages(16, 1);
+        batch.extend(messages(MAX_BATCH_SIZE + 4, 1));
+        batch.extend(messages(16, 2));
+        assert_eq!(batch_lens(batch), vec![1, 1, 2]);
     }
 }
@@ -1,7 +1,5 @@
-#![cfg(any(feature = "audit", feature = "rtnetlink"))]
+#![cfg(any(feature = "audit", feature = "rtnetlink", feature = "genetlink"))]
 
-#[macro_use]
-extern crate futures;
 #[macro_use]
 extern crate lazy_static;
 #[macro_use]
@@ -22,27 +20,110 @@ pub use crate::errors::*;
 mod handle;
 pub use crate::handle::*;
 
+mod message;
+pub use crate::message::*;
+
+mod blocking;
+pub use crate::blocking::*;
+
+mod channel;
+pub use crate::channel::ChannelCapacities;
+pub(crate) use crate::channel::{channel, Sender};
+
+mod listener;
+pub(crate) use crate::listener::Listener;
+pub use crate::listener::{DropPolicy, IncomingMessages, IncomingMessagesWithMeta, MessageFilter};
+
 mod request;
-pub(crate) use crate::request::Request;
+pub(crate) use crate::request::{Command, Request, ResponseReceiver};
+
+#[cfg(all(test, feature = "rtnetlink", feature = "tokio_support"))]
+mod testing;
 
-use netlink_packet::NetlinkMessage;
-pub use netlink_sys::{Protocol, SocketAddr};
+pub use netlink_sys::{
+    AsyncSocket, MessageTypeFilter, NetworkNamespace, Protocol, RecvMeta, SockFilter, Socket,
+    SocketAddr,
+};
+
+#[cfg(feature = "tokio_support")]
+pub use netlink_sys::TokioSocket;
+
+#[cfg(feature = "smol_support")]
+pub use netlink_sys::SmolSocket;
+
+#[cfg(feature = "async_std_support")]
+pub use netlink_sys::AsyncStdSocket;
 
-use futures::sync::mpsc::{unbounded, UnboundedReceiver};
 use std::io;
 
+/// Create a connection, which socket is registered with the tokio reactor (see
+/// [`new_connection_with_socket`](fn.new_connection_with_socket.html) to use another runtime).
+#[cfg(feature = "tokio_support")]
 pub fn new_connection(
     protocol: Protocol,
-) -> io::Result<(
-    Connection,
-    ConnectionHandle,
-    UnboundedReceiver<NetlinkMessage>,
-)> {
-    let (requests_tx, requests_rx) = unbounded::<Request>();
-    let (messages_tx, messages_rx) = unbounded::<NetlinkMessage>();
-    Ok((
-        Connection::new(requests_rx, messages_tx, protocol)?,
-        ConnectionHandle::new(requests_tx),
-        messages_rx,
+) -> io::Result<(Connection<TokioSocket>, ConnectionHandle, IncomingMessages)> {
+    new_connection_with_socket(protocol)
+}
+
+/// Same as [`new_connection`](fn.new_connection.html), but the netlink socket is created in the
+/// given network namespace. The connection can then be driven from any thread, and the requests
+/// made with its handle apply to that namespace.
+#[cfg(feature = "tokio_support")]
+pub fn new_connection_in_namespace(
+    protocol: Protocol,
+    ns: NetworkNamespace,
+) -> io::Result<(Connection<TokioSocket>, ConnectionHandle, IncomingMessages)> {
+    new_connection_in_namespace_with_socket(protocol, ns)
+}
+
+/// Same as [`new_connection`](fn.new_connection.html), but with the given socket type, so that
+/// the connection can be spawned on the runtime this socket belongs to (for instance, the
+/// connection of a `SmolSocket` is spawned with `smol::spawn`).
+pub fn new_connection_with_socket<S: AsyncSocket>(
+    protocol: Protocol,
+) -> io::Result<(Connection<S>, ConnectionHandle, IncomingMessages)> {
+    Ok(new_connection_from_socket(
+        S::new(protocol)?,
+        NetlinkCodec::for_protocol(protocol),
+        ChannelCapacities::default(),
+    ))
+}
+
+/// Same as [`new_connection_in_namespace`](fn.new_connection_in_namespace.html), but with the
+/// given socket type.
+pub fn new_connection_in_namespace_with_socket<S: AsyncSocket>(
+    protocol: Protocol,
+    ns: NetworkNamespace,
+) -> io::Result<(Connection<S>, ConnectionHandle, IncomingMessages)> {
+    Ok(new_connection_from_socket(
+        S::new_in_namespace(protocol, ns)?,
+        NetlinkCodec::for_protocol(protocol),
+        ChannelCapacities::default(),
     ))
 }
+
+/// Create a connection from an existing socket, with the given codec (see
+/// [`NetlinkCodec::for_protocol`](struct.NetlinkCodec.html#method.for_protocol)) and capacities
+/// for the channels between the connection and its handles (see
+/// [`ChannelCapacities`](struct.ChannelCapacities.html)). The socket can be created in another
+/// network namespace with `AsyncSocket::new_in_namespace`.
+///
+/// This is also how connections are made for messages other than `NetlinkMessage` (see
+/// [`Message`](trait.Message.html)).
+pub fn new_connection_from_socket<S: AsyncSocket, T: Message>(
+    socket: S,
+    codec: NetlinkCodec<T>,
+    capacities: ChannelCapacities,
+) -> (Connection<S, T>, ConnectionHandle<T>, IncomingMessages<T>) {
+    let (requests_tx, requests_rx) = channel::<Command<T>>(capacities.requests);
+    let (listener, messages) = Listener::new(
+        MessageFilter::All,
+        capacities.incoming_messages.unwrap_or(usize::MAX),
+        DropPolicy::Backpressure,
+    );
+    (
+        Connection::new(requests_rx, listener, socket, codec),
+        ConnectionHandle::new(requests_tx, capacities.responses),
+        messages,
+    )
+}
@@ -0,0 +1,432 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use futures::Stream;
+use netlink_packet::NetlinkMessage;
+use netlink_sys::RecvMeta;
+
+use crate::Message;
+
+/// Selects the unsolicited messages a listener receives (see
+/// [`ConnectionHandle::listen`](struct.ConnectionHandle.html#method.listen))
+pub enum MessageFilter<T = NetlinkMessage> {
+    /// All the messages
+    All,
+    /// The messages with one of the given types (the `nlmsg_type` field of the header)
+    MessageTypes(Vec<u16>),
+    /// The messages for which the predicate returns `true`
+    Predicate(Box<dyn Fn(&T) -> bool + Send>),
+    /// The messages sent to one of the given multicast groups. This relies on `NETLINK_PKTINFO`,
+    /// which the connections enable: if the kernel does not support it, no message matches.
+    Groups(Vec<u32>),
+}
+
+impl<T: Message> MessageFilter<T> {
+    /// Filter that accepts the messages with one of the given types
+    pub fn message_types(types: &[u16]) -> Self {
+        MessageFilter::MessageTypes(types.to_vec())
+    }
+
+    /// Filter that accepts the messages for which the given predicate returns `true`
+    pub fn predicate<F>(predicate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + 'static,
+    {
+        MessageFilter::Predicate(Box::new(predicate))
+    }
+
+    /// Filter that accepts the messages sent to one of the given multicast groups
+    pub fn groups(groups: &[u32]) -> Self {
+        MessageFilter::Groups(groups.to_vec())
+    }
+
+    /// Check whether the given message, received in a datagram with the given metadata, passes
+    /// the filter
+    pub fn matches(&self, message: &T, meta: &RecvMeta) -> bool {
+        match self {
+            MessageFilter::All => true,
+            MessageFilter::MessageTypes(types) => types.contains(&message.header().message_type()),
+            MessageFilter::Predicate(predicate) => predicate(message),
+            MessageFilter::Groups(groups) => {
+                meta.group.is_some_and(|group| groups.contains(&group))
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for MessageFilter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageFilter::All => write!(f, "All"),
+            MessageFilter::MessageTypes(types) => write!(f, "MessageTypes({:?})", types),
+            MessageFilter::Predicate(_) => write!(f, "Predicate"),
+            MessageFilter::Groups(groups) => write!(f, "Groups({:?})", groups),
+        }
+    }
+}
+
+/// What happens to a message that arrives when the buffer of a listener is full
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DropPolicy {
+    /// Keep the buffered messages, and discard the new one
+    DropNewest,
+    /// Discard the oldest buffered message to make room for the new one
+    DropOldest,
+    /// Do not discard any message: the connection stops reading from the socket until the stream
+    /// is read (see [`ChannelCapacities`](struct.ChannelCapacities.html))
+    Backpressure,
+}
+
+// State shared by a listener, owned by the connection, and its stream of messages
+#[derive(Debug)]
+struct Shared<T> {
+    messages: VecDeque<(T, RecvMeta)>,
+    capacity: usize,
+    policy: DropPolicy,
+    // Number of messages discarded because the buffer was full
+    dropped: u64,
+    // Whether the stream has been dropped
+    closed: bool,
+    // Whether the connection is gone
+    finished: bool,
+    // Task reading the stream, woken up when a message is buffered
+    waker: Option<Waker>,
+    // Connection waiting for room in the buffer, woken up when a message is read
+    connection_waker: Option<Waker>,
+}
+
+/// Stream of the unsolicited messages received by a connection, that match the filter of a
+/// listener (see [`ConnectionHandle::listen`](struct.ConnectionHandle.html#method.listen)). The
+/// stream ends when the connection is dropped.
+#[derive(Debug)]
+pub struct IncomingMessages<T = NetlinkMessage> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> IncomingMessages<T> {
+    /// Number of messages that were discarded because this stream was not read fast enough
+    pub fn dropped(&self) -> u64 {
+        self.shared.lock().unwrap().dropped
+    }
+
+    /// Turn this stream into a stream of the messages along with the metadata of the datagrams
+    /// they were received in: the multicast group they were sent to, and the network namespace
+    /// they come from (see [`RecvMeta`](struct.RecvMeta.html))
+    pub fn with_meta(self) -> IncomingMessagesWithMeta<T> {
+        IncomingMessagesWithMeta { inner: self }
+    }
+
+    fn poll_next_message(&self, cx: &mut Context<'_>) -> Poll<Option<(T, RecvMeta)>> {
+        let mut shared = self.shared.lock().unwrap();
+        if let Some(message) = shared.messages.pop_front() {
+            if let Some(waker) = shared.connection_waker.take() {
+                waker.wake();
+            }
+            Poll::Ready(Some(message))
+        } else if shared.finished {
+            Poll::Ready(None)
+        } else {
+            shared.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Stream for IncomingMessages<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_next_message(cx)
+            .map(|item| item.map(|(message, _)| message))
+    }
+}
+
+/// Same as [`IncomingMessages`](struct.IncomingMessages.html), but the messages come with the
+/// metadata of the datagrams they were received in (see
+/// [`IncomingMessages::with_meta`](struct.IncomingMessages.html#method.with_meta))
+#[derive(Debug)]
+pub struct IncomingMessagesWithMeta<T = NetlinkMessage> {
+    inner: IncomingMessages<T>,
+}
+
+impl<T> IncomingMessagesWithMeta<T> {
+    /// Number of messages that were discarded because this stream was not read fast enough
+    pub fn dropped(&self) -> u64 {
+        self.inner.dropped()
+    }
+}
+
+impl<T> Stream for IncomingMessagesWithMeta<T> {
+    type Item = (T, RecvMeta);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_message(cx)
+    }
+}
+
+impl<T> Drop for IncomingMessages<T> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.closed = true;
+        shared.messages.clear();
+        if let Some(waker) = shared.connection_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// The connection side of an [`IncomingMessages`](struct.IncomingMessages.html) stream
+#[derive(Debug)]
+pub(crate) struct Listener<T> {
+    filter: MessageFilter<T>,
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T: Message> Listener<T> {
+    pub(crate) fn new(
+        filter: MessageFilter<T>,
+        capacity: usize,
+        policy: DropPolicy,
+    ) -> (Self, IncomingMessages<T>) {
+        let shared = Arc::new(Mutex::new(Shared {
+            messages: VecDeque::new(),
+            // a listener that cannot buffer anything would never get a message
+            capacity: capacity.max(1),
+            policy,
+            dropped: 0,
+            closed: false,
+            finished: false,
+            waker: None,
+            connection_waker: None,
+        }));
+        let listener = Listener {
+            filter,
+            shared: shared.clone(),
+        };
+        (listener, IncomingMessages { shared })
+    }
+
+    // Check whether the listener can take the message. Only the listeners that apply
+    // backpressure may not: the current task is then woken up when the stream is read.
+    pub(crate) fn poll_ready(
+        &self,
+        cx: &mut Context<'_>,
+        message: &T,
+        meta: &RecvMeta,
+    ) -> Poll<()> {
+        let mut shared = self.shared.lock().unwrap();
+        if shared.closed
+            || shared.policy != DropPolicy::Backpressure
+            || shared.messages.len() < shared.capacity
+            || !self.filter.matches(message, meta)
+        {
+            return Poll::Ready(());
+        }
+        shared.connection_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    // Buffer the message if it matches the filter. Messages that tell that other messages were
+    // lost are always buffered. Return `false` if the stream has been dropped.
+    pub(crate) fn forward(&self, message: &T, meta: RecvMeta) -> bool {
+        let mut shared = self.shared.lock().unwrap();
+        if shared.closed {
+            return false;
+        }
+        if !message.is_overrun() && !self.filter.matches(message, &meta) {
+            return true;
+        }
+
+        if shared.messages.len() >= shared.capacity {
+            match shared.policy {
+                DropPolicy::DropNewest => {
+                    trace!("listener buffer is full: dropping the new message");
+                    shared.dropped += 1;
+                    return true;
+                }
+                DropPolicy::DropOldest => {
+                    trace!("listener buffer is full: dropping the oldest message");
+                    shared.dropped += 1;
+                    let _ = shared.messages.pop_front();
+                }
+                // `poll_ready` made sure there is room for the message, unless it tells that
+                // messages were lost, which is never dropped
+                DropPolicy::Backpressure => {}
+            }
+        }
+        shared.messages.push_back((message.clone(), meta));
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+        true
+    }
+}
+
+impl<T> Drop for Listener<T> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.finished = true;
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use futures::executor::block_on_stream;
+    use futures::task::{self, ArcWake};
+    use futures::StreamExt;
+    use netlink_packet::NetlinkPayload;
+
+    fn message(sequence_number: u32) -> NetlinkMessage {
+        let mut message = NetlinkMessage::from(NetlinkPayload::Noop);
+        message.header_mut().set_sequence_number(sequence_number);
+        message
+    }
+
+    fn meta(group: u32) -> RecvMeta {
+        RecvMeta {
+            group: Some(group),
+            nsid: None,
+        }
+    }
+
+    // Sequence numbers of the messages of the stream, once the listener is gone
+    fn sequence_numbers(messages: IncomingMessages) -> Vec<u32> {
+        block_on_stream(messages)
+            .map(|message| message.header().sequence_number())
+            .collect()
+    }
+
+    // Waker that records whether it was woken up
+    #[derive(Default)]
+    struct Flag(AtomicBool);
+
+    impl ArcWake for Flag {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn drop_newest() {
+        let (listener, messages) = Listener::new(MessageFilter::All, 2, DropPolicy::DropNewest);
+        for sequence_number in 1..=4 {
+            assert!(listener.forward(&message(sequence_number), meta(0)));
+        }
+        assert_eq!(messages.dropped(), 2);
+        drop(listener);
+        assert_eq!(sequence_numbers(messages), vec![1, 2]);
+    }
+
+    #[test]
+    fn drop_oldest() {
+        let (listener, messages) = Listener::new(MessageFilter::All, 2, DropPolicy::DropOldest);
+        for sequence_number in 1..=4 {
+            assert!(listener.forward(&message(sequence_number), meta(0)));
+        }
+        assert_eq!(messages.dropped(), 2);
+        drop(listener);
+        assert_eq!(sequence_numbers(messages), vec![3, 4]);
+    }
+
+    #[test]
+    fn backpressure() {
+        let (listener, mut messages) =
+            Listener::new(MessageFilter::All, 2, DropPolicy::Backpressure);
+        let flag = Arc::new(Flag::default());
+        let waker = task::waker(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        for sequence_number in 1..=2 {
+            assert!(listener
+                .poll_ready(&mut cx, &message(sequence_number), &meta(0))
+                .is_ready());
+            assert!(listener.forward(&message(sequence_number), meta(0)));
+        }
+        // the buffer is full: the connection must wait until the stream is read
+        assert!(listener
+            .poll_ready(&mut cx, &message(3), &meta(0))
+            .is_pending());
+        assert!(!flag.0.load(Ordering::SeqCst));
+
+        let first = messages.poll_next_unpin(&mut cx);
+        assert!(matches!(first, Poll::Ready(Some(ref m)) if m.header().sequence_number() == 1));
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert!(listener
+            .poll_ready(&mut cx, &message(3), &meta(0))
+            .is_ready());
+        assert!(listener.forward(&message(3), meta(0)));
+
+        assert_eq!(messages.dropped(), 0);
+        drop(listener);
+        assert_eq!(sequence_numbers(messages), vec![2, 3]);
+    }
+
+    #[test]
+    fn backpressure_filtered_out() {
+        let filter = MessageFilter::message_types(&[42]);
+        let (listener, _messages) = Listener::new(filter, 1, DropPolicy::Backpressure);
+        let mut message = message(1);
+        message.header_mut().set_message_type(42);
+        assert!(listener.forward(&message, meta(0)));
+
+        // a full listener does not hold the messages it does not want
+        let mut cx = Context::from_waker(task::noop_waker_ref());
+        assert!(listener
+            .poll_ready(&mut cx, &self::message(2), &meta(0))
+            .is_ready());
+        assert!(listener
+            .poll_ready(&mut cx, &message, &meta(0))
+            .is_pending());
+    }
+
+    #[test]
+    fn filter_predicate() {
+        let filter = MessageFilter::predicate(|message: &NetlinkMessage| {
+            message.header().sequence_number() > 3
+        });
+        let (listener, messages) = Listener::new(filter, 8, DropPolicy::DropNewest);
+        for sequence_number in 1..=5 {
+            assert!(listener.forward(&message(sequence_number), meta(0)));
+        }
+        drop(listener);
+        assert_eq!(sequence_numbers(messages), vec![4, 5]);
+    }
+
+    #[test]
+    fn forward_meta() {
+        let (listener, messages) = Listener::new(MessageFilter::All, 8, DropPolicy::DropNewest);
+        assert!(listener.forward(&message(1), meta(0)));
+        assert!(listener.forward(&message(2), meta(3)));
+        drop(listener);
+
+        let received: Vec<_> = block_on_stream(messages.with_meta())
+            .map(|(message, meta)| (message.header().sequence_number(), meta.group))
+            .collect();
+        assert_eq!(received, vec![(1, Some(0)), (2, Some(3))]);
+    }
+
+    #[test]
+    fn filter_groups() {
+        let filter = MessageFilter::groups(&[1, 5]);
+        let (listener, messages) = Listener::new(filter, 8, DropPolicy::DropNewest);
+        assert!(listener.forward(&message(1), meta(1)));
+        assert!(listener.forward(&message(2), meta(0)));
+        assert!(listener.forward(&message(3), meta(5)));
+        assert!(listener.forward(&message(4), RecvMeta::default()));
+        drop(listener);
+
+        let received: Vec<_> = block_on_stream(messages)
+            .map(|message| message.header().sequence_number())
+            .collect();
+        assert_eq!(received, vec![1, 3]);
+    }
+}
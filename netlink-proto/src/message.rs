@@ -0,0 +1,94 @@
+use std::fmt;
+
+use netlink_packet::constants::{NLMSG_DONE, NLMSG_NOOP, NLMSG_OVERRUN};
+use netlink_packet::{
+    DecodeError, Emitable, NetlinkBuffer, NetlinkHeader, NetlinkMessage, NetlinkPayload, Parseable,
+};
+
+/// A message that can be sent and received by a [`Connection`](struct.Connection.html).
+///
+/// The connection does not look at the payload of the messages: it only reads and sets their
+/// header, to match the responses with the requests, and it needs to recognize the messages that
+/// are common to all the netlink protocols (end of dump, errors, acknowledgements, etc.). Besides
+/// `NetlinkMessage`, this lets protocols that are not supported by `netlink-packet` bring their
+/// own message type.
+pub trait Message: Emitable + Clone + fmt::Debug + Send + 'static {
+    /// Parse a message from a buffer that holds exactly one message
+    fn parse_buffer<B: AsRef<[u8]>>(buffer: &NetlinkBuffer<&B>) -> Result<Self, DecodeError>;
+
+    fn header(&self) -> &NetlinkHeader;
+
+    fn header_mut(&mut self) -> &mut NetlinkHeader;
+
+    /// Make the header consistent with the payload (length and message type), before the message
+    /// is emitted
+    fn finalize(&mut self);
+
+    /// Check if the message is a `NLMSG_ERROR` message with a negative error code
+    fn is_error(&self) -> bool;
+
+    /// Check if the message is a `NLMSG_ERROR` message with a non-negative error code
+    fn is_ack(&self) -> bool;
+
+    /// Build the `NLMSG_OVERRUN` message that tells the listeners that messages were lost
+    fn overrun() -> Self;
+
+    fn is_done(&self) -> bool {
+        self.header().message_type() == NLMSG_DONE
+    }
+
+    fn is_noop(&self) -> bool {
+        self.header().message_type() == NLMSG_NOOP
+    }
+
+    fn is_overrun(&self) -> bool {
+        self.header().message_type() == NLMSG_OVERRUN
+    }
+
+    /// Check if the kernel set the `NLM_F_DUMP_INTR` flag
+    fn is_dump_interrupted(&self) -> bool {
+        self.header().flags().has_dump_intr()
+    }
+}
+
+impl Message for NetlinkMessage {
+    fn parse_buffer<B: AsRef<[u8]>>(buffer: &NetlinkBuffer<&B>) -> Result<Self, DecodeError> {
+        <NetlinkBuffer<_> as Parseable<NetlinkMessage>>::parse(buffer)
+    }
+
+    fn header(&self) -> &NetlinkHeader {
+        NetlinkMessage::header(self)
+    }
+
+    fn header_mut(&mut self) -> &mut NetlinkHeader {
+        NetlinkMessage::header_mut(self)
+    }
+
+    fn finalize(&mut self) {
+        NetlinkMessage::finalize(self)
+    }
+
+    fn is_error(&self) -> bool {
+        NetlinkMessage::is_error(self)
+    }
+
+    fn is_ack(&self) -> bool {
+        NetlinkMessage::is_ack(self)
+    }
+
+    fn overrun() -> Self {
+        NetlinkMessage::from(NetlinkPayload::Overrun(vec![]))
+    }
+
+    fn is_done(&self) -> bool {
+        NetlinkMessage::is_done(self)
+    }
+
+    fn is_noop(&self) -> bool {
+        NetlinkMessage::is_noop(self)
+    }
+
+    fn is_overrun(&self) -> bool {
+        NetlinkMessage::is_overrun(self)
+    }
+}
@@ -1,26 +1,37 @@
-use futures::sync::mpsc::UnboundedSender;
-use netlink_packet::NetlinkMessage;
+use std::io;
+use std::time::Duration;
+
+use futures::channel::oneshot;
 use netlink_sys::SocketAddr;
 
+use crate::channel::{Receiver, Sender};
+use crate::errors::Error;
+use crate::listener::Listener;
+
+/// Channel on which the connection forwards the response to a request
+pub(crate) type ResponseSender<T> = Sender<Result<T, Error<T>>>;
+
+/// Channel on which a handle receives the response to a request
+pub(crate) type ResponseReceiver<T> = Receiver<Result<T, Error<T>>>;
+
 #[derive(Debug)]
-pub(crate) struct Request {
-    pub chan: UnboundedSender<NetlinkMessage>,
-    pub message: NetlinkMessage,
+pub(crate) struct Request<T> {
+    // The messages to send in a single datagram, along with the channels on which their
+    // responses are forwarded. Most requests carry a single message, batches carry several.
+    pub messages: Vec<(T, ResponseSender<T>)>,
     pub destination: SocketAddr,
+    // If `None`, the default timeout of the connection applies
+    pub timeout: Option<Duration>,
 }
 
-impl From<(UnboundedSender<NetlinkMessage>, NetlinkMessage, SocketAddr)> for Request {
-    fn from(parts: (UnboundedSender<NetlinkMessage>, NetlinkMessage, SocketAddr)) -> Self {
-        Request {
-            chan: parts.0,
-            message: parts.1,
-            destination: parts.2,
-        }
-    }
-}
-
-impl Into<(UnboundedSender<NetlinkMessage>, NetlinkMessage, SocketAddr)> for Request {
-    fn into(self) -> (UnboundedSender<NetlinkMessage>, NetlinkMessage, SocketAddr) {
-        (self.chan, self.message, self.destination)
-    }
+/// What a `ConnectionHandle` asks the `Connection` to do
+#[derive(Debug)]
+pub(crate) enum Command<T> {
+    Request(Request<T>),
+    // Join a multicast group, and report the outcome on the channel
+    AddMembership(u32, oneshot::Sender<io::Result<()>>),
+    // Leave a multicast group, and report the outcome on the channel
+    DropMembership(u32, oneshot::Sender<io::Result<()>>),
+    // Forward a copy of the unsolicited messages that match the filter of the listener
+    Listen(Listener<T>),
 }
@@ -0,0 +1,76 @@
+//! A fake kernel, to test how connections deal with the responses to their requests
+use std::thread;
+use std::time::Duration;
+
+use bytes::BytesMut;
+use netlink_packet::{NetlinkFlags, NetlinkMessage, NetlinkPayload};
+use netlink_sys::{Protocol, Socket, SocketAddr, TokioSocket};
+
+use crate::codecs::{Decoder, Encoder, NetlinkCodec};
+use crate::{
+    new_connection_from_socket, ChannelCapacities, Connection, ConnectionHandle, IncomingMessages,
+};
+
+/// Create a connection, which requests are answered by `handler` instead of the kernel. The
+/// handler is called with each message received by the fake kernel, and returns the replies,
+/// which are sent in a datagram each. The requests must be sent to the returned address.
+///
+/// This must be called from within a tokio runtime.
+pub(crate) fn fake_connection<F>(
+    capacities: ChannelCapacities,
+    mut handler: F,
+) -> (
+    Connection<TokioSocket>,
+    ConnectionHandle,
+    IncomingMessages,
+    SocketAddr,
+)
+where
+    F: FnMut(&NetlinkMessage) -> Vec<NetlinkMessage> + Send + 'static,
+{
+    let mut kernel = Socket::new(Protocol::UserSock).unwrap();
+    let kernel_addr = kernel.bind_auto().unwrap();
+    // the thread stops once the test is over, and no more requests are received
+    kernel
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+    thread::spawn(move || {
+        let mut codec = NetlinkCodec::<NetlinkMessage>::for_protocol(Protocol::Route);
+        let mut buf = vec![0; 64 * 1024];
+        while let Ok((n, source)) = kernel.recv_from(&mut buf[..], 0) {
+            let mut reader = BytesMut::from(&buf[..n]);
+            while let Some(request) = codec.decode(&mut reader).unwrap() {
+                for mut reply in handler(&request) {
+                    reply.finalize();
+                    let mut writer = BytesMut::new();
+                    codec.encode(reply, &mut writer).unwrap();
+                    // the connection may be gone already
+                    let _ = kernel.send_to(&writer, &source, 0);
+                }
+            }
+        }
+    });
+
+    let mut socket = TokioSocket::new(Protocol::UserSock).unwrap();
+    socket.bind_auto().unwrap();
+    let (connection, handle, messages) = new_connection_from_socket(
+        socket,
+        NetlinkCodec::for_protocol(Protocol::Route),
+        capacities,
+    );
+    (connection, handle, messages, kernel_addr)
+}
+
+/// Build a reply to the given request
+pub(crate) fn reply(
+    request: &NetlinkMessage,
+    flags: u16,
+    payload: NetlinkPayload,
+) -> NetlinkMessage {
+    let mut reply = NetlinkMessage::from(payload);
+    reply
+        .header_mut()
+        .set_sequence_number(request.header().sequence_number())
+        .set_flags(NetlinkFlags::from(flags));
+    reply
+}
@@ -0,0 +1,62 @@
+use std::io;
+use std::task::{Context, Poll};
+
+use super::{NetworkNamespace, Protocol, RecvMeta, Socket, SocketAddr};
+
+/// A netlink socket registered with the reactor of an asynchronous runtime.
+///
+/// This is implemented by [`TokioSocket`](struct.TokioSocket.html) (`tokio_support` feature),
+/// [`SmolSocket`](struct.SmolSocket.html) (`smol_support` feature) and
+/// [`AsyncStdSocket`](struct.AsyncStdSocket.html) (`async_std_support` feature), so that the
+/// code built on top of it does not have to pick a runtime for its users.
+///
+/// The `poll_*` methods behave like their [`Socket`](struct.Socket.html) counterparts, except that
+/// they return `Poll::Pending` instead of `io::ErrorKind::WouldBlock`, after arranging for the
+/// current task to be woken up once the socket is ready.
+pub trait AsyncSocket: Sized + Unpin {
+    /// Register a socket with the reactor. The socket is switched to non-blocking mode.
+    fn from_socket(socket: Socket) -> io::Result<Self>;
+
+    /// Create a new socket, registered with the reactor.
+    fn new(protocol: Protocol) -> io::Result<Self> {
+        Self::from_socket(Socket::new(protocol)?)
+    }
+
+    /// Create a new socket in the given network namespace (see
+    /// [`Socket::new_in_namespace`](struct.Socket.html#method.new_in_namespace)), registered with
+    /// the reactor.
+    fn new_in_namespace(protocol: Protocol, ns: NetworkNamespace) -> io::Result<Self> {
+        Self::from_socket(Socket::new_in_namespace(protocol, ns)?)
+    }
+
+    /// Return a reference to the underlying socket
+    fn socket_ref(&self) -> &Socket;
+
+    /// Return a mutable reference to the underlying socket, to set its options for instance
+    fn socket_mut(&mut self) -> &mut Socket;
+
+    fn poll_send(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>>;
+
+    fn poll_send_to(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        target: &SocketAddr,
+    ) -> Poll<io::Result<usize>>;
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>>;
+
+    fn poll_recv_from(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>>;
+
+    fn poll_recv_msg(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr, RecvMeta)>>;
+
+    fn poll_peek_len(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>>;
+}
@@ -1047,11 +1047,24 @@ pub const AUDIT_NLGRP_NONE: u32 = 0;
 /// Multicast group to listen for audit events
 pub const AUDIT_NLGRP_READLOG: u32 = 1;
 
+// Extended acknowledgement attributes, appended to the NLMSG_ERROR messages when
+// NETLINK_EXT_ACK is set on the socket
 pub const NLMSGERR_ATTR_UNUSED: int = 0;
+/// Error message string
 pub const NLMSGERR_ATTR_MSG: int = 1;
+/// Offset of the invalid attribute in the original message, counting from the beginning of the
+/// header
 pub const NLMSGERR_ATTR_OFFS: int = 2;
+/// Arbitrary subsystem specific cookie to be used, in the success case, to identify a created
+/// object or operation or similar
 pub const NLMSGERR_ATTR_COOKIE: int = 3;
-pub const NLMSGERR_ATTR_MAX: int = 3;
+/// Policy for a rejected attribute
+pub const NLMSGERR_ATTR_POLICY: int = 4;
+/// Type of a missing required attribute
+pub const NLMSGERR_ATTR_MISS_TYPE: int = 5;
+/// Offset of the nest where the attribute was missing
+pub const NLMSGERR_ATTR_MISS_NEST: int = 6;
+pub const NLMSGERR_ATTR_MAX: int = 6;
 
 pub const NL_MMAP_STATUS_UNUSED: int = 0;
 pub const NL_MMAP_STATUS_RESERVED: int = 1;
@@ -1592,3 +1605,764 @@ pub const RTEXT_FILTER_BRVLAN_COMPRESSED: int = 4;
 pub const RTEXT_FILTER_SKIP_STATS: int = 8;
 pub const ARPOP_REQUEST: int = 1;
 pub const ARPOP_REPLY: int = 2;
+
+// ==========================================
+// generic netlink
+// ==========================================
+
+/// Length of the generic netlink header (`struct genlmsghdr`)
+pub const GENL_HDRLEN: usize = 4;
+/// Maximum length of a generic netlink family name, including the nul byte
+pub const GENL_NAMSIZ: usize = 16;
+
+/// Message type of the generic netlink controller (`nlctrl`). Contrary to the other generic
+/// netlink families, its identifier is fixed.
+pub const GENL_ID_CTRL: u16 = 16;
+pub const GENL_ID_VFS_DQUOT: u16 = 17;
+pub const GENL_ID_PMCRAID: u16 = 18;
+pub const GENL_START_ALLOC: u16 = 19;
+
+pub const GENL_ADMIN_PERM: u32 = 1;
+pub const GENL_CMD_CAP_DO: u32 = 2;
+pub const GENL_CMD_CAP_DUMP: u32 = 4;
+pub const GENL_CMD_CAP_HASPOL: u32 = 8;
+pub const GENL_UNS_ADMIN_PERM: u32 = 16;
+
+pub const CTRL_CMD_UNSPEC: u8 = 0;
+pub const CTRL_CMD_NEWFAMILY: u8 = 1;
+pub const CTRL_CMD_DELFAMILY: u8 = 2;
+pub const CTRL_CMD_GETFAMILY: u8 = 3;
+pub const CTRL_CMD_NEWOPS: u8 = 4;
+pub const CTRL_CMD_DELOPS: u8 = 5;
+pub const CTRL_CMD_GETOPS: u8 = 6;
+pub const CTRL_CMD_NEWMCAST_GRP: u8 = 7;
+pub const CTRL_CMD_DELMCAST_GRP: u8 = 8;
+pub const CTRL_CMD_GETMCAST_GRP: u8 = 9;
+pub const CTRL_CMD_GETPOLICY: u8 = 10;
+
+pub const CTRL_ATTR_UNSPEC: u16 = 0;
+pub const CTRL_ATTR_FAMILY_ID: u16 = 1;
+pub const CTRL_ATTR_FAMILY_NAME: u16 = 2;
+pub const CTRL_ATTR_VERSION: u16 = 3;
+pub const CTRL_ATTR_HDRSIZE: u16 = 4;
+pub const CTRL_ATTR_MAXATTR: u16 = 5;
+pub const CTRL_ATTR_OPS: u16 = 6;
+pub const CTRL_ATTR_MCAST_GROUPS: u16 = 7;
+pub const CTRL_ATTR_POLICY: u16 = 8;
+pub const CTRL_ATTR_OP_POLICY: u16 = 9;
+pub const CTRL_ATTR_OP: u16 = 10;
+
+pub const CTRL_ATTR_OP_UNSPEC: u16 = 0;
+pub const CTRL_ATTR_OP_ID: u16 = 1;
+pub const CTRL_ATTR_OP_FLAGS: u16 = 2;
+
+pub const CTRL_ATTR_MCAST_GRP_UNSPEC: u16 = 0;
+pub const CTRL_ATTR_MCAST_GRP_NAME: u16 = 1;
+pub const CTRL_ATTR_MCAST_GRP_ID: u16 = 2;
+
+// ==========================================
+// ethtool generic netlink family
+// ==========================================
+
+pub const ETHTOOL_GENL_NAME: &str = "ethtool";
+pub const ETHTOOL_GENL_VERSION: u8 = 1;
+pub const ETHTOOL_MCGRP_MONITOR_NAME: &str = "monitor";
+
+// messages from user space to the kernel
+pub const ETHTOOL_MSG_USER_NONE: u8 = 0;
+pub const ETHTOOL_MSG_STRSET_GET: u8 = 1;
+pub const ETHTOOL_MSG_LINKINFO_GET: u8 = 2;
+pub const ETHTOOL_MSG_LINKINFO_SET: u8 = 3;
+pub const ETHTOOL_MSG_LINKMODES_GET: u8 = 4;
+pub const ETHTOOL_MSG_LINKMODES_SET: u8 = 5;
+pub const ETHTOOL_MSG_LINKSTATE_GET: u8 = 6;
+pub const ETHTOOL_MSG_DEBUG_GET: u8 = 7;
+pub const ETHTOOL_MSG_DEBUG_SET: u8 = 8;
+pub const ETHTOOL_MSG_WOL_GET: u8 = 9;
+pub const ETHTOOL_MSG_WOL_SET: u8 = 10;
+pub const ETHTOOL_MSG_FEATURES_GET: u8 = 11;
+pub const ETHTOOL_MSG_FEATURES_SET: u8 = 12;
+pub const ETHTOOL_MSG_PRIVFLAGS_GET: u8 = 13;
+pub const ETHTOOL_MSG_PRIVFLAGS_SET: u8 = 14;
+pub const ETHTOOL_MSG_RINGS_GET: u8 = 15;
+pub const ETHTOOL_MSG_RINGS_SET: u8 = 16;
+pub const ETHTOOL_MSG_CHANNELS_GET: u8 = 17;
+pub const ETHTOOL_MSG_CHANNELS_SET: u8 = 18;
+pub const ETHTOOL_MSG_COALESCE_GET: u8 = 19;
+pub const ETHTOOL_MSG_COALESCE_SET: u8 = 20;
+pub const ETHTOOL_MSG_PAUSE_GET: u8 = 21;
+pub const ETHTOOL_MSG_PAUSE_SET: u8 = 22;
+
+// messages from the kernel to user space. Note that the numbering is not the same than for the
+// messages sent by user space.
+pub const ETHTOOL_MSG_KERNEL_NONE: u8 = 0;
+pub const ETHTOOL_MSG_STRSET_GET_REPLY: u8 = 1;
+pub const ETHTOOL_MSG_LINKINFO_GET_REPLY: u8 = 2;
+pub const ETHTOOL_MSG_LINKINFO_NTF: u8 = 3;
+pub const ETHTOOL_MSG_LINKMODES_GET_REPLY: u8 = 4;
+pub const ETHTOOL_MSG_LINKMODES_NTF: u8 = 5;
+pub const ETHTOOL_MSG_LINKSTATE_GET_REPLY: u8 = 6;
+pub const ETHTOOL_MSG_DEBUG_GET_REPLY: u8 = 7;
+pub const ETHTOOL_MSG_DEBUG_NTF: u8 = 8;
+pub const ETHTOOL_MSG_WOL_GET_REPLY: u8 = 9;
+pub const ETHTOOL_MSG_WOL_NTF: u8 = 10;
+pub const ETHTOOL_MSG_FEATURES_GET_REPLY: u8 = 11;
+pub const ETHTOOL_MSG_FEATURES_SET_REPLY: u8 = 12;
+pub const ETHTOOL_MSG_FEATURES_NTF: u8 = 13;
+pub const ETHTOOL_MSG_PRIVFLAGS_GET_REPLY: u8 = 14;
+pub const ETHTOOL_MSG_PRIVFLAGS_NTF: u8 = 15;
+pub const ETHTOOL_MSG_RINGS_GET_REPLY: u8 = 16;
+pub const ETHTOOL_MSG_RINGS_NTF: u8 = 17;
+pub const ETHTOOL_MSG_CHANNELS_GET_REPLY: u8 = 18;
+pub const ETHTOOL_MSG_CHANNELS_NTF: u8 = 19;
+pub const ETHTOOL_MSG_COALESCE_GET_REPLY: u8 = 20;
+pub const ETHTOOL_MSG_COALESCE_NTF: u8 = 21;
+pub const ETHTOOL_MSG_PAUSE_GET_REPLY: u8 = 22;
+pub const ETHTOOL_MSG_PAUSE_NTF: u8 = 23;
+
+/// Use compact bitsets in the replies
+pub const ETHTOOL_FLAG_COMPACT_BITSETS: u32 = 1;
+/// Provide optional reply for SET or ACT requests
+pub const ETHTOOL_FLAG_OMIT_REPLY: u32 = 2;
+/// Request statistics, if supported by the driver
+pub const ETHTOOL_FLAG_STATS: u32 = 4;
+
+pub const ETHTOOL_A_HEADER_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_HEADER_DEV_INDEX: u16 = 1;
+pub const ETHTOOL_A_HEADER_DEV_NAME: u16 = 2;
+pub const ETHTOOL_A_HEADER_FLAGS: u16 = 3;
+
+pub const ETHTOOL_A_BITSET_BIT_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_BITSET_BIT_INDEX: u16 = 1;
+pub const ETHTOOL_A_BITSET_BIT_NAME: u16 = 2;
+pub const ETHTOOL_A_BITSET_BIT_VALUE: u16 = 3;
+
+pub const ETHTOOL_A_BITSET_BITS_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_BITSET_BITS_BIT: u16 = 1;
+
+pub const ETHTOOL_A_BITSET_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_BITSET_NOMASK: u16 = 1;
+pub const ETHTOOL_A_BITSET_SIZE: u16 = 2;
+pub const ETHTOOL_A_BITSET_BITS: u16 = 3;
+pub const ETHTOOL_A_BITSET_VALUE: u16 = 4;
+pub const ETHTOOL_A_BITSET_MASK: u16 = 5;
+
+pub const ETHTOOL_A_LINKINFO_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_LINKINFO_HEADER: u16 = 1;
+pub const ETHTOOL_A_LINKINFO_PORT: u16 = 2;
+pub const ETHTOOL_A_LINKINFO_PHYADDR: u16 = 3;
+pub const ETHTOOL_A_LINKINFO_TP_MDIX: u16 = 4;
+pub const ETHTOOL_A_LINKINFO_TP_MDIX_CTRL: u16 = 5;
+pub const ETHTOOL_A_LINKINFO_TRANSCEIVER: u16 = 6;
+
+pub const ETHTOOL_A_LINKMODES_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_LINKMODES_HEADER: u16 = 1;
+pub const ETHTOOL_A_LINKMODES_AUTONEG: u16 = 2;
+pub const ETHTOOL_A_LINKMODES_OURS: u16 = 3;
+pub const ETHTOOL_A_LINKMODES_PEER: u16 = 4;
+pub const ETHTOOL_A_LINKMODES_SPEED: u16 = 5;
+pub const ETHTOOL_A_LINKMODES_DUPLEX: u16 = 6;
+pub const ETHTOOL_A_LINKMODES_MASTER_SLAVE_CFG: u16 = 7;
+pub const ETHTOOL_A_LINKMODES_MASTER_SLAVE_STATE: u16 = 8;
+pub const ETHTOOL_A_LINKMODES_LANES: u16 = 9;
+
+pub const ETHTOOL_A_LINKSTATE_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_LINKSTATE_HEADER: u16 = 1;
+pub const ETHTOOL_A_LINKSTATE_LINK: u16 = 2;
+pub const ETHTOOL_A_LINKSTATE_SQI: u16 = 3;
+pub const ETHTOOL_A_LINKSTATE_SQI_MAX: u16 = 4;
+pub const ETHTOOL_A_LINKSTATE_EXT_STATE: u16 = 5;
+pub const ETHTOOL_A_LINKSTATE_EXT_SUBSTATE: u16 = 6;
+
+pub const ETHTOOL_A_FEATURES_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_FEATURES_HEADER: u16 = 1;
+pub const ETHTOOL_A_FEATURES_HW: u16 = 2;
+pub const ETHTOOL_A_FEATURES_WANTED: u16 = 3;
+pub const ETHTOOL_A_FEATURES_ACTIVE: u16 = 4;
+pub const ETHTOOL_A_FEATURES_NOCHANGE: u16 = 5;
+
+pub const ETHTOOL_A_RINGS_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_RINGS_HEADER: u16 = 1;
+pub const ETHTOOL_A_RINGS_RX_MAX: u16 = 2;
+pub const ETHTOOL_A_RINGS_RX_MINI_MAX: u16 = 3;
+pub const ETHTOOL_A_RINGS_RX_JUMBO_MAX: u16 = 4;
+pub const ETHTOOL_A_RINGS_TX_MAX: u16 = 5;
+pub const ETHTOOL_A_RINGS_RX: u16 = 6;
+pub const ETHTOOL_A_RINGS_RX_MINI: u16 = 7;
+pub const ETHTOOL_A_RINGS_RX_JUMBO: u16 = 8;
+pub const ETHTOOL_A_RINGS_TX: u16 = 9;
+
+pub const ETHTOOL_A_CHANNELS_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_CHANNELS_HEADER: u16 = 1;
+pub const ETHTOOL_A_CHANNELS_RX_MAX: u16 = 2;
+pub const ETHTOOL_A_CHANNELS_TX_MAX: u16 = 3;
+pub const ETHTOOL_A_CHANNELS_OTHER_MAX: u16 = 4;
+pub const ETHTOOL_A_CHANNELS_COMBINED_MAX: u16 = 5;
+pub const ETHTOOL_A_CHANNELS_RX_COUNT: u16 = 6;
+pub const ETHTOOL_A_CHANNELS_TX_COUNT: u16 = 7;
+pub const ETHTOOL_A_CHANNELS_OTHER_COUNT: u16 = 8;
+pub const ETHTOOL_A_CHANNELS_COMBINED_COUNT: u16 = 9;
+
+pub const ETHTOOL_A_COALESCE_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_COALESCE_HEADER: u16 = 1;
+pub const ETHTOOL_A_COALESCE_RX_USECS: u16 = 2;
+pub const ETHTOOL_A_COALESCE_RX_MAX_FRAMES: u16 = 3;
+pub const ETHTOOL_A_COALESCE_RX_USECS_IRQ: u16 = 4;
+pub const ETHTOOL_A_COALESCE_RX_MAX_FRAMES_IRQ: u16 = 5;
+pub const ETHTOOL_A_COALESCE_TX_USECS: u16 = 6;
+pub const ETHTOOL_A_COALESCE_TX_MAX_FRAMES: u16 = 7;
+pub const ETHTOOL_A_COALESCE_TX_USECS_IRQ: u16 = 8;
+pub const ETHTOOL_A_COALESCE_TX_MAX_FRAMES_IRQ: u16 = 9;
+pub const ETHTOOL_A_COALESCE_STATS_BLOCK_USECS: u16 = 10;
+pub const ETHTOOL_A_COALESCE_USE_ADAPTIVE_RX: u16 = 11;
+pub const ETHTOOL_A_COALESCE_USE_ADAPTIVE_TX: u16 = 12;
+pub const ETHTOOL_A_COALESCE_PKT_RATE_LOW: u16 = 13;
+pub const ETHTOOL_A_COALESCE_RX_USECS_LOW: u16 = 14;
+pub const ETHTOOL_A_COALESCE_RX_MAX_FRAMES_LOW: u16 = 15;
+pub const ETHTOOL_A_COALESCE_TX_USECS_LOW: u16 = 16;
+pub const ETHTOOL_A_COALESCE_TX_MAX_FRAMES_LOW: u16 = 17;
+pub const ETHTOOL_A_COALESCE_PKT_RATE_HIGH: u16 = 18;
+pub const ETHTOOL_A_COALESCE_RX_USECS_HIGH: u16 = 19;
+pub const ETHTOOL_A_COALESCE_RX_MAX_FRAMES_HIGH: u16 = 20;
+pub const ETHTOOL_A_COALESCE_TX_USECS_HIGH: u16 = 21;
+pub const ETHTOOL_A_COALESCE_TX_MAX_FRAMES_HIGH: u16 = 22;
+pub const ETHTOOL_A_COALESCE_RATE_SAMPLE_INTERVAL: u16 = 23;
+
+pub const ETHTOOL_A_PAUSE_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_PAUSE_HEADER: u16 = 1;
+pub const ETHTOOL_A_PAUSE_AUTONEG: u16 = 2;
+pub const ETHTOOL_A_PAUSE_RX: u16 = 3;
+pub const ETHTOOL_A_PAUSE_TX: u16 = 4;
+pub const ETHTOOL_A_PAUSE_STATS: u16 = 5;
+
+pub const ETHTOOL_A_PAUSE_STAT_UNSPEC: u16 = 0;
+pub const ETHTOOL_A_PAUSE_STAT_PAD: u16 = 1;
+pub const ETHTOOL_A_PAUSE_STAT_TX_FRAMES: u16 = 2;
+pub const ETHTOOL_A_PAUSE_STAT_RX_FRAMES: u16 = 3;
+
+pub const DUPLEX_HALF: u8 = 0;
+pub const DUPLEX_FULL: u8 = 1;
+pub const DUPLEX_UNKNOWN: u8 = 0xff;
+
+pub const AUTONEG_DISABLE: u8 = 0;
+pub const AUTONEG_ENABLE: u8 = 1;
+
+pub const SPEED_UNKNOWN: u32 = 0xffff_ffff;
+
+pub const PORT_TP: u8 = 0;
+pub const PORT_AUI: u8 = 1;
+pub const PORT_BNC: u8 = 2;
+pub const PORT_MII: u8 = 3;
+pub const PORT_FIBRE: u8 = 4;
+pub const PORT_DA: u8 = 5;
+pub const PORT_NONE: u8 = 0xef;
+pub const PORT_OTHER: u8 = 0xff;
+
+pub const XCVR_INTERNAL: u8 = 0;
+pub const XCVR_EXTERNAL: u8 = 1;
+
+pub const ETH_TP_MDI_INVALID: u8 = 0;
+pub const ETH_TP_MDI: u8 = 1;
+pub const ETH_TP_MDI_X: u8 = 2;
+pub const ETH_TP_MDI_AUTO: u8 = 3;
+
+// ==========================================
+// devlink generic netlink family
+// ==========================================
+pub const DEVLINK_GENL_NAME: &str = "devlink";
+pub const DEVLINK_GENL_VERSION: u8 = 1;
+pub const DEVLINK_GENL_MCGRP_CONFIG_NAME: &str = "config";
+
+pub const DEVLINK_CMD_UNSPEC: u8 = 0;
+pub const DEVLINK_CMD_GET: u8 = 1;
+pub const DEVLINK_CMD_SET: u8 = 2;
+pub const DEVLINK_CMD_NEW: u8 = 3;
+pub const DEVLINK_CMD_DEL: u8 = 4;
+pub const DEVLINK_CMD_PORT_GET: u8 = 5;
+pub const DEVLINK_CMD_PORT_SET: u8 = 6;
+pub const DEVLINK_CMD_PORT_NEW: u8 = 7;
+pub const DEVLINK_CMD_PORT_DEL: u8 = 8;
+pub const DEVLINK_CMD_PORT_SPLIT: u8 = 9;
+pub const DEVLINK_CMD_PORT_UNSPLIT: u8 = 10;
+pub const DEVLINK_CMD_SB_GET: u8 = 11;
+pub const DEVLINK_CMD_SB_SET: u8 = 12;
+pub const DEVLINK_CMD_SB_NEW: u8 = 13;
+pub const DEVLINK_CMD_SB_DEL: u8 = 14;
+pub const DEVLINK_CMD_SB_POOL_GET: u8 = 15;
+pub const DEVLINK_CMD_SB_POOL_SET: u8 = 16;
+pub const DEVLINK_CMD_SB_POOL_NEW: u8 = 17;
+pub const DEVLINK_CMD_SB_POOL_DEL: u8 = 18;
+pub const DEVLINK_CMD_SB_PORT_POOL_GET: u8 = 19;
+pub const DEVLINK_CMD_SB_PORT_POOL_SET: u8 = 20;
+pub const DEVLINK_CMD_SB_PORT_POOL_NEW: u8 = 21;
+pub const DEVLINK_CMD_SB_PORT_POOL_DEL: u8 = 22;
+pub const DEVLINK_CMD_SB_TC_POOL_BIND_GET: u8 = 23;
+pub const DEVLINK_CMD_SB_TC_POOL_BIND_SET: u8 = 24;
+pub const DEVLINK_CMD_SB_TC_POOL_BIND_NEW: u8 = 25;
+pub const DEVLINK_CMD_SB_TC_POOL_BIND_DEL: u8 = 26;
+pub const DEVLINK_CMD_SB_OCC_SNAPSHOT: u8 = 27;
+pub const DEVLINK_CMD_SB_OCC_MAX_CLEAR: u8 = 28;
+pub const DEVLINK_CMD_ESWITCH_GET: u8 = 29;
+pub const DEVLINK_CMD_ESWITCH_SET: u8 = 30;
+pub const DEVLINK_CMD_DPIPE_TABLE_GET: u8 = 31;
+pub const DEVLINK_CMD_DPIPE_ENTRIES_GET: u8 = 32;
+pub const DEVLINK_CMD_DPIPE_HEADERS_GET: u8 = 33;
+pub const DEVLINK_CMD_DPIPE_TABLE_COUNTERS_SET: u8 = 34;
+pub const DEVLINK_CMD_RESOURCE_SET: u8 = 35;
+pub const DEVLINK_CMD_RESOURCE_DUMP: u8 = 36;
+pub const DEVLINK_CMD_RELOAD: u8 = 37;
+pub const DEVLINK_CMD_PARAM_GET: u8 = 38;
+pub const DEVLINK_CMD_PARAM_SET: u8 = 39;
+pub const DEVLINK_CMD_PARAM_NEW: u8 = 40;
+pub const DEVLINK_CMD_PARAM_DEL: u8 = 41;
+pub const DEVLINK_CMD_REGION_GET: u8 = 42;
+pub const DEVLINK_CMD_REGION_SET: u8 = 43;
+pub const DEVLINK_CMD_REGION_NEW: u8 = 44;
+pub const DEVLINK_CMD_REGION_DEL: u8 = 45;
+pub const DEVLINK_CMD_REGION_READ: u8 = 46;
+pub const DEVLINK_CMD_PORT_PARAM_GET: u8 = 47;
+pub const DEVLINK_CMD_PORT_PARAM_SET: u8 = 48;
+pub const DEVLINK_CMD_PORT_PARAM_NEW: u8 = 49;
+pub const DEVLINK_CMD_PORT_PARAM_DEL: u8 = 50;
+pub const DEVLINK_CMD_INFO_GET: u8 = 51;
+pub const DEVLINK_CMD_HEALTH_REPORTER_GET: u8 = 52;
+pub const DEVLINK_CMD_HEALTH_REPORTER_SET: u8 = 53;
+pub const DEVLINK_CMD_HEALTH_REPORTER_RECOVER: u8 = 54;
+pub const DEVLINK_CMD_HEALTH_REPORTER_DIAGNOSE: u8 = 55;
+pub const DEVLINK_CMD_HEALTH_REPORTER_DUMP_GET: u8 = 56;
+pub const DEVLINK_CMD_HEALTH_REPORTER_DUMP_CLEAR: u8 = 57;
+pub const DEVLINK_CMD_FLASH_UPDATE: u8 = 58;
+
+pub const DEVLINK_ATTR_UNSPEC: u16 = 0;
+pub const DEVLINK_ATTR_BUS_NAME: u16 = 1;
+pub const DEVLINK_ATTR_DEV_NAME: u16 = 2;
+pub const DEVLINK_ATTR_PORT_INDEX: u16 = 3;
+pub const DEVLINK_ATTR_PORT_TYPE: u16 = 4;
+pub const DEVLINK_ATTR_PORT_DESIRED_TYPE: u16 = 5;
+pub const DEVLINK_ATTR_PORT_NETDEV_IFINDEX: u16 = 6;
+pub const DEVLINK_ATTR_PORT_NETDEV_NAME: u16 = 7;
+pub const DEVLINK_ATTR_PORT_IBDEV_NAME: u16 = 8;
+pub const DEVLINK_ATTR_PORT_SPLIT_COUNT: u16 = 9;
+pub const DEVLINK_ATTR_PORT_SPLIT_GROUP: u16 = 10;
+pub const DEVLINK_ATTR_SB_INDEX: u16 = 11;
+pub const DEVLINK_ATTR_SB_SIZE: u16 = 12;
+pub const DEVLINK_ATTR_SB_INGRESS_POOL_COUNT: u16 = 13;
+pub const DEVLINK_ATTR_SB_EGRESS_POOL_COUNT: u16 = 14;
+pub const DEVLINK_ATTR_SB_INGRESS_TC_COUNT: u16 = 15;
+pub const DEVLINK_ATTR_SB_EGRESS_TC_COUNT: u16 = 16;
+pub const DEVLINK_ATTR_SB_POOL_INDEX: u16 = 17;
+pub const DEVLINK_ATTR_SB_POOL_TYPE: u16 = 18;
+pub const DEVLINK_ATTR_SB_POOL_SIZE: u16 = 19;
+pub const DEVLINK_ATTR_SB_POOL_THRESHOLD_TYPE: u16 = 20;
+pub const DEVLINK_ATTR_SB_THRESHOLD: u16 = 21;
+pub const DEVLINK_ATTR_SB_TC_INDEX: u16 = 22;
+pub const DEVLINK_ATTR_SB_OCC_CUR: u16 = 23;
+pub const DEVLINK_ATTR_SB_OCC_MAX: u16 = 24;
+pub const DEVLINK_ATTR_ESWITCH_MODE: u16 = 25;
+pub const DEVLINK_ATTR_ESWITCH_INLINE_MODE: u16 = 26;
+pub const DEVLINK_ATTR_DPIPE_TABLES: u16 = 27;
+pub const DEVLINK_ATTR_DPIPE_TABLE: u16 = 28;
+pub const DEVLINK_ATTR_DPIPE_TABLE_NAME: u16 = 29;
+pub const DEVLINK_ATTR_DPIPE_TABLE_SIZE: u16 = 30;
+pub const DEVLINK_ATTR_DPIPE_TABLE_MATCHES: u16 = 31;
+pub const DEVLINK_ATTR_DPIPE_TABLE_ACTIONS: u16 = 32;
+pub const DEVLINK_ATTR_DPIPE_TABLE_COUNTERS_ENABLED: u16 = 33;
+pub const DEVLINK_ATTR_DPIPE_ENTRIES: u16 = 34;
+pub const DEVLINK_ATTR_DPIPE_ENTRY: u16 = 35;
+pub const DEVLINK_ATTR_DPIPE_ENTRY_INDEX: u16 = 36;
+pub const DEVLINK_ATTR_DPIPE_ENTRY_MATCH_VALUES: u16 = 37;
+pub const DEVLINK_ATTR_DPIPE_ENTRY_ACTION_VALUES: u16 = 38;
+pub const DEVLINK_ATTR_DPIPE_ENTRY_COUNTER: u16 = 39;
+pub const DEVLINK_ATTR_DPIPE_MATCH: u16 = 40;
+pub const DEVLINK_ATTR_DPIPE_MATCH_VALUE: u16 = 41;
+pub const DEVLINK_ATTR_DPIPE_MATCH_TYPE: u16 = 42;
+pub const DEVLINK_ATTR_DPIPE_ACTION: u16 = 43;
+pub const DEVLINK_ATTR_DPIPE_ACTION_VALUE: u16 = 44;
+pub const DEVLINK_ATTR_DPIPE_ACTION_TYPE: u16 = 45;
+pub const DEVLINK_ATTR_DPIPE_VALUE: u16 = 46;
+pub const DEVLINK_ATTR_DPIPE_VALUE_MASK: u16 = 47;
+pub const DEVLINK_ATTR_DPIPE_VALUE_MAPPING: u16 = 48;
+pub const DEVLINK_ATTR_DPIPE_HEADERS: u16 = 49;
+pub const DEVLINK_ATTR_DPIPE_HEADER: u16 = 50;
+pub const DEVLINK_ATTR_DPIPE_HEADER_NAME: u16 = 51;
+pub const DEVLINK_ATTR_DPIPE_HEADER_ID: u16 = 52;
+pub const DEVLINK_ATTR_DPIPE_HEADER_FIELDS: u16 = 53;
+pub const DEVLINK_ATTR_DPIPE_HEADER_GLOBAL: u16 = 54;
+pub const DEVLINK_ATTR_DPIPE_HEADER_INDEX: u16 = 55;
+pub const DEVLINK_ATTR_DPIPE_FIELD: u16 = 56;
+pub const DEVLINK_ATTR_DPIPE_FIELD_NAME: u16 = 57;
+pub const DEVLINK_ATTR_DPIPE_FIELD_ID: u16 = 58;
+pub const DEVLINK_ATTR_DPIPE_FIELD_BITWIDTH: u16 = 59;
+pub const DEVLINK_ATTR_DPIPE_FIELD_MAPPING_TYPE: u16 = 60;
+pub const DEVLINK_ATTR_PAD: u16 = 61;
+pub const DEVLINK_ATTR_ESWITCH_ENCAP_MODE: u16 = 62;
+pub const DEVLINK_ATTR_RESOURCE_LIST: u16 = 63;
+pub const DEVLINK_ATTR_RESOURCE: u16 = 64;
+pub const DEVLINK_ATTR_RESOURCE_NAME: u16 = 65;
+pub const DEVLINK_ATTR_RESOURCE_ID: u16 = 66;
+pub const DEVLINK_ATTR_RESOURCE_SIZE: u16 = 67;
+pub const DEVLINK_ATTR_RESOURCE_SIZE_NEW: u16 = 68;
+pub const DEVLINK_ATTR_RESOURCE_SIZE_VALID: u16 = 69;
+pub const DEVLINK_ATTR_RESOURCE_SIZE_MIN: u16 = 70;
+pub const DEVLINK_ATTR_RESOURCE_SIZE_MAX: u16 = 71;
+pub const DEVLINK_ATTR_RESOURCE_SIZE_GRAN: u16 = 72;
+pub const DEVLINK_ATTR_RESOURCE_UNIT: u16 = 73;
+pub const DEVLINK_ATTR_RESOURCE_OCC: u16 = 74;
+pub const DEVLINK_ATTR_DPIPE_TABLE_RESOURCE_ID: u16 = 75;
+pub const DEVLINK_ATTR_DPIPE_TABLE_RESOURCE_UNITS: u16 = 76;
+pub const DEVLINK_ATTR_PORT_FLAVOUR: u16 = 77;
+pub const DEVLINK_ATTR_PORT_NUMBER: u16 = 78;
+pub const DEVLINK_ATTR_PORT_SPLIT_SUBPORT_NUMBER: u16 = 79;
+pub const DEVLINK_ATTR_PARAM: u16 = 80;
+pub const DEVLINK_ATTR_PARAM_NAME: u16 = 81;
+pub const DEVLINK_ATTR_PARAM_GENERIC: u16 = 82;
+pub const DEVLINK_ATTR_PARAM_TYPE: u16 = 83;
+pub const DEVLINK_ATTR_PARAM_VALUES_LIST: u16 = 84;
+pub const DEVLINK_ATTR_PARAM_VALUE: u16 = 85;
+pub const DEVLINK_ATTR_PARAM_VALUE_DATA: u16 = 86;
+pub const DEVLINK_ATTR_PARAM_VALUE_CMODE: u16 = 87;
+pub const DEVLINK_ATTR_REGION_NAME: u16 = 88;
+pub const DEVLINK_ATTR_REGION_SIZE: u16 = 89;
+pub const DEVLINK_ATTR_REGION_SNAPSHOTS: u16 = 90;
+pub const DEVLINK_ATTR_REGION_SNAPSHOT: u16 = 91;
+pub const DEVLINK_ATTR_REGION_SNAPSHOT_ID: u16 = 92;
+pub const DEVLINK_ATTR_REGION_CHUNKS: u16 = 93;
+pub const DEVLINK_ATTR_REGION_CHUNK: u16 = 94;
+pub const DEVLINK_ATTR_REGION_CHUNK_DATA: u16 = 95;
+pub const DEVLINK_ATTR_REGION_CHUNK_ADDR: u16 = 96;
+pub const DEVLINK_ATTR_REGION_CHUNK_LEN: u16 = 97;
+pub const DEVLINK_ATTR_INFO_DRIVER_NAME: u16 = 98;
+pub const DEVLINK_ATTR_INFO_SERIAL_NUMBER: u16 = 99;
+pub const DEVLINK_ATTR_INFO_VERSION_FIXED: u16 = 100;
+pub const DEVLINK_ATTR_INFO_VERSION_RUNNING: u16 = 101;
+pub const DEVLINK_ATTR_INFO_VERSION_STORED: u16 = 102;
+pub const DEVLINK_ATTR_INFO_VERSION_NAME: u16 = 103;
+pub const DEVLINK_ATTR_INFO_VERSION_VALUE: u16 = 104;
+pub const DEVLINK_ATTR_SB_POOL_CELL_SIZE: u16 = 105;
+pub const DEVLINK_ATTR_FMSG: u16 = 106;
+pub const DEVLINK_ATTR_FMSG_OBJ_NEST_START: u16 = 107;
+pub const DEVLINK_ATTR_FMSG_PAIR_NEST_START: u16 = 108;
+pub const DEVLINK_ATTR_FMSG_ARR_NEST_START: u16 = 109;
+pub const DEVLINK_ATTR_FMSG_NEST_END: u16 = 110;
+pub const DEVLINK_ATTR_FMSG_OBJ_NAME: u16 = 111;
+pub const DEVLINK_ATTR_FMSG_OBJ_VALUE_TYPE: u16 = 112;
+pub const DEVLINK_ATTR_FMSG_OBJ_VALUE_DATA: u16 = 113;
+pub const DEVLINK_ATTR_HEALTH_REPORTER: u16 = 114;
+pub const DEVLINK_ATTR_HEALTH_REPORTER_NAME: u16 = 115;
+pub const DEVLINK_ATTR_HEALTH_REPORTER_STATE: u16 = 116;
+pub const DEVLINK_ATTR_HEALTH_REPORTER_ERR_COUNT: u16 = 117;
+pub const DEVLINK_ATTR_HEALTH_REPORTER_RECOVER_COUNT: u16 = 118;
+pub const DEVLINK_ATTR_HEALTH_REPORTER_DUMP_TS: u16 = 119;
+pub const DEVLINK_ATTR_HEALTH_REPORTER_GRACEFUL_PERIOD: u16 = 120;
+pub const DEVLINK_ATTR_HEALTH_REPORTER_AUTO_RECOVER: u16 = 121;
+pub const DEVLINK_ATTR_FLASH_UPDATE_FILE_NAME: u16 = 122;
+pub const DEVLINK_ATTR_FLASH_UPDATE_COMPONENT: u16 = 123;
+pub const DEVLINK_ATTR_FLASH_UPDATE_STATUS_MSG: u16 = 124;
+pub const DEVLINK_ATTR_FLASH_UPDATE_STATUS_DONE: u16 = 125;
+pub const DEVLINK_ATTR_FLASH_UPDATE_STATUS_TOTAL: u16 = 126;
+pub const DEVLINK_ATTR_PORT_PCI_PF_NUMBER: u16 = 127;
+pub const DEVLINK_ATTR_PORT_PCI_VF_NUMBER: u16 = 128;
+
+pub const DEVLINK_PORT_TYPE_NOTSET: u16 = 0;
+pub const DEVLINK_PORT_TYPE_AUTO: u16 = 1;
+pub const DEVLINK_PORT_TYPE_ETH: u16 = 2;
+pub const DEVLINK_PORT_TYPE_IB: u16 = 3;
+
+pub const DEVLINK_PORT_FLAVOUR_PHYSICAL: u16 = 0;
+pub const DEVLINK_PORT_FLAVOUR_CPU: u16 = 1;
+pub const DEVLINK_PORT_FLAVOUR_DSA: u16 = 2;
+pub const DEVLINK_PORT_FLAVOUR_PCI_PF: u16 = 3;
+pub const DEVLINK_PORT_FLAVOUR_PCI_VF: u16 = 4;
+
+pub const DEVLINK_SB_POOL_TYPE_INGRESS: u8 = 0;
+pub const DEVLINK_SB_POOL_TYPE_EGRESS: u8 = 1;
+
+pub const DEVLINK_SB_THRESHOLD_TYPE_STATIC: u8 = 0;
+pub const DEVLINK_SB_THRESHOLD_TYPE_DYNAMIC: u8 = 1;
+
+pub const DEVLINK_ESWITCH_MODE_LEGACY: u16 = 0;
+pub const DEVLINK_ESWITCH_MODE_SWITCHDEV: u16 = 1;
+
+pub const DEVLINK_PARAM_CMODE_RUNTIME: u8 = 0;
+pub const DEVLINK_PARAM_CMODE_DRIVERINIT: u8 = 1;
+pub const DEVLINK_PARAM_CMODE_PERMANENT: u8 = 2;
+
+// The type of a parameter value is given as a netlink attribute type
+pub const DEVLINK_PARAM_TYPE_U8: u8 = 1;
+pub const DEVLINK_PARAM_TYPE_U16: u8 = 2;
+pub const DEVLINK_PARAM_TYPE_U32: u8 = 3;
+pub const DEVLINK_PARAM_TYPE_STRING: u8 = 5;
+pub const DEVLINK_PARAM_TYPE_BOOL: u8 = 6;
+
+pub const DEVLINK_HEALTH_REPORTER_STATE_HEALTHY: u8 = 0;
+pub const DEVLINK_HEALTH_REPORTER_STATE_ERROR: u8 = 1;
+
+// ==========================================
+// MPTCP path manager generic netlink family
+// ==========================================
+pub const MPTCP_PM_NAME: &str = "mptcp_pm";
+pub const MPTCP_PM_VER: u8 = 1;
+pub const MPTCP_PM_CMD_GRP_NAME: &str = "mptcp_pm_cmds";
+pub const MPTCP_PM_EV_GRP_NAME: &str = "mptcp_pm_events";
+
+pub const MPTCP_PM_CMD_UNSPEC: u8 = 0;
+pub const MPTCP_PM_CMD_ADD_ADDR: u8 = 1;
+pub const MPTCP_PM_CMD_DEL_ADDR: u8 = 2;
+pub const MPTCP_PM_CMD_GET_ADDR: u8 = 3;
+pub const MPTCP_PM_CMD_FLUSH_ADDRS: u8 = 4;
+pub const MPTCP_PM_CMD_SET_LIMITS: u8 = 5;
+pub const MPTCP_PM_CMD_GET_LIMITS: u8 = 6;
+pub const MPTCP_PM_CMD_SET_FLAGS: u8 = 7;
+
+pub const MPTCP_PM_ATTR_UNSPEC: u16 = 0;
+pub const MPTCP_PM_ATTR_ADDR: u16 = 1;
+pub const MPTCP_PM_ATTR_RCV_ADD_ADDRS: u16 = 2;
+pub const MPTCP_PM_ATTR_SUBFLOWS: u16 = 3;
+pub const MPTCP_PM_ATTR_TOKEN: u16 = 4;
+pub const MPTCP_PM_ATTR_LOC_ID: u16 = 5;
+pub const MPTCP_PM_ATTR_ADDR_REMOTE: u16 = 6;
+
+pub const MPTCP_PM_ADDR_ATTR_UNSPEC: u16 = 0;
+pub const MPTCP_PM_ADDR_ATTR_FAMILY: u16 = 1;
+pub const MPTCP_PM_ADDR_ATTR_ID: u16 = 2;
+pub const MPTCP_PM_ADDR_ATTR_ADDR4: u16 = 3;
+pub const MPTCP_PM_ADDR_ATTR_ADDR6: u16 = 4;
+pub const MPTCP_PM_ADDR_ATTR_PORT: u16 = 5;
+pub const MPTCP_PM_ADDR_ATTR_FLAGS: u16 = 6;
+pub const MPTCP_PM_ADDR_ATTR_IF_IDX: u16 = 7;
+
+pub const MPTCP_PM_ADDR_FLAG_SIGNAL: u32 = 1;
+pub const MPTCP_PM_ADDR_FLAG_SUBFLOW: u32 = 1 << 1;
+pub const MPTCP_PM_ADDR_FLAG_BACKUP: u32 = 1 << 2;
+pub const MPTCP_PM_ADDR_FLAG_FULLMESH: u32 = 1 << 3;
+
+// Events are sent to the `mptcp_pm_events` multicast group, with the event type as generic
+// netlink command. Note that they overlap with the MPTCP_PM_CMD_* commands.
+pub const MPTCP_EVENT_UNSPEC: u8 = 0;
+pub const MPTCP_EVENT_CREATED: u8 = 1;
+pub const MPTCP_EVENT_ESTABLISHED: u8 = 2;
+pub const MPTCP_EVENT_CLOSED: u8 = 3;
+pub const MPTCP_EVENT_ANNOUNCED: u8 = 6;
+pub const MPTCP_EVENT_REMOVED: u8 = 7;
+pub const MPTCP_EVENT_SUB_ESTABLISHED: u8 = 10;
+pub const MPTCP_EVENT_SUB_CLOSED: u8 = 11;
+pub const MPTCP_EVENT_SUB_PRIORITY: u8 = 13;
+
+pub const MPTCP_ATTR_UNSPEC: u16 = 0;
+pub const MPTCP_ATTR_TOKEN: u16 = 1;
+pub const MPTCP_ATTR_FAMILY: u16 = 2;
+pub const MPTCP_ATTR_LOC_ID: u16 = 3;
+pub const MPTCP_ATTR_REM_ID: u16 = 4;
+pub const MPTCP_ATTR_SADDR4: u16 = 5;
+pub const MPTCP_ATTR_SADDR6: u16 = 6;
+pub const MPTCP_ATTR_DADDR4: u16 = 7;
+pub const MPTCP_ATTR_DADDR6: u16 = 8;
+pub const MPTCP_ATTR_SPORT: u16 = 9;
+pub const MPTCP_ATTR_DPORT: u16 = 10;
+pub const MPTCP_ATTR_BACKUP: u16 = 11;
+pub const MPTCP_ATTR_ERROR: u16 = 12;
+pub const MPTCP_ATTR_FLAGS: u16 = 13;
+pub const MPTCP_ATTR_TIMEOUT: u16 = 14;
+pub const MPTCP_ATTR_IF_IDX: u16 = 15;
+pub const MPTCP_ATTR_RESET_REASON: u16 = 16;
+pub const MPTCP_ATTR_RESET_FLAGS: u16 = 17;
+pub const MPTCP_ATTR_SERVER_SIDE: u16 = 18;
+
+// ==========================================
+// IPVS generic netlink family
+// ==========================================
+pub const IPVS_GENL_NAME: &str = "IPVS";
+pub const IPVS_GENL_VERSION: u8 = 1;
+
+pub const IPVS_CMD_UNSPEC: u8 = 0;
+pub const IPVS_CMD_NEW_SERVICE: u8 = 1;
+pub const IPVS_CMD_SET_SERVICE: u8 = 2;
+pub const IPVS_CMD_DEL_SERVICE: u8 = 3;
+pub const IPVS_CMD_GET_SERVICE: u8 = 4;
+pub const IPVS_CMD_NEW_DEST: u8 = 5;
+pub const IPVS_CMD_SET_DEST: u8 = 6;
+pub const IPVS_CMD_DEL_DEST: u8 = 7;
+pub const IPVS_CMD_GET_DEST: u8 = 8;
+pub const IPVS_CMD_NEW_DAEMON: u8 = 9;
+pub const IPVS_CMD_DEL_DAEMON: u8 = 10;
+pub const IPVS_CMD_GET_DAEMON: u8 = 11;
+pub const IPVS_CMD_SET_CONFIG: u8 = 12;
+pub const IPVS_CMD_GET_CONFIG: u8 = 13;
+pub const IPVS_CMD_SET_INFO: u8 = 14;
+pub const IPVS_CMD_GET_INFO: u8 = 15;
+pub const IPVS_CMD_ZERO: u8 = 16;
+pub const IPVS_CMD_FLUSH: u8 = 17;
+
+pub const IPVS_CMD_ATTR_UNSPEC: u16 = 0;
+pub const IPVS_CMD_ATTR_SERVICE: u16 = 1;
+pub const IPVS_CMD_ATTR_DEST: u16 = 2;
+pub const IPVS_CMD_ATTR_DAEMON: u16 = 3;
+pub const IPVS_CMD_ATTR_TIMEOUT_TCP: u16 = 4;
+pub const IPVS_CMD_ATTR_TIMEOUT_TCP_FIN: u16 = 5;
+pub const IPVS_CMD_ATTR_TIMEOUT_UDP: u16 = 6;
+
+pub const IPVS_SVC_ATTR_UNSPEC: u16 = 0;
+pub const IPVS_SVC_ATTR_AF: u16 = 1;
+pub const IPVS_SVC_ATTR_PROTOCOL: u16 = 2;
+pub const IPVS_SVC_ATTR_ADDR: u16 = 3;
+pub const IPVS_SVC_ATTR_PORT: u16 = 4;
+pub const IPVS_SVC_ATTR_FWMARK: u16 = 5;
+pub const IPVS_SVC_ATTR_SCHED_NAME: u16 = 6;
+pub const IPVS_SVC_ATTR_FLAGS: u16 = 7;
+pub const IPVS_SVC_ATTR_TIMEOUT: u16 = 8;
+pub const IPVS_SVC_ATTR_NETMASK: u16 = 9;
+pub const IPVS_SVC_ATTR_STATS: u16 = 10;
+pub const IPVS_SVC_ATTR_PE_NAME: u16 = 11;
+pub const IPVS_SVC_ATTR_STATS64: u16 = 12;
+
+pub const IPVS_DEST_ATTR_UNSPEC: u16 = 0;
+pub const IPVS_DEST_ATTR_ADDR: u16 = 1;
+pub const IPVS_DEST_ATTR_PORT: u16 = 2;
+pub const IPVS_DEST_ATTR_FWD_METHOD: u16 = 3;
+pub const IPVS_DEST_ATTR_WEIGHT: u16 = 4;
+pub const IPVS_DEST_ATTR_U_THRESH: u16 = 5;
+pub const IPVS_DEST_ATTR_L_THRESH: u16 = 6;
+pub const IPVS_DEST_ATTR_ACTIVE_CONNS: u16 = 7;
+pub const IPVS_DEST_ATTR_INACT_CONNS: u16 = 8;
+pub const IPVS_DEST_ATTR_PERSIST_CONNS: u16 = 9;
+pub const IPVS_DEST_ATTR_STATS: u16 = 10;
+pub const IPVS_DEST_ATTR_ADDR_FAMILY: u16 = 11;
+pub const IPVS_DEST_ATTR_STATS64: u16 = 12;
+pub const IPVS_DEST_ATTR_TUN_TYPE: u16 = 13;
+pub const IPVS_DEST_ATTR_TUN_PORT: u16 = 14;
+pub const IPVS_DEST_ATTR_TUN_FLAGS: u16 = 15;
+
+pub const IPVS_STATS_ATTR_UNSPEC: u16 = 0;
+pub const IPVS_STATS_ATTR_CONNS: u16 = 1;
+pub const IPVS_STATS_ATTR_INPKTS: u16 = 2;
+pub const IPVS_STATS_ATTR_OUTPKTS: u16 = 3;
+pub const IPVS_STATS_ATTR_INBYTES: u16 = 4;
+pub const IPVS_STATS_ATTR_OUTBYTES: u16 = 5;
+pub const IPVS_STATS_ATTR_CPS: u16 = 6;
+pub const IPVS_STATS_ATTR_INPPS: u16 = 7;
+pub const IPVS_STATS_ATTR_OUTPPS: u16 = 8;
+pub const IPVS_STATS_ATTR_INBPS: u16 = 9;
+pub const IPVS_STATS_ATTR_OUTBPS: u16 = 10;
+pub const IPVS_STATS_ATTR_PAD: u16 = 11;
+
+// Service flags
+pub const IP_VS_SVC_F_PERSISTENT: u32 = 0x0001;
+pub const IP_VS_SVC_F_HASHED: u32 = 0x0002;
+pub const IP_VS_SVC_F_ONEPACKET: u32 = 0x0004;
+pub const IP_VS_SVC_F_SCHED1: u32 = 0x0008;
+pub const IP_VS_SVC_F_SCHED2: u32 = 0x0010;
+pub const IP_VS_SVC_F_SCHED3: u32 = 0x0020;
+
+// Forwarding methods of the destinations
+pub const IP_VS_CONN_F_FWD_MASK: u32 = 0x0007;
+pub const IP_VS_CONN_F_MASQ: u32 = 0x0000;
+pub const IP_VS_CONN_F_LOCALNODE: u32 = 0x0001;
+pub const IP_VS_CONN_F_TUNNEL: u32 = 0x0002;
+pub const IP_VS_CONN_F_DROUTE: u32 = 0x0003;
+pub const IP_VS_CONN_F_BYPASS: u32 = 0x0004;
+
+// Protocols of the virtual services
+pub const IPPROTO_TCP: u16 = libc::IPPROTO_TCP as u16;
+pub const IPPROTO_UDP: u16 = libc::IPPROTO_UDP as u16;
+pub const IPPROTO_SCTP: u16 = libc::IPPROTO_SCTP as u16;
+
+// ==========================================
+// team generic netlink family
+// ==========================================
+pub const TEAM_GENL_NAME: &str = "team";
+pub const TEAM_GENL_VERSION: u8 = 1;
+pub const TEAM_GENL_CHANGE_EVENT_MC_GRP_NAME: &str = "change_event";
+
+pub const TEAM_STRING_MAX_LEN: usize = 32;
+
+pub const TEAM_CMD_NOOP: u8 = 0;
+pub const TEAM_CMD_OPTIONS_SET: u8 = 1;
+pub const TEAM_CMD_OPTIONS_GET: u8 = 2;
+pub const TEAM_CMD_PORT_LIST_GET: u8 = 3;
+
+pub const TEAM_ATTR_UNSPEC: u16 = 0;
+pub const TEAM_ATTR_TEAM_IFINDEX: u16 = 1;
+pub const TEAM_ATTR_LIST_OPTION: u16 = 2;
+pub const TEAM_ATTR_LIST_PORT: u16 = 3;
+
+pub const TEAM_ATTR_ITEM_OPTION_UNSPEC: u16 = 0;
+pub const TEAM_ATTR_ITEM_OPTION: u16 = 1;
+
+pub const TEAM_ATTR_OPTION_UNSPEC: u16 = 0;
+pub const TEAM_ATTR_OPTION_NAME: u16 = 1;
+pub const TEAM_ATTR_OPTION_CHANGED: u16 = 2;
+pub const TEAM_ATTR_OPTION_TYPE: u16 = 3;
+pub const TEAM_ATTR_OPTION_DATA: u16 = 4;
+pub const TEAM_ATTR_OPTION_REMOVED: u16 = 5;
+pub const TEAM_ATTR_OPTION_PORT_IFINDEX: u16 = 6;
+pub const TEAM_ATTR_OPTION_ARRAY_INDEX: u16 = 7;
+
+pub const TEAM_ATTR_ITEM_PORT_UNSPEC: u16 = 0;
+pub const TEAM_ATTR_ITEM_PORT: u16 = 1;
+
+pub const TEAM_ATTR_PORT_UNSPEC: u16 = 0;
+pub const TEAM_ATTR_PORT_IFINDEX: u16 = 1;
+pub const TEAM_ATTR_PORT_CHANGED: u16 = 2;
+pub const TEAM_ATTR_PORT_LINKUP: u16 = 3;
+pub const TEAM_ATTR_PORT_SPEED: u16 = 4;
+pub const TEAM_ATTR_PORT_DUPLEX: u16 = 5;
+pub const TEAM_ATTR_PORT_REMOVED: u16 = 6;
+
+// The type of an option value is given as a netlink attribute type
+pub const TEAM_OPTION_TYPE_U32: u8 = 3;
+pub const TEAM_OPTION_TYPE_STRING: u8 = 5;
+pub const TEAM_OPTION_TYPE_BOOL: u8 = 6;
+pub const TEAM_OPTION_TYPE_BINARY: u8 = 11;
+pub const TEAM_OPTION_TYPE_S32: u8 = 14;
+
+// Socket filters (see `Documentation/networking/filter.txt` in the Linux kernel source tree)
+pub const SO_ATTACH_FILTER: int = 26;
+pub const SO_DETACH_FILTER: int = 27;
+
+// Classic BPF instruction classes
+pub const BPF_LD: u16 = 0x00;
+pub const BPF_LDX: u16 = 0x01;
+pub const BPF_ST: u16 = 0x02;
+pub const BPF_STX: u16 = 0x03;
+pub const BPF_ALU: u16 = 0x04;
+pub const BPF_JMP: u16 = 0x05;
+pub const BPF_RET: u16 = 0x06;
+pub const BPF_MISC: u16 = 0x07;
+
+// Size of the operand of the load instructions
+pub const BPF_W: u16 = 0x00;
+pub const BPF_H: u16 = 0x08;
+pub const BPF_B: u16 = 0x10;
+
+// Addressing mode of the load instructions
+pub const BPF_IMM: u16 = 0x00;
+pub const BPF_ABS: u16 = 0x20;
+pub const BPF_IND: u16 = 0x40;
+pub const BPF_MEM: u16 = 0x60;
+pub const BPF_LEN: u16 = 0x80;
+pub const BPF_MSH: u16 = 0xa0;
+
+// ALU and jump operations
+pub const BPF_ADD: u16 = 0x00;
+pub const BPF_SUB: u16 = 0x10;
+pub const BPF_MUL: u16 = 0x20;
+pub const BPF_DIV: u16 = 0x30;
+pub const BPF_OR: u16 = 0x40;
+pub const BPF_AND: u16 = 0x50;
+pub const BPF_LSH: u16 = 0x60;
+pub const BPF_RSH: u16 = 0x70;
+pub const BPF_NEG: u16 = 0x80;
+pub const BPF_MOD: u16 = 0x90;
+pub const BPF_XOR: u16 = 0xa0;
+pub const BPF_JA: u16 = 0x00;
+pub const BPF_JEQ: u16 = 0x10;
+pub const BPF_JGT: u16 = 0x20;
+pub const BPF_JGE: u16 = 0x30;
+pub const BPF_JSET: u16 = 0x40;
+
+// Source operand of the ALU and jump instructions
+pub const BPF_K: u16 = 0x00;
+pub const BPF_X: u16 = 0x08;
+
+// Return value of the return instructions
+pub const BPF_A: u16 = 0x10;
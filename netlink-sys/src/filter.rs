@@ -0,0 +1,227 @@
+//! Classic BPF socket filters, to drop unwanted messages in the kernel
+use std::io::{Error, ErrorKind, Result};
+use std::os::unix::io::AsRawFd;
+
+use crate::constants::*;
+
+use super::sys::setsockopt;
+use super::Socket;
+
+/// Offset of the `nlmsg_type` field in the netlink header
+const NLMSG_TYPE_OFFSET: u32 = 4;
+
+/// Maximum number of instructions of a classic BPF program. This also fits in the `len` field
+/// of `struct sock_fprog`, which is a `u16`.
+const BPF_MAXINSNS: usize = 4096;
+
+/// A classic BPF instruction (`struct sock_filter`)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SockFilter {
+    pub code: u16,
+    pub jt: u8,
+    pub jf: u8,
+    pub k: u32,
+}
+
+impl SockFilter {
+    /// Equivalent of the `BPF_STMT` macro
+    pub fn stmt(code: u16, k: u32) -> Self {
+        SockFilter {
+            code,
+            jt: 0,
+            jf: 0,
+            k,
+        }
+    }
+
+    /// Equivalent of the `BPF_JUMP` macro
+    pub fn jump(code: u16, k: u32, jt: u8, jf: u8) -> Self {
+        SockFilter { code, jt, jf, k }
+    }
+}
+
+/// A classic BPF program (`struct sock_fprog`)
+#[repr(C)]
+struct SockFprog {
+    len: u16,
+    filter: *const SockFilter,
+}
+
+/// Build a filter that only accepts the messages with the given types. For instance, a listener
+/// that is only interested in new links can drop all the other rtnetlink notifications with:
+///
+/// ```rust,no_run
+/// use netlink_sys::constants::RTM_NEWLINK;
+/// use netlink_sys::{MessageTypeFilter, Protocol, Socket};
+///
+/// let mut socket = Socket::new(Protocol::Route).unwrap();
+/// let filter = MessageTypeFilter::new().accept(RTM_NEWLINK).build();
+/// socket.attach_filter(&filter).unwrap();
+/// ```
+///
+/// The control messages (`NLMSG_NOOP`, `NLMSG_ERROR`, `NLMSG_DONE` and `NLMSG_OVERRUN`) are always
+/// accepted, so that acknowledgements and errors still reach the socket.
+///
+/// Note that the filter runs once per datagram, and only looks at the first message it contains.
+/// This is fine for notifications, which are sent one per datagram, but the messages of a dump
+/// are batched, and are accepted or dropped together.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MessageTypeFilter {
+    message_types: Vec<u16>,
+}
+
+impl MessageTypeFilter {
+    pub fn new() -> Self {
+        MessageTypeFilter::default()
+    }
+
+    /// Accept the messages with the given type
+    pub fn accept(mut self, message_type: u16) -> Self {
+        if !self.message_types.contains(&message_type) {
+            self.message_types.push(message_type);
+        }
+        self
+    }
+
+    /// Generate the BPF program
+    ///
+    /// # Panics
+    ///
+    /// This panics if more than 255 message types (including the control messages) are accepted,
+    /// since the offset of a BPF jump must fit in a byte.
+    pub fn build(&self) -> Vec<SockFilter> {
+        let mut message_types = vec![NLMSG_NOOP, NLMSG_ERROR, NLMSG_DONE, NLMSG_OVERRUN];
+        for message_type in &self.message_types {
+            if !message_types.contains(message_type) {
+                message_types.push(*message_type);
+            }
+        }
+        assert!(
+            message_types.len() <= 255,
+            "too many message types in the filter"
+        );
+        let count = message_types.len();
+
+        let mut program = Vec::with_capacity(count + 3);
+        program.push(SockFilter::stmt(
+            BPF_LD | BPF_H | BPF_ABS,
+            NLMSG_TYPE_OFFSET,
+        ));
+        for (i, message_type) in message_types.into_iter().enumerate() {
+            // BPF loads are big endian, while the netlink headers are in native endianness
+            let k = u32::from(u16::from_be_bytes(message_type.to_ne_bytes()));
+            // jump to the "accept" instruction, after the remaining comparisons and the "drop"
+            // instruction
+            let jt = (count - i) as u8;
+            program.push(SockFilter::jump(BPF_JMP | BPF_JEQ | BPF_K, k, jt, 0));
+        }
+        // drop
+        program.push(SockFilter::stmt(BPF_RET | BPF_K, 0));
+        // accept the whole datagram
+        program.push(SockFilter::stmt(BPF_RET | BPF_K, u32::MAX));
+        program
+    }
+}
+
+impl Socket {
+    /// `SO_ATTACH_FILTER`. Attach a classic BPF program to the socket. The datagrams for which
+    /// the program returns 0 are dropped by the kernel, before being queued to the socket. See
+    /// [`MessageTypeFilter`](struct.MessageTypeFilter.html) to generate filters on the message
+    /// types.
+    ///
+    /// Programs longer than 4096 instructions (`BPF_MAXINSNS`) are rejected with an
+    /// `InvalidInput` error.
+    pub fn attach_filter(&mut self, filter: &[SockFilter]) -> Result<()> {
+        if filter.len() > BPF_MAXINSNS || filter.len() > u16::MAX as usize {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "BPF program too long: {} instructions (at most {})",
+                    filter.len(),
+                    BPF_MAXINSNS
+                ),
+            ));
+        }
+        let program = SockFprog {
+            len: filter.len() as u16,
+            filter: filter.as_ptr(),
+        };
+        setsockopt(
+            self.as_raw_fd(),
+            libc::SOL_SOCKET,
+            SO_ATTACH_FILTER,
+            program,
+        )
+    }
+
+    /// `SO_DETACH_FILTER`. Remove the filter previously attached to the socket.
+    pub fn detach_filter(&mut self) -> Result<()> {
+        let value: libc::c_int = 0;
+        setsockopt(self.as_raw_fd(), libc::SOL_SOCKET, SO_DETACH_FILTER, value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Protocol, SocketAddr};
+
+    #[test]
+    fn message_type_filter() {
+        let program = MessageTypeFilter::new()
+            .accept(RTM_NEWLINK)
+            .accept(NLMSG_ERROR)
+            .accept(RTM_NEWLINK)
+            .build();
+        // one load, five comparisons, drop and accept
+        assert_eq!(program.len(), 8);
+        assert_eq!(program[0], SockFilter::stmt(0x28, 4));
+        let k = u32::from(u16::from_be_bytes(RTM_NEWLINK.to_ne_bytes()));
+        assert_eq!(program[5], SockFilter::jump(0x15, k, 1, 0));
+        assert_eq!(program[6], SockFilter::stmt(0x06, 0));
+        assert_eq!(program[7], SockFilter::stmt(0x06, 0xffff_ffff));
+    }
+
+    #[test]
+    fn attach_filter() {
+        let mut sock = Socket::new(Protocol::Route).unwrap();
+        sock.bind_auto().unwrap();
+        sock.connect(&SocketAddr::new(0, 0)).unwrap();
+
+        let filter = MessageTypeFilter::new().accept(RTM_NEWLINK).build();
+        sock.attach_filter(&filter).unwrap();
+
+        // RTM_GETLINK dump request: netlink header followed by an empty ifinfomsg
+        let mut request = [0u8; 32];
+        request[0..4].copy_from_slice(&32u32.to_ne_bytes());
+        request[4..6].copy_from_slice(&RTM_GETLINK.to_ne_bytes());
+        request[6..8].copy_from_slice(&(NLM_F_REQUEST | NLM_F_DUMP).to_ne_bytes());
+        sock.send(&request[..], 0).unwrap();
+
+        let mut buf = vec![0; 32 * 1024];
+        let n = sock.recv(&mut buf[..], 0).unwrap();
+        assert!(n >= 16);
+        assert_eq!(u16::from_ne_bytes([buf[4], buf[5]]), RTM_NEWLINK);
+
+        sock.detach_filter().unwrap();
+    }
+
+    #[test]
+    fn attach_too_long_filter() {
+        let mut sock = Socket::new(Protocol::Route).unwrap();
+        let accept = SockFilter::stmt(BPF_RET | BPF_K, u32::MAX);
+
+        let filter = vec![accept; BPF_MAXINSNS];
+        sock.attach_filter(&filter).unwrap();
+
+        let filter = vec![accept; BPF_MAXINSNS + 1];
+        let err = sock.attach_filter(&filter).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        // would be truncated to a single instruction
+        let filter = vec![accept; u16::MAX as usize + 2];
+        let err = sock.attach_filter(&filter).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
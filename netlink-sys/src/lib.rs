@@ -4,6 +4,15 @@ pub use self::protocols::*;
 mod sys;
 pub use self::sys::*;
 
+mod netns;
+pub use self::netns::*;
+
+mod filter;
+pub use self::filter::*;
+
+mod async_socket;
+pub use self::async_socket::*;
+
 #[cfg(feature = "mio_support")]
 extern crate mio as mio_crate;
 #[cfg(feature = "mio_support")]
@@ -13,13 +22,19 @@ mod mio;
 #[macro_use]
 extern crate log;
 
-#[cfg(feature = "tokio_support")]
-#[macro_use]
-extern crate futures;
-
 #[cfg(feature = "tokio_support")]
 mod tokio;
 #[cfg(feature = "tokio_support")]
 pub use self::tokio::*;
 
+#[cfg(feature = "smol_support")]
+mod smol;
+#[cfg(feature = "smol_support")]
+pub use self::smol::*;
+
+#[cfg(feature = "async_std_support")]
+mod async_std;
+#[cfg(feature = "async_std_support")]
+pub use self::async_std::*;
+
 pub mod constants;
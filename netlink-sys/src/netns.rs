@@ -0,0 +1,111 @@
+//! Creation of netlink sockets in other network namespaces
+use std::ffi::CString;
+use std::io::{Error, ErrorKind, Result};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::RawFd;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use super::{Protocol, Socket};
+
+/// A network namespace
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkNamespace {
+    /// A namespace file, for instance `/var/run/netns/NAME` for the namespaces created with
+    /// `ip netns add NAME`
+    Path(PathBuf),
+    /// A file descriptor referring to a namespace file. It is not closed by this library.
+    Fd(RawFd),
+    /// The namespace of the process with the given pid
+    Pid(libc::pid_t),
+}
+
+impl NetworkNamespace {
+    // Open the namespace file, and return a file descriptor that must be closed by the caller
+    fn open(&self) -> Result<RawFd> {
+        match *self {
+            NetworkNamespace::Path(ref path) => open_ns(path),
+            NetworkNamespace::Fd(fd) => {
+                let res = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+                if res < 0 {
+                    return Err(Error::last_os_error());
+                }
+                Ok(res)
+            }
+            NetworkNamespace::Pid(pid) => open_ns(format!("/proc/{}/ns/net", pid)),
+        }
+    }
+}
+
+fn open_ns<P: AsRef<Path>>(path: P) -> Result<RawFd> {
+    let path = CString::new(path.as_ref().as_os_str().as_bytes())
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+    let res = unsafe { libc::open(path.as_ptr(), libc::O_RDONLY | libc::O_CLOEXEC) };
+    if res < 0 {
+        return Err(Error::last_os_error());
+    }
+    Ok(res)
+}
+
+fn setns(fd: RawFd) -> Result<()> {
+    let res = unsafe { libc::setns(fd, libc::CLONE_NEWNET) };
+    if res < 0 {
+        return Err(Error::last_os_error());
+    }
+    Ok(())
+}
+
+// Move the current thread to the given namespace, run `f`, and move the thread back to its
+// original namespace.
+fn run_in_namespace<F, T>(ns: &NetworkNamespace, f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T>,
+{
+    let target = ns.open()?;
+    let original = match open_ns("/proc/thread-self/ns/net") {
+        Ok(fd) => fd,
+        Err(e) => {
+            unsafe { libc::close(target) };
+            return Err(e);
+        }
+    };
+
+    let res = setns(target).and_then(|()| {
+        let res = f();
+        // If we fail to switch back, the thread is left in the wrong namespace: report the error
+        // even if `f` succeeded, so that the result is dropped.
+        setns(original).and(res)
+    });
+
+    unsafe {
+        libc::close(target);
+        libc::close(original);
+    }
+    res
+}
+
+impl Socket {
+    /// Create a netlink socket in the given network namespace.
+    ///
+    /// A netlink socket belongs to the namespace it has been created in, for its whole lifetime.
+    /// To create the socket, a helper thread is moved into the namespace with `setns` and moved
+    /// back once the socket is open, so that the namespace of the calling thread is not modified.
+    /// The socket can then be used from any thread. This requires `CAP_SYS_ADMIN`.
+    pub fn new_in_namespace(protocol: Protocol, ns: NetworkNamespace) -> Result<Self> {
+        thread::spawn(move || run_in_namespace(&ns, || Socket::new(protocol)))
+            .join()
+            .unwrap_or_else(|_| Err(Error::other("the namespace helper thread panicked")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn open_invalid_namespace() {
+        let ns = NetworkNamespace::Path("/this/namespace/does/not/exist".into());
+        let err = Socket::new_in_namespace(Protocol::Route, ns).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::constants::*;
 
 /// List of netlink protocols
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Protocol {
     /// Receives routing and link updates and may be used to modify the routing tables (both IPv4
     /// and IPv6), IP addresses, link parameters, neighbor setups, queueing disciplines, traffic
@@ -0,0 +1,93 @@
+use std::io;
+use std::task::{Context, Poll};
+
+use async_io::Async;
+use futures::ready;
+
+use super::sys;
+use super::AsyncSocket;
+
+/// An I/O object representing a netlink socket, registered with the `async-io` reactor used by
+/// `smol`.
+pub struct SmolSocket(Async<sys::Socket>);
+
+impl SmolSocket {
+    fn poll_read_with<T, F>(&mut self, cx: &mut Context<'_>, mut op: F) -> Poll<io::Result<T>>
+    where
+        F: FnMut(&sys::Socket) -> io::Result<T>,
+    {
+        loop {
+            match op(self.0.get_ref()) {
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                res => return Poll::Ready(res),
+            }
+            // The socket is not readable: wait for the reactor to tell us it is, and try again
+            ready!(self.0.poll_readable(cx))?;
+        }
+    }
+
+    fn poll_write_with<T, F>(&mut self, cx: &mut Context<'_>, mut op: F) -> Poll<io::Result<T>>
+    where
+        F: FnMut(&sys::Socket) -> io::Result<T>,
+    {
+        loop {
+            match op(self.0.get_ref()) {
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                res => return Poll::Ready(res),
+            }
+            ready!(self.0.poll_writable(cx))?;
+        }
+    }
+}
+
+impl AsyncSocket for SmolSocket {
+    fn from_socket(socket: sys::Socket) -> io::Result<Self> {
+        // Async::new() switches the socket to non-blocking mode
+        Ok(SmolSocket(Async::new(socket)?))
+    }
+
+    fn socket_ref(&self) -> &sys::Socket {
+        self.0.get_ref()
+    }
+
+    fn socket_mut(&mut self) -> &mut sys::Socket {
+        self.0.get_mut()
+    }
+
+    fn poll_send(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.poll_write_with(cx, |socket| socket.send(buf, 0))
+    }
+
+    fn poll_send_to(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        target: &sys::SocketAddr,
+    ) -> Poll<io::Result<usize>> {
+        self.poll_write_with(cx, |socket| socket.send_to(buf, target, 0))
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        self.poll_read_with(cx, |socket| socket.recv(buf, 0))
+    }
+
+    fn poll_recv_from(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, sys::SocketAddr)>> {
+        self.poll_read_with(cx, |socket| socket.recv_from(buf, 0))
+    }
+
+    fn poll_recv_msg(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, sys::SocketAddr, sys::RecvMeta)>> {
+        self.poll_read_with(cx, |socket| socket.recv_msg(buf, 0))
+    }
+
+    fn poll_peek_len(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        self.poll_read_with(cx, |socket| socket.peek_len(0))
+    }
+}
@@ -2,9 +2,10 @@
 use libc;
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::io::{Error, Result};
+use std::io::{Error, ErrorKind, Result};
 use std::mem;
-use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::time::Duration;
 
 use super::Protocol;
 
@@ -17,6 +18,13 @@ impl AsRawFd for Socket {
     }
 }
 
+impl AsFd for Socket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // The file descriptor is valid until the socket is dropped
+        unsafe { BorrowedFd::borrow_raw(self.0) }
+    }
+}
+
 impl Drop for Socket {
     fn drop(&mut self) {
         unsafe { libc::close(self.0) };
@@ -110,6 +118,52 @@ impl SocketAddr {
     }
 }
 
+/// Metadata of a received datagram, carried by its control messages (see
+/// [`Socket::recv_msg`](struct.Socket.html#method.recv_msg)).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RecvMeta {
+    /// Multicast group the datagram was sent to (`Some(0)` for unicast datagrams), or `None` if
+    /// `NETLINK_PKTINFO` is not enabled on the socket (see
+    /// [`Socket::set_pktinfo`](struct.Socket.html#method.set_pktinfo)) or the control messages
+    /// of the datagram were truncated.
+    pub group: Option<u32>,
+    /// Identifier of the network namespace the datagram originates from. Only set when
+    /// `NETLINK_LISTEN_ALL_NSID` is enabled on the socket (see
+    /// [`Socket::set_listen_all_namespaces`](struct.Socket.html#method.set_listen_all_namespaces)),
+    /// and the datagram comes from a namespace that has an nsid assigned.
+    pub nsid: Option<i32>,
+}
+
+impl RecvMeta {
+    // Parse the control messages of a message filled by recvmsg
+    unsafe fn from_msghdr(msg: &libc::msghdr) -> Self {
+        let mut meta = RecvMeta::default();
+        let mut cmsg = libc::CMSG_FIRSTHDR(msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_NETLINK {
+                let data = libc::CMSG_DATA(cmsg);
+                match (*cmsg).cmsg_type {
+                    // struct nl_pktinfo only contains the group
+                    libc::NETLINK_PKTINFO => {
+                        meta.group = Some(std::ptr::read_unaligned(data as *const u32))
+                    }
+                    libc::NETLINK_LISTEN_ALL_NSID => {
+                        meta.nsid = Some(std::ptr::read_unaligned(data as *const i32))
+                    }
+                    _ => {}
+                }
+            }
+            cmsg = libc::CMSG_NXTHDR(msg, cmsg);
+        }
+        meta
+    }
+}
+
+// Number of u64 words of the storage for the control messages: it must be large enough for both
+// a nl_pktinfo and a nsid, which are both 4 bytes long, that is `2 * CMSG_SPACE(4)` bytes. It is
+// made of u64 so that it is correctly aligned for a cmsghdr.
+const CMSG_BUFFER_WORDS: usize = 8;
+
 impl Socket {
     pub fn new(protocol: Protocol) -> Result<Self> {
         let res =
@@ -242,6 +296,56 @@ impl Socket {
         Ok((res as usize, SocketAddr(addr)))
     }
 
+    /// Same as [`recv_from`](#method.recv_from), but uses `recvmsg` so that the control
+    /// messages attached to the datagram are also received. They carry the multicast group the
+    /// datagram was sent to, and the network namespace it originates from, if the socket is
+    /// configured to receive them.
+    ///
+    /// If the control messages do not fit in the buffer that receives them, the datagram is still
+    /// returned, but without its metadata.
+    pub fn recv_msg(
+        &self,
+        buf: &mut [u8],
+        flags: libc::c_int,
+    ) -> Result<(usize, SocketAddr, RecvMeta)> {
+        let mut control = [0u64; CMSG_BUFFER_WORDS];
+        self.recv_msg_with_control(buf, flags, &mut control[..])
+    }
+
+    fn recv_msg_with_control(
+        &self,
+        buf: &mut [u8],
+        flags: libc::c_int,
+        control: &mut [u64],
+    ) -> Result<(usize, SocketAddr, RecvMeta)> {
+        let mut addr = unsafe { mem::zeroed::<libc::sockaddr_nl>() };
+        let mut iov = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+
+        let mut msg = unsafe { mem::zeroed::<libc::msghdr>() };
+        msg.msg_name = &mut addr as *mut libc::sockaddr_nl as *mut libc::c_void;
+        msg.msg_namelen = mem::size_of_val(&addr) as libc::socklen_t;
+        msg.msg_iov = &mut iov as *mut libc::iovec;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = mem::size_of_val(control) as _;
+
+        let res = unsafe { libc::recvmsg(self.0, &mut msg as *mut libc::msghdr, flags) };
+        if res < 0 {
+            return Err(Error::last_os_error());
+        }
+        // The datagram has been consumed: it is still returned, but its metadata would be wrong
+        let meta = if msg.msg_flags & libc::MSG_CTRUNC != 0 {
+            log::warn!("the control messages of a datagram were truncated: ignoring them");
+            RecvMeta::default()
+        } else {
+            unsafe { RecvMeta::from_msghdr(&msg) }
+        };
+        Ok((res as usize, SocketAddr(addr), meta))
+    }
+
     pub fn recv(&self, buf: &mut [u8], flags: libc::c_int) -> Result<usize> {
         let buf_ptr = buf.as_mut_ptr() as *mut libc::c_void;
         let buf_len = buf.len() as libc::size_t;
@@ -253,6 +357,15 @@ impl Socket {
         Ok(res as usize)
     }
 
+    /// Return the length of the next datagram, without removing it from the socket queue. This
+    /// can be used to make sure the buffer passed to [`recv`](#method.recv) and its variants is
+    /// large enough: when it is not, the end of the datagram is silently discarded.
+    pub fn peek_len(&self, flags: libc::c_int) -> Result<usize> {
+        // With MSG_TRUNC, recv returns the real length of the datagram, even if it is longer than
+        // the buffer. With MSG_PEEK, the datagram is left in the queue.
+        self.recv(&mut [], flags | libc::MSG_PEEK | libc::MSG_TRUNC)
+    }
+
     pub fn send_to(&self, buf: &[u8], addr: &SocketAddr, flags: libc::c_int) -> Result<usize> {
         let (addr_ptr, addr_len) = addr.as_raw();
         let buf_ptr = buf.as_ptr() as *const libc::c_void;
@@ -304,11 +417,141 @@ impl Socket {
         )
     }
 
-    pub fn list_membership(&self) -> Vec<u32> {
-        unimplemented!();
-        // getsockopt won't be enough here, because we may need to perform 2 calls, and because the
-        // length of the list returned by libc::getsockopt is returned by mutating the length
-        // argument, which our implementation of getsockopt forbids.
+    /// `NETLINK_LIST_MEMBERSHIPS` (since Linux 4.2). Return the multicast groups this socket is a
+    /// member of, including the groups above 32 that cannot be represented in
+    /// [`SocketAddr::multicast_groups`](struct.SocketAddr.html#method.multicast_groups).
+    pub fn list_membership(&self) -> Result<Vec<u32>> {
+        let bitmap =
+            getsockopt_vec::<u32>(self.0, libc::SOL_NETLINK, libc::NETLINK_LIST_MEMBERSHIPS)?;
+        // bit `n` of the bitmap is set if the socket is a member of group `n + 1`
+        let mut groups = vec![];
+        for (i, word) in bitmap.into_iter().enumerate() {
+            for bit in 0..32 {
+                if word & (1 << bit) != 0 {
+                    groups.push(i as u32 * 32 + bit + 1);
+                }
+            }
+        }
+        Ok(groups)
+    }
+
+    /// `NETLINK_EXT_ACK` (since Linux 4.12). When set, the kernel appends extended information to
+    /// the error and acknowledgement messages: a human readable error message, and the offset of
+    /// the attribute that caused the error, for instance.
+    pub fn set_ext_ack(&mut self, value: bool) -> Result<()> {
+        let value: libc::c_int = if value { 1 } else { 0 };
+        setsockopt(self.0, libc::SOL_NETLINK, libc::NETLINK_EXT_ACK, value)
+    }
+
+    pub fn get_ext_ack(&self) -> Result<bool> {
+        let res = getsockopt::<libc::c_int>(self.0, libc::SOL_NETLINK, libc::NETLINK_EXT_ACK)?;
+        Ok(res == 1)
+    }
+
+    /// `NETLINK_GET_STRICT_CHK` (since Linux 4.20). When set, the kernel strictly validates the
+    /// headers and attributes of the dump requests, and uses them to filter the dump results
+    /// instead of ignoring them.
+    pub fn set_strict_check(&mut self, value: bool) -> Result<()> {
+        let value: libc::c_int = if value { 1 } else { 0 };
+        setsockopt(
+            self.0,
+            libc::SOL_NETLINK,
+            libc::NETLINK_GET_STRICT_CHK,
+            value,
+        )
+    }
+
+    pub fn get_strict_check(&self) -> Result<bool> {
+        let res =
+            getsockopt::<libc::c_int>(self.0, libc::SOL_NETLINK, libc::NETLINK_GET_STRICT_CHK)?;
+        Ok(res == 1)
+    }
+
+    /// `SO_RCVBUF`. Set the maximum size of the socket receive buffer. The kernel doubles the
+    /// value to leave room for its bookkeeping, and caps it to `net.core.rmem_max`. Listeners that
+    /// receive many notifications may need a larger buffer to avoid `ENOBUFS` errors.
+    pub fn set_rcvbuf(&mut self, size: usize) -> Result<()> {
+        setsockopt(
+            self.0,
+            libc::SOL_SOCKET,
+            libc::SO_RCVBUF,
+            size as libc::c_int,
+        )
+    }
+
+    /// `SO_RCVBUFFORCE`. Same as [`set_rcvbuf`](#method.set_rcvbuf), but `net.core.rmem_max` is
+    /// ignored. This requires `CAP_NET_ADMIN`.
+    pub fn set_rcvbuf_force(&mut self, size: usize) -> Result<()> {
+        setsockopt(
+            self.0,
+            libc::SOL_SOCKET,
+            libc::SO_RCVBUFFORCE,
+            size as libc::c_int,
+        )
+    }
+
+    pub fn get_rcvbuf(&self) -> Result<usize> {
+        let res = getsockopt::<libc::c_int>(self.0, libc::SOL_SOCKET, libc::SO_RCVBUF)?;
+        Ok(res as usize)
+    }
+
+    /// `SO_SNDBUF`. Set the maximum size of the socket send buffer. The kernel doubles the value
+    /// to leave room for its bookkeeping, and caps it to `net.core.wmem_max`.
+    pub fn set_sndbuf(&mut self, size: usize) -> Result<()> {
+        setsockopt(
+            self.0,
+            libc::SOL_SOCKET,
+            libc::SO_SNDBUF,
+            size as libc::c_int,
+        )
+    }
+
+    pub fn get_sndbuf(&self) -> Result<usize> {
+        let res = getsockopt::<libc::c_int>(self.0, libc::SOL_SOCKET, libc::SO_SNDBUF)?;
+        Ok(res as usize)
+    }
+
+    /// `SO_RCVTIMEO`. Set a timeout for the blocking receive operations. When the timeout
+    /// expires, they fail with `WouldBlock`. `None` (the default) means that they block
+    /// indefinitely.
+    pub fn set_read_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        let value = match timeout {
+            Some(timeout) => {
+                if timeout == Duration::from_secs(0) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "cannot set a zero duration timeout",
+                    ));
+                }
+                let mut value = libc::timeval {
+                    tv_sec: timeout.as_secs() as libc::time_t,
+                    tv_usec: timeout.subsec_micros() as libc::suseconds_t,
+                };
+                // a timeout of less than a microsecond would be rounded down to zero, which
+                // disables the timeout
+                if value.tv_sec == 0 && value.tv_usec == 0 {
+                    value.tv_usec = 1;
+                }
+                value
+            }
+            None => libc::timeval {
+                tv_sec: 0,
+                tv_usec: 0,
+            },
+        };
+        setsockopt(self.0, libc::SOL_SOCKET, libc::SO_RCVTIMEO, value)
+    }
+
+    pub fn get_read_timeout(&self) -> Result<Option<Duration>> {
+        let value = getsockopt::<libc::timeval>(self.0, libc::SOL_SOCKET, libc::SO_RCVTIMEO)?;
+        if value.tv_sec == 0 && value.tv_usec == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(
+                Duration::from_secs(value.tv_sec as u64)
+                    + Duration::from_micros(value.tv_usec as u64),
+            ))
+        }
     }
 
     /// `NETLINK_BROADCAST_ERROR` (since Linux 2.6.30). When not set, `netlink_broadcast()` only
@@ -410,8 +653,47 @@ fn getsockopt<T: Copy>(fd: RawFd, level: libc::c_int, option: libc::c_int) -> Re
     }
 }
 
+/// Wrapper around `getsockopt` for options which value is an array with arbitrary length, such as
+/// `NETLINK_LIST_MEMBERSHIPS`. `getsockopt` truncates the value to the size of the storage, and
+/// sets the length argument to the size it actually needs, so the call is retried with a larger
+/// storage until the whole value fits.
+fn getsockopt_vec<T: Copy>(fd: RawFd, level: libc::c_int, option: libc::c_int) -> Result<Vec<T>> {
+    let mut slots: Vec<T> = vec![unsafe { mem::zeroed() }; 1];
+    loop {
+        let slots_len = slots.len() * mem::size_of::<T>();
+        let mut value_len = slots_len as libc::socklen_t;
+
+        let res = unsafe {
+            libc::getsockopt(
+                fd,
+                level,
+                option,
+                slots.as_mut_ptr() as *mut libc::c_void,
+                &mut value_len as *mut libc::socklen_t,
+            )
+        };
+        if res < 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let value_len = value_len as usize;
+        if value_len <= slots_len {
+            slots.truncate(value_len / mem::size_of::<T>());
+            return Ok(slots);
+        }
+        // the storage was too small: grow it to the size the kernel asked for, and try again
+        let len = value_len.div_ceil(mem::size_of::<T>());
+        slots.resize(len, unsafe { mem::zeroed() });
+    }
+}
+
 // adapted from rust standard library
-fn setsockopt<T>(fd: RawFd, level: libc::c_int, option: libc::c_int, payload: T) -> Result<()> {
+pub(crate) fn setsockopt<T>(
+    fd: RawFd,
+    level: libc::c_int,
+    option: libc::c_int,
+    payload: T,
+) -> Result<()> {
     unsafe {
         let payload = &payload as *const T as *const libc::c_void;
         let payload_len = mem::size_of::<T>() as libc::socklen_t;
@@ -460,6 +742,27 @@ mod test {
         sock.set_non_blocking(false).unwrap();
     }
 
+    #[test]
+    fn read_timeout() {
+        let mut sock = Socket::new(Protocol::Route).unwrap();
+        assert_eq!(sock.get_read_timeout().unwrap(), None);
+        // the kernel rounds the timeout to its clock resolution
+        sock.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+        assert_eq!(
+            sock.get_read_timeout().unwrap(),
+            Some(Duration::from_secs(1))
+        );
+
+        sock.set_read_timeout(Some(Duration::from_millis(10)))
+            .unwrap();
+        sock.bind_auto().unwrap();
+        let err = sock.recv(&mut [0; 64], 0).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WouldBlock);
+
+        sock.set_read_timeout(None).unwrap();
+        assert_eq!(sock.get_read_timeout().unwrap(), None);
+    }
+
     #[test]
     fn options() {
         let mut sock = Socket::new(Protocol::Route).unwrap();
@@ -479,6 +782,16 @@ mod test {
         sock.set_broadcast_error(false).unwrap();
         assert!(!sock.get_broadcast_error().unwrap());
 
+        sock.set_ext_ack(true).unwrap();
+        assert!(sock.get_ext_ack().unwrap());
+        sock.set_ext_ack(false).unwrap();
+        assert!(!sock.get_ext_ack().unwrap());
+
+        sock.set_strict_check(true).unwrap();
+        assert!(sock.get_strict_check().unwrap());
+        sock.set_strict_check(false).unwrap();
+        assert!(!sock.get_strict_check().unwrap());
+
         // FIXME: these require root permissions
         // sock.set_listen_all_namespaces(true).unwrap();
         // assert!(sock.get_listen_all_namespaces().unwrap());
@@ -486,6 +799,112 @@ mod test {
         // assert!(!sock.get_listen_all_namespaces().unwrap());
     }
 
+    #[test]
+    fn list_membership() {
+        let mut sock = Socket::new(Protocol::Route).unwrap();
+        sock.bind_auto().unwrap();
+        assert!(sock.list_membership().unwrap().is_empty());
+
+        // groups above 32 cannot be represented in the `SocketAddr`, and require the bitmap to be
+        // more than one word long
+        sock.add_membership(libc::RTNLGRP_LINK).unwrap();
+        sock.add_membership(libc::RTNLGRP_BRVLAN).unwrap();
+        assert_eq!(
+            sock.list_membership().unwrap(),
+            vec![libc::RTNLGRP_LINK, libc::RTNLGRP_BRVLAN]
+        );
+
+        sock.drop_membership(libc::RTNLGRP_LINK).unwrap();
+        assert_eq!(sock.list_membership().unwrap(), vec![libc::RTNLGRP_BRVLAN]);
+    }
+
+    #[test]
+    fn recv_msg() {
+        let mut receiver = Socket::new(Protocol::Route).unwrap();
+        let addr = receiver.bind_auto().unwrap();
+        let mut sender = Socket::new(Protocol::Route).unwrap();
+        let sender_addr = sender.bind_auto().unwrap();
+        let mut buf = [0; 16];
+
+        // without NETLINK_PKTINFO, no control message is received
+        sender.send_to(&[1, 2, 3, 4], &addr, 0).unwrap();
+        let (n, source, meta) = receiver.recv_msg(&mut buf[..], 0).unwrap();
+        assert_eq!(&buf[..n], &[1, 2, 3, 4]);
+        assert_eq!(source.port_number(), sender_addr.port_number());
+        assert_eq!(meta, RecvMeta::default());
+
+        // with NETLINK_PKTINFO, unicast datagrams are reported as sent to group 0
+        receiver.set_pktinfo(true).unwrap();
+        sender.send_to(&[5, 6, 7, 8], &addr, 0).unwrap();
+        let (n, _, meta) = receiver.recv_msg(&mut buf[..], 0).unwrap();
+        assert_eq!(&buf[..n], &[5, 6, 7, 8]);
+        assert_eq!(
+            meta,
+            RecvMeta {
+                group: Some(0),
+                nsid: None,
+            }
+        );
+    }
+
+    #[test]
+    fn recv_msg_truncated_control() {
+        let mut receiver = Socket::new(Protocol::Route).unwrap();
+        let addr = receiver.bind_auto().unwrap();
+        receiver.set_pktinfo(true).unwrap();
+        let mut sender = Socket::new(Protocol::Route).unwrap();
+        sender.bind_auto().unwrap();
+        let mut buf = [0; 16];
+
+        // the buffer is too small for the nl_pktinfo control message: the datagram is received
+        // without its metadata
+        sender.send_to(&[1, 2, 3, 4], &addr, 0).unwrap();
+        let mut control = [0u64; 1];
+        let (n, _, meta) = receiver
+            .recv_msg_with_control(&mut buf[..], 0, &mut control[..])
+            .unwrap();
+        assert_eq!(&buf[..n], &[1, 2, 3, 4]);
+        assert_eq!(meta, RecvMeta::default());
+
+        // the next datagram is received normally
+        sender.send_to(&[5, 6, 7, 8], &addr, 0).unwrap();
+        let (n, _, meta) = receiver.recv_msg(&mut buf[..], 0).unwrap();
+        assert_eq!(&buf[..n], &[5, 6, 7, 8]);
+        assert_eq!(meta.group, Some(0));
+    }
+
+    #[test]
+    fn cmsg_buffer_size() {
+        // room for a nl_pktinfo and a nsid
+        let space = unsafe { libc::CMSG_SPACE(mem::size_of::<u32>() as u32) } as usize;
+        assert!(CMSG_BUFFER_WORDS * mem::size_of::<u64>() >= 2 * space);
+    }
+
+    #[test]
+    fn buffer_sizes() {
+        let mut sock = Socket::new(Protocol::Route).unwrap();
+
+        // the kernel doubles the requested sizes
+        sock.set_rcvbuf(4096).unwrap();
+        assert_eq!(sock.get_rcvbuf().unwrap(), 8192);
+        sock.set_sndbuf(4096).unwrap();
+        assert_eq!(sock.get_sndbuf().unwrap(), 8192);
+    }
+
+    #[test]
+    fn peek_len() {
+        let mut receiver = Socket::new(Protocol::Route).unwrap();
+        let addr = receiver.bind_auto().unwrap();
+        let mut sender = Socket::new(Protocol::Route).unwrap();
+        sender.bind_auto().unwrap();
+
+        sender.send_to(&[0; 100], &addr, 0).unwrap();
+        assert_eq!(receiver.peek_len(0).unwrap(), 100);
+        // the datagram is still there
+        let mut buf = [0xff; 128];
+        assert_eq!(receiver.recv(&mut buf[..], 0).unwrap(), 100);
+    }
+
     #[test]
     fn address() {
         let mut addr = SocketAddr::new(42, 1234);
@@ -1,15 +1,16 @@
 use std::io;
 use std::io::Result;
+use std::task::{Context, Poll};
 
-use crate::mio_crate as mio;
-use futures::{Async, Poll};
-use tokio_reactor::PollEvented;
+use ::tokio::io::unix::AsyncFd;
+use futures::future::poll_fn;
+use futures::ready;
 
 use super::sys;
-use super::Protocol;
+use super::{AsyncSocket, NetworkNamespace, Protocol, SockFilter};
 
-/// An I/O object representing a UDP socket.
-pub struct TokioSocket(PollEvented<sys::Socket>);
+/// An I/O object representing a netlink socket, registered with the tokio reactor.
+pub struct TokioSocket(AsyncFd<sys::Socket>);
 
 impl TokioSocket {
     /// This function will create a new UDP socket and attempt to bind it to
@@ -22,10 +23,16 @@ impl TokioSocket {
         self.0.get_mut().bind_auto()
     }
 
+    /// Create a new socket. This must be called from within a tokio runtime, since the socket
+    /// is registered with its reactor.
     pub fn new(protocol: Protocol) -> io::Result<Self> {
-        let socket = sys::Socket::new(protocol)?;
-        socket.set_non_blocking(true)?;
-        Ok(TokioSocket(PollEvented::new(socket)))
+        <Self as AsyncSocket>::new(protocol)
+    }
+
+    /// Create a socket in the given network namespace (see
+    /// [`Socket::new_in_namespace`](struct.Socket.html#method.new_in_namespace))
+    pub fn new_in_namespace(protocol: Protocol, ns: NetworkNamespace) -> io::Result<Self> {
+        <Self as AsyncSocket>::new_in_namespace(protocol, ns)
     }
 
     pub fn connect(&self, addr: &sys::SocketAddr) -> io::Result<()> {
@@ -40,88 +47,275 @@ impl TokioSocket {
         self.0.get_mut().drop_membership(group)
     }
 
-    pub fn poll_send(&mut self, buf: &[u8]) -> Poll<usize, io::Error> {
-        // Check if the socket it writable. If PollEvented::poll_write_ready returns NotReady, it
-        // will already have arranged for the current task to be notified when the socket becomes
-        // writable, so we can just return.
-        trace!("poll_send: checking if socket is writable");
-        try_ready!(self.0.poll_write_ready());
+    /// Return the multicast groups this socket is a member of (see
+    /// [`Socket::list_membership`](struct.Socket.html#method.list_membership))
+    pub fn list_membership(&self) -> Result<Vec<u32>> {
+        self.0.get_ref().list_membership()
+    }
+
+    pub fn poll_send(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        loop {
+            // Check if the socket it writable. If AsyncFd::poll_write_ready returns Pending, it
+            // will already have arranged for the current task to be notified when the socket
+            // becomes writable, so we can just return.
+            trace!("poll_send: checking if socket is writable");
+            let mut guard = ready!(self.0.poll_write_ready(cx))?;
 
-        trace!("poll_send: socket is writable");
-        match self.0.get_ref().send(buf, 0) {
-            Ok(n) => {
-                trace!("poll_send: wrote {} bytes", n);
-                Ok(Async::Ready(n))
-            }
-            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-                // If the socket is not writable, make sure the current task get notified when the
-                // socket becomes writable again.
-                trace!("poll_send: could not write, notifying the current task");
-                self.0.clear_write_ready()?;
-                Ok(Async::NotReady)
+            trace!("poll_send: socket is writable");
+            match guard.try_io(|inner| inner.get_ref().send(buf, 0)) {
+                Ok(res) => {
+                    trace!("poll_send: wrote {:?} bytes", res);
+                    return Poll::Ready(res);
+                }
+                // If the socket is not writable, the readiness has been cleared by try_io: poll
+                // it again so that the current task gets notified when the socket becomes
+                // writable again.
+                Err(_would_block) => {
+                    trace!("poll_send: could not write, polling the socket again");
+                    continue;
+                }
             }
-            Err(e) => Err(e),
         }
     }
 
-    pub fn poll_recv(&mut self, buf: &mut [u8]) -> Poll<usize, io::Error> {
-        trace!("poll_recv: checking if socket is readable");
-        try_ready!(self.0.poll_read_ready(mio::Ready::readable()));
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        loop {
+            trace!("poll_recv: checking if socket is readable");
+            let mut guard = ready!(self.0.poll_read_ready(cx))?;
 
-        trace!("poll_recv: socket is readable");
-        match self.0.get_ref().recv(buf, 0) {
-            Ok(n) => {
-                trace!("poll_recv: read {} bytes", n);
-                Ok(Async::Ready(n))
+            trace!("poll_recv: socket is readable");
+            match guard.try_io(|inner| inner.get_ref().recv(buf, 0)) {
+                Ok(res) => {
+                    trace!("poll_recv: read {:?} bytes", res);
+                    return Poll::Ready(res);
+                }
+                Err(_would_block) => {
+                    trace!("poll_recv: could not read, polling the socket again");
+                    continue;
+                }
             }
-            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-                // If the socket is not readable, make sure the current task get notified when the
-                // socket becomes readable again.
-                trace!("poll_recv: could not read, notifying the current task");
-                self.0.clear_read_ready(mio::Ready::readable())?;
-                Ok(Async::NotReady)
-            }
-            Err(e) => Err(e),
         }
     }
 
-    pub fn poll_send_to(&mut self, buf: &[u8], target: &sys::SocketAddr) -> Poll<usize, io::Error> {
-        trace!("poll_send_to: checking if socket is writable");
-        try_ready!(self.0.poll_write_ready());
+    pub fn poll_send_to(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        target: &sys::SocketAddr,
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            trace!("poll_send_to: checking if socket is writable");
+            let mut guard = ready!(self.0.poll_write_ready(cx))?;
 
-        trace!("poll_send_to: socket is writable");
-        match self.0.get_ref().send_to(buf, target, 0) {
-            Ok(n) => {
-                trace!("poll_send_to: wrote {} bytes", n);
-                Ok(Async::Ready(n))
+            trace!("poll_send_to: socket is writable");
+            match guard.try_io(|inner| inner.get_ref().send_to(buf, target, 0)) {
+                Ok(res) => {
+                    trace!("poll_send_to: wrote {:?} bytes", res);
+                    return Poll::Ready(res);
+                }
+                Err(_would_block) => {
+                    trace!("poll_send_to: could not write, polling the socket again");
+                    continue;
+                }
             }
-            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-                // If the socket is not writable, make sure the current task get notified when the
-                // socket becomes writable again.
-                trace!("poll_send_to: could not write, notifying the current task");
-                self.0.clear_write_ready()?;
-                Ok(Async::NotReady)
+        }
+    }
+
+    pub fn poll_recv_from(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, sys::SocketAddr)>> {
+        loop {
+            trace!("poll_recv_from: checking if socket is readable");
+            let mut guard = ready!(self.0.poll_read_ready(cx))?;
+
+            trace!("poll_recv_from: socket is readable");
+            match guard.try_io(|inner| inner.get_ref().recv_from(buf, 0)) {
+                Ok(res) => {
+                    trace!("poll_recv_from: read {:?}", res);
+                    return Poll::Ready(res);
+                }
+                Err(_would_block) => {
+                    trace!("poll_recv_from: could not read, polling the socket again");
+                    continue;
+                }
             }
-            Err(e) => Err(e),
         }
     }
 
-    pub fn poll_recv_from(&mut self, buf: &mut [u8]) -> Poll<(usize, sys::SocketAddr), io::Error> {
-        trace!("poll_recv_from: checking if socket is readable");
-        try_ready!(self.0.poll_read_ready(mio::Ready::readable()));
+    /// Same as [`poll_recv_from`](#method.poll_recv_from), but also return the metadata carried
+    /// by the control messages of the datagram (see
+    /// [`Socket::recv_msg`](struct.Socket.html#method.recv_msg))
+    pub fn poll_recv_msg(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, sys::SocketAddr, sys::RecvMeta)>> {
+        loop {
+            trace!("poll_recv_msg: checking if socket is readable");
+            let mut guard = ready!(self.0.poll_read_ready(cx))?;
 
-        trace!("poll_recv_from: socket is readable");
-        match self.0.get_ref().recv_from(buf, 0) {
-            Ok(n) => {
-                trace!("poll_recv_from: read {} bytes from {}", n.0, n.1);
-                Ok(Async::Ready(n))
+            trace!("poll_recv_msg: socket is readable");
+            match guard.try_io(|inner| inner.get_ref().recv_msg(buf, 0)) {
+                Ok(res) => {
+                    trace!("poll_recv_msg: read {:?}", res);
+                    return Poll::Ready(res);
+                }
+                Err(_would_block) => {
+                    trace!("poll_recv_msg: could not read, polling the socket again");
+                    continue;
+                }
             }
-            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-                trace!("poll_recv: could not read, notifying the current task");
-                self.0.clear_read_ready(mio::Ready::readable())?;
-                Ok(Async::NotReady)
+        }
+    }
+
+    /// Return the length of the next datagram, once a datagram has been received (see
+    /// [`Socket::peek_len`](struct.Socket.html#method.peek_len))
+    pub fn poll_peek_len(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        loop {
+            trace!("poll_peek_len: checking if socket is readable");
+            let mut guard = ready!(self.0.poll_read_ready(cx))?;
+
+            match guard.try_io(|inner| inner.get_ref().peek_len(0)) {
+                Ok(res) => {
+                    trace!("poll_peek_len: next datagram is {:?} bytes long", res);
+                    return Poll::Ready(res);
+                }
+                Err(_would_block) => {
+                    trace!("poll_peek_len: could not read, polling the socket again");
+                    continue;
+                }
             }
-            Err(e) => Err(e),
         }
     }
+
+    pub async fn send(&mut self, buf: &[u8]) -> io::Result<usize> {
+        poll_fn(|cx| self.poll_send(cx, buf)).await
+    }
+
+    pub async fn send_to(&mut self, buf: &[u8], target: &sys::SocketAddr) -> io::Result<usize> {
+        poll_fn(|cx| self.poll_send_to(cx, buf, target)).await
+    }
+
+    pub async fn recv(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        poll_fn(|cx| self.poll_recv(cx, buf)).await
+    }
+
+    pub async fn recv_from(&mut self, buf: &mut [u8]) -> io::Result<(usize, sys::SocketAddr)> {
+        poll_fn(|cx| self.poll_recv_from(cx, buf)).await
+    }
+
+    pub async fn recv_msg(
+        &mut self,
+        buf: &mut [u8],
+    ) -> io::Result<(usize, sys::SocketAddr, sys::RecvMeta)> {
+        poll_fn(|cx| self.poll_recv_msg(cx, buf)).await
+    }
+
+    /// Attach a BPF program to the socket (see
+    /// [`Socket::attach_filter`](struct.Socket.html#method.attach_filter))
+    pub fn attach_filter(&mut self, filter: &[SockFilter]) -> Result<()> {
+        self.0.get_mut().attach_filter(filter)
+    }
+
+    /// Remove the BPF program attached to the socket (see
+    /// [`Socket::detach_filter`](struct.Socket.html#method.detach_filter))
+    pub fn detach_filter(&mut self) -> Result<()> {
+        self.0.get_mut().detach_filter()
+    }
+
+    /// Enable or disable `NETLINK_EXT_ACK` (see
+    /// [`Socket::set_ext_ack`](struct.Socket.html#method.set_ext_ack))
+    pub fn set_ext_ack(&mut self, value: bool) -> Result<()> {
+        self.0.get_mut().set_ext_ack(value)
+    }
+
+    /// Enable or disable `NETLINK_GET_STRICT_CHK` (see
+    /// [`Socket::set_strict_check`](struct.Socket.html#method.set_strict_check))
+    pub fn set_strict_check(&mut self, value: bool) -> Result<()> {
+        self.0.get_mut().set_strict_check(value)
+    }
+
+    /// Set the size of the receive buffer (see
+    /// [`Socket::set_rcvbuf`](struct.Socket.html#method.set_rcvbuf))
+    pub fn set_rcvbuf(&mut self, size: usize) -> Result<()> {
+        self.0.get_mut().set_rcvbuf(size)
+    }
+
+    /// Set the size of the receive buffer, ignoring the system limit (see
+    /// [`Socket::set_rcvbuf_force`](struct.Socket.html#method.set_rcvbuf_force))
+    pub fn set_rcvbuf_force(&mut self, size: usize) -> Result<()> {
+        self.0.get_mut().set_rcvbuf_force(size)
+    }
+
+    /// Set the size of the send buffer (see
+    /// [`Socket::set_sndbuf`](struct.Socket.html#method.set_sndbuf))
+    pub fn set_sndbuf(&mut self, size: usize) -> Result<()> {
+        self.0.get_mut().set_sndbuf(size)
+    }
+
+    /// Enable or disable `NETLINK_PKTINFO` (see
+    /// [`Socket::set_pktinfo`](struct.Socket.html#method.set_pktinfo))
+    pub fn set_pktinfo(&mut self, value: bool) -> Result<()> {
+        self.0.get_mut().set_pktinfo(value)
+    }
+
+    /// Enable or disable `NETLINK_LISTEN_ALL_NSID` (see
+    /// [`Socket::set_listen_all_namespaces`](struct.Socket.html#method.set_listen_all_namespaces))
+    pub fn set_listen_all_namespaces(&mut self, value: bool) -> Result<()> {
+        self.0.get_mut().set_listen_all_namespaces(value)
+    }
+}
+
+impl AsyncSocket for TokioSocket {
+    fn from_socket(socket: sys::Socket) -> io::Result<Self> {
+        socket.set_non_blocking(true)?;
+        Ok(TokioSocket(AsyncFd::new(socket)?))
+    }
+
+    fn socket_ref(&self) -> &sys::Socket {
+        self.0.get_ref()
+    }
+
+    fn socket_mut(&mut self) -> &mut sys::Socket {
+        self.0.get_mut()
+    }
+
+    fn poll_send(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        TokioSocket::poll_send(self, cx, buf)
+    }
+
+    fn poll_send_to(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        target: &sys::SocketAddr,
+    ) -> Poll<io::Result<usize>> {
+        TokioSocket::poll_send_to(self, cx, buf, target)
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        TokioSocket::poll_recv(self, cx, buf)
+    }
+
+    fn poll_recv_from(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, sys::SocketAddr)>> {
+        TokioSocket::poll_recv_from(self, cx, buf)
+    }
+
+    fn poll_recv_msg(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, sys::SocketAddr, sys::RecvMeta)>> {
+        TokioSocket::poll_recv_msg(self, cx, buf)
+    }
+
+    fn poll_peek_len(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        TokioSocket::poll_peek_len(self, cx)
+    }
 }
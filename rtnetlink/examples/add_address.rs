@@ -2,20 +2,20 @@
 //! You need to be root run this example.
 
 use std::env;
-use std::thread::spawn;
 
-use futures::{Future, Stream};
+use futures::TryStreamExt;
 use ipnetwork::IpNetwork;
-use tokio_core::reactor::Core;
 
 use rtnetlink::packet::LinkNla;
-use rtnetlink::{new_connection, ErrorKind};
+use rtnetlink::{new_connection, Error, ErrorKind, Handle};
 
-fn main() {
+#[tokio::main]
+async fn main() -> Result<(), ()> {
     // Parse the arguments
     let args: Vec<String> = env::args().collect();
     if args.len() != 3 {
-        return usage();
+        usage();
+        return Ok(());
     }
     let link_name = &args[1];
     let ip: IpNetwork = args[2].parse().unwrap_or_else(|_| {
@@ -27,51 +27,53 @@ fn main() {
     let (connection, handle) = new_connection().unwrap();
 
     // Spawn the connection on the event loop
-    spawn(move || Core::new().unwrap().run(connection));
+    tokio::spawn(connection);
 
-    handle
+    match add_address(link_name, ip, handle).await {
+        Ok(()) => println!("done"),
+        // We handle permission denied errors gracefully
+        Err(ref e) if is_permission_denied(e) => eprintln!("permission denied!"),
+        // but just report any other error
+        Err(e) => eprintln!("{}", e),
+    }
+    Ok(())
+}
+
+async fn add_address(link_name: &str, ip: IpNetwork, handle: Handle) -> Result<(), Error> {
+    let mut links = handle
         // The the "link" handle
         .link()
         // Create a "get" request from the link handle. We could tweak the request here, before
         // calling "execute()"
         .get()
-        // Turn the request into a runnable future
-        .execute()
-        // The future is a stream of link message. We are interested only in a specific link, so we
-        // filter out the other message.
-        .filter(|link_msg| {
-            for nla in link_msg.nlas() {
-                if let LinkNla::IfName(ref name) = nla {
-                    return name == link_name;
+        // Turn the request into a stream of link messages
+        .execute();
+    // We are interested only in a specific link, so we skip the other messages
+    while let Some(link_msg) = links.try_next().await? {
+        for nla in link_msg.nlas() {
+            if let LinkNla::IfName(ref name) = nla {
+                if name == link_name {
+                    return handle
+                        // Get an "address" handle
+                        .address()
+                        // Create an "add" request
+                        .add(link_msg.header().index(), ip.ip(), ip.prefix())
+                        // Execute the request
+                        .execute()
+                        .await;
                 }
             }
-            false
-        })
-        .take(1)
-        .for_each(|link_msg| {
-            handle
-                // Get an "address" handle
-                .address()
-                // Create an "add" request
-                .add(link_msg.header().index(), ip.ip(), ip.prefix())
-                // Turn the request into a future
-                .execute()
-                .and_then(|_| {
-                    println!("done");
-                    Ok(())
-                })
-                .or_else(|e| match e.kind() {
-                    // We handle permission denied errors gracefully
-                    ErrorKind::NetlinkError(ref err_msg) if err_msg.code == -1 => {
-                        eprintln!("permission denied!");
-                        Ok(())
-                    }
-                    // but just propagate any other error
-                    _ => Err(e),
-                })
-        })
-        .wait()
-        .unwrap();
+        }
+    }
+    eprintln!("link {} not found", link_name);
+    Ok(())
+}
+
+fn is_permission_denied(e: &Error) -> bool {
+    match e.kind() {
+        ErrorKind::NetlinkError(ref err_msg) => err_msg.code == -1,
+        _ => false,
+    }
 }
 
 fn usage() {
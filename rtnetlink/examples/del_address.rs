@@ -1,14 +1,13 @@
 use std::env;
-use std::thread::spawn;
 
-use futures::{Future, Stream};
+use futures::TryStreamExt;
 use ipnetwork::IpNetwork;
-use tokio_core::reactor::Core;
 
 use rtnetlink::new_connection;
 use rtnetlink::packet::LinkNla;
 
-fn main() {
+#[tokio::main]
+async fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() != 3 {
         return usage();
@@ -22,11 +21,11 @@ fn main() {
     // Create a netlink connection, and a handle to send requests via this connection
     let (connection, handle) = new_connection().unwrap();
 
-    // The connection we run in its own thread
-    spawn(move || Core::new().unwrap().run(connection));
+    // The connection runs in the background
+    tokio::spawn(connection);
 
     // Get the list of links
-    let links = handle.link().get().execute().collect().wait().unwrap();
+    let links: Vec<_> = handle.link().get().execute().try_collect().await.unwrap();
 
     for link in links {
         for nla in link.nlas() {
@@ -35,7 +34,7 @@ fn main() {
                     let req = handle
                         .address()
                         .del(link.header().index(), ip.ip(), ip.prefix());
-                    match req.execute().wait() {
+                    match req.execute().await {
                         Ok(()) => println!("done"),
                         Err(e) => eprintln!("error: {}", e),
                     }
@@ -1,13 +1,12 @@
 use std::env;
-use std::thread::spawn;
 
-use futures::{Future, Stream};
-use tokio_core::reactor::Core;
+use futures::TryStreamExt;
 
 use rtnetlink::new_connection;
 use rtnetlink::packet::LinkNla;
 
-fn main() {
+#[tokio::main]
+async fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() != 2 {
         return usage();
@@ -15,10 +14,10 @@ fn main() {
     let link_name = &args[1];
 
     let (connection, handle) = new_connection().unwrap();
-    spawn(move || Core::new().unwrap().run(connection));
+    tokio::spawn(connection);
 
     // Get the list of links
-    let links = handle.link().get().execute().collect().wait().unwrap();
+    let links: Vec<_> = handle.link().get().execute().try_collect().await.unwrap();
 
     for link in links {
         for nla in link.nlas() {
@@ -27,9 +26,9 @@ fn main() {
                 if name != link_name {
                     continue;
                 }
-                println!("setting link {} down", link_name);
-                // Set it down
-                match handle.link().del(link.header().index()).execute().wait() {
+                println!("deleting link {}", link_name);
+                // Delete it
+                match handle.link().del(link.header().index()).execute().await {
                     Ok(()) => println!("done"),
                     Err(e) => eprintln!("error: {}", e),
                 }
@@ -1,21 +1,19 @@
-use futures::{Future, Stream};
+use futures::TryStreamExt;
 use rtnetlink::new_connection;
-use tokio_core::reactor::Core;
 
-fn main() {
+#[tokio::main]
+async fn main() {
     // Create a netlink connection, and a handle to send requests via this connection
     let (connection, handle) = new_connection().unwrap();
 
-    // The connection will run in an event loop
-    let mut core = Core::new().unwrap();
-    core.handle().spawn(connection.map_err(|_| ()));
+    // The connection will run in the background
+    tokio::spawn(connection);
 
     // Create a netlink request
-    let request = handle.link().get().execute().for_each(|link| {
-        println!("{:#?}", link);
-        Ok(())
-    });
+    let mut links = handle.link().get().execute();
 
-    // Run the request on the event loop
-    core.run(request).unwrap();
+    // Wait for the responses
+    while let Some(link) = links.try_next().await.unwrap() {
+        println!("{:#?}", link);
+    }
 }
@@ -1,12 +1,10 @@
-use std::thread::spawn;
-
-use futures::{Future, Stream};
-use tokio_core::reactor::Core;
+use futures::TryStreamExt;
 
 use rtnetlink::new_connection;
 use rtnetlink::packet::LinkNla;
 
-fn main() {
+#[tokio::main]
+async fn main() {
     let args: Vec<String> = std::env::args().collect();
     if args.len() != 2 {
         return usage();
@@ -16,51 +14,51 @@ fn main() {
     // Create a netlink connection, and a handle to send requests via this connection
     let (connection, handle) = new_connection().unwrap();
 
-    // The connection we run in its own thread
-    spawn(move || Core::new().unwrap().run(connection));
+    // The connection runs in the background
+    tokio::spawn(connection);
 
-    handle
+    if let Err(e) = flush_addresses(&handle, link_name).await {
+        eprintln!("{}", e);
+    }
+}
+
+async fn flush_addresses(handle: &rtnetlink::Handle, link_name: &str) -> Result<(), String> {
+    let mut link_msgs: Vec<_> = handle
         .link()
         .get()
         .execute()
         // Filter the link to keep the one with the wanted name
-        .filter(|link_msg| {
+        .try_filter(|link_msg| {
+            let mut is_match = false;
             for nla in link_msg.nlas() {
                 if let LinkNla::IfName(ref name) = nla {
-                    return name == link_name;
+                    is_match = name == link_name;
                 }
             }
-            false
-        })
-        // Convert the stream into a future
-        .collect()
-        .map_err(|e| format!("{}", e))
-        // Make sure we found 1 and only 1 link with the given name, and return it
-        .and_then(|mut link_msgs| {
-            if link_msgs.len() > 1 {
-                Err(format!("Found multiple links named {}", link_name))
-            } else if link_msgs.is_empty() {
-                Err(format!("Link {} not found", link_name))
-            } else {
-                Ok(link_msgs.drain(..).next().unwrap())
-            }
+            futures::future::ready(is_match)
         })
-        // Flush the addresses on the link
-        .and_then(|link_msg| {
-            handle
-                .address()
-                .flush(link_msg.header().index())
-                .execute()
-                .map(|_| println!("done"))
-                .map_err(|e| format!("{}", e))
-        })
-        // Print the potential errors
-        .or_else(|e| {
-            eprintln!("{}", e);
-            Ok(()) as Result<(), String>
-        })
-        .wait()
-        .unwrap();
+        .try_collect()
+        .await
+        .map_err(|e| format!("{}", e))?;
+
+    // Make sure we found 1 and only 1 link with the given name
+    let link_msg = if link_msgs.len() > 1 {
+        return Err(format!("Found multiple links named {}", link_name));
+    } else if link_msgs.is_empty() {
+        return Err(format!("Link {} not found", link_name));
+    } else {
+        link_msgs.drain(..).next().unwrap()
+    };
+
+    // Flush the addresses on the link
+    handle
+        .address()
+        .flush(link_msg.header().index())
+        .execute()
+        .await
+        .map_err(|e| format!("{}", e))?;
+    println!("done");
+    Ok(())
 }
 
 fn usage() {
@@ -0,0 +1,30 @@
+use futures::StreamExt;
+use rtnetlink::new_connection;
+use rtnetlink::packet::RtnlGroup;
+
+#[tokio::main]
+async fn main() {
+    // Create a netlink connection, and a handle to send requests via this connection
+    let (connection, mut handle) = new_connection().unwrap();
+
+    // The connection will run in the background
+    tokio::spawn(connection);
+
+    // Listen to the link and address notifications (equivalent to `ip monitor link address`)
+    let mut notifications = handle
+        .monitor(&[
+            RtnlGroup::Link,
+            RtnlGroup::Ipv4Ifaddr,
+            RtnlGroup::Ipv6Ifaddr,
+        ])
+        .await
+        .unwrap();
+
+    while let Some(notification) = notifications.next().await {
+        match notification {
+            Ok(notification) => println!("{:?}", notification),
+            // the links and addresses should be dumped again to resynchronize
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
@@ -1,13 +1,12 @@
 use std::env;
-use std::thread::spawn;
 
-use futures::{Future, Stream};
-use tokio_core::reactor::Core;
+use futures::TryStreamExt;
 
 use rtnetlink::new_connection;
 use rtnetlink::packet::LinkNla;
 
-fn main() {
+#[tokio::main]
+async fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() != 2 {
         return usage();
@@ -17,11 +16,11 @@ fn main() {
     // Create a connection and a handle to use it
     let (connection, handle) = new_connection().unwrap();
 
-    // Spawn the connection in a background thread. All we need is the handle.
-    spawn(move || Core::new().unwrap().run(connection));
+    // Spawn the connection in the background. All we need is the handle.
+    tokio::spawn(connection);
 
     // Get the list of links
-    let links = handle.link().get().execute().collect().wait().unwrap();
+    let links: Vec<_> = handle.link().get().execute().try_collect().await.unwrap();
 
     for link in links {
         for nla in link.nlas() {
@@ -37,7 +36,7 @@ fn main() {
                     .set(link.header().index())
                     .down()
                     .execute()
-                    .wait()
+                    .await
                 {
                     Ok(()) => println!("done"),
                     Err(e) => eprintln!("error: {}", e),
@@ -1,4 +1,4 @@
-use futures::{Future, Stream};
+use futures::TryStreamExt;
 use std::net::{IpAddr, Ipv4Addr};
 
 use crate::packet::constants::{
@@ -67,20 +67,20 @@ impl AddressAddRequest {
     }
 
     /// Execute the request.
-    pub fn execute(self) -> impl Future<Item = (), Error = Error> {
+    pub async fn execute(self) -> Result<(), Error> {
         let AddressAddRequest {
             mut handle,
             message,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::NewAddress(message));
         req.header_mut().set_flags(*ADD_FLAGS);
-        handle.request(req).for_each(|message| {
-            if let NetlinkPayload::Error(ref err_message) = message.payload() {
-                Err(ErrorKind::NetlinkError(err_message.clone()).into())
-            } else {
-                Ok(())
+        let mut response = handle.request(req)?;
+        while let Some(message) = response.try_next().await? {
+            if let NetlinkPayload::Error(err_message) = message.payload() {
+                return Err(ErrorKind::NetlinkError(err_message.clone()).into());
             }
-        })
+        }
+        Ok(())
     }
 
     /// Return a mutable reference to the request message.
@@ -1,8 +1,11 @@
-use futures::{Future, Stream};
+use futures::future;
+use futures::TryStreamExt;
 use std::net::IpAddr;
 
 use crate::packet::constants::{NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REQUEST};
-use crate::packet::{AddressNla, NetlinkFlags, NetlinkMessage, NetlinkPayload, RtnlMessage};
+use crate::packet::{
+    AddressMessage, AddressNla, NetlinkFlags, NetlinkMessage, NetlinkPayload, RtnlMessage,
+};
 
 use super::AddressHandle;
 use crate::{Error, ErrorKind, Handle};
@@ -31,7 +34,7 @@ impl AddressDelRequest {
     }
 
     /// Execute the request
-    pub fn execute(self) -> impl Future<Item = (), Error = Error> {
+    pub async fn execute(self) -> Result<(), Error> {
         let AddressDelRequest {
             handle,
             index,
@@ -39,51 +42,53 @@ impl AddressDelRequest {
             prefix_len,
         } = self;
 
-        AddressHandle::new(handle.clone())
-            .get()
-            .execute()
-            .filter(move |msg| {
-                if msg.header.index != index {
-                    return false;
-                }
-                if msg.header.prefix_len != prefix_len {
-                    return false;
-                }
-                for nla in msg.nlas.iter() {
-                    match nla {
-                        AddressNla::Unspec(bytes)
-                        | AddressNla::Address(bytes)
-                        | AddressNla::Local(bytes)
-                        | AddressNla::Multicast(bytes)
-                        | AddressNla::Anycast(bytes) => {
-                            let is_match = match address {
-                                IpAddr::V4(address) => bytes[..] == address.octets()[..],
-                                IpAddr::V6(address) => bytes[..] == address.octets()[..],
-                            };
-                            if is_match {
-                                return true;
-                            }
+        let is_match = |msg: &AddressMessage| {
+            if msg.header.index != index {
+                return false;
+            }
+            if msg.header.prefix_len != prefix_len {
+                return false;
+            }
+            for nla in msg.nlas.iter() {
+                match nla {
+                    AddressNla::Unspec(bytes)
+                    | AddressNla::Address(bytes)
+                    | AddressNla::Local(bytes)
+                    | AddressNla::Multicast(bytes)
+                    | AddressNla::Anycast(bytes) => {
+                        let is_match = match address {
+                            IpAddr::V4(address) => bytes[..] == address.octets()[..],
+                            IpAddr::V6(address) => bytes[..] == address.octets()[..],
+                        };
+                        if is_match {
+                            return true;
                         }
-                        _ => {}
                     }
+                    _ => {}
                 }
-                false
-            })
-            .map(move |msg| {
-                let mut req = NetlinkMessage::from(RtnlMessage::DelAddress(msg));
-                req.header_mut().set_flags(*DEL_FLAGS);
-                handle.clone().request(req).for_each(|message| {
-                    if let NetlinkPayload::Error(ref err_message) = message.payload() {
-                        Err(ErrorKind::NetlinkError(err_message.clone()).into())
-                    } else {
-                        Ok(())
-                    }
-                })
-            })
-            // 0xff is arbitrary. It is the max amount of futures that will be
-            // buffered.
-            .buffer_unordered(0xff)
-            // turn the stream into a future.
-            .for_each(|()| Ok(()))
+            }
+            false
+        };
+
+        // Retrieve the matching addresses first: deleting them while the dump is in progress
+        // would interrupt it.
+        let addresses: Vec<AddressMessage> = AddressHandle::new(handle.clone())
+            .get()
+            .execute()
+            .try_filter(|msg| future::ready(is_match(msg)))
+            .try_collect()
+            .await?;
+
+        for msg in addresses {
+            let mut req = NetlinkMessage::from(RtnlMessage::DelAddress(msg));
+            req.header_mut().set_flags(*DEL_FLAGS);
+            let mut response = handle.clone().request(req)?;
+            while let Some(message) = response.try_next().await? {
+                if let NetlinkPayload::Error(err_message) = message.payload() {
+                    return Err(ErrorKind::NetlinkError(err_message.clone()).into());
+                }
+            }
+        }
+        Ok(())
     }
 }
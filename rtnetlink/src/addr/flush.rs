@@ -1,7 +1,8 @@
-use futures::{Future, Stream};
+use futures::future;
+use futures::TryStreamExt;
 
 use crate::packet::constants::{NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REQUEST};
-use crate::packet::{NetlinkFlags, NetlinkMessage, NetlinkPayload, RtnlMessage};
+use crate::packet::{AddressMessage, NetlinkFlags, NetlinkMessage, NetlinkPayload, RtnlMessage};
 
 use super::AddressHandle;
 use crate::{Error, ErrorKind, Handle};
@@ -23,28 +24,28 @@ impl AddressFlushRequest {
     }
 
     /// Execute the request
-    pub fn execute(self) -> impl Future<Item = (), Error = Error> {
-        let handle = self.handle.clone();
-        let index = self.index;
-        AddressHandle::new(self.handle.clone())
+    pub async fn execute(self) -> Result<(), Error> {
+        let AddressFlushRequest { handle, index } = self;
+
+        // Retrieve the addresses first: deleting them while the dump is in progress would
+        // interrupt it.
+        let addresses: Vec<AddressMessage> = AddressHandle::new(handle.clone())
             .get()
             .execute()
-            .filter(move |msg| msg.header.index == index)
-            .map(move |msg| {
-                let mut req = NetlinkMessage::from(RtnlMessage::DelAddress(msg));
-                req.header_mut().set_flags(*DEL_FLAGS);
-                handle.clone().request(req).for_each(|message| {
-                    if let NetlinkPayload::Error(ref err_message) = message.payload() {
-                        Err(ErrorKind::NetlinkError(err_message.clone()).into())
-                    } else {
-                        Ok(())
-                    }
-                })
-            })
-            // 0xff is arbitrary. It is the max amount of futures that will be
-            // buffered.
-            .buffer_unordered(0xff)
-            // turn the stream into a future.
-            .for_each(|()| Ok(()))
+            .try_filter(|msg| future::ready(msg.header.index == index))
+            .try_collect()
+            .await?;
+
+        for msg in addresses {
+            let mut req = NetlinkMessage::from(RtnlMessage::DelAddress(msg));
+            req.header_mut().set_flags(*DEL_FLAGS);
+            let mut response = handle.clone().request(req)?;
+            while let Some(message) = response.try_next().await? {
+                if let NetlinkPayload::Error(err_message) = message.payload() {
+                    return Err(ErrorKind::NetlinkError(err_message.clone()).into());
+                }
+            }
+        }
+        Ok(())
     }
 }
@@ -1,4 +1,5 @@
-use futures::Stream;
+use futures::future::{self, Either};
+use futures::{FutureExt, Stream, StreamExt};
 
 use crate::packet::constants::{NLM_F_DUMP, NLM_F_REQUEST};
 use crate::packet::{AddressMessage, NetlinkFlags, NetlinkMessage, NetlinkPayload, RtnlMessage};
@@ -21,20 +22,23 @@ impl AddressGetRequest {
         AddressGetRequest { handle, message }
     }
 
-    pub fn execute(self) -> impl Stream<Item = AddressMessage, Error = Error> {
+    pub fn execute(self) -> impl Stream<Item = Result<AddressMessage, Error>> {
         let AddressGetRequest {
             mut handle,
             message,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::GetAddress(message));
         req.header_mut().set_flags(*GET_FLAGS);
-        handle.request(req).and_then(move |msg| {
-            let (header, payload) = msg.into_parts();
-            if let NetlinkPayload::Rtnl(RtnlMessage::NewAddress(msg)) = payload {
-                Ok(msg)
-            } else {
-                Err(ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into())
-            }
-        })
+        match handle.request(req) {
+            Ok(response) => Either::Left(response.map(move |msg| {
+                let (header, payload) = msg?.into_parts();
+                if let NetlinkPayload::Rtnl(RtnlMessage::NewAddress(msg)) = payload {
+                    Ok(msg)
+                } else {
+                    Err(ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into())
+                }
+            })),
+            Err(e) => Either::Right(future::err::<AddressMessage, Error>(e).into_stream()),
+        }
     }
 }
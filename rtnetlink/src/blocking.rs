@@ -0,0 +1,174 @@
+use crate::packet::constants::{NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST};
+use crate::packet::{
+    AddressMessage, LinkLayerType, LinkMessage, NetlinkFlags, NetlinkMessage, NetlinkPayload,
+    RtnlMessage,
+};
+use failure::ResultExt;
+use netlink_proto::{BlockingConnection, SocketAddr};
+
+use crate::{Error, ErrorKind};
+
+lazy_static! {
+    static ref KERNEL_UNICAST: SocketAddr = SocketAddr::new(0, 0);
+}
+
+/// A synchronous equivalent of [`Handle`](struct.Handle.html), for programs that do not run an
+/// event loop. The requests are performed on the calling thread.
+pub struct BlockingHandle(BlockingConnection);
+
+impl BlockingHandle {
+    pub(crate) fn new(conn: BlockingConnection) -> Self {
+        BlockingHandle(conn)
+    }
+
+    /// Return a mutable reference to the underlying connection, to set a timeout for instance
+    pub fn connection_mut(&mut self) -> &mut BlockingConnection {
+        &mut self.0
+    }
+
+    /// Send a netlink message, and wait for the whole response.
+    pub fn request(&mut self, message: NetlinkMessage) -> Result<Vec<NetlinkMessage>, Error> {
+        Ok(self
+            .0
+            .request(message, *KERNEL_UNICAST)
+            .context(ErrorKind::RequestFailed)?)
+    }
+
+    pub fn notify(&mut self, message: NetlinkMessage) -> Result<(), Error> {
+        self.0
+            .notify(message, *KERNEL_UNICAST)
+            .context(ErrorKind::RequestFailed)?;
+        Ok(())
+    }
+
+    /// Send a netlink message that expects an acknowledgement (`NLM_F_ACK` must be set), and
+    /// wait for it. If an error message is received instead, it is returned as an error.
+    pub fn acked_request(&mut self, message: NetlinkMessage) -> Result<(), Error> {
+        for message in self.request(message)? {
+            if let NetlinkPayload::Error(err_message) = message.into_parts().1 {
+                return Err(ErrorKind::NetlinkError(err_message).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Retrieve the list of links (equivalent to `ip link show`)
+    pub fn get_links(&mut self) -> Result<Vec<LinkMessage>, Error> {
+        let mut message = LinkMessage::new();
+        // see LinkGetRequest: the header of dump requests must be zeroed
+        message
+            .header_mut()
+            .set_link_layer_type(LinkLayerType::Netrom);
+        let mut req = NetlinkMessage::from(RtnlMessage::GetLink(message));
+        req.header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST | NLM_F_DUMP));
+        self.request(req)?
+            .into_iter()
+            .map(|msg| {
+                let (header, payload) = msg.into_parts();
+                match payload {
+                    NetlinkPayload::Rtnl(RtnlMessage::NewLink(msg)) => Ok(msg),
+                    NetlinkPayload::Error(err_msg) => Err(ErrorKind::NetlinkError(err_msg).into()),
+                    _ => Err(
+                        ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into(),
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    /// Delete the link with the given index (equivalent to `ip link del`)
+    pub fn del_link(&mut self, index: u32) -> Result<(), Error> {
+        let mut message = LinkMessage::new();
+        message.header_mut().set_index(index);
+        let mut req = NetlinkMessage::from(RtnlMessage::DelLink(message));
+        req.header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST | NLM_F_ACK));
+        self.acked_request(req)
+    }
+
+    /// Retrieve the list of addresses (equivalent to `ip addr show`)
+    pub fn get_addresses(&mut self) -> Result<Vec<AddressMessage>, Error> {
+        let mut req = NetlinkMessage::from(RtnlMessage::GetAddress(AddressMessage::default()));
+        req.header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST | NLM_F_DUMP));
+        self.request(req)?
+            .into_iter()
+            .map(|msg| {
+                let (header, payload) = msg.into_parts();
+                match payload {
+                    NetlinkPayload::Rtnl(RtnlMessage::NewAddress(msg)) => Ok(msg),
+                    NetlinkPayload::Error(err_msg) => Err(ErrorKind::NetlinkError(err_msg).into()),
+                    _ => Err(
+                        ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into(),
+                    ),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use failure::Fail;
+
+    use super::*;
+    use crate::new_blocking_connection;
+
+    fn is_timeout(err: &Error) -> bool {
+        let cause = err
+            .cause()
+            .and_then(|cause| cause.downcast_ref::<netlink_proto::Error>());
+        matches!(
+            cause.map(|e| e.kind()),
+            Some(netlink_proto::ErrorKind::Timeout)
+        )
+    }
+
+    #[test]
+    fn get_links_and_addresses() {
+        let mut handle = new_blocking_connection().unwrap();
+        // the dumps are reassembled from several messages, one per link or address
+        let links = handle.get_links().unwrap();
+        assert!(links.iter().any(|link| link.header().index() == 1));
+        assert!(!handle.get_addresses().unwrap().is_empty());
+    }
+
+    #[test]
+    fn netlink_error() {
+        let mut handle = new_blocking_connection().unwrap();
+        match handle.del_link(i32::MAX as u32).unwrap_err().kind() {
+            ErrorKind::NetlinkError(err) => assert_eq!(err.code, -libc::ENODEV),
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+        // the error ends the response, and the next request gets its own
+        assert!(!handle.get_links().unwrap().is_empty());
+    }
+
+    #[test]
+    fn skip_stale_replies() {
+        let mut handle = new_blocking_connection().unwrap();
+        let links = handle.get_links().unwrap().len();
+
+        // time out before the kernel replies
+        handle
+            .connection_mut()
+            .set_timeout(Some(Duration::from_nanos(1)));
+        assert!(is_timeout(&handle.get_links().unwrap_err()));
+
+        // the late reply to the previous dump is not mixed with the new one
+        handle.connection_mut().set_timeout(None);
+        assert_eq!(handle.get_links().unwrap().len(), links);
+
+        // nor is it taken for unsolicited messages
+        handle
+            .connection_mut()
+            .set_timeout(Some(Duration::from_millis(50)));
+        match handle.connection_mut().next_message().unwrap_err().kind() {
+            netlink_proto::ErrorKind::Timeout => {}
+            kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+}
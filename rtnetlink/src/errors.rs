@@ -12,12 +12,22 @@ pub enum ErrorKind {
     #[fail(display = "Received an unexpected message {:?}", _0)]
     UnexpectedMessage(NetlinkMessage),
 
-    #[fail(display = "Received a netlink error message {:?}", _0)]
+    #[fail(display = "Received a netlink error message: {}", _0)]
     NetlinkError(ErrorMessage),
 
     #[fail(display = "A netlink request failed")]
     RequestFailed,
 
+    #[fail(
+        display = "A dump was interrupted, and its result may be inconsistent: the request should be sent again"
+    )]
+    DumpInterrupted,
+
+    #[fail(
+        display = "Notifications were lost because the receive buffer of the socket overflowed"
+    )]
+    NotificationsLost,
+
     #[fail(
         display = "Received a link message (RTM_GETLINK, RTM_NEWLINK, RTM_SETLINK or RTMGETLINK) with an invalid hardware address attribute: {:?}.",
         _0
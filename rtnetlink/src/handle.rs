@@ -1,7 +1,11 @@
-use crate::packet::NetlinkMessage;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::packet::{NetlinkMessage, NetlinkPayload, RtnlGroup, RtnlMessage};
 use failure::{Fail, ResultExt};
-use futures::Stream;
-use netlink_proto::{ConnectionHandle, SocketAddr};
+use futures::future::{self, Future};
+use futures::{FutureExt, Stream, StreamExt};
+use netlink_proto::{ConnectionHandle, DropPolicy, MessageFilter, SocketAddr};
 
 use crate::{AddressHandle, Error, ErrorKind, LinkHandle};
 
@@ -9,30 +13,161 @@ lazy_static! {
     static ref KERNEL_UNICAST: SocketAddr = SocketAddr::new(0, 0);
 }
 
+/// A handle to send requests to one or several connections (see
+/// [`new_pooled_connection`](fn.new_pooled_connection.html)).
 #[derive(Clone, Debug)]
-pub struct Handle(ConnectionHandle);
+pub struct Handle {
+    // Connections the requests are dispatched to
+    connections: Vec<ConnectionHandle>,
+    // Index of the connection the next request is sent to, shared by the clones of the handle
+    next: Arc<AtomicUsize>,
+    // Connection that joins the multicast groups
+    multicast: ConnectionHandle,
+}
 
 impl Handle {
     pub(crate) fn new(conn: ConnectionHandle) -> Self {
-        Handle(conn)
+        Handle::pooled(vec![conn.clone()], conn)
+    }
+
+    pub(crate) fn pooled(connections: Vec<ConnectionHandle>, multicast: ConnectionHandle) -> Self {
+        Handle {
+            connections,
+            next: Arc::new(AtomicUsize::new(0)),
+            multicast,
+        }
+    }
+
+    // Pick the connection the next request is sent to. Requests are spread evenly across the
+    // connections, so that a long dump does not hold up the requests that come after it.
+    fn connection(&mut self) -> &mut ConnectionHandle {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.connections.len();
+        &mut self.connections[index]
     }
 
+    /// Send a netlink message, and get the response as a stream of messages (see
+    /// [`ConnectionHandle::request`](../netlink_proto/struct.ConnectionHandle.html#method.request)).
     pub fn request(
         &mut self,
         message: NetlinkMessage,
-    ) -> impl Stream<Item = NetlinkMessage, Error = Error> {
-        self.0
+    ) -> Result<impl Stream<Item = Result<NetlinkMessage, Error>>, Error> {
+        let response = self
+            .connection()
             .request(message, *KERNEL_UNICAST)
-            .map_err(|e| e.context(ErrorKind::RequestFailed).into())
+            .context(ErrorKind::RequestFailed)?;
+        Ok(response.map(|item| item.map_err(response_error)))
+    }
+
+    /// Send the interrupted dumps again, up to `retries` times (see
+    /// [`ConnectionHandle::set_dump_retries`](../netlink_proto/struct.ConnectionHandle.html#method.set_dump_retries)).
+    pub fn set_dump_retries(&mut self, retries: u32) {
+        for connection in self.connections.iter_mut() {
+            connection.set_dump_retries(retries);
+        }
+    }
+
+    /// Send several messages, packing as many of them as possible in each datagram (see
+    /// [`ConnectionHandle::batch`](../netlink_proto/struct.ConnectionHandle.html#method.batch)).
+    /// The returned future resolves into the results of the messages, in the same order as the
+    /// messages. The messages should have the `NLM_F_ACK` flag set.
+    pub fn batch(
+        &mut self,
+        messages: Vec<NetlinkMessage>,
+    ) -> Result<impl Future<Output = Vec<Result<Vec<NetlinkMessage>, Error>>>, Error> {
+        let results = self
+            .connection()
+            .batch(messages, *KERNEL_UNICAST)
+            .context(ErrorKind::RequestFailed)?;
+        Ok(results.map(|results| {
+            results
+                .into_iter()
+                .map(|result| result.map_err(batch_error))
+                .collect()
+        }))
+    }
+
+    /// Wait until the connections can take a new request, when the requests channels are bounded
+    /// (see [`ConnectionHandle::ready`](../netlink_proto/struct.ConnectionHandle.html#method.ready)).
+    pub async fn ready(&mut self) -> Result<(), Error> {
+        for connection in self.connections.iter_mut() {
+            connection.ready().await.context(ErrorKind::RequestFailed)?;
+        }
+        Ok(())
     }
 
     pub fn notify(&mut self, msg: NetlinkMessage) -> Result<(), Error> {
-        self.0
+        self.connection()
             .notify(msg, *KERNEL_UNICAST)
             .context(ErrorKind::RequestFailed)?;
         Ok(())
     }
 
+    /// Join the given multicast group, so that its notifications are received by the streams
+    /// returned by [`monitor`](#method.monitor). This can be called while the connection is
+    /// running.
+    pub async fn subscribe(&mut self, group: RtnlGroup) -> Result<(), Error> {
+        self.multicast
+            .subscribe(group)
+            .await
+            .context(ErrorKind::RequestFailed)?;
+        Ok(())
+    }
+
+    /// Leave the given multicast group
+    pub async fn unsubscribe(&mut self, group: RtnlGroup) -> Result<(), Error> {
+        self.multicast
+            .unsubscribe(group)
+            .await
+            .context(ErrorKind::RequestFailed)?;
+        Ok(())
+    }
+
+    /// Join the given multicast groups, and get the notifications of these groups (for instance
+    /// the `NewLink` and `DelLink` messages of `RtnlGroup::Link`) as a stream. The stream only
+    /// yields the notifications of the given groups, even if the connection is subscribed to
+    /// other groups: the other messages are filtered out by the connection, based on the group
+    /// each datagram was sent to (see
+    /// [`MessageFilter::Groups`](../netlink_proto/enum.MessageFilter.html#variant.Groups)).
+    ///
+    /// When the receive buffer of the socket overflows, the kernel drops notifications, and the
+    /// stream yields an [`ErrorKind::NotificationsLost`](enum.ErrorKind.html) error: the state
+    /// that the notifications describe should then be dumped again. The stream goes on after that
+    /// error. With a pooled connection, the notifications are received on a socket of their own,
+    /// so that a flood of notifications does not fill the receive buffer of the sockets the
+    /// requests are sent on.
+    pub async fn monitor(
+        &mut self,
+        groups: &[RtnlGroup],
+    ) -> Result<impl Stream<Item = Result<RtnlMessage, Error>>, Error> {
+        let filter = MessageFilter::groups(
+            &groups
+                .iter()
+                .map(|group| u32::from(*group))
+                .collect::<Vec<_>>(),
+        );
+        // Listen first, so that no notification is missed between the subscription and the
+        // creation of the stream
+        let messages = self
+            .multicast
+            .listen(filter, usize::MAX, DropPolicy::DropNewest)
+            .context(ErrorKind::RequestFailed)?;
+        for group in groups {
+            self.subscribe(*group).await?;
+        }
+
+        Ok(messages.filter_map(|message| {
+            let item = if message.is_overrun() {
+                Some(Err(ErrorKind::NotificationsLost.into()))
+            } else {
+                match message.into_parts().1 {
+                    NetlinkPayload::Rtnl(message) => Some(Ok(message)),
+                    _ => None,
+                }
+            };
+            future::ready(item)
+        }))
+    }
+
     /// Create a new handle, specifically for link requests (equivalent to `ip link` commands)
     pub fn link(&self) -> LinkHandle {
         LinkHandle::new(self.clone())
@@ -43,3 +178,101 @@ impl Handle {
         AddressHandle::new(self.clone())
     }
 }
+
+fn response_error(e: netlink_proto::Error) -> Error {
+    let kind = if e.is_retryable() {
+        ErrorKind::DumpInterrupted
+    } else {
+        ErrorKind::RequestFailed
+    };
+    e.context(kind).into()
+}
+
+fn batch_error(e: netlink_proto::Error) -> Error {
+    match e.into_inner() {
+        netlink_proto::ErrorKind::NetlinkError(message) => match message.into_parts() {
+            (_, NetlinkPayload::Error(err_message)) => ErrorKind::NetlinkError(err_message).into(),
+            (header, payload) => {
+                ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into()
+            }
+        },
+        kind => response_error(kind.into()),
+    }
+}
+
+#[cfg(all(test, feature = "tokio_support"))]
+mod test {
+    use futures::FutureExt;
+
+    use super::*;
+    use crate::new_pooled_connection;
+    use crate::packet::constants::NLM_F_REQUEST;
+    use crate::packet::{LinkLayerType, LinkMessage, NetlinkFlags};
+
+    fn get_loopback() -> NetlinkMessage {
+        let mut link = LinkMessage::new();
+        // the kernel checks that the header of the request is zeroed, except for the index
+        link.header_mut()
+            .set_index(1)
+            .set_link_layer_type(LinkLayerType::Netrom);
+        let mut message = NetlinkMessage::from(RtnlMessage::GetLink(link));
+        message
+            .header_mut()
+            .set_flags(NetlinkFlags::from(NLM_F_REQUEST));
+        message
+    }
+
+    #[tokio::test]
+    async fn pool_size() {
+        let (connections, handle) = new_pooled_connection(3).unwrap();
+        // the multicast notifications are received on a connection of their own
+        assert_eq!(connections.len(), 4);
+        assert_eq!(handle.connections.len(), 3);
+
+        // a pool always has a connection for the requests
+        let (connections, handle) = new_pooled_connection(0).unwrap();
+        assert_eq!(connections.len(), 2);
+        assert_eq!(handle.connections.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn round_robin() {
+        let (connections, mut handle) = new_pooled_connection(3).unwrap();
+        // the connections do not run yet: the requests wait for the connection they were
+        // dispatched to
+        let mut responses: Vec<_> = (0..6)
+            .map(|_| Box::pin(handle.request(get_loopback()).unwrap()))
+            .collect();
+        // the clones of the handle carry on from where the handle is
+        let mut clone = handle.clone();
+        responses.push(Box::pin(clone.request(get_loopback()).unwrap()));
+
+        // run the connections of the pool one after the other: each one only answers the
+        // requests dispatched to it
+        for (index, connection) in connections.into_iter().take(3).enumerate() {
+            tokio::spawn(connection);
+            for (request, response) in responses.iter_mut().enumerate() {
+                if request % 3 == index {
+                    let message = response.next().await.unwrap().unwrap();
+                    assert!(
+                        matches!(
+                            message.payload(),
+                            NetlinkPayload::Rtnl(RtnlMessage::NewLink(link)) if link.header().index() == 1
+                        ),
+                        "unexpected reply: {:?}",
+                        message
+                    );
+                } else if request % 3 > index {
+                    assert!(response.next().now_or_never().is_none());
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn dump_retries() {
+        let (_connections, mut handle) = new_pooled_connection(2).unwrap();
+        handle.set_dump_retries(3);
+        assert!(handle.connections.iter().all(|c| c.dump_retries() == 3));
+    }
+}
@@ -7,53 +7,39 @@
 //! # Example: listing links
 //!
 //! ```rust,no_run
-//! extern crate futures;
-//! extern crate rtnetlink;
-//! extern crate tokio_core;
-//!
-//! use futures::{Stream, Future};
+//! use futures::TryStreamExt;
 //! use rtnetlink::new_connection;
-//! use tokio_core::reactor::Core;
 //!
-//! fn main() {
+//! #[tokio::main]
+//! async fn main() {
 //!     // Create a netlink connection, and a handle to send requests via this connection
 //!     let (connection, handle) = new_connection().unwrap();
 //!
-//!     // The connection will run in an event loop
-//!     let mut core = Core::new().unwrap();
-//!     core.handle().spawn(connection.map_err(|_| ()));
+//!     // The connection will run in the background
+//!     tokio::spawn(connection);
 //!
 //!     /// Create a netlink request
-//!     let request = handle.link().get().execute().for_each(|link| {
-//!         println!("{:#?}", link);
-//!         Ok(())
-//!     });
+//!     let mut links = handle.link().get().execute();
 //!
-//!     /// Run the request on the event loop
-//!     core.run(request).unwrap();
+//!     /// Wait for the responses
+//!     while let Some(link) = links.try_next().await.unwrap() {
+//!         println!("{:#?}", link);
+//!     }
 //! }
 //! ```
 //!
 //! # Example: creating a veth pair
 //!
 //! ```rust,no_run
-//! extern crate futures;
-//! extern crate rtnetlink;
-//! extern crate tokio_core;
-//!
-//! use std::thread::spawn;
-//!
-//! use futures::Future;
-//! use tokio_core::reactor::Core;
-//!
 //! use rtnetlink::new_connection;
 //!
-//! fn main() {
+//! #[tokio::main]
+//! async fn main() {
 //!     // Create a netlink connection, and a handle to send requests via this connection
 //!     let (connection, handle) = new_connection().unwrap();
 //!
-//!     // The connection we run in its own thread
-//!     spawn(move || Core::new().unwrap().run(connection));
+//!     // The connection runs in the background
+//!     tokio::spawn(connection);
 //!
 //!     // Create a request to create the veth pair
 //!     handle
@@ -62,7 +48,7 @@
 //!         .veth("veth-rs-1".into(), "veth-rs-2".into())
 //!         // Execute the request, and wait for it to finish
 //!         .execute()
-//!         .wait()
+//!         .await
 //!         .unwrap();
 //! }
 //! ```
@@ -70,29 +56,24 @@
 //! # Example: deleting a link by name
 //!
 //! ```rust,no_run
-//! extern crate futures;
-//! extern crate rtnetlink;
-//! extern crate tokio_core;
-//!
 //! use std::env;
-//! use std::thread::spawn;
 //!
-//! use futures::{Stream, Future};
-//! use tokio_core::reactor::Core;
+//! use futures::TryStreamExt;
 //!
 //! use rtnetlink::new_connection;
 //! use rtnetlink::packet::LinkNla;
 //!
-//! fn main() {
+//! #[tokio::main]
+//! async fn main() {
 //!     let args: Vec<String> = env::args().collect();
 //!     if args.len() != 2 { panic!("expected one link name as argument"); }
 //!     let link_name = &args[1];
 //!
 //!     let (connection, handle) = new_connection().unwrap();
-//!     spawn(move || Core::new().unwrap().run(connection));
+//!     tokio::spawn(connection);
 //!
 //!     // Get the list of links
-//!     let links = handle.link().get().execute().collect().wait().unwrap();
+//!     let links: Vec<_> = handle.link().get().execute().try_collect().await.unwrap();
 //!
 //!     // Find the link with the name provided as argument, and delete it
 //!     for link in links {
@@ -101,7 +82,7 @@
 //!             if let LinkNla::IfName(ref name) = nla {
 //!                 if name == link_name {
 //!                     // Set it down
-//!                     handle.link().del(link.header().index()).execute().wait().unwrap();
+//!                     handle.link().del(link.header().index()).execute().await.unwrap();
 //!                     return;
 //!                 }
 //!             }
@@ -120,11 +101,24 @@ use failure;
 pub use crate::packet::constants;
 pub use netlink_packet as packet;
 use netlink_proto;
-pub use netlink_proto::{Connection, Protocol};
+pub use netlink_proto::AsyncSocket;
+#[cfg(feature = "async_std_support")]
+pub use netlink_proto::AsyncStdSocket;
+use netlink_proto::NetlinkCodec;
+#[cfg(feature = "smol_support")]
+pub use netlink_proto::SmolSocket;
+#[cfg(feature = "tokio_support")]
+pub use netlink_proto::TokioSocket;
+pub use netlink_proto::{
+    BlockingConnection, ChannelCapacities, Connection, NetworkNamespace, Protocol,
+};
 
 mod handle;
 pub use crate::handle::*;
 
+mod blocking;
+pub use crate::blocking::*;
+
 mod errors;
 pub use crate::errors::*;
 
@@ -136,7 +130,97 @@ pub use crate::addr::*;
 
 use std::io;
 
-pub fn new_connection() -> io::Result<(Connection, Handle)> {
-    let (conn, handle, _) = netlink_proto::new_connection(Protocol::Route)?;
+#[cfg(feature = "tokio_support")]
+pub fn new_connection() -> io::Result<(Connection<TokioSocket>, Handle)> {
+    new_connection_with_socket()
+}
+
+/// Same as [`new_connection`](fn.new_connection.html), but the connection is opened in the given
+/// network namespace, so that the requests made with the handle apply to that namespace.
+#[cfg(feature = "tokio_support")]
+pub fn new_connection_in_namespace(
+    ns: NetworkNamespace,
+) -> io::Result<(Connection<TokioSocket>, Handle)> {
+    new_connection_in_namespace_with_socket(ns)
+}
+
+/// Same as [`new_connection`](fn.new_connection.html), but with the given socket type, so that
+/// the connection can run on another runtime than tokio (`SmolSocket` with the `smol_support`
+/// feature, or `AsyncStdSocket` with the `async_std_support` feature).
+pub fn new_connection_with_socket<S: AsyncSocket>() -> io::Result<(Connection<S>, Handle)> {
+    let (conn, handle, _) = netlink_proto::new_connection_with_socket(Protocol::Route)?;
     Ok((conn, Handle::new(handle)))
 }
+
+/// Same as [`new_connection_in_namespace`](fn.new_connection_in_namespace.html), but with the
+/// given socket type.
+pub fn new_connection_in_namespace_with_socket<S: AsyncSocket>(
+    ns: NetworkNamespace,
+) -> io::Result<(Connection<S>, Handle)> {
+    let (conn, handle, _) =
+        netlink_proto::new_connection_in_namespace_with_socket(Protocol::Route, ns)?;
+    Ok((conn, Handle::new(handle)))
+}
+
+/// Create a pool of `size` connections for the requests, and a connection for the multicast
+/// notifications (see [`Handle::monitor`](struct.Handle.html#method.monitor)), each with its own
+/// socket. The handle dispatches the requests across the connections of the pool, so that a long
+/// dump does not hold up the other requests, and the notifications do not share the socket
+/// receive buffer with the responses.
+///
+/// All the returned connections (`size + 1` of them, the pool having at least one connection)
+/// must be spawned.
+#[cfg(feature = "tokio_support")]
+pub fn new_pooled_connection(size: usize) -> io::Result<(Vec<Connection<TokioSocket>>, Handle)> {
+    new_pooled_connection_with_socket(size)
+}
+
+/// Same as [`new_pooled_connection`](fn.new_pooled_connection.html), but with the given socket
+/// type.
+pub fn new_pooled_connection_with_socket<S: AsyncSocket>(
+    size: usize,
+) -> io::Result<(Vec<Connection<S>>, Handle)> {
+    let mut connections = Vec::with_capacity(size + 1);
+    let mut handles = Vec::with_capacity(size);
+    for _ in 0..size.max(1) {
+        let (conn, handle, _) = netlink_proto::new_connection_with_socket(Protocol::Route)?;
+        connections.push(conn);
+        handles.push(handle);
+    }
+    let (conn, multicast, _) = netlink_proto::new_connection_with_socket(Protocol::Route)?;
+    connections.push(conn);
+    Ok((connections, Handle::pooled(handles, multicast)))
+}
+
+/// Create a connection from an existing `NETLINK_ROUTE` socket, with the given capacities for
+/// the channels between the connection and the handle (see
+/// [`ChannelCapacities`](struct.ChannelCapacities.html)), so that slow consumers of large dumps
+/// or notifications do not make memory grow without limit.
+pub fn new_connection_from_socket<S: AsyncSocket>(
+    socket: S,
+    capacities: ChannelCapacities,
+) -> (Connection<S>, Handle) {
+    let (conn, handle, _) = netlink_proto::new_connection_from_socket(
+        socket,
+        NetlinkCodec::for_protocol(Protocol::Route),
+        capacities,
+    );
+    (conn, Handle::new(handle))
+}
+
+/// Create a synchronous connection, for programs that do not run an event loop (see
+/// [`BlockingHandle`](struct.BlockingHandle.html))
+pub fn new_blocking_connection() -> io::Result<BlockingHandle> {
+    Ok(BlockingHandle::new(BlockingConnection::new(
+        Protocol::Route,
+    )?))
+}
+
+/// Same as [`new_blocking_connection`](fn.new_blocking_connection.html), but the connection is
+/// opened in the given network namespace.
+pub fn new_blocking_connection_in_namespace(ns: NetworkNamespace) -> io::Result<BlockingHandle> {
+    Ok(BlockingHandle::new(BlockingConnection::new_in_namespace(
+        Protocol::Route,
+        ns,
+    )?))
+}
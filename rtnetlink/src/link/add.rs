@@ -1,4 +1,4 @@
-use futures::{Future, Stream};
+use futures::TryStreamExt;
 
 use crate::packet::constants::{IFF_UP, NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REQUEST};
 use crate::packet::{
@@ -31,20 +31,20 @@ impl LinkAddRequest {
     }
 
     /// Execute the request.
-    pub fn execute(self) -> impl Future<Item = (), Error = Error> {
+    pub async fn execute(self) -> Result<(), Error> {
         let LinkAddRequest {
             mut handle,
             message,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::NewLink(message));
         req.header_mut().set_flags(*ADD_FLAGS);
-        handle.request(req).for_each(|message| {
-            if let NetlinkPayload::Error(ref err_message) = message.payload() {
-                Err(ErrorKind::NetlinkError(err_message.clone()).into())
-            } else {
-                Ok(())
+        let mut response = handle.request(req)?;
+        while let Some(message) = response.try_next().await? {
+            if let NetlinkPayload::Error(err_message) = message.payload() {
+                return Err(ErrorKind::NetlinkError(err_message.clone()).into());
             }
-        })
+        }
+        Ok(())
     }
 
     /// Return a mutable reference to the request message.
@@ -57,20 +57,12 @@ impl LinkAddRequest {
     /// could do:
     ///
     /// ```rust,no_run
-    /// extern crate futures;
-    /// extern crate rtnetlink;
-    /// extern crate tokio_core;
-    ///
-    /// use std::thread::spawn;
-    ///
-    /// use futures::Future;
-    /// use tokio_core::reactor::Core;
-    ///
     /// use rtnetlink::new_connection;
     ///
-    /// fn main() {
+    /// #[tokio::main]
+    /// async fn main() {
     ///     let (connection, handle) = new_connection().unwrap();
-    ///     spawn(move || Core::new().unwrap().run(connection));
+    ///     tokio::spawn(connection);
     ///     let vlan_id = 100;
     ///     let link_id = 6;
     ///     let mut request = handle.link().add().vlan("my-vlan-itf".into(), link_id, vlan_id);
@@ -78,7 +70,7 @@ impl LinkAddRequest {
     ///     request.message_mut().header_mut().flags_mut().unset_up();
     ///     request.message_mut().header_mut().change_mask_mut().unset_up();
     ///     // send the request
-    ///     request.execute().wait().unwrap();
+    ///     request.execute().await.unwrap();
     /// }
     pub fn message_mut(&mut self) -> &mut LinkMessage {
         &mut self.message
@@ -101,6 +93,13 @@ impl LinkAddRequest {
             .up()
     }
 
+    /// Create a team device. Its ports and runner are then configured through the `team` generic
+    /// netlink family.
+    /// This is equivalent to `ip link add NAME type team`.
+    pub fn team(self, name: String) -> Self {
+        self.name(name).link_info(LinkInfoKind::Team, None).up()
+    }
+
     /// Create VLAN on a link.
     /// This is equivalent to `ip link add link LINK name NAME type vlan id VLAN_ID`,
     /// but instead of specifying a link name (`LINK`), we specify a link index.
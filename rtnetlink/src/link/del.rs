@@ -1,4 +1,4 @@
-use futures::{Future, Stream};
+use futures::TryStreamExt;
 
 use crate::packet::constants::{NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REQUEST};
 use crate::packet::{LinkMessage, NetlinkFlags, NetlinkMessage, NetlinkPayload, RtnlMessage};
@@ -23,20 +23,20 @@ impl LinkDelRequest {
     }
 
     /// Execute the request
-    pub fn execute(self) -> impl Future<Item = (), Error = Error> {
+    pub async fn execute(self) -> Result<(), Error> {
         let LinkDelRequest {
             mut handle,
             message,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::DelLink(message));
         req.header_mut().set_flags(*DEL_FLAGS);
-        handle.request(req).for_each(|message| {
-            if let NetlinkPayload::Error(ref err_message) = message.payload() {
-                Err(ErrorKind::NetlinkError(err_message.clone()).into())
-            } else {
-                Ok(())
+        let mut response = handle.request(req)?;
+        while let Some(message) = response.try_next().await? {
+            if let NetlinkPayload::Error(err_message) = message.payload() {
+                return Err(ErrorKind::NetlinkError(err_message.clone()).into());
             }
-        })
+        }
+        Ok(())
     }
 
     /// Return a mutable reference to the request
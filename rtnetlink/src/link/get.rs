@@ -1,7 +1,10 @@
-use futures::Stream;
+use futures::future::{self, Either};
+use futures::{FutureExt, Stream, StreamExt};
 
 use crate::packet::constants::{NLM_F_DUMP, NLM_F_REQUEST};
-use crate::packet::{LinkMessage, NetlinkFlags, NetlinkMessage, NetlinkPayload, RtnlMessage};
+use crate::packet::{
+    LinkLayerType, LinkMessage, NetlinkFlags, NetlinkMessage, NetlinkPayload, RtnlMessage,
+};
 
 use crate::{Error, ErrorKind, Handle};
 
@@ -17,26 +20,34 @@ pub struct LinkGetRequest {
 
 impl LinkGetRequest {
     pub(crate) fn new(handle: Handle) -> Self {
-        let message = LinkMessage::new();
+        let mut message = LinkMessage::new();
+        // With NETLINK_GET_STRICT_CHK, the kernel rejects the dump requests which header is not
+        // zeroed, so the link layer type must be ARPHRD_NETROM (0), not the default ARPHRD_ETHER.
+        message
+            .header_mut()
+            .set_link_layer_type(LinkLayerType::Netrom);
         LinkGetRequest { handle, message }
     }
 
     /// Execute the request
-    pub fn execute(self) -> impl Stream<Item = LinkMessage, Error = Error> {
+    pub fn execute(self) -> impl Stream<Item = Result<LinkMessage, Error>> {
         let LinkGetRequest {
             mut handle,
             message,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::GetLink(message));
         req.header_mut().set_flags(*GET_FLAGS);
-        handle.request(req).and_then(move |msg| {
-            let (header, payload) = msg.into_parts();
-            if let NetlinkPayload::Rtnl(RtnlMessage::NewLink(msg)) = payload {
-                Ok(msg)
-            } else {
-                Err(ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into())
-            }
-        })
+        match handle.request(req) {
+            Ok(response) => Either::Left(response.map(move |msg| {
+                let (header, payload) = msg?.into_parts();
+                if let NetlinkPayload::Rtnl(RtnlMessage::NewLink(msg)) = payload {
+                    Ok(msg)
+                } else {
+                    Err(ErrorKind::UnexpectedMessage(NetlinkMessage::new(header, payload)).into())
+                }
+            })),
+            Err(e) => Either::Right(future::err::<LinkMessage, Error>(e).into_stream()),
+        }
     }
 
     /// Return a mutable reference to the request
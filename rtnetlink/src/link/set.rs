@@ -1,4 +1,4 @@
-use futures::{Future, Stream};
+use futures::TryStreamExt;
 
 use crate::packet::constants::{IFF_UP, NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REQUEST};
 use crate::packet::{
@@ -25,20 +25,20 @@ impl LinkSetRequest {
     }
 
     /// Execute the request
-    pub fn execute(self) -> impl Future<Item = (), Error = Error> {
+    pub async fn execute(self) -> Result<(), Error> {
         let LinkSetRequest {
             mut handle,
             message,
         } = self;
         let mut req = NetlinkMessage::from(RtnlMessage::SetLink(message));
         req.header_mut().set_flags(*SET_FLAGS);
-        handle.request(req).for_each(|message| {
-            if let NetlinkPayload::Error(ref err_message) = message.payload() {
-                Err(ErrorKind::NetlinkError(err_message.clone()).into())
-            } else {
-                Ok(())
+        let mut response = handle.request(req)?;
+        while let Some(message) = response.try_next().await? {
+            if let NetlinkPayload::Error(err_message) = message.payload() {
+                return Err(ErrorKind::NetlinkError(err_message.clone()).into());
             }
-        })
+        }
+        Ok(())
     }
 
     /// Return a mutable reference to the request
@@ -75,6 +75,20 @@ impl LinkSetRequest {
         self
     }
 
+    /// Enslave the link with the given index to the master link (bridge, bond, team, etc.) with
+    /// index `master_index` (equivalent to `ip link set DEV master MASTER`)
+    pub fn master(mut self, master_index: u32) -> Self {
+        self.message.append_nla(LinkNla::Master(master_index));
+        self
+    }
+
+    /// Release the link with the given index from its master link (equivalent to
+    /// `ip link set DEV nomaster`)
+    pub fn nomaster(mut self) -> Self {
+        self.message.append_nla(LinkNla::Master(0));
+        self
+    }
+
     /// Set the hardware address of the link with the given index (equivalent to `ip link set DEV address ADDRESS`)
     pub fn address(mut self, address: Vec<u8>) -> Self {
         self.message.append_nla(LinkNla::Address(address));